@@ -1,5 +1,3 @@
-use tallystick;
-
 use criterion::{criterion_group, criterion_main, Benchmark, Criterion, Throughput};
 use rand::prelude::*;
 use std::cmp::Eq;
@@ -176,7 +174,7 @@ fn approval<T: Eq + Clone + Hash>(mut votes: Vec<Vec<T>>, num_candidates: usize)
     let mut tally = tallystick::approval::DefaultApprovalTally::with_capacity(1, num_candidates);
 
     for vote in votes.drain(0..) {
-        tally.add(vote);
+        tally.add(vote).unwrap();
     }
 
     tally.winners();
@@ -186,7 +184,7 @@ fn score<T: Eq + Clone + Hash>(mut votes: Vec<Vec<(T, u64)>>, num_candidates: us
     let mut tally = tallystick::score::DefaultScoreTally::with_capacity(1, num_candidates);
 
     for vote in votes.drain(0..) {
-        tally.add(vote);
+        tally.add(vote).unwrap();
     }
 
     tally.winners();
@@ -197,7 +195,7 @@ fn stv<T: Eq + Clone + Hash>(mut votes: Vec<Vec<T>>, num_candidates: usize) {
     let mut tally = tallystick::stv::DefaultTally::with_capacity(1, tallystick::Quota::Droop, num_candidates, votes.len());
 
     for vote in votes.drain(0..) {
-        tally.add(vote);
+        tally.add(vote).unwrap();
     }
 
     tally.winners();
@@ -239,7 +237,7 @@ fn random_transitive_votes(n: u32) -> Vec<Vec<u8>> {
         all_votes.push(vote);
     }
 
-    return all_votes;
+    all_votes
 }
 
 fn random_scored_votes(n: u32) -> Vec<Vec<(u8, u64)>> {
@@ -259,7 +257,7 @@ fn random_scored_votes(n: u32) -> Vec<Vec<(u8, u64)>> {
         all_votes.push(vote);
     }
 
-    return all_votes;
+    all_votes
 }
 
 fn random_single_votes(n: u32) -> Vec<u8> {
@@ -268,7 +266,7 @@ fn random_single_votes(n: u32) -> Vec<u8> {
     for _ in 0..n {
         all_votes.push(rng.gen_range(0, 10));
     }
-    return all_votes;
+    all_votes
 }
 
 fn static_transitive_votes(n: u32) -> Vec<Vec<&'static str>> {
@@ -284,9 +282,9 @@ fn static_transitive_votes(n: u32) -> Vec<Vec<&'static str>> {
     for _ in 0..(2 * n) {
         all_votes.push(vec!["Chattanooga", "Knoxville", "Nashville", "Memphis"]);
     }
-    for _ in 0..(1 * n) {
+    for _ in 0..n {
         all_votes.push(vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"]);
     }
 
-    return all_votes;
+    all_votes
 }
@@ -1,11 +1,23 @@
-use num_traits::cast::NumCast;
+use hashbrown::HashSet;
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
 use num_traits::Num;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
+use super::errors::TallyError;
+use super::margin;
 use super::plurality::PluralityTally;
+use super::quorum::{turnout_methods, MinimumSupport, Turnout};
+use super::result::BallotSummary;
+use super::result::CountedCandidates;
 use super::result::RankedCandidate;
 use super::result::RankedWinners;
+use super::result::Standings;
+use super::result::TalliedWinners;
+use super::shard::Shard;
+use super::MarginOfVictory;
+use super::TallyCheckpoint;
 
 /// An approval tally using `u64` integers to count votes.
 /// `DefaultApprovalTally` is generally preferred over `ApprovalTally`.
@@ -17,16 +29,51 @@ use super::result::RankedWinners;
 ///
 ///    // An election for Judge
 ///    let mut tally = DefaultApprovalTally::<&str>::new(1);
-///    tally.add(vec!["Judge Judy", "Notorious RBG"]);
-///    tally.add(vec!["Judge Dredd"]);
-///    tally.add(vec!["Abe Vigoda", "Notorious RBG"]);
-///    tally.add(vec!["Judge Dredd", "Notorious RBG"]);
+///    tally.add(vec!["Judge Judy", "Notorious RBG"])?;
+///    tally.add(vec!["Judge Dredd"])?;
+///    tally.add(vec!["Abe Vigoda", "Notorious RBG"])?;
+///    tally.add(vec!["Judge Dredd", "Notorious RBG"])?;
 ///
 ///    let winners = tally.winners().into_unranked();
 ///    assert!(winners[0] == "Notorious RBG");
+/// # Ok::<(), tallystick::TallyError>(())
 /// ```
 pub type DefaultApprovalTally<T> = ApprovalTally<T, u64>;
 
+/// Specifies how an approval tally ranks candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Candidates are ranked by their approvals. Disapprovals are recorded, but do not affect the result.
+    #[default]
+    Approval,
+
+    /// Candidates are ranked by their net approval: their approvals less their disapprovals.
+    /// Ballots can disapprove of candidates with [`add_with_disapprovals()`](struct.ApprovalTally.html#method.add_with_disapprovals).
+    NetApproval,
+}
+
+/// A candidate's approvals and disapprovals, as returned by [`ApprovalTally::net_totals()`](struct.ApprovalTally.html#method.net_totals).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalTotal<T, C> {
+    /// The candidate
+    pub candidate: T,
+
+    /// The total weight of ballots approving the candidate.
+    pub approvals: C,
+
+    /// The total weight of ballots disapproving of the candidate.
+    pub disapprovals: C,
+}
+
+impl<T, C: Clone + Num> ApprovalTotal<T, C> {
+    /// Get the candidate's net approval: their approvals less their disapprovals.
+    ///
+    /// Use a signed count type, such as `i64` or `f64`, if a candidate may have more disapprovals than approvals.
+    pub fn net(&self) -> C {
+        self.approvals.clone() - self.disapprovals.clone()
+    }
+}
+
 /// A generic approval tally.
 ///
 /// Generics:
@@ -39,26 +86,33 @@ pub type DefaultApprovalTally<T> = ApprovalTally<T, u64>;
 ///
 ///    // An election for Judge using floats as the count type.
 ///    let mut tally = ApprovalTally::<&str, f64>::new(1);
-///    tally.add_weighted(vec!["Judge Judy", "Notorious RBG"], 0.5);
-///    tally.add_weighted(vec!["Judge Dredd"], 2.0);
-///    tally.add_weighted(vec!["Abe Vigoda", "Notorious RBG"], 3.2);
-///    tally.add_weighted(vec!["Judge Dredd", "Notorious RBG"], 1.0);
+///    tally.add_weighted(vec!["Judge Judy", "Notorious RBG"], 0.5)?;
+///    tally.add_weighted(vec!["Judge Dredd"], 2.0)?;
+///    tally.add_weighted(vec!["Abe Vigoda", "Notorious RBG"], 3.2)?;
+///    tally.add_weighted(vec!["Judge Dredd", "Notorious RBG"], 1.0)?;
 ///
 ///    let winners = tally.winners().into_unranked();
 ///    assert!(winners[0] == "Notorious RBG");
+/// # Ok::<(), tallystick::TallyError>(())
 /// ```
+#[derive(Clone)]
 pub struct ApprovalTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     plurality: PluralityTally<T, C>,
+    disapprovals: PluralityTally<T, C>,
+    variant: Variant,
+    turnout: Turnout<C>,
+    candidates: Option<HashSet<T>>, // Known candidates, if votes are checked against them
+    max_approvals: Option<usize>,   // Most candidates a vote may approve
 }
 
 impl<T, C> ApprovalTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     /// Create a new `ApprovalTally` with the given number of winners.
     ///
@@ -67,6 +121,11 @@ where
     pub fn new(num_winners: usize) -> Self {
         ApprovalTally {
             plurality: PluralityTally::new(num_winners),
+            disapprovals: PluralityTally::new(num_winners),
+            variant: Variant::Approval,
+            turnout: Turnout::new(),
+            candidates: None,
+            max_approvals: None,
         }
     }
 
@@ -74,35 +133,258 @@ where
     pub fn with_capacity(num_winners: usize, expected_candidates: usize) -> Self {
         ApprovalTally {
             plurality: PluralityTally::with_capacity(num_winners, expected_candidates),
+            disapprovals: PluralityTally::new(num_winners),
+            variant: Variant::Approval,
+            turnout: Turnout::new(),
+            candidates: None,
+            max_approvals: None,
         }
     }
 
-    /// Add a new vote
-    pub fn add(&mut self, mut selection: Vec<T>) {
-        for vote in selection.drain(0..) {
-            self.plurality.add(vote);
+    /// Create a new `ApprovalTally` with the given number of winners, and the provided candidates.
+    ///
+    /// Votes are then checked against the candidates:
+    /// a vote approving or disapproving of an unknown candidate is rejected with `TallyError::UnknownCandidate`,
+    /// and a vote naming a candidate more than once with `TallyError::VoteHasDuplicateCandidates`.
+    /// Candidates without any approvals are included in the totals.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::approval::DefaultApprovalTally;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut tally = DefaultApprovalTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+    ///    tally.add(vec!["Alice", "Bob"])?;
+    ///    assert_eq!(tally.add(vec!["Bob", "Dave"]), Err(TallyError::UnknownCandidate { position: 1 }));
+    ///    assert_eq!(tally.add(vec!["Bob", "Bob"]), Err(TallyError::VoteHasDuplicateCandidates { position: 1 }));
+    ///    assert_eq!(tally.totals()[2], ("Carol", 0));
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_candidates(num_winners: usize, candidates: Vec<T>) -> Self {
+        let mut tally = Self::with_capacity(num_winners, candidates.len());
+        tally.add_candidates(candidates);
+        tally
+    }
+
+    /// Add a candidate to the tally.
+    ///
+    /// Once a candidate has been added, votes are checked against the known candidates,
+    /// as described for [`with_candidates()`](#method.with_candidates).
+    pub fn add_candidate(&mut self, candidate: T) {
+        self.plurality.running_total.entry(candidate.clone()).or_insert_with(C::zero);
+        self.candidates.get_or_insert_with(HashSet::new).insert(candidate);
+    }
+
+    /// Add some candidates to the tally.
+    pub fn add_candidates(&mut self, candidates: Vec<T>) {
+        for candidate in candidates {
+            self.add_candidate(candidate);
         }
     }
 
-    /// Add a vote by reference.
-    pub fn add_ref(&mut self, selection: &[T]) {
-        for vote in selection {
-            self.plurality.add_ref(vote);
+    /// Limit the number of candidates a ballot may approve, such as to the number of seats.
+    ///
+    /// Votes that approve more than `max` candidates are rejected with `TallyError::TooManyApprovals`.
+    /// Disapprovals do not count towards the maximum.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::approval::DefaultApprovalTally;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut tally = DefaultApprovalTally::new(2).with_max_approvals(2);
+    ///    tally.add(vec!["Alice", "Bob"])?;
+    ///
+    ///    let error = tally.add(vec!["Alice", "Bob", "Carol"]).unwrap_err();
+    ///    assert_eq!(error, TallyError::TooManyApprovals { approvals: 3, max: 2 });
+    ///    assert_eq!(error.to_string(), "tallystick: vote approves 3 candidates but at most 2 are allowed");
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_max_approvals(mut self, max: usize) -> Self {
+        self.max_approvals = Some(max);
+        self
+    }
+
+    /// Check the validity of a vote, without adding it.
+    ///
+    /// If candidates have been added to the tally, a vote must approve only known candidates, each at most once.
+    /// If a maximum number of approvals has been set, a vote must not approve more candidates than that.
+    /// Otherwise every vote is valid.
+    pub fn check_vote(&self, selection: &[T]) -> Result<(), TallyError> {
+        self.check_approvals(selection.len())?;
+        self.check_candidates(selection, &[])
+    }
+
+    // Check that a vote approves no more candidates than the maximum, if one has been set.
+    fn check_approvals(&self, approvals: usize) -> Result<(), TallyError> {
+        match self.max_approvals {
+            Some(max) if approvals > max => Err(TallyError::TooManyApprovals {
+                approvals: approvals,
+                max: max,
+            }),
+            _ => Ok(()),
         }
     }
 
+    // Check that a vote names only known candidates, each at most once, if candidates have been added.
+    // Disapproved candidates are positioned after the approved candidates.
+    fn check_candidates(&self, approved: &[T], disapproved: &[T]) -> Result<(), TallyError> {
+        if let Some(candidates) = &self.candidates {
+            let vote: Vec<&T> = approved.iter().chain(disapproved).collect();
+            for (position, candidate) in vote.iter().enumerate() {
+                if !candidates.contains(*candidate) {
+                    return Err(TallyError::UnknownCandidate { position });
+                }
+            }
+            crate::util::check_duplicates_transitive_vote(&vote)?;
+        }
+        Ok(())
+    }
+
+    /// Set how candidates are ranked. Defaults to [`Variant::Approval`](enum.Variant.html#variant.Approval).
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::approval::{DefaultApprovalTally, Variant};
+    ///
+    ///    let mut tally = DefaultApprovalTally::new(1).with_variant(Variant::NetApproval);
+    ///    tally.add_with_disapprovals(vec!["Alice", "Bob"], vec!["Carol"])?;
+    ///    tally.add_with_disapprovals(vec!["Alice", "Carol"], vec!["Bob"])?;
+    ///    tally.add_with_disapprovals(vec!["Bob"], vec!["Alice"])?;
+    ///
+    ///    // Alice and Bob both have two approvals and one disapproval, so they tie until Bob is disapproved of again.
+    ///    let totals = tally.net_totals();
+    ///    assert_eq!((totals[2].candidate, totals[2].approvals, totals[2].disapprovals), ("Carol", 1, 1));
+    ///
+    ///    tally.add_with_disapprovals(vec![], vec!["Bob"])?;
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Require winners to have a minimum level of support.
+    ///
+    /// Winners without the minimum support are left out of [`winners()`](#method.winners), leaving their seats unfilled.
+    pub fn with_minimum_support(mut self, support: MinimumSupport<C>) -> Self {
+        self.turnout.support = Some(support);
+        self
+    }
+
+    /// Add a new vote
+    ///
+    /// The vote is checked with [`check_vote()`](#method.check_vote), and the tally is left unchanged if it is not valid.
+    pub fn add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted(selection, C::one())
+    }
+
+    /// Add a vote by reference.
+    pub fn add_ref(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        self.add_weighted_ref(selection, C::one())
+    }
+
     /// Add a weighted vote.
     /// By default takes a weight as a `usize` integer, but can be customized by using `ApprovalTally` with a custom vote type.
-    pub fn add_weighted(&mut self, mut selection: Vec<T>, weight: C) {
+    pub fn add_weighted(&mut self, mut selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.check_vote(&selection)?;
+        self.turnout.add_valid(&weight);
         for vote in selection.drain(0..) {
-            self.plurality.add_weighted(vote, weight);
+            self.plurality.add_to_total(vote, weight.clone());
         }
+        Ok(())
     }
 
     /// Add a weighted vote by reference.
-    pub fn add_weighted_ref(&mut self, selection: &[T], weight: C) {
+    pub fn add_weighted_ref(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.check_vote(selection)?;
+        self.turnout.add_valid(&weight);
         for vote in selection {
-            self.plurality.add_weighted_ref(vote, weight);
+            self.plurality.add_to_total_ref(vote, weight.clone());
+        }
+        Ok(())
+    }
+
+    /// Add a vote that approves some candidates and disapproves of others.
+    ///
+    /// Disapprovals only affect the result of a [`Variant::NetApproval`](enum.Variant.html#variant.NetApproval) tally.
+    /// The vote is checked like any other, with the positions of disapproved candidates following the approved candidates.
+    pub fn add_with_disapprovals(&mut self, approved: Vec<T>, disapproved: Vec<T>) -> Result<(), TallyError> {
+        self.add_with_disapprovals_weighted(approved, disapproved, C::one())
+    }
+
+    /// Add a weighted vote that approves some candidates and disapproves of others.
+    pub fn add_with_disapprovals_weighted(&mut self, approved: Vec<T>, disapproved: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.check_approvals(approved.len())?;
+        self.check_candidates(&approved, &disapproved)?;
+        self.turnout.add_valid(&weight);
+        for vote in approved {
+            self.plurality.add_to_total(vote, weight.clone());
+        }
+        for vote in disapproved {
+            // Candidates that are only disapproved of are still ranked.
+            self.plurality.running_total.entry(vote.clone()).or_insert_with(C::zero);
+            self.disapprovals.add_to_total(vote, weight.clone());
+        }
+        Ok(())
+    }
+
+    turnout_methods!(winners);
+
+    /// Add a spoiled ballot, which was cast but rejected for the given reason, such as `"overvote"`.
+    ///
+    /// Spoiled ballots count towards turnout, but not towards any candidate. Ballots with the same reason are totalled together.
+    pub fn add_spoiled(&mut self, reason: &str) {
+        self.add_spoiled_weighted(reason, C::one());
+    }
+
+    /// Add a weighted spoiled ballot.
+    pub fn add_spoiled_weighted(&mut self, reason: &str, weight: C) {
+        self.turnout.add_spoiled(reason, weight);
+    }
+
+    /// Get the total weight of spoiled ballots for each reason, in the order each reason was first recorded.
+    pub fn spoiled(&self) -> Vec<(String, C)> {
+        self.turnout.spoiled.clone()
+    }
+
+    /// Get a summary of the valid, blank and spoiled ballots cast.
+    pub fn ballot_summary(&self) -> BallotSummary<C> {
+        self.turnout.summary()
+    }
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// The other tally's number of winners, quorum and minimum support are ignored.
+    pub fn merge(&mut self, other: Self) {
+        self.turnout.merge(other.turnout);
+        self.plurality.merge(other.plurality);
+        self.disapprovals.merge(other.disapprovals);
+        if let Some(other) = other.candidates {
+            self.candidates.get_or_insert_with(HashSet::new).extend(other);
+        }
+    }
+
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot. Ballots keep their approvals of other candidates.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.plurality.withdraw_candidate(candidate);
+        self.disapprovals.withdraw_candidate(candidate);
+        if let Some(candidates) = &mut self.candidates {
+            candidates.remove(candidate);
         }
     }
 
@@ -114,9 +396,34 @@ where
 
     /// Get a ranked list of winners. Winners with the same rank are tied.
     /// The number of winners might be greater than the requested `num_winners` if there is a tie.
-    /// In approval voting, the winning candidate(s) is the one most approved by all voters.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    /// If a minimum support is set, winners without it are left out and their seats are unfilled.
+    /// In approval voting, the winning candidate(s) is the one most approved by all voters,
+    /// or with the highest net approval for a [`Variant::NetApproval`](enum.Variant.html#variant.NetApproval) tally.
     pub fn winners(&self) -> RankedWinners<T> {
-        self.plurality.winners()
+        self.winners_n(self.plurality.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        let winners = self.turnout.decide(self.ranking().into_ranked(num_winners));
+        self.turnout
+            .supported(winners, |candidate| self.plurality.running_total.get(candidate).cloned())
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
     }
 
     /// Get vote totals for this tally.
@@ -130,8 +437,8 @@ where
     ///    use tallystick::approval::DefaultApprovalTally;
     ///
     ///    let mut tally = DefaultApprovalTally::new(1);
-    ///    tally.add_weighted(vec!["Alice", "Bob"], 30);
-    ///    tally.add_weighted(vec!["Bob", "Carol"], 10);
+    ///    tally.add_weighted(vec!["Alice", "Bob"], 30)?;
+    ///    tally.add_weighted(vec!["Bob", "Carol"], 10)?;
     ///
     ///    for (candidate, num_votes) in tally.totals().iter() {
     ///       println!("{} got {} votes", candidate, num_votes);
@@ -140,11 +447,55 @@ where
     ///    //   Alice got 30 votes
     ///    //   Bob got 40 votes
     ///    //   Carol got 10 votes
+    /// # Ok::<(), tallystick::TallyError>(())
     /// ```
     pub fn totals(&self) -> Vec<(T, C)> {
         self.plurality.totals()
     }
 
+    /// Get the total weight of disapprovals for each candidate, from highest to lowest.
+    pub fn disapprovals(&self) -> Vec<(T, C)> {
+        self.disapprovals.totals()
+    }
+
+    /// Get each candidate's approvals and disapprovals, ranked as this tally ranks them.
+    pub fn net_totals(&self) -> Vec<ApprovalTotal<T, C>> {
+        let zero = C::zero();
+        self.ranking()
+            .into_vec()
+            .into_iter()
+            .map(|(candidate, _)| ApprovalTotal {
+                approvals: self.plurality.running_total.get(&candidate).unwrap_or(&zero).clone(),
+                disapprovals: self.disapprovals.running_total.get(&candidate).unwrap_or(&zero).clone(),
+                candidate: candidate,
+            })
+            .collect()
+    }
+
+    /// Get a ranked list of winners with their approval totals. Winners with the same rank are tied.
+    ///
+    /// This is [`winners()`](#method.winners) joined with [`totals()`](#method.totals).
+    pub fn winners_with_totals(&self) -> TalliedWinners<T, C> {
+        self.winners().with_totals(&self.totals())
+    }
+
+    /// Get the margin of victory: the lead of the last winner over the runner-up.
+    ///
+    /// The margin is exact: adding ballots that approve only the runner-up,
+    /// or changing ballots that approve the winner but not the runner-up to the reverse, ties the runner-up with the winner or puts them ahead.
+    ///
+    /// For a [`Variant::NetApproval`](enum.Variant.html#variant.NetApproval) tally, the margin is of net approval,
+    /// and a single ballot can close it by two: approving the runner-up and disapproving of the winner.
+    ///
+    /// Returns `None` if there are no more candidates than seats, so every candidate wins.
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        match self.variant {
+            Variant::Approval => self.plurality.margin_of_victory(),
+            Variant::NetApproval => margin::from_totals(self.ranking().into_vec(), self.plurality.num_winners)
+                .map(|margin| margin.with_per_ballot(C::one() + C::one())),
+        }
+    }
+
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
     ///
@@ -153,8 +504,8 @@ where
     ///    use tallystick::approval::DefaultApprovalTally;
     ///
     ///    let mut tally = DefaultApprovalTally::new(1);
-    ///    tally.add_weighted(vec!["Alice", "Bob"], 30);
-    ///    tally.add_weighted(vec!["Bob", "Carol"], 10);
+    ///    tally.add_weighted(vec!["Alice", "Bob"], 30)?;
+    ///    tally.add_weighted(vec!["Bob", "Carol"], 10)?;
     ///    
     ///    for ranked in tally.ranked().iter() {
     ///       println!("{} has a rank of {}", ranked.candidate, ranked.rank);
@@ -163,28 +514,230 @@ where
     ///    //   Bob has a rank of 0
     ///    //   Alice has a rank of 1
     ///    //   Carol has a rank of 2
+    /// # Ok::<(), tallystick::TallyError>(())
     /// ```
     pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
-        self.plurality.ranked()
+        self.ranking().into_ranked(0).into_vec()
+    }
+
+    // Get the counts candidates are ranked by.
+    // Net approval can be negative, so it is offset by the most disapprovals of any candidate to stay non-negative for unsigned count types.
+    // Offsetting every candidate equally leaves their ranking and the differences between them unchanged.
+    fn ranking(&self) -> CountedCandidates<T, C> {
+        match self.variant {
+            Variant::Approval => self.plurality.get_counted(),
+            Variant::NetApproval => {
+                let zero = C::zero();
+                let mut offset = C::zero();
+                for disapprovals in self.disapprovals.running_total.values() {
+                    if *disapprovals > offset {
+                        offset = disapprovals.clone();
+                    }
+                }
+                let mut counted = CountedCandidates::new();
+                for (candidate, approvals) in self.plurality.running_total.iter() {
+                    let disapprovals = self.disapprovals.running_total.get(candidate).unwrap_or(&zero).clone();
+                    counted.push(candidate.clone(), approvals.clone() + (offset.clone() - disapprovals));
+                }
+                counted
+            }
+        }
+    }
+
+    /// Get each candidate with their approval count, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, C> {
+        self.plurality.counted()
+    }
+}
+
+impl<T, C> ApprovalTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                 // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd, // Vote count type
+{
+    /// Add a new vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any approved candidate's total, or the total weight of valid ballots, would overflow the count type.
+    pub fn checked_add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.checked_add_weighted(selection, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any approved candidate's total, or the total weight of valid ballots, would overflow the count type.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.checked_add_weighted_ref(&selection, weight)
+    }
+
+    /// Add a weighted vote by reference, checking for overflow.
+    ///
+    /// The vote is also checked with [`check_vote()`](#method.check_vote).
+    pub fn checked_add_weighted_ref(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.check_vote(selection)?;
+        self.plurality
+            .check_add_ballot(selection.iter().map(|vote| (vote, weight.clone())))?;
+        self.turnout.check_add_valid(&weight)?;
+        self.add_weighted_ref(selection, weight)
+    }
+}
+
+impl<T, C> Shard for ApprovalTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn shard(&self) -> Self {
+        ApprovalTally {
+            plurality: self.plurality.shard(),
+            disapprovals: self.disapprovals.shard(),
+            variant: self.variant,
+            turnout: self.turnout.shard(),
+            candidates: self.candidates.clone(),
+            max_approvals: self.max_approvals,
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        ApprovalTally::merge(self, shard);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Quorum;
 
     #[test]
-    fn approval_basic() {
+    fn approval_basic() -> Result<(), TallyError> {
         let mut tally = DefaultApprovalTally::new(1);
-        tally.add_ref(&vec!["Alice"]);
-        tally.add_weighted_ref(&vec!["Alice", "Bob"], 2);
+        tally.add_ref(&["Alice"])?;
+        tally.add_weighted_ref(&["Alice", "Bob"], 2)?;
 
         let winners = tally.winners().into_unranked();
         assert_eq!(winners, vec!["Alice"]);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_checked() {
+        let mut tally = ApprovalTally::<&str, u8>::new(1);
+        tally.checked_add_weighted(vec!["Alice", "Bob"], 200).unwrap();
+        tally.checked_add(vec!["Carol"]).unwrap();
+        assert_eq!(
+            tally.checked_add_weighted(vec!["Carol", "Alice"], 100),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(tally.totals()[2], ("Carol", 1));
+
+        // Approvals of a repeated candidate are summed before they are checked.
+        assert_eq!(
+            tally.checked_add_weighted(vec!["Alice", "Alice"], 30),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(
+            tally.checked_add_weighted(vec!["Dave", "Dave"], 200),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(tally.totals()[2], ("Carol", 1));
+        assert_eq!(tally.totals().len(), 3);
     }
 
     #[test]
-    fn approval_lumen() {
+    fn approval_candidates() -> Result<(), TallyError> {
+        let mut tally = DefaultApprovalTally::with_candidates(1, vec!["Alice", "Bob"]);
+        tally.add_candidate("Carol");
+        tally.checked_add(vec!["Alice", "Carol"])?;
+        assert_eq!(
+            tally.checked_add_weighted(vec!["Carol", "Dave"], 2),
+            Err(TallyError::UnknownCandidate { position: 1 })
+        );
+        assert_eq!(
+            tally.checked_add(vec!["Carol", "Alice", "Carol"]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 2 })
+        );
+        assert_eq!(tally.totals()[2], ("Bob", 0));
+
+        tally.withdraw_candidate(&"Carol");
+        assert_eq!(tally.check_vote(&["Carol"]), Err(TallyError::UnknownCandidate { position: 0 }));
+
+        // Votes added without checking for overflow are checked too, and disapprovals are positioned after approvals.
+        assert_eq!(tally.add_ref(&["Dave"]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(
+            tally.add_with_disapprovals(vec!["Alice"], vec!["Bob", "Dave"]),
+            Err(TallyError::UnknownCandidate { position: 2 })
+        );
+        assert_eq!(
+            tally.add_with_disapprovals_weighted(vec!["Alice"], vec!["Alice"], 2),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        tally.add_with_disapprovals_weighted(vec!["Bob"], vec!["Alice"], 2)?;
+        assert_eq!(tally.totals(), vec![("Bob", 2), ("Alice", 1)]);
+        assert_eq!(tally.disapprovals(), vec![("Alice", 2)]);
+
+        // Without candidates, any vote is accepted.
+        let mut tally = DefaultApprovalTally::new(1);
+        tally.checked_add(vec!["Dave", "Dave"])?;
+        assert_eq!(tally.totals(), vec![("Dave", 2)]);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_max_approvals() -> Result<(), TallyError> {
+        let mut tally = DefaultApprovalTally::with_candidates(2, vec!["Alice", "Bob", "Carol"]).with_max_approvals(2);
+        tally.checked_add(vec!["Alice", "Carol"])?;
+        tally.checked_add(vec![])?;
+
+        // The cap is checked before the candidates, and a rejected vote leaves the tally unchanged.
+        assert_eq!(
+            tally.checked_add(vec!["Alice", "Bob", "Dave"]),
+            Err(TallyError::TooManyApprovals { approvals: 3, max: 2 })
+        );
+        assert_eq!(tally.checked_add(vec!["Dave"]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(tally.totals()[0].1, 1);
+
+        // Every way of adding a vote is capped, though disapprovals do not count towards the cap.
+        let error = TallyError::TooManyApprovals { approvals: 3, max: 2 };
+        assert_eq!(tally.add(vec!["Alice", "Bob", "Carol"]), Err(error.clone()));
+        assert_eq!(tally.add_weighted_ref(&["Alice", "Bob", "Carol"], 2), Err(error.clone()));
+        assert_eq!(tally.add_with_disapprovals(vec!["Alice", "Bob", "Carol"], vec![]), Err(error));
+        tally.add_with_disapprovals(vec!["Bob"], vec!["Alice", "Carol"])?;
+        assert_eq!(tally.totals()[0].1, 1);
+        assert_eq!(tally.disapprovals().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_net() -> Result<(), TallyError> {
+        let mut tally = ApprovalTally::<&str, i64>::new(1).with_variant(Variant::NetApproval);
+        tally.add_with_disapprovals(vec!["Alice"], vec!["Bob", "Carol"])?;
+        tally.add_with_disapprovals_weighted(vec!["Bob"], vec!["Carol"], 2)?;
+        tally.add(vec!["Bob", "Alice"])?;
+
+        // Alice: 2 - 0, Bob: 3 - 1, Carol: 0 - 3
+        let nets: Vec<(&str, i64)> = tally.net_totals().iter().map(|t| (t.candidate, t.net())).collect();
+        assert_eq!(nets[2], ("Carol", -3));
+        assert_eq!(tally.ranked()[2], ("Carol", 1));
+        assert_eq!(tally.disapprovals()[0], ("Carol", 3));
+        assert_eq!(tally.margin_of_victory().map(|m| m.margin), Some(0));
+
+        // Unsigned counts rank by net approval too.
+        let mut tally = DefaultApprovalTally::new(1).with_variant(Variant::NetApproval);
+        tally.add_with_disapprovals_weighted(vec!["Alice"], vec!["Bob"], 3)?;
+        tally.add_with_disapprovals_weighted(vec!["Bob"], vec![], 4)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        let margin = tally.margin_of_victory().unwrap();
+        assert_eq!((margin.winner, margin.runner_up, margin.margin), ("Alice", "Bob", 2));
+        assert_eq!(margin.ballots_to_add(), 1);
+
+        // The default variant ignores disapprovals.
+        let tally = tally.with_variant(Variant::Approval);
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_lumen() -> Result<(), TallyError> {
         // From: https://courses.lumenlearning.com/wmopen-mathforliberalarts/chapter/introduction-approval-voting/
 
         let matrix = "The Matrix";
@@ -192,13 +745,13 @@ mod tests {
         let titanic = "Titanic";
 
         let mut tally = DefaultApprovalTally::with_capacity(1, 3);
-        tally.add_weighted(vec![scream, matrix], 3);
-        tally.add_weighted(vec![titanic, matrix], 2);
-        tally.add(vec![titanic, scream, matrix]);
-        tally.add(vec![matrix]);
-        tally.add(vec![titanic, scream]);
-        tally.add(vec![titanic]);
-        tally.add(vec![scream]);
+        tally.add_weighted(vec![scream, matrix], 3)?;
+        tally.add_weighted(vec![titanic, matrix], 2)?;
+        tally.add(vec![titanic, scream, matrix])?;
+        tally.add(vec![matrix])?;
+        tally.add(vec![titanic, scream])?;
+        tally.add(vec![titanic])?;
+        tally.add(vec![scream])?;
 
         let candidates = tally.candidates();
         assert_eq!(candidates.len(), 3);
@@ -210,8 +763,56 @@ mod tests {
         assert_eq!(ranked, vec![(matrix, 0), (scream, 1), (titanic, 2)]);
 
         let winners = tally.winners();
-        assert_eq!(winners.contains(&matrix), true);
-        assert_eq!(winners.contains(&scream), false);
-        assert_eq!(winners.contains(&titanic), false);
+        assert!(winners.contains(&matrix));
+        assert!(!winners.contains(&scream));
+        assert!(!winners.contains(&titanic));
+        Ok(())
+    }
+
+    #[test]
+    fn approval_quorum() -> Result<(), TallyError> {
+        let mut tally = DefaultApprovalTally::new(1).with_quorum(Quorum::ValidVotes(3));
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add_blank_weighted(5);
+        assert_eq!(tally.blank(), 5);
+        assert!(!tally.quorum_met());
+        assert!(tally.winners().is_empty());
+
+        tally.add_ref(&["Bob"])?;
+        assert!(tally.quorum_met());
+        assert_eq!(tally.winners().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_minimum_support() -> Result<(), TallyError> {
+        // Support is measured against ballots, not approvals.
+        let mut tally = DefaultApprovalTally::new(2).with_minimum_support(MinimumSupport::Percent(50));
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.add(vec!["Alice", "Carol"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add(vec!["Carol"])?;
+
+        let winners = tally.winners();
+        assert_eq!(winners.unfilled(), 0);
+        assert_eq!(winners.into_unranked(), vec!["Alice", "Carol"]);
+
+        tally.add_weighted(vec!["Dave"], 2)?;
+        let winners = tally.winners();
+        assert_eq!(winners.unfilled(), 1);
+        assert_eq!(winners.into_unranked(), vec!["Alice"]);
+
+        // The minimum support can be set after votes are added.
+        let mut tally = DefaultApprovalTally::new(2);
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add(vec!["Carol"])?;
+        let tally = tally
+            .with_minimum_support(MinimumSupport::Percent(50))
+            .with_quorum(Quorum::ValidVotes(3));
+        assert!(tally.quorum_met());
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        Ok(())
     }
 }
@@ -0,0 +1,559 @@
+use num_traits::FromPrimitive;
+use num_traits::Num;
+use std::convert::TryInto;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+use super::approval::ApprovalTally;
+use super::condorcet::CondorcetTally;
+use super::plurality::PluralityTally;
+use super::score::ScoreTally;
+use super::util::{check_duplicates_ranked_vote, split_ranked_vote};
+use super::TallyCheckpoint;
+use super::TallyError;
+
+#[cfg(feature = "nightly")]
+use super::borda::BordaTally;
+#[cfg(feature = "nightly")]
+use super::election::Method;
+#[cfg(feature = "nightly")]
+use super::schulze::SchulzeTally;
+#[cfg(feature = "nightly")]
+use super::{irv, stv, Numeric, RankedWinners};
+
+/// A single ballot in a `BallotBox`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Ballot<T, C> {
+    /// A ranked ballot, as a list of (candidate, rank) pairs. Ranks are ascending, and equally ranked candidates are equally preferred.
+    Ranked(Vec<(T, u32)>),
+
+    /// A scored ballot, as a list of (candidate, score) pairs.
+    Scored(Vec<(T, C)>),
+}
+
+impl<T: Clone, C: Clone + PartialOrd> Ballot<T, C> {
+    /// Get the ballot as a ranked ballot.
+    ///
+    /// Scored ballots are ranked from the highest score to the lowest, with equal scores ranked equally.
+    pub fn ranking(&self) -> Vec<(T, u32)> {
+        match self {
+            Ballot::Ranked(ranked) => ranked.clone(),
+            Ballot::Scored(scored) => {
+                let mut sorted: Vec<&(T, C)> = scored.iter().collect();
+                sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+                let mut ranked = Vec::with_capacity(sorted.len());
+                let mut rank = 0;
+                for (i, (candidate, score)) in sorted.iter().enumerate() {
+                    if i > 0 && *score != sorted[i - 1].1 {
+                        rank += 1;
+                    }
+                    ranked.push((candidate.clone(), rank));
+                }
+                ranked
+            }
+        }
+    }
+
+    /// Get every candidate on the ballot, in the order they appear.
+    pub fn candidates(&self) -> Vec<T> {
+        match self {
+            Ballot::Ranked(ranked) => ranked.iter().map(|(candidate, _)| candidate.clone()).collect(),
+            Ballot::Scored(scored) => scored.iter().map(|(candidate, _)| candidate.clone()).collect(),
+        }
+    }
+}
+
+/// A box of ballots, stored once and added to any number of tallies.
+///
+/// Ballots can be ranked or scored. Ranked methods read scored ballots in order of score,
+/// and approval tallies approve every candidate on a ranked ballot, or every candidate scored above zero.
+///
+/// Adding the box to a tally adds every ballot or none of them: if a ballot is rejected,
+/// the error gives its index and the tally is left as it was.
+///
+/// # Example
+/// ```
+///    use tallystick::ballotbox::BallotBox;
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::TallyError;
+///
+///    let mut ballots = BallotBox::<&str>::new();
+///    ballots.add(vec!["Alice", "Bob", "Carlos"])?;
+///    ballots.add(vec!["Bob", "Carlos", "Alice"])?;
+///    ballots.add_weighted(vec!["Alice", "Carlos", "Bob"], 2)?;
+///
+///    let mut condorcet = DefaultCondorcetTally::with_candidates(1, ballots.candidates());
+///    ballots.add_to_condorcet(&mut condorcet)?;
+///
+///    let mut plurality = DefaultPluralityTally::new(1);
+///    ballots.add_to_plurality(&mut plurality)?;
+///
+///    assert_eq!(condorcet.winners().into_unranked(), plurality.winners().into_unranked());
+///    # Ok::<(), TallyError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BallotBox<T, C = u64> {
+    ballots: Vec<(Ballot<T, C>, C)>,
+}
+
+impl<T, C> Default for BallotBox<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> BallotBox<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    /// Create a new, empty `BallotBox`.
+    pub fn new() -> Self {
+        BallotBox { ballots: Vec::new() }
+    }
+
+    /// Create a new `BallotBox` with capacity for the expected number of ballots.
+    pub fn with_capacity(expected_ballots: usize) -> Self {
+        BallotBox {
+            ballots: Vec::with_capacity(expected_ballots),
+        }
+    }
+
+    /// Add a ballot, listing candidates in order of preference.
+    pub fn add(&mut self, ballot: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted(ballot, C::one())
+    }
+
+    /// Add a weighted ballot, listing candidates in order of preference.
+    pub fn add_weighted(&mut self, ballot: Vec<T>, weight: C) -> Result<(), TallyError> {
+        // Safe to unwrap here since we can't have more than u32::MAX candidates anyways.
+        let ranked = ballot
+            .into_iter()
+            .enumerate()
+            .map(|(rank, c)| (c, rank.try_into().unwrap()))
+            .collect();
+        self.ranked_add_weighted(ranked, weight)
+    }
+
+    /// Add a ranked ballot, as a list of (candidate, rank) pairs.
+    pub fn ranked_add(&mut self, ballot: Vec<(T, u32)>) -> Result<(), TallyError> {
+        self.ranked_add_weighted(ballot, C::one())
+    }
+
+    /// Add a weighted ranked ballot, as a list of (candidate, rank) pairs.
+    pub fn ranked_add_weighted(&mut self, ballot: Vec<(T, u32)>, weight: C) -> Result<(), TallyError> {
+        check_duplicates_ranked_vote(&ballot)?;
+        self.ballots.push((Ballot::Ranked(ballot), weight));
+        Ok(())
+    }
+
+    /// Add a scored ballot, as a list of (candidate, score) pairs.
+    pub fn scored_add(&mut self, ballot: Vec<(T, C)>) -> Result<(), TallyError> {
+        self.scored_add_weighted(ballot, C::one())
+    }
+
+    /// Add a weighted scored ballot, as a list of (candidate, score) pairs.
+    pub fn scored_add_weighted(&mut self, ballot: Vec<(T, C)>, weight: C) -> Result<(), TallyError> {
+        for (i, (candidate, _)) in ballot.iter().enumerate() {
+            if let Some(offset) = ballot[i + 1..].iter().position(|(c, _)| c == candidate) {
+                return Err(TallyError::VoteHasDuplicateCandidates { position: i + 1 + offset });
+            }
+        }
+        self.ballots.push((Ballot::Scored(ballot), weight));
+        Ok(())
+    }
+
+    /// Get the number of ballots in the box.
+    pub fn len(&self) -> usize {
+        self.ballots.len()
+    }
+
+    /// Check if the box has no ballots.
+    pub fn is_empty(&self) -> bool {
+        self.ballots.is_empty()
+    }
+
+    /// Get the ballots in the box, with their weights, in the order they were added.
+    pub fn ballots(&self) -> &[(Ballot<T, C>, C)] {
+        &self.ballots
+    }
+
+    /// Get every candidate on any ballot, in the order they first appear.
+    pub fn candidates(&self) -> Vec<T> {
+        let mut candidates = Vec::new();
+        for (ballot, _) in self.ballots.iter() {
+            for candidate in ballot.candidates() {
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Add every ballot to a plurality tally, as a vote for its first preference.
+    ///
+    /// A vote with several equal first preferences is split equally between them.
+    pub fn add_to_plurality(&self, tally: &mut PluralityTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            self.add_strict_orderings(|ordering, weight| {
+                if let Some(first) = ordering.first() {
+                    tally.add_weighted_ref(first, weight);
+                }
+                Ok(())
+            })
+        })
+    }
+
+    /// Add every ballot to an approval tally, approving every ranked candidate and every candidate scored above zero.
+    pub fn add_to_approval(&self, tally: &mut ApprovalTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                let approved: Vec<T> = match ballot {
+                    Ballot::Ranked(_) => ballot.candidates(),
+                    Ballot::Scored(scored) => scored
+                        .iter()
+                        .filter(|(_, score)| *score > C::zero())
+                        .map(|(c, _)| c.clone())
+                        .collect(),
+                };
+                tally.add_weighted(approved, weight.clone()).map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every ballot to a score tally.
+    ///
+    /// A ranked ballot has no scores, so it is rejected with `TallyError::UnscoredBallot`.
+    pub fn add_to_score(&self, tally: &mut ScoreTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                match ballot {
+                    Ballot::Scored(scored) => tally.add_weighted_ref(scored, weight.clone()),
+                    Ballot::Ranked(_) => Err(TallyError::UnscoredBallot),
+                }
+                .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every ballot to a borda tally. `nightly`
+    ///
+    /// A vote with equally ranked candidates is split equally between every order of those candidates.
+    #[cfg(feature = "nightly")]
+    pub fn add_to_borda(&self, tally: &mut BordaTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), weight.clone())
+                    .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every ballot to a condorcet tally.
+    ///
+    /// The tally must already have every candidate in the box. See [`candidates()`](#method.candidates).
+    pub fn add_to_condorcet(&self, tally: &mut CondorcetTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), weight.clone())
+                    .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every ballot to a schulze tally. `nightly`
+    ///
+    /// The tally must already have every candidate in the box. See [`candidates()`](#method.candidates).
+    #[cfg(feature = "nightly")]
+    pub fn add_to_schulze(&self, tally: &mut SchulzeTally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), weight.clone())
+                    .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Add every ballot to an STV tally. `nightly`
+    #[cfg(feature = "nightly")]
+    pub fn add_to_stv(&self, tally: &mut stv::Tally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), weight.clone())
+                    .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    // Add every ballot as strict orderings of its candidates, splitting votes with equally ranked candidates.
+    fn add_strict_orderings<F>(&self, mut add: F) -> Result<(), TallyError>
+    where
+        F: FnMut(Vec<T>, C) -> Result<(), TallyError>,
+    {
+        for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+            let split = split_ranked_vote(&ballot.ranking(), weight.clone()).map_err(|e| e.on_ballot(index))?;
+            for (ordering, part) in split {
+                add(ordering, part).map_err(|e| e.on_ballot(index))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, C> BallotBox<T, C>
+where
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // Vote count type
+{
+    /// Add every ballot to an IRV tally. `nightly`
+    pub fn add_to_irv(&self, tally: &mut irv::Tally<T, C>) -> Result<(), TallyError> {
+        add_all(tally, |tally| {
+            for (index, (ballot, weight)) in self.ballots.iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), weight.clone())
+                    .map_err(|e| e.on_ballot(index))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Tally the ballots with several methods, and compare their results. `nightly`
+    ///
+    /// Every method fills `num_winners` seats, except IRV which always elects a single winner.
+    /// Approval counts ballots as [`add_to_approval()`](#method.add_to_approval) does, so a scored ballot only approves candidates scored above zero.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::ballotbox::BallotBox;
+    ///    use tallystick::election::Method;
+    ///    use tallystick::schulze::Variant;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut ballots = BallotBox::<&str>::new();
+    ///    ballots.add_weighted(vec!["Alice", "Bob", "Carlos"], 4)?;
+    ///    ballots.add_weighted(vec!["Bob", "Alice", "Carlos"], 3)?;
+    ///    ballots.add_weighted(vec!["Carlos", "Bob", "Alice"], 2)?;
+    ///
+    ///    let comparison = ballots.compare_methods(&[Method::Plurality, Method::Irv, Method::Schulze(Variant::Winning)], 1)?;
+    ///
+    ///    // Bob is the Condorcet winner, but plurality elects Alice.
+    ///    assert_eq!(comparison.condorcet_winner, Some("Bob"));
+    ///    assert_eq!(comparison.elects_condorcet_winner(), vec![false, true, true]);
+    ///    assert!(!comparison.agree(0, 2));
+    ///    assert!(comparison.agree(1, 2));
+    ///    # Ok::<(), TallyError>(())
+    /// ```
+    pub fn compare_methods(&self, methods: &[Method<C>], num_winners: usize) -> Result<MethodComparison<T, C>, TallyError> {
+        let candidates = self.candidates();
+
+        let ranked: Vec<(Vec<(T, u32)>, C)> = self
+            .ballots
+            .iter()
+            .map(|(ballot, weight)| (ballot.ranking(), weight.clone()))
+            .collect();
+
+        let mut results = Vec::with_capacity(methods.len());
+        for method in methods.iter() {
+            let num_winners = if *method == Method::Irv { 1 } else { num_winners };
+            let winners = match method {
+                // Approval reads scored ballots by score rather than by ranking, so it is counted like `add_to_approval`.
+                Method::Approval => {
+                    let mut tally = ApprovalTally::with_capacity(num_winners, candidates.len());
+                    self.add_to_approval(&mut tally)?;
+                    tally.winners()
+                }
+                _ => method.tally_ranked(num_winners, &candidates, &ranked)?,
+            };
+            results.push((method.clone(), winners));
+        }
+
+        let agreement = results
+            .iter()
+            .map(|(_, a)| results.iter().map(|(_, b)| same_winners(a, b)).collect())
+            .collect();
+
+        let mut condorcet = CondorcetTally::with_candidates(1, candidates);
+        self.add_to_condorcet(&mut condorcet)?;
+
+        Ok(MethodComparison {
+            results: results,
+            agreement: agreement,
+            condorcet_winner: condorcet.condorcet_winner(),
+        })
+    }
+}
+
+// Add ballots to a tally with `add`, restoring the tally if a ballot is rejected.
+fn add_all<S, F>(tally: &mut S, add: F) -> Result<(), TallyError>
+where
+    S: Clone,
+    F: FnOnce(&mut S) -> Result<(), TallyError>,
+{
+    let checkpoint = TallyCheckpoint::new(tally.clone());
+    let result = add(tally);
+    if result.is_err() {
+        *tally = checkpoint.into_tally();
+    }
+    result
+}
+
+// Check if two sets of winners contain the same candidates, regardless of rank.
+#[cfg(feature = "nightly")]
+fn same_winners<T: Clone + Eq + Hash>(a: &RankedWinners<T>, b: &RankedWinners<T>) -> bool {
+    a.len() == b.len() && a.iter().all(|ranked| b.contains(&ranked.candidate))
+}
+
+/// A comparison of the results of several tally methods on the same ballots. `nightly`
+///
+/// See [`BallotBox::compare_methods()`](struct.BallotBox.html#method.compare_methods).
+#[cfg(feature = "nightly")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodComparison<T: Clone + Eq, C> {
+    /// The winners of each method, in the order the methods were given.
+    pub results: Vec<(Method<C>, RankedWinners<T>)>,
+
+    /// Whether each pair of methods elected the same winners, regardless of rank. Indexed by the order the methods were given.
+    pub agreement: Vec<Vec<bool>>,
+
+    /// The candidate that beats every other candidate head to head, if there is one.
+    pub condorcet_winner: Option<T>,
+}
+
+#[cfg(feature = "nightly")]
+impl<T: Clone + Eq + Hash, C> MethodComparison<T, C> {
+    /// Check if two methods elected the same winners, by the order the methods were given.
+    pub fn agree(&self, a: usize, b: usize) -> bool {
+        self.agreement[a][b]
+    }
+
+    /// Check if every method elected the same winners.
+    pub fn unanimous(&self) -> bool {
+        self.agreement.iter().all(|row| row.iter().all(|agree| *agree))
+    }
+
+    /// Check, for each method, if it elected the Condorcet winner. Always false if there is no Condorcet winner.
+    pub fn elects_condorcet_winner(&self) -> Vec<bool> {
+        self.results
+            .iter()
+            .map(|(_, winners)| self.condorcet_winner.as_ref().is_some_and(|winner| winners.contains(winner)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plurality::DefaultPluralityTally;
+
+    #[test]
+    fn ballot_box_test() -> Result<(), TallyError> {
+        let mut ballots = BallotBox::<&str, u64>::new();
+        ballots.add(vec!["Alice", "Bob"])?;
+        ballots.ranked_add_weighted(vec![("Bob", 0), ("Carol", 0)], 2)?;
+        ballots.scored_add(vec![("Carol", 5), ("Alice", 0), ("Bob", 5)])?;
+        assert_eq!(
+            ballots.add(vec!["Alice", "Alice"]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        assert_eq!(ballots.len(), 3);
+        assert_eq!(ballots.candidates(), vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(ballots.ballots()[2].0.ranking(), vec![("Carol", 0), ("Bob", 0), ("Alice", 1)]);
+
+        // Equal first preferences split the vote, and a rejected ballot leaves the tally as it was.
+        let mut plurality = DefaultPluralityTally::new(1);
+        plurality.add("Dave");
+        assert_eq!(
+            ballots.add_to_plurality(&mut plurality),
+            Err(TallyError::IndivisibleWeight.on_ballot(2))
+        );
+        assert_eq!(plurality.totals(), vec![("Dave", 1)]);
+
+        let mut approval = ApprovalTally::new(1);
+        ballots.add_to_approval(&mut approval)?;
+        assert_eq!(approval.totals(), vec![("Bob", 4), ("Carol", 3), ("Alice", 1)]);
+
+        let mut score = ScoreTally::new(1);
+        assert_eq!(ballots.add_to_score(&mut score), Err(TallyError::UnscoredBallot.on_ballot(0)));
+        assert!(score.totals().is_empty());
+
+        // Carol is unknown, so the second ballot is rejected after the first was added.
+        let mut condorcet = CondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+        assert_eq!(
+            ballots.add_to_condorcet(&mut condorcet),
+            Err(TallyError::UnknownCandidate { position: 1 }.on_ballot(1))
+        );
+        assert!(condorcet.totals().iter().all(|(_, count)| *count == 0));
+
+        let mut condorcet = CondorcetTally::with_candidates(1, ballots.candidates());
+        ballots.add_to_condorcet(&mut condorcet)?;
+        assert_eq!(condorcet.winners().into_unranked(), vec!["Bob"]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn compare_methods_test() -> Result<(), TallyError> {
+        let mut ballots = BallotBox::<&str, u64>::new();
+        ballots.add_weighted(vec!["Alice", "Bob", "Carol"], 2)?;
+        ballots.add_weighted(vec!["Bob", "Carol", "Alice"], 2)?;
+        ballots.add_weighted(vec!["Carol", "Alice", "Bob"], 2)?;
+        ballots.ranked_add_weighted(vec![("Alice", 0), ("Bob", 0)], 2)?;
+
+        let methods = vec![Method::Borda, Method::Approval, Method::Stv(crate::Quota::Droop)];
+        let comparison = ballots.compare_methods(&methods, 2)?;
+        assert_eq!(comparison.results.len(), 3);
+        assert!(comparison
+            .results
+            .iter()
+            .all(|(_, winners)| winners.contains(&"Alice") && winners.contains(&"Bob")));
+        assert!(comparison.unanimous());
+
+        // Alice beats Bob but ties with Carol head to head, so there is no Condorcet winner.
+        assert_eq!(comparison.condorcet_winner, None);
+        assert_eq!(comparison.elects_condorcet_winner(), vec![false, false, false]);
+
+        // Condorcet methods count a tie on a single ballot directly, without splitting its weight.
+        // Alice now beats Carol as well as Bob.
+        ballots.ranked_add(vec![("Alice", 0), ("Bob", 0), ("Carol", 1)])?;
+        let comparison = ballots.compare_methods(&[Method::Condorcet, Method::Schulze(crate::schulze::Variant::Winning)], 1)?;
+        assert_eq!(comparison.results[0].1.clone().into_unranked(), vec!["Alice"]);
+        assert!(comparison.unanimous());
+        assert_eq!(comparison.condorcet_winner, Some("Alice"));
+        assert_eq!(
+            ballots.compare_methods(&[Method::Plurality], 1).err(),
+            Some(TallyError::IndivisibleWeight.on_ballot(4))
+        );
+
+        // Approval counts equal ranks without splitting, and does not approve candidates scored zero.
+        let mut ballots = BallotBox::<&str, u64>::new();
+        ballots.ranked_add(vec![("Alice", 0), ("Bob", 0)])?;
+        ballots.scored_add(vec![("Alice", 1), ("Carol", 0)])?;
+        ballots.scored_add(vec![("Bob", 2), ("Carol", 0)])?;
+        ballots.scored_add(vec![("Alice", 2), ("Carol", 0)])?;
+        let comparison = ballots.compare_methods(&[Method::Approval], 1)?;
+        assert_eq!(comparison.results[0].1.clone().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+}
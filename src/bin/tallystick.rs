@@ -0,0 +1,477 @@
+//! Count the ballots in a file from the command line.
+//!
+//! Requires the `cli` feature to be enabled:
+//!
+//! ```text
+//! cargo install tallystick --features cli
+//! tallystick --method stv --winners 3 ballots.blt
+//! ```
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+
+use serde_json::json;
+use tallystick::approval::ApprovalTally;
+use tallystick::ballotbox::BallotBox;
+use tallystick::borda::{self, BordaTally};
+use tallystick::condorcet::CondorcetTally;
+use tallystick::plurality::PluralityTally;
+use tallystick::rankedpairs::{self, RankedPairsTally};
+use tallystick::schulze::{self, SchulzeTally};
+use tallystick::score::ScoreTally;
+use tallystick::util::{self, ParsedVote};
+use tallystick::{irv, stv, Exhausted, Quota, RankedCandidate, RankedWinners, Round, Transfer};
+
+const USAGE: &str = "Count the ballots in a file.
+
+Usage: tallystick [OPTIONS] [FILE]
+
+Reads ballots from FILE, or from standard input if no file is given.
+
+Options:
+  -m, --method <METHOD>    plurality, approval, score, borda, condorcet, schulze, schulze-margin,
+                           schulze-ratio, ranked-pairs, ranked-pairs-margin, irv or stv [default: schulze]
+  -w, --winners <N>        Number of winners [default: the seats in a BLT file, or 1]
+  -q, --quota <QUOTA>      STV quota: droop, hagenbach, hare or imperiali [default: droop]
+  -f, --format <FORMAT>    votes, blt, preflib or json [default: inferred from the file extension, or votes]
+      --json               Print the results as JSON
+  -h, --help               Print this help";
+
+const METHODS: &[&str] = &[
+    "plurality",
+    "approval",
+    "score",
+    "borda",
+    "condorcet",
+    "schulze",
+    "schulze-margin",
+    "schulze-ratio",
+    "ranked-pairs",
+    "ranked-pairs-margin",
+    "irv",
+    "stv",
+];
+
+// Votes are counted as floats, so that surplus transfers keep their fractional value and ballots can have decimal weights.
+type Count = f64;
+
+struct Options {
+    method: String,
+    winners: Option<usize>,
+    quota: Quota<Count>,
+    format: Option<String>,
+    json: bool,
+    file: Option<String>,
+}
+
+// Ballots read from a file, with any declared candidates and number of seats.
+struct Input {
+    candidates: Vec<String>,
+    ballots: BallotBox<String, Count>,
+    seats: Option<usize>,
+}
+
+// The results of a count, ready to print.
+struct Report {
+    winners: RankedWinners<String>,
+    totals: Vec<(String, Count)>,
+    ranking: Vec<RankedCandidate<String>>,
+    exhausted: Vec<Exhausted<Count>>,
+    quota: Option<Count>,
+    rounds: Vec<Round<String, Count>>,
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(message) => {
+            eprintln!("tallystick: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    // Errors from the library already name the crate, so they are printed as they are.
+    if let Err(message) = run(&options) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+// An error raised by the command line tool itself.
+fn fail<M: std::fmt::Display>(message: M) -> String {
+    format!("tallystick: {}", message)
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let input: Box<dyn Read> = match &options.file {
+        Some(path) => Box::new(File::open(path).map_err(|e| fail(format!("cannot open {}: {}", path, e)))?),
+        None => Box::new(io::stdin()),
+    };
+    let format = match &options.format {
+        Some(format) => format.clone(),
+        None => infer_format(options.file.as_deref()),
+    };
+
+    let Input {
+        mut candidates,
+        ballots,
+        seats,
+    } = read_ballots(input, &format)?;
+    if candidates.is_empty() {
+        candidates = ballots.candidates();
+    }
+    let num_winners = options.winners.or(seats).unwrap_or(1);
+
+    let report = count(options, num_winners, candidates, &ballots)?;
+    if options.json {
+        print_json(options, &report)?;
+    } else {
+        print_text(options, &report);
+    }
+    Ok(())
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        method: "schulze".to_string(),
+        winners: None,
+        quota: Quota::Droop,
+        format: None,
+        json: false,
+        file: None,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} requires a value", name));
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--json" => options.json = true,
+            "-m" | "--method" => options.method = value(&arg)?,
+            "-f" | "--format" => options.format = Some(value(&arg)?),
+            "-w" | "--winners" => {
+                let winners = value(&arg)?;
+                options.winners = Some(winners.parse().map_err(|_| format!("invalid number of winners {}", winners))?);
+            }
+            "-q" | "--quota" => {
+                options.quota = match value(&arg)?.as_str() {
+                    "droop" => Quota::Droop,
+                    "hagenbach" => Quota::Hagenbach,
+                    "hare" => Quota::Hare,
+                    "imperiali" => Quota::Imperiali,
+                    quota => return Err(format!("unknown quota {}", quota)),
+                }
+            }
+            _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {}", arg)),
+            _ if options.file.is_some() => return Err(format!("unexpected argument {}", arg)),
+            _ if arg == "-" => {}
+            _ => options.file = Some(arg),
+        }
+    }
+
+    if !METHODS.contains(&options.method.as_str()) {
+        return Err(format!("unknown method {}", options.method));
+    }
+    Ok(Some(options))
+}
+
+fn infer_format(file: Option<&str>) -> String {
+    let extension = file
+        .and_then(|file| Path::new(file).extension())
+        .and_then(|extension| extension.to_str());
+    match extension.map(str::to_lowercase).as_deref() {
+        Some("blt") => "blt",
+        Some("soc") | Some("soi") | Some("toc") | Some("toi") => "preflib",
+        Some("json") => "json",
+        _ => "votes",
+    }
+    .to_string()
+}
+
+fn read_ballots<R: Read>(input: R, format: &str) -> Result<Input, String> {
+    let mut ballots = BallotBox::new();
+    let mut withdrawn = Vec::new();
+    let (candidates, votes, seats) = match format {
+        "votes" => {
+            let parsed = util::read_votes(input).map_err(|e| e.to_string())?;
+            (parsed.candidates, parsed.votes, None)
+        }
+        "blt" => {
+            let blt = util::read_blt(input).map_err(|e| e.to_string())?;
+            withdrawn = blt.withdrawn;
+            let candidates = blt.candidates.into_iter().filter(|c| !withdrawn.contains(c)).collect();
+            (candidates, blt.votes, Some(blt.seats))
+        }
+        "preflib" => {
+            let preflib = util::read_preflib(input).map_err(|e| e.to_string())?;
+            (preflib.candidates, preflib.votes, None)
+        }
+        "json" => {
+            let json = util::read_votes_json(input).map_err(|e| e.to_string())?;
+            let candidates = json.candidates.clone();
+            let ballots = json.into_ballot_box().map_err(|e| e.to_string())?;
+            return Ok(Input {
+                candidates,
+                ballots,
+                seats: None,
+            });
+        }
+        format => return Err(fail(format!("unknown format {}", format))),
+    };
+
+    // Withdrawn candidates are struck from every ballot, and the candidates ranked below them move up.
+    for (index, (vote, weight)) in votes.into_iter().enumerate() {
+        let added = match vote {
+            ParsedVote::Scored(scored) => ballots.scored_add_weighted(scored, weight),
+            vote => {
+                let mut ranked = vote.into_ranked();
+                ranked.retain(|(candidate, _)| !withdrawn.contains(candidate));
+                ballots.ranked_add_weighted(ranked, weight)
+            }
+        };
+        added.map_err(|e| e.on_ballot(index).to_string())?;
+    }
+    Ok(Input {
+        candidates,
+        ballots,
+        seats,
+    })
+}
+
+fn count(options: &Options, num_winners: usize, candidates: Vec<String>, ballots: &BallotBox<String, Count>) -> Result<Report, String> {
+    let mut report = Report {
+        winners: RankedWinners::default(),
+        totals: Vec::new(),
+        ranking: Vec::new(),
+        exhausted: Vec::new(),
+        quota: None,
+        rounds: Vec::new(),
+    };
+    let tally_error = |e: tallystick::TallyError| e.to_string();
+
+    match options.method.as_str() {
+        "plurality" => {
+            let mut tally = PluralityTally::new(num_winners);
+            ballots.add_to_plurality(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+            report.totals = tally.totals();
+        }
+        "approval" => {
+            let mut tally = ApprovalTally::new(num_winners);
+            ballots.add_to_approval(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+            report.totals = tally.totals();
+        }
+        "score" => {
+            let mut tally = ScoreTally::new(num_winners);
+            ballots.add_to_score(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+            report.totals = tally.totals();
+        }
+        "borda" => {
+            let mut tally = BordaTally::new(num_winners, borda::Variant::Borda);
+            ballots.add_to_borda(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+            report.totals = tally.totals();
+        }
+        "condorcet" => {
+            let mut tally = CondorcetTally::with_candidates(num_winners, candidates);
+            ballots.add_to_condorcet(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+        }
+        "schulze" | "schulze-margin" | "schulze-ratio" => {
+            let variant = match options.method.as_str() {
+                "schulze-margin" => schulze::Variant::Margin,
+                "schulze-ratio" => schulze::Variant::Ratio,
+                _ => schulze::Variant::Winning,
+            };
+            let mut tally = SchulzeTally::with_candidates(num_winners, variant, candidates);
+            ballots.add_to_schulze(&mut tally).map_err(tally_error)?;
+            report.winners = tally.winners();
+        }
+        "ranked-pairs" | "ranked-pairs-margin" => {
+            let variant = match options.method.as_str() {
+                "ranked-pairs-margin" => rankedpairs::Variant::Margin,
+                _ => rankedpairs::Variant::Winning,
+            };
+            let mut tally = RankedPairsTally::with_candidates(num_winners, variant, candidates);
+            for (index, (ballot, weight)) in ballots.ballots().iter().enumerate() {
+                tally
+                    .ranked_add_weighted(&ballot.ranking(), *weight)
+                    .map_err(|e| tally_error(e.on_ballot(index)))?;
+            }
+            report.winners = tally.winners();
+        }
+        "irv" => {
+            let mut tally = irv::Tally::with_candidates(Transfer::Meek, candidates);
+            ballots.add_to_irv(&mut tally).map_err(tally_error)?;
+            report.winners = tally.tally_winners();
+            report.ranking = tally.tally_ranked();
+            report.exhausted = tally.exhausted();
+        }
+        "stv" => {
+            let mut tally = stv::Tally::with_candidates(num_winners, options.quota.clone(), candidates);
+            ballots.add_to_stv(&mut tally).map_err(tally_error)?;
+            let sheet = tally.count_sheet();
+            report.winners = tally.winners();
+            report.exhausted = tally.exhausted();
+            report.quota = Some(sheet.quota);
+            report.rounds = sheet.rounds;
+        }
+        method => return Err(fail(format!("unknown method {}", method))),
+    }
+
+    Ok(report)
+}
+
+fn print_text(options: &Options, report: &Report) {
+    println!("Method: {}", options.method);
+    println!("Winners:");
+    for ranked in report.winners.iter() {
+        println!("  {}. {}", ranked.rank + 1, ranked.candidate);
+    }
+    if report.winners.check_overflow() {
+        println!("  (tied for the last seat)");
+    }
+
+    if !report.totals.is_empty() {
+        println!("Totals:");
+        for (candidate, total) in report.totals.iter() {
+            println!("  {}: {}", candidate, total);
+        }
+    }
+    if !report.ranking.is_empty() {
+        println!("Ranking:");
+        for ranked in report.ranking.iter() {
+            println!("  {}. {}", ranked.rank + 1, ranked.candidate);
+        }
+    }
+    if let Some(quota) = report.quota {
+        println!("Quota: {}", quota);
+    }
+    if !report.rounds.is_empty() {
+        println!("Rounds:");
+        for (number, round) in report.rounds.iter().enumerate() {
+            println!("  Round {}:", number + 1);
+            for (candidate, votes) in round.totals.iter() {
+                println!("    {}: {}", candidate, votes);
+            }
+            if !round.elected.is_empty() {
+                println!("    Elected: {}", round.elected.join(", "));
+            }
+            if !round.eliminated.is_empty() {
+                println!("    Eliminated: {}", round.eliminated.join(", "));
+            }
+        }
+    }
+    if !report.exhausted.is_empty() {
+        println!("Exhausted ballots:");
+        for (round, exhausted) in report.exhausted.iter().enumerate() {
+            println!("  Round {}: {} ballots, weight {}", round + 1, exhausted.ballots, exhausted.weight);
+        }
+    }
+}
+
+fn print_json(options: &Options, report: &Report) -> Result<(), String> {
+    let ranked = |ranked: &[RankedCandidate<String>]| -> Vec<serde_json::Value> {
+        ranked.iter().map(|r| json!({ "candidate": r.candidate, "rank": r.rank })).collect()
+    };
+    let output = json!({
+        "method": options.method,
+        "num_winners": report.winners.num_winners,
+        "winners": ranked(&report.winners.winners),
+        "totals": report.totals.iter().map(|(candidate, total)| json!({ "candidate": candidate, "total": total })).collect::<Vec<_>>(),
+        "ranking": ranked(&report.ranking),
+        "exhausted": report.exhausted.iter().map(|e| json!({ "ballots": e.ballots, "weight": e.weight })).collect::<Vec<_>>(),
+        "quota": report.quota,
+        "rounds": report.rounds.iter().map(|round| json!({
+            "totals": round.totals.iter().map(|(candidate, votes)| json!({ "candidate": candidate, "votes": votes })).collect::<Vec<_>>(),
+            "elected": round.elected,
+            "eliminated": round.eliminated,
+            "exhausted": { "ballots": round.exhausted.ballots, "weight": round.exhausted.weight },
+        })).collect::<Vec<_>>(),
+    });
+    let output = serde_json::to_string_pretty(&output).map_err(|e| fail(format!("cannot write results: {}", e)))?;
+    println!("{}", output);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(method: &str) -> Options {
+        Options {
+            method: method.to_string(),
+            winners: None,
+            quota: Quota::Droop,
+            format: None,
+            json: false,
+            file: None,
+        }
+    }
+
+    // Read and count a ballot file, as the command line would.
+    fn count_file(method: &str, format: &str, file: &str) -> Result<Report, String> {
+        let Input {
+            mut candidates,
+            ballots,
+            seats,
+        } = read_ballots(file.as_bytes(), format)?;
+        if candidates.is_empty() {
+            candidates = ballots.candidates();
+        }
+        count(&options(method), seats.unwrap_or(1), candidates, &ballots)
+    }
+
+    #[test]
+    fn cli_stv_surplus() -> Result<(), String> {
+        // Alice's surplus of 2 over the quota of 5 elects Bob ahead of Carol.
+        let blt = "3 2
+7 1 2 0
+4 3 0
+3 2 0
+0
+\"Alice\"
+\"Bob\"
+\"Carol\"
+\"Surplus test\"
+";
+        let report = count_file("stv", "blt", blt)?;
+        assert_eq!(report.winners.into_unranked(), vec!["Alice", "Bob"]);
+        assert_eq!(report.quota, Some(5.0));
+        assert_eq!(report.rounds[1].totals, vec![("Bob".to_string(), 5.0), ("Carol".to_string(), 4.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn cli_withdrawn_and_ties() -> Result<(), String> {
+        // Dave is withdrawn, and the ballots ranking him count for their next preference.
+        let blt = "4 1
+-4
+3 4 1 0
+2 2 0
+2 3 0
+1 1=2 0
+0
+\"Alice\"
+\"Bob\"
+\"Carol\"
+\"Dave\"
+\"Withdrawn test\"
+";
+        for method in METHODS.iter().filter(|m| !["score", "approval", "plurality"].contains(m)) {
+            let report = count_file(method, "blt", blt)?;
+            assert_eq!(report.winners.into_unranked(), vec!["Alice"], "{}", method);
+        }
+
+        let error = count_file("stv", "blt", "1 1\n1 7 0\n0\n\"Alice\"\n\"Title\"\n").err().unwrap();
+        assert!(!error.starts_with("tallystick: tallystick:"), "{}", error);
+        Ok(())
+    }
+}
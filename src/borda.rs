@@ -1,16 +1,25 @@
 use super::check_duplicate;
+use super::condorcet::CondorcetTally;
+use super::margin::{self, MarginOfVictory};
 use super::plurality::PluralityTally;
+use super::quorum::{turnout_methods, Quorum, Turnout};
 use super::result::CountedCandidates;
 use super::result::RankedCandidate;
 use super::result::RankedWinners;
+use super::result::Standings;
+use super::result::TalliedWinners;
 use super::Numeric;
+use super::Selection;
+use super::TallyCheckpoint;
 use super::TallyError;
+use super::Truncation;
 use hashbrown::HashMap;
 use hashbrown::HashSet;
+use num_traits::FromPrimitive;
 use num_traits::Num;
-use num_traits::NumCast;
 use std::hash::Hash;
 use std::ops::AddAssign;
+use std::rc::Rc;
 
 // We often convert `C` (vote count types) to and from small integers.
 // If we can't sucessfully convert a small integer into `C`, we panic since converting from a small is trivial.
@@ -110,7 +119,7 @@ pub enum Variant<C> {
     Custom(Box<dyn Fn(usize, usize, usize) -> C>),
 }
 
-impl<C: Numeric + Num + NumCast> Variant<C> {
+impl<C: Numeric + Num + FromPrimitive> Variant<C> {
     /// Get the number of points for a candidate at a certain position on a ballot.
     ///
     /// - `candidate_position` is the position of the candidate on the marked ballot. It is `0` for the 1st candidate, `1` for the second candidate etc.
@@ -122,22 +131,58 @@ impl<C: Numeric + Num + NumCast> Variant<C> {
         // Unwrapping options SHOULD be good here. It's very unlikely that C can't represent a small unsigned integer.
         // If it is the case that a small integer can't be represented in C, that's a bug.
         match self {
-            Variant::Borda => C::from(num_candidates - candidate_position - 1).expect(C_FROM_PANIC),
-            Variant::ClassicBorda => C::from(num_candidates - candidate_position).expect(C_FROM_PANIC),
+            Variant::Borda => C::from_usize(num_candidates - candidate_position - 1).expect(C_FROM_PANIC),
+            Variant::ClassicBorda => C::from_usize(num_candidates - candidate_position).expect(C_FROM_PANIC),
             Variant::Dowdall => {
                 if !C::fraction() {
                     panic!(
                         "tallystick::borda::Variant::Dowdall cannot be used with an integer count type. Please use a float or a rational."
                     )
                 }
-                C::one() / C::from(candidate_position + 1).expect(C_FROM_PANIC)
+                C::one() / C::from_usize(candidate_position + 1).expect(C_FROM_PANIC)
             }
-            Variant::ModifiedClassicBorda => C::from(num_marked - candidate_position).expect(C_FROM_PANIC),
+            Variant::ModifiedClassicBorda => C::from_usize(num_marked.saturating_sub(candidate_position)).expect(C_FROM_PANIC),
             Variant::Custom(boxed_func) => boxed_func(candidate_position, num_candidates, num_marked),
         }
     }
 }
 
+/// Specifies how candidates left unranked on a truncated ballot are scored.
+///
+/// Points are shared by dividing in the count type, so integer count types round shares down.
+/// Use a float or a rational count type for exact shares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unranked {
+    /// Unranked candidates are given no points, and the points for the positions left unranked are not awarded.
+    #[default]
+    Zero,
+
+    /// Unranked candidates are treated as tied for the positions left unranked, each getting the average of the points for those positions.
+    ///
+    /// Example point allocation for a ballot ranking two of four candidates with [`Variant::Borda`](enum.Variant.html#variant.Borda):
+    ///
+    /// | Position on ballot  | Candiate | Points |
+    /// | --------------------|----------|--------|
+    /// | 0                   | Alice    | 3      |
+    /// | 1                   | Bob      | 2      |
+    /// | Unranked            | Carlos   | ½      |
+    /// | Unranked            | Dave     | ½      |
+    Average,
+
+    /// The points for the positions left unranked are shared equally between the ranked candidates,
+    /// so that every ballot awards the same number of points.
+    ///
+    /// Example point allocation for a ballot ranking two of four candidates with [`Variant::Borda`](enum.Variant.html#variant.Borda):
+    ///
+    /// | Position on ballot  | Candiate | Points |
+    /// | --------------------|----------|--------|
+    /// | 0                   | Alice    | 3½     |
+    /// | 1                   | Bob      | 2½     |
+    /// | Unranked            | Carlos   | 0      |
+    /// | Unranked            | Dave     | 0      |
+    Redistribute,
+}
+
 /// A borda tally using `u64` integers to count votes.
 /// `DefaultBordaTally` is generally preferred over `BordaTally`, except when using the `Variant::Dowdall` variant.
 /// Since this is an alias, refer to [`BordaTally`](struct.BordaTally.html) for method documentation.
@@ -166,6 +211,13 @@ impl<C: Numeric + Num + NumCast> Variant<C> {
 /// ```
 pub type DefaultBordaTally<T> = BordaTally<T, u64>;
 
+/// A borda tally using `num_rational::Rational64` to count votes. `rational`
+///
+/// Rational counts let `Variant::Dowdall` be tallied exactly.
+/// Since this is an alias, refer to [`BordaTally`](struct.BordaTally.html) for method documentation.
+#[cfg(feature = "rational")]
+pub type RationalBordaTally<T> = BordaTally<T, num_rational::Rational64>;
+
 /// A generic borda tally.
 ///
 /// Generics:
@@ -186,21 +238,29 @@ pub type DefaultBordaTally<T> = BordaTally<T, u64>;
 ///
 ///    let winners = tally.winners();
 /// ```
+///
+/// Unlike the plurality, approval and score tallies, `BordaTally` has no `checked_*` methods.
+/// Points are only totalled when the tally is counted, and depend on how many candidates there are by then,
+/// so choose a count type wide enough for the number of ballots times the most points a ballot can award.
+#[derive(Clone)]
 pub struct BordaTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
-    running_total: HashMap<Vec<T>, C>,
+    running_total: HashMap<Selection<T>, C>,
     candidates: HashSet<T>,
     num_winners: usize,
-    variant: Variant<C>,
+    variant: Rc<Variant<C>>, // Shared between snapshots, since a custom variant cannot be cloned
+    turnout: Turnout<C>,
+    truncation: Truncation,
+    unranked: Unranked,
 }
 
 impl<T, C> BordaTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     /// Create a new `BordaTally` with the given number of winners.
     ///
@@ -211,7 +271,10 @@ where
             running_total: HashMap::new(),
             candidates: HashSet::new(),
             num_winners: num_winners,
-            variant: variant,
+            variant: Rc::new(variant),
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            unranked: Unranked::Zero,
         }
     }
 
@@ -221,10 +284,43 @@ where
             running_total: HashMap::with_capacity(expected_candidates),
             candidates: HashSet::with_capacity(expected_candidates),
             num_winners: num_winners,
-            variant: variant,
+            variant: Rc::new(variant),
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            unranked: Unranked::Zero,
         }
     }
 
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    /// A full ranking is checked against every candidate the tally has seen so far, including those on the ballot being added.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Set how candidates left unranked on a truncated ballot are scored. Defaults to [`Unranked::Zero`](enum.Unranked.html#variant.Zero).
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::borda::{BordaTally, Unranked, Variant};
+    ///
+    ///    let mut tally = BordaTally::<&str, f64>::new(1, Variant::Borda).with_unranked(Unranked::Average);
+    ///    tally.add(vec!["Alice", "Bob", "Carlos", "Dave"])?;
+    ///    tally.add(vec!["Dave"])?;
+    ///
+    ///    // Alice, Bob and Carlos share the 2, 1 and 0 points for the positions the second ballot leaves unranked.
+    ///    let mut totals = tally.totals();
+    ///    totals.sort_by(|a, b| a.0.cmp(b.0));
+    ///    assert_eq!(totals, vec![("Alice", 4.0), ("Bob", 3.0), ("Carlos", 2.0), ("Dave", 3.0)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_unranked(mut self, unranked: Unranked) -> Self {
+        self.unranked = unranked;
+        self
+    }
+
     /// Add a new vote
     ///
     /// Votes are represented as a vector of ranked candidates, ordered by preference.
@@ -242,22 +338,73 @@ where
     /// By default takes a weight as a `usize` integer, but can be customized by using `BordaTally` with a custom vote type.
     pub fn add_weighted(&mut self, vote: Vec<T>, weight: C) -> Result<(), TallyError> {
         check_duplicate(&vote)?;
+        self.check_truncation(&vote)?;
 
+        self.turnout.add_valid(&weight);
+        self.add_selection(vote, weight);
+
+        Ok(())
+    }
+
+    /// Add a ranked vote.
+    ///
+    /// A ranked vote is a list of tuples of (candidate, rank), where rank is ascending.
+    /// Two candidates with the same rank are equal in preference, and share the points of the positions they take up.
+    pub fn ranked_add(&mut self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.ranked_add_weighted(vote, C::one())
+    }
+
+    /// Add a ranked vote with a weight.
+    ///
+    /// Equally ranked candidates are each given the average of the points for the positions they take up,
+    /// by splitting the vote equally between every order of those candidates.
+    /// Integer count types return `TallyError::IndivisibleWeight` if the weight cannot be split evenly,
+    /// and a vote with more than 5040 such orders returns `TallyError::TooManyTies`.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::borda::DefaultBordaTally;
+    ///    use tallystick::borda::Variant;
+    ///
+    ///    let mut tally = DefaultBordaTally::new(1, Variant::Borda);
+    ///    tally.ranked_add_weighted(&[("Alice", 0), ("Bob", 1), ("Carlos", 1)], 2)?;
+    ///
+    ///    // Bob and Carlos share the 1 and 0 points for second and third place.
+    ///    let mut totals = tally.totals();
+    ///    totals.sort();
+    ///    assert_eq!(totals, vec![("Alice", 4), ("Bob", 1), ("Carlos", 1)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        let split = crate::util::split_ranked_vote(vote, weight.clone())?;
+        if let Some((selection, _)) = split.first() {
+            self.check_truncation(selection)?;
+        }
+
+        self.turnout.add_valid(&weight);
+        for (selection, part) in split {
+            self.add_selection(selection, part);
+        }
+
+        Ok(())
+    }
+
+    // Add a checked vote to the running total, registering any new candidates on it.
+    fn add_selection(&mut self, vote: Vec<T>, weight: C) {
         for candidate in vote.iter() {
             if !self.candidates.contains(candidate) {
                 self.candidates.insert(candidate.clone());
             }
         }
 
-        let entry = self.running_total.entry(vote);
+        let entry = self.running_total.entry(Selection::from_vec(vote));
         *entry.or_insert(C::zero()) += weight;
-
-        Ok(())
     }
 
     /// Add a weighted vote by reference
     pub fn add_weighted_ref(&mut self, vote: &[T], weight: C) -> Result<(), TallyError> {
         check_duplicate(vote)?;
+        self.check_truncation(vote)?;
 
         for candidate in vote.iter() {
             if !self.candidates.contains(candidate) {
@@ -265,32 +412,89 @@ where
             }
         }
 
-        let entry = self.running_total.entry(vote.to_vec());
-        *entry.or_insert(C::zero()) += weight;
+        self.turnout.add_valid(&weight);
+        // Look the vote up by reference, so a vote that has been seen before is not copied.
+        match self.running_total.get_mut(vote) {
+            Some(votecount) => *votecount += weight,
+            None => {
+                self.running_total.insert(vote.iter().cloned().collect(), weight);
+            }
+        }
 
         Ok(())
     }
 
+    // Check a vote against the truncation policy, counting any new candidates on the vote.
+    fn check_truncation(&self, vote: &[T]) -> Result<(), TallyError> {
+        let new_candidates = vote.iter().filter(|c| !self.candidates.contains(*c)).count();
+        self.truncation.check(vote.len(), self.candidates.len() + new_candidates)
+    }
+
+    turnout_methods!(winners);
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    /// The candidate is removed from every ballot, and candidates ranked below them move up.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        if !self.candidates.remove(candidate) {
+            return;
+        }
+
+        let mut running_total = HashMap::with_capacity(self.running_total.len());
+        for (mut selection, votecount) in self.running_total.drain() {
+            selection.retain(|c| c != candidate);
+            *running_total.entry(selection).or_insert(C::zero()) += votecount;
+        }
+        self.running_total = running_total;
+    }
+
     /// Get a ranked list of winners. Winners with the same rank are tied.
     /// The number of winners might be greater than the requested `num_winners` if there is a tie.
     /// In a borda count, the winners are determine by what candidate obtains the most points.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
     pub fn winners(&self) -> RankedWinners<T> {
-        let mut counted = CountedCandidates::new();
-        for (candidate, votecount) in self.totals().iter() {
-            counted.push(candidate.clone(), *votecount);
-        }
+        self.winners_n(self.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.turnout.decide(self.counted().into_ranked(num_winners))
+    }
 
-        counted.into_ranked(self.num_winners)
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
     }
 
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
-        let mut counted = CountedCandidates::new();
-        for (candidate, votecount) in self.totals().iter() {
-            counted.push(candidate.clone(), *votecount);
-        }
+        self.counted().into_ranked(0).into_vec()
+    }
 
-        counted.into_ranked(0).into_vec()
+    /// Get each candidate with their point total, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, C> {
+        self.totals().into()
     }
 
     /// Get point totals for this tally.
@@ -315,18 +519,169 @@ where
     /// ```
     pub fn totals(&self) -> Vec<(T, C)> {
         // Make a little plurality tally and use borda points as weights
-        let mut plurality = PluralityTally::with_capacity(self.num_winners, self.candidates.len());
+        let num_candidates = self.candidates.len();
+        let mut plurality = PluralityTally::with_capacity(self.num_winners, num_candidates);
         for (selection, votecount) in self.running_total.iter() {
-            let num_marked = selection.len();
-            for (position, candidate) in selection.iter().enumerate() {
-                let points: C = self.variant.points(position, self.candidates.len(), num_marked);
-                plurality.add_weighted_ref(candidate, *votecount * points);
+            let (points, unranked_points) = self.ballot_points(selection.len());
+            for (candidate, points) in selection.iter().zip(points) {
+                plurality.add_to_total_ref(candidate, votecount.clone() * points);
+            }
+            if self.unranked == Unranked::Average && selection.len() < num_candidates {
+                for candidate in self.candidates.iter().filter(|c| !selection.contains(c)) {
+                    plurality.add_to_total_ref(candidate, votecount.clone() * unranked_points.clone());
+                }
             }
         }
 
         plurality.totals()
     }
 
+    /// Get how many ballots ranked each candidate at each position, and the points each position earned them.
+    ///
+    /// Candidates are returned in the order of [`totals()`](#method.totals).
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::borda::DefaultBordaTally;
+    ///    use tallystick::borda::Variant;
+    ///
+    ///    let mut tally = DefaultBordaTally::new(1, Variant::Borda);
+    ///    for _ in 0..3 { tally.add(vec!["Alice", "Bob", "Carol"])?; }
+    ///    tally.add(vec!["Bob", "Alice"])?;
+    ///
+    ///    let alice = &tally.position_breakdown()[0];
+    ///    assert_eq!(alice.candidate, "Alice");
+    ///    assert_eq!(alice.ballots, vec![3, 1, 0]);
+    ///    assert_eq!(alice.points, vec![6, 1, 0]);
+    ///    assert_eq!(alice.total(), 7);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn position_breakdown(&self) -> Vec<PositionBreakdown<T, C>> {
+        let num_candidates = self.candidates.len();
+        let mut breakdowns = HashMap::<T, PositionBreakdown<T, C>>::with_capacity(num_candidates);
+        for candidate in self.candidates.iter() {
+            breakdowns.insert(candidate.clone(), PositionBreakdown::new(candidate.clone(), num_candidates));
+        }
+
+        for (selection, votecount) in self.running_total.iter() {
+            let (points, unranked_points) = self.ballot_points(selection.len());
+            for (position, (candidate, points)) in selection.iter().zip(points).enumerate() {
+                // Ok to unwrap since every candidate on a ballot is a candidate of the tally.
+                let breakdown = breakdowns.get_mut(candidate).unwrap();
+                breakdown.ballots[position] += votecount.clone();
+                breakdown.points[position] += votecount.clone() * points;
+            }
+            for (candidate, breakdown) in breakdowns.iter_mut() {
+                if !selection.contains(candidate) {
+                    breakdown.unranked_ballots += votecount.clone();
+                    breakdown.unranked_points += votecount.clone() * unranked_points.clone();
+                }
+            }
+        }
+
+        // Ok to unwrap since totals are given for every candidate of the tally.
+        self.totals()
+            .into_iter()
+            .map(|(candidate, _)| breakdowns.remove(&candidate).unwrap())
+            .collect()
+    }
+
+    // Points for each position on a ballot ranking `num_marked` candidates, and for each candidate it leaves unranked.
+    fn ballot_points(&self, num_marked: usize) -> (Vec<C>, C) {
+        let num_candidates = self.candidates.len();
+        let num_unranked = num_candidates - num_marked;
+
+        // Points for the positions left unranked, to share out if the policy calls for it.
+        let sharers = match self.unranked {
+            Unranked::Zero => 0,
+            Unranked::Average => num_unranked,
+            Unranked::Redistribute if num_unranked != 0 => num_marked,
+            Unranked::Redistribute => 0,
+        };
+        let mut share = C::zero();
+        if sharers != 0 {
+            let mut remaining = C::zero();
+            for position in num_marked..num_candidates {
+                remaining += self.variant.points(position, num_candidates, num_marked);
+            }
+            share = remaining / C::from_usize(sharers).expect(C_FROM_PANIC);
+        }
+
+        let mut points = Vec::with_capacity(num_marked);
+        for position in 0..num_marked {
+            let mut position_points: C = self.variant.points(position, num_candidates, num_marked);
+            if self.unranked == Unranked::Redistribute {
+                position_points += share.clone();
+            }
+            points.push(position_points);
+        }
+
+        match self.unranked {
+            Unranked::Average => (points, share),
+            _ => (points, C::zero()),
+        }
+    }
+
+    /// Get the margin of victory: the lead in points of the last winner over the runner-up.
+    ///
+    /// A single ballot can close the margin by at most the widest gap in points it can put between two candidates,
+    /// which depends on the variant, how unranked candidates are scored, and how short ballots may be.
+    /// Adding [`ballots_to_add()`](../struct.MarginOfVictory.html#method.ballots_to_add) such ballots ties the runner-up with the winner.
+    /// Quorum is not taken into account.
+    ///
+    /// Returns `None` if there are no more candidates than seats, so every candidate wins,
+    /// or if no ballot can put one candidate ahead of another.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::borda::DefaultBordaTally;
+    ///    use tallystick::borda::Variant;
+    ///
+    ///    let mut tally = DefaultBordaTally::new(1, Variant::Borda);
+    ///    for _ in 0..3 { tally.add(vec!["Alice", "Bob", "Carol"]).unwrap() }
+    ///    tally.add(vec!["Bob", "Carol", "Alice"]).unwrap();
+    ///
+    ///    // Alice has 6 points, and Bob has 5.
+    ///    let margin = tally.margin_of_victory().unwrap();
+    ///    assert_eq!((margin.winner, margin.runner_up, margin.margin), ("Alice", "Bob", 1));
+    /// ```
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        let margin = margin::from_totals(self.totals(), self.num_winners)?;
+        Some(margin.with_per_ballot(self.max_swing()?))
+    }
+
+    // The widest gap in points a single valid ballot can put between two candidates, checking every ballot length the truncation policy allows.
+    fn max_swing(&self) -> Option<C> {
+        let num_candidates = self.candidates.len();
+        let shortest = std::cmp::max(self.truncation.required(num_candidates), 1);
+
+        let mut swing: Option<C> = None;
+        for num_marked in shortest..=num_candidates {
+            let (points, unranked_points) = self.ballot_points(num_marked);
+            for (high_position, high) in points.iter().enumerate() {
+                let ranked_lows = points.iter().enumerate().filter(|(position, _)| *position != high_position);
+                let unranked_low = if num_marked < num_candidates {
+                    Some(&unranked_points)
+                } else {
+                    None
+                };
+                for low in ranked_lows.map(|(_, low)| low).chain(unranked_low) {
+                    if high > low && swing.as_ref().is_none_or(|swing| high.clone() - low.clone() > *swing) {
+                        swing = Some(high.clone() - low.clone());
+                    }
+                }
+            }
+        }
+        swing
+    }
+
+    /// Get a ranked list of winners with their point totals. Winners with the same rank are tied.
+    ///
+    /// This is [`winners()`](#method.winners) joined with [`totals()`](#method.totals).
+    pub fn winners_with_totals(&self) -> TalliedWinners<T, C> {
+        self.winners().with_totals(&self.totals())
+    }
+
     /// Get a list of all candidates seen by this tally.
     /// Candidates are returned in no particular order.
     pub fn candidates(&self) -> Vec<T> {
@@ -334,6 +689,186 @@ where
     }
 }
 
+/// A pairwise borda tally using `u64` integers to count votes.
+/// Since this is an alias, refer to [`PairwiseBordaTally`](struct.PairwiseBordaTally.html) for method documentation.
+pub type DefaultPairwiseBordaTally<T> = PairwiseBordaTally<T, u64>;
+
+/// A tournament-style borda count, scoring candidates from the pairwise comparison matrix rather than ballot positions.
+///
+/// Each candidate is given a point for every vote preferring them over another candidate,
+/// summed over every other candidate. With fully ranked ballots this matches [`Variant::Borda`](enum.Variant.html#variant.Borda).
+/// Unlike a positional count, a truncated ballot prefers every ranked candidate over every unranked one,
+/// and equally ranked or unranked candidates earn no points from each other.
+///
+/// Votes are counted by a [`CondorcetTally`](../condorcet/struct.CondorcetTally.html), so candidates must be added before votes.
+///
+/// # Example
+/// ```
+///    use tallystick::borda::DefaultPairwiseBordaTally;
+///
+///    let mut tally = DefaultPairwiseBordaTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+///    tally.add(&["Alice", "Bob", "Carlos"])?;
+///    tally.add(&["Bob"])?;
+///
+///    // Bob is preferred over Carlos twice, and over Alice once.
+///    assert_eq!(tally.totals(), vec![("Bob", 3), ("Alice", 2), ("Carlos", 0)]);
+///    assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+/// # Ok::<(), tallystick::TallyError>(())
+/// ```
+#[derive(Clone)]
+pub struct PairwiseBordaTally<T, C = u64>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    condorcet: CondorcetTally<T, C>,
+}
+
+impl<T, C> PairwiseBordaTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    /// Create a new `PairwiseBordaTally` with the given number of winners.
+    ///
+    /// If there is a tie, the number of winners might be more than `num_winners`.
+    pub fn new(num_winners: usize) -> Self {
+        PairwiseBordaTally {
+            condorcet: CondorcetTally::new(num_winners),
+        }
+    }
+
+    /// Create a new `PairwiseBordaTally` with the given number of winners and candidates.
+    pub fn with_candidates(num_winners: usize, candidates: Vec<T>) -> Self {
+        PairwiseBordaTally {
+            condorcet: CondorcetTally::with_candidates(num_winners, candidates),
+        }
+    }
+
+    /// Require a quorum for this tally to reach a decision.
+    ///
+    /// If the quorum is not met, [`winners()`](#method.winners) returns no winners.
+    pub fn with_quorum(mut self, quorum: Quorum<C>) -> Self {
+        self.condorcet = self.condorcet.with_quorum(quorum);
+        self
+    }
+
+    /// Add a candidate to the tally.
+    pub fn add_candidate(&mut self, candidate: T) {
+        self.condorcet.add_candidate(candidate);
+    }
+
+    /// Add some candidates to the tally.
+    pub fn add_candidates(&mut self, candidates: Vec<T>) {
+        self.condorcet.add_candidates(candidates);
+    }
+
+    /// Add a vote, ranking candidates in order of preference.
+    pub fn add(&mut self, vote: &[T]) -> Result<(), TallyError> {
+        self.condorcet.add(vote)
+    }
+
+    /// Add a weighted vote.
+    pub fn add_weighted(&mut self, vote: &[T], weight: C) -> Result<(), TallyError> {
+        self.condorcet.add_weighted(vote, weight)
+    }
+
+    /// Add a ranked vote.
+    ///
+    /// A ranked vote is a list of tuples of (candidate, rank), where rank is ascending.
+    /// Two candidates with the same rank are equal in preference.
+    pub fn ranked_add(&mut self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.condorcet.ranked_add(vote)
+    }
+
+    /// Add a ranked vote with a weight.
+    pub fn ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        self.condorcet.ranked_add_weighted(vote, weight)
+    }
+
+    /// Check if the quorum for this tally has been met. Always true if no quorum is set.
+    pub fn quorum_met(&self) -> bool {
+        self.condorcet.quorum_met()
+    }
+
+    /// Get a ranked list of winners. Winners with the same rank are tied.
+    /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    pub fn winners(&self) -> RankedWinners<T> {
+        self.winners_n(self.condorcet.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.condorcet.turnout.decide(self.counted().into_ranked(num_winners))
+    }
+
+    /// Get a ranked list of all candidates. Candidates with the same rank are tied.
+    pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
+        self.counted().into_ranked(0).into_vec()
+    }
+
+    /// Get each candidate with their point total, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, C> {
+        self.totals().into()
+    }
+
+    /// Get point totals for this tally: for each candidate, the sum of the votes preferring them in each of their pairwise contests.
+    pub fn totals(&self) -> Vec<(T, C)> {
+        let candidates = self.condorcet.candidates();
+        let mut plurality = PluralityTally::with_capacity(self.condorcet.num_winners, candidates.len());
+        for candidate in candidates.iter() {
+            plurality.add_to_total_ref(candidate, C::zero());
+        }
+        for ((winner, _), votecount) in self.condorcet.totals() {
+            plurality.add_to_total_ref(&winner, votecount);
+        }
+
+        plurality.totals()
+    }
+}
+
+/// How many ballots ranked a candidate at each position, and the points each position earned them.
+///
+/// See [`BordaTally::position_breakdown()`](struct.BordaTally.html#method.position_breakdown).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionBreakdown<T, C> {
+    /// The candidate.
+    pub candidate: T,
+
+    /// `ballots[i]` is the weight of the ballots ranking the candidate at position `i`, where `0` is first place.
+    pub ballots: Vec<C>,
+
+    /// `points[i]` is the points the candidate earned from the ballots ranking them at position `i`.
+    pub points: Vec<C>,
+
+    /// The weight of the ballots that left the candidate unranked.
+    pub unranked_ballots: C,
+
+    /// The points the candidate earned from ballots that left them unranked, as set by [`Unranked`](enum.Unranked.html).
+    pub unranked_points: C,
+}
+
+impl<T, C: Clone + Num> PositionBreakdown<T, C> {
+    // An empty breakdown with a position for every candidate.
+    fn new(candidate: T, num_candidates: usize) -> Self {
+        PositionBreakdown {
+            candidate: candidate,
+            ballots: vec![C::zero(); num_candidates],
+            points: vec![C::zero(); num_candidates],
+            unranked_ballots: C::zero(),
+            unranked_points: C::zero(),
+        }
+    }
+
+    /// The total points of the candidate, from every position and from ballots that left them unranked.
+    pub fn total(&self) -> C {
+        self.points
+            .iter()
+            .fold(self.unranked_points.clone(), |total, points| total + points.clone())
+    }
+}
+
 /// TODO: Stub
 #[allow(dead_code)]
 pub type DefaultNansonTally<T> = NansonTally<T, u64>;
@@ -342,8 +877,8 @@ pub type DefaultNansonTally<T> = NansonTally<T, u64>;
 #[allow(dead_code)]
 pub struct NansonTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     borda: BordaTally<T, C>,
 }
@@ -356,8 +891,8 @@ pub type DefaultBaldwinTally<T> = BaldwinTally<T, u64>;
 #[allow(dead_code)]
 pub struct BaldwinTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     borda: BordaTally<T, C>,
 }
@@ -451,4 +986,258 @@ mod tests {
         // Dowdall should panic when using integers
         let _points: u64 = Variant::Dowdall.points(0, 4, 4);
     }
+
+    #[test]
+    fn borda_bigint_test() -> Result<(), TallyError> {
+        use num_bigint::BigUint;
+
+        let mut tally = BordaTally::<&str, BigUint>::new(1, Variant::Borda);
+        tally.add_weighted(vec!["Andrew", "Catherine", "Brian", "David"], BigUint::from(51u32))?;
+        tally.add_weighted(vec!["Catherine", "Brian", "David", "Andrew"], BigUint::from(5u32))?;
+        tally.add_weighted(vec!["Brian", "Catherine", "David", "Andrew"], BigUint::from(23u32))?;
+        tally.add_weighted(vec!["David", "Catherine", "Brian", "Andrew"], BigUint::from(21u32))?;
+
+        assert_eq!(tally.totals()[0], ("Catherine", BigUint::from(205u32)));
+        assert_eq!(tally.winners().into_unranked(), vec!["Catherine"]);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rational")]
+    fn borda_rational_test() -> Result<(), TallyError> {
+        use num_rational::Rational64;
+
+        let mut dowdall_tally = RationalBordaTally::new(1, Variant::Dowdall);
+        dowdall_tally.add_weighted(vec!["Andrew", "Catherine", "Brian", "David"], Rational64::from(51))?;
+        dowdall_tally.add_weighted(vec!["Catherine", "Brian", "David", "Andrew"], Rational64::from(5))?;
+        dowdall_tally.add_weighted(vec!["Brian", "Catherine", "David", "Andrew"], Rational64::from(23))?;
+        dowdall_tally.add_weighted(vec!["David", "Catherine", "Brian", "Andrew"], Rational64::from(21))?;
+
+        let totals = dowdall_tally.totals();
+        assert_eq!(totals[0], ("Andrew", Rational64::new(253, 4)));
+        assert_eq!(totals[1], ("Catherine", Rational64::new(105, 2)));
+        assert_eq!(totals[2], ("Brian", Rational64::new(99, 2)));
+        assert_eq!(totals[3], ("David", Rational64::new(517, 12)));
+        assert!(dowdall_tally.winners().into_unranked() == vec!["Andrew"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_withdraw_test() -> Result<(), TallyError> {
+        let mut tally = DefaultBordaTally::new(1, Variant::Borda);
+        tally.add_weighted(vec!["Andrew", "Catherine", "Brian", "David"], 51)?;
+        tally.add_weighted(vec!["Catherine", "Brian", "David", "Andrew"], 5)?;
+        tally.add_weighted(vec!["Brian", "Catherine", "David", "Andrew"], 23)?;
+        tally.add_weighted(vec!["David", "Catherine", "Brian", "Andrew"], 21)?;
+        tally.add_weighted(vec!["David", "Brian", "Catherine", "Andrew"], 1)?;
+        tally.withdraw_candidate(&"David");
+
+        let mut expected = DefaultBordaTally::new(1, Variant::Borda);
+        expected.add_weighted(vec!["Andrew", "Catherine", "Brian"], 51)?;
+        expected.add_weighted(vec!["Catherine", "Brian", "Andrew"], 26)?;
+        expected.add_weighted(vec!["Brian", "Catherine", "Andrew"], 24)?;
+
+        assert_eq!(tally.totals(), expected.totals());
+        assert_eq!(tally.candidates().len(), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_truncation_test() -> Result<(), TallyError> {
+        let mut tally = DefaultBordaTally::new(1, Variant::Borda).with_truncation(Truncation::RequireFull);
+        tally.add(vec!["Alice", "Bob", "Carlos"])?;
+        assert_eq!(
+            tally.add_ref(&["Bob", "Alice"]),
+            Err(TallyError::TruncatedBallot { rankings: 2, required: 3 })
+        );
+        assert_eq!(
+            tally.add(vec!["Bob", "Alice", "Dave"]),
+            Err(TallyError::TruncatedBallot { rankings: 3, required: 4 })
+        );
+        assert_eq!(tally.totals(), vec![("Alice", 2), ("Bob", 1), ("Carlos", 0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_ranked_test() -> Result<(), TallyError> {
+        let mut tally = DefaultBordaTally::new(1, Variant::ClassicBorda);
+        tally.ranked_add(&[("Alice", 1), ("Bob", 0), ("Carlos", 2)])?;
+        tally.ranked_add_weighted(&[("Alice", 0), ("Bob", 1), ("Carlos", 1)], 2)?;
+        tally.ranked_add_weighted(&[("Alice", 0), ("Bob", 0), ("Carlos", 0)], 6)?;
+
+        // Strict rankings count as ordered votes, and equal ranks average the points of the positions they share.
+        let mut totals = tally.totals();
+        totals.sort();
+        assert_eq!(totals, vec![("Alice", 2 + 6 + 12), ("Bob", 3 + 3 + 12), ("Carlos", 1 + 3 + 12)]);
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        assert_eq!(tally.ranked_add(&[("Alice", 0), ("Bob", 0)]), Err(TallyError::IndivisibleWeight));
+        assert_eq!(
+            tally.ranked_add(&[("Alice", 0), ("Alice", 1)]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        assert_eq!(tally.totals().iter().map(|(_, t)| t).sum::<u64>(), 20 + 18 + 16);
+
+        let mut tally = BordaTally::<&str, f64>::new(1, Variant::Borda).with_truncation(Truncation::RequireFull);
+        tally.ranked_add(&[("Alice", 0), ("Bob", 1), ("Carlos", 1)])?;
+        assert_eq!(
+            tally.ranked_add(&[("Alice", 0), ("Bob", 0)]),
+            Err(TallyError::TruncatedBallot { rankings: 2, required: 3 })
+        );
+        let mut totals = tally.totals();
+        totals.sort_by(|a, b| a.0.cmp(b.0));
+        assert_eq!(totals, vec![("Alice", 2.0), ("Bob", 0.5), ("Carlos", 0.5)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_unranked_test() -> Result<(), TallyError> {
+        let count = |variant: Variant<f64>, unranked: Unranked| -> Result<Vec<(&str, f64)>, TallyError> {
+            let mut tally = BordaTally::<&str, f64>::new(1, variant).with_unranked(unranked);
+            tally.add(vec!["Alice", "Bob", "Carlos", "Dave"])?;
+            tally.add(vec!["Dave", "Carlos"])?;
+            tally.add(vec![])?;
+            let mut totals = tally.totals();
+            totals.sort_by(|a, b| a.0.cmp(b.0));
+            Ok(totals)
+        };
+
+        assert_eq!(
+            count(Variant::Borda, Unranked::Zero)?,
+            vec![("Alice", 3.0), ("Bob", 2.0), ("Carlos", 3.0), ("Dave", 3.0)]
+        );
+        // The empty ballot ties every candidate, sharing all of its points.
+        assert_eq!(
+            count(Variant::Borda, Unranked::Average)?,
+            vec![("Alice", 5.0), ("Bob", 4.0), ("Carlos", 4.5), ("Dave", 4.5)]
+        );
+        assert_eq!(
+            count(Variant::Borda, Unranked::Redistribute)?,
+            vec![("Alice", 3.0), ("Bob", 2.0), ("Carlos", 3.5), ("Dave", 3.5)]
+        );
+
+        // Positions past the last ranked candidate are worth nothing in a modified Borda count.
+        assert_eq!(
+            count(Variant::ModifiedClassicBorda, Unranked::Average)?,
+            count(Variant::ModifiedClassicBorda, Unranked::Zero)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_margin_test() -> Result<(), TallyError> {
+        let per_ballot = |variant: Variant<f64>, truncation: Truncation, unranked: Unranked| -> Result<Option<f64>, TallyError> {
+            let mut tally = BordaTally::<&str, f64>::new(1, variant)
+                .with_truncation(truncation)
+                .with_unranked(unranked);
+            tally.add_weighted(vec!["Alice", "Bob", "Carlos", "Dave"], 2.0)?;
+            tally.add(vec!["Bob", "Alice", "Carlos", "Dave"])?;
+            Ok(tally.margin_of_victory().map(|margin| margin.per_ballot))
+        };
+
+        assert_eq!(per_ballot(Variant::Borda, Truncation::Allow, Unranked::Zero)?, Some(3.0));
+        assert_eq!(per_ballot(Variant::Borda, Truncation::Allow, Unranked::Average)?, Some(3.0));
+        assert_eq!(per_ballot(Variant::ClassicBorda, Truncation::Allow, Unranked::Zero)?, Some(4.0));
+        assert_eq!(
+            per_ballot(Variant::ClassicBorda, Truncation::RequireFull, Unranked::Zero)?,
+            Some(3.0)
+        );
+
+        // The last place on a full ballot is still worth a point in the Dowdall system.
+        assert_eq!(per_ballot(Variant::Dowdall, Truncation::Allow, Unranked::Zero)?, Some(1.0));
+        assert_eq!(per_ballot(Variant::Dowdall, Truncation::RequireFull, Unranked::Zero)?, Some(0.75));
+
+        // A full modified Borda ballot gives its last place a point.
+        assert_eq!(
+            per_ballot(Variant::ModifiedClassicBorda, Truncation::Allow, Unranked::Zero)?,
+            Some(3.0)
+        );
+
+        // A ballot ranking only the runner-up gives them all the points.
+        assert_eq!(per_ballot(Variant::Borda, Truncation::Allow, Unranked::Redistribute)?, Some(6.0));
+
+        // No ballot can separate candidates that every position scores the same.
+        assert_eq!(
+            per_ballot(Variant::Custom(Box::new(|_, _, _| 1.0)), Truncation::RequireFull, Unranked::Zero)?,
+            None
+        );
+
+        let mut tally = DefaultBordaTally::new(1, Variant::Borda);
+        for _ in 0..3 {
+            tally.add(vec!["Alice", "Bob", "Carol"])?;
+        }
+        tally.add(vec!["Bob", "Carol", "Alice"])?;
+        let margin = tally.margin_of_victory().unwrap();
+        assert_eq!((margin.margin, margin.per_ballot), (1, 2));
+        assert_eq!(margin.ballots_to_add(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_position_breakdown_test() -> Result<(), TallyError> {
+        let mut tally = BordaTally::<&str, f64>::new(1, Variant::Borda).with_unranked(Unranked::Average);
+        tally.add_weighted(vec!["Alice", "Bob", "Carlos"], 2.0)?;
+        tally.add(vec!["Carlos", "Alice"])?;
+        tally.add(vec!["Bob"])?;
+
+        let breakdown = tally.position_breakdown();
+        let candidates: Vec<&str> = breakdown.iter().map(|b| b.candidate).collect();
+        assert_eq!(candidates, tally.totals().into_iter().map(|(c, _)| c).collect::<Vec<_>>());
+        for b in breakdown.iter() {
+            let total = tally.totals().into_iter().find(|(c, _)| *c == b.candidate).unwrap().1;
+            assert_eq!(b.total(), total);
+            assert_eq!(b.ballots.iter().sum::<f64>() + b.unranked_ballots, 4.0);
+        }
+
+        let alice = breakdown.iter().find(|b| b.candidate == "Alice").unwrap();
+        assert_eq!(alice.ballots, vec![2.0, 1.0, 0.0]);
+        assert_eq!(alice.points, vec![4.0, 1.0, 0.0]);
+        assert_eq!((alice.unranked_ballots, alice.unranked_points), (1.0, 0.5));
+
+        let carlos = breakdown.iter().find(|b| b.candidate == "Carlos").unwrap();
+        assert_eq!(carlos.ballots, vec![1.0, 0.0, 2.0]);
+        assert_eq!(carlos.points, vec![2.0, 0.0, 0.0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn borda_pairwise_test() -> Result<(), TallyError> {
+        let candidates = vec!["Andrew", "Brian", "Catherine", "David"];
+        let votes = [
+            (vec!["Andrew", "Catherine", "Brian", "David"], 51),
+            (vec!["Catherine", "Brian", "David", "Andrew"], 5),
+            (vec!["Brian", "Catherine", "David", "Andrew"], 23),
+            (vec!["David", "Catherine", "Brian", "Andrew"], 21),
+        ];
+
+        // With full rankings, pairwise and positional counts agree.
+        let mut positional = DefaultBordaTally::new(1, Variant::Borda);
+        let mut pairwise = DefaultPairwiseBordaTally::with_candidates(1, candidates.clone());
+        for (vote, weight) in votes.iter() {
+            positional.add_weighted(vote.clone(), *weight)?;
+            pairwise.add_weighted(vote, *weight)?;
+        }
+        assert_eq!(pairwise.totals(), positional.totals());
+        assert_eq!(pairwise.winners().into_unranked(), vec!["Catherine"]);
+
+        // Truncated and tied ballots prefer ranked candidates over the rest, and nobody scores from a tie.
+        let mut pairwise = DefaultPairwiseBordaTally::with_candidates(1, candidates);
+        pairwise.add(&["David"])?;
+        pairwise.ranked_add_weighted(&[("Andrew", 0), ("Brian", 0), ("Catherine", 1)], 2)?;
+        let mut totals = pairwise.totals();
+        totals.sort();
+        assert_eq!(totals, vec![("Andrew", 4), ("Brian", 4), ("Catherine", 2), ("David", 3)]);
+        assert_eq!(pairwise.winners().len(), 2);
+        assert_eq!(pairwise.add(&["Eve"]), Err(TallyError::UnknownCandidate { position: 0 }));
+
+        Ok(())
+    }
 }
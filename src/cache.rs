@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+
+// A result computed from a tally, so that it is only recomputed after the tally changes.
+//
+// Tallies keep a generation counter that is bumped whenever votes or candidates change.
+// A cached result is reused as long as it was computed at the tally's current generation.
+pub(crate) struct Cache<V> {
+    cached: Mutex<Option<(u64, V)>>,
+}
+
+impl<V: Clone> Cache<V> {
+    pub(crate) fn new() -> Self {
+        Cache { cached: Mutex::new(None) }
+    }
+
+    // Get the result at the given generation, computing it if the cached result is stale.
+    pub(crate) fn get_or_compute<F: FnOnce() -> V>(&self, generation: u64, compute: F) -> V {
+        // A poisoned lock only means a computation panicked, so the cache can still be used.
+        let mut cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        match &*cached {
+            Some((cached_generation, value)) if *cached_generation == generation => value.clone(),
+            _ => {
+                let value = compute();
+                *cached = Some((generation, value.clone()));
+                value
+            }
+        }
+    }
+}
+
+impl<V: Clone> Clone for Cache<V> {
+    fn clone(&self) -> Self {
+        let cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        Cache {
+            cached: Mutex::new(cached.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_test() {
+        let cache = Cache::new();
+        assert_eq!(cache.get_or_compute(0, || 1), 1);
+        assert_eq!(cache.get_or_compute(0, || 2), 1);
+        assert_eq!(cache.get_or_compute(1, || 3), 3);
+        assert_eq!(cache.clone().get_or_compute(1, || 4), 3);
+    }
+}
@@ -0,0 +1,39 @@
+/// A snapshot of a tally, that the tally can later be restored to.
+///
+/// Take a checkpoint with a tally's `snapshot()` method before importing a batch of votes.
+/// If the batch turns out to be wrong, `restore()` the checkpoint to undo it, without recounting the votes added before it.
+///
+/// A checkpoint is a full copy of the tally, so taking one costs as much time and memory as cloning the tally.
+/// Keep checkpoints for the batches that may need undoing, rather than after every vote.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    tally.add("Alice");
+///    tally.add("Alice");
+///    tally.add("Bob");
+///
+///    let checkpoint = tally.snapshot();
+///    for _ in 0..5 { tally.add("Bob") }
+///    assert_eq!(tally.totals(), vec![("Bob", 6), ("Alice", 2)]);
+///
+///    // The batch was imported by mistake, so undo it.
+///    tally.restore(checkpoint);
+///    assert_eq!(tally.totals(), vec![("Alice", 2), ("Bob", 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TallyCheckpoint<S> {
+    tally: S,
+}
+
+impl<S> TallyCheckpoint<S> {
+    pub(crate) fn new(tally: S) -> Self {
+        TallyCheckpoint { tally: tally }
+    }
+
+    pub(crate) fn into_tally(self) -> S {
+        self.tally
+    }
+}
@@ -1,14 +1,25 @@
+use super::cache::Cache;
 use super::errors::TallyError;
+use super::intern::{CandidateId, CandidateSet};
+use super::quorum::{turnout_methods, Turnout};
+use super::shard::Shard;
+use super::MarginOfVictory;
 use super::RankedCandidate;
 use super::RankedWinners;
+use super::Standings;
+use super::TallyCheckpoint;
+use super::Truncation;
 
 use hashbrown::HashMap;
-use num_traits::cast::NumCast;
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
 use num_traits::Num;
+use num_traits::Signed;
 use petgraph::algo::tarjan_scc;
 use petgraph::graph::NodeIndex;
 use petgraph::Graph;
 use std::convert::TryInto;
+use std::fmt::Display;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
@@ -39,6 +50,35 @@ use std::ops::AddAssign;
 /// ```
 pub type DefaultCondorcetTally<T> = CondorcetTally<T, u64>;
 
+/// Specifies how a Condorcet tally ranks candidates that form a cycle, when there is no Condorcet winner among them.
+///
+/// Candidates in a cycle are those in the same Smith set: each can be reached from the others by a chain of pairwise wins.
+/// A completion rule ranks them against each other, counting only the contests between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Completion {
+    /// Report the cycle: candidates in a cycle are tied, so the whole Smith set wins.
+    #[default]
+    Cycle,
+
+    /// Rank candidates by the total votes preferring them over each other candidate in the cycle.
+    Borda,
+
+    /// Rank candidates by instant-runoff between the candidates in the cycle, eliminating the candidate ranked first on the fewest ballots.
+    /// A ballot ranking several remaining candidates equal first does not count towards any of them.
+    ///
+    /// Instant-runoff needs the ballots themselves, so the tally records every ballot.
+    Irv,
+
+    /// Rank candidates by their largest pairwise defeat within the cycle, smallest first. A candidate never defeated has no defeat.
+    Minimax,
+
+    /// Rank candidates by their pairwise wins within the cycle, with a pairwise tie counting as half a win.
+    Copeland,
+}
+
+// A ballot as the rank of each candidate id, with its weight.
+pub(crate) type Ballot<C> = (Vec<(usize, u32)>, C);
+
 /// A generic condorcet tally.
 ///
 /// Generics:
@@ -63,21 +103,29 @@ pub type DefaultCondorcetTally<T> = CondorcetTally<T, u64>;
 ///
 ///    let winners = tally.winners();
 /// ```
+#[derive(Clone)]
 pub struct CondorcetTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Count type
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
 {
-    pub(crate) running_total: HashMap<(usize, usize), C>,
+    pub(crate) running_total: PairwiseCounts<C>,
     pub(crate) num_winners: usize,
     pub(crate) candidates: HashMap<T, usize>, // Map candiates to a unique integer identifiers
+    next_id: usize,                           // Ids are never reused, so recorded ballots never refer to a later candidate
     check_votes: bool,
+    truncation: Truncation,
+    pub(crate) turnout: Turnout<C>,
+    pub(crate) generation: u64, // Bumped whenever votes or candidates change, invalidating cached results
+    ranked: Cache<Vec<RankedCandidate<T>>>,
+    completion: Completion,
+    ballots: Option<Vec<Ballot<C>>>, // Recorded ballots, if the completion rule needs them
 }
 
 impl<T, C> CondorcetTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Count type
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
 {
     /// Create a new `CondorcetTally` with the given number of winners.
     ///
@@ -85,25 +133,61 @@ where
     /// (See [`winners()`](#method.winners) for more information on ties.)
     pub fn new(num_winners: usize) -> Self {
         CondorcetTally {
-            running_total: HashMap::new(),
+            running_total: PairwiseCounts::new(),
             num_winners: num_winners,
             candidates: HashMap::new(),
+            next_id: 0,
             check_votes: true,
+            truncation: Truncation::Allow,
+            turnout: Turnout::new(),
+            generation: 0,
+            ranked: Cache::new(),
+            completion: Completion::Cycle,
+            ballots: None,
         }
     }
 
     /// Create a new `CondorcetTally` with the given number of winners, and the provided candidates
     pub fn with_candidates(num_winners: usize, candidates: Vec<T>) -> Self {
         let mut tally = CondorcetTally {
-            running_total: HashMap::with_capacity(candidates.len() ^ 2),
+            running_total: PairwiseCounts::with_capacity(candidates.len()),
             num_winners: num_winners,
             candidates: HashMap::with_capacity(candidates.len()),
+            next_id: 0,
             check_votes: true,
+            truncation: Truncation::Allow,
+            turnout: Turnout::new(),
+            generation: 0,
+            ranked: Cache::new(),
+            completion: Completion::Cycle,
+            ballots: None,
         };
         tally.add_candidates(candidates);
         tally
     }
 
+    /// Create a new `CondorcetTally` with the given number of winners, and the candidates in a [`CandidateSet`](../intern/struct.CandidateSet.html).
+    ///
+    /// Each candidate has the same id in the tally as in the set, so votes translated to ids by the set can be added with
+    /// [`add_ids()`](#method.add_ids), without hashing or cloning candidates.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///    use tallystick::intern::CandidateSet;
+    ///
+    ///    let candidates = CandidateSet::with_candidates(vec!["Alice".to_string(), "Bob".to_string()]);
+    ///    let mut tally = DefaultCondorcetTally::from_candidate_set(1, &candidates);
+    ///    tally.add_ids(&candidates.lookup_vote(&["Bob".to_string(), "Alice".to_string()])?)?;
+    ///
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Bob".to_string()]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn from_candidate_set(num_winners: usize, candidates: &CandidateSet<T>) -> Self {
+        // Candidates are given ids in the order they are added, which is the order they were interned.
+        Self::with_candidates(num_winners, candidates.candidates().to_vec())
+    }
+
     /// Make this tally an unchecked tally, forgoing vote validity checking
     ///
     /// When using an unchecked tally, all vote adding methods will return Ok(), so you may elide checking for errors.
@@ -112,10 +196,56 @@ where
         self
     }
 
+    /// Set how candidates in a cycle are ranked. Defaults to [`Completion::Cycle`](enum.Completion.html#variant.Cycle),
+    /// which leaves them tied.
+    ///
+    /// Completing with [`Completion::Irv`](enum.Completion.html#variant.Irv) records every ballot.
+    /// Set it before adding votes, since ballots added earlier are not recorded.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::{Completion, DefaultCondorcetTally};
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..4 { tally.add(&vec!["Alice", "Bob", "Carlos"])?; }
+    ///    for _ in 0..3 { tally.add(&vec!["Bob", "Carlos", "Alice"])?; }
+    ///    for _ in 0..2 { tally.add(&vec!["Carlos", "Alice", "Bob"])?; }
+    ///
+    ///    // Alice beats Bob, Bob beats Carlos, and Carlos beats Alice.
+    ///    assert_eq!(tally.winners().len(), 3);
+    ///
+    ///    // Alice's only defeat, 5 to 4 against Carlos, is the smallest.
+    ///    let tally = tally.with_completion(Completion::Minimax);
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    ///    assert_eq!(tally.smith_set(), vec!["Alice", "Bob", "Carlos"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_completion(mut self, completion: Completion) -> Self {
+        self.completion = completion;
+        if completion == Completion::Irv {
+            self.record_ballots();
+        }
+        self.generation += 1;
+        self
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    /// The policy is checked along with the other vote checks, so it has no effect on an [`unchecked()`](#method.unchecked) tally.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
     /// Add a candidate to the tally.
     pub fn add_candidate(&mut self, candidate: T) {
-        let candidate_id = self.candidates.len();
+        // Withdrawn candidates keep their ids, so take a fresh id rather than the number of candidates.
+        let candidate_id = self.next_id;
+        self.next_id += 1;
         self.candidates.insert(candidate, candidate_id);
+        self.running_total.reserve(candidate_id + 1);
+        self.generation += 1;
     }
 
     /// Add some candidates to the tally.
@@ -125,6 +255,18 @@ where
         }
     }
 
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    /// Pairwise counts between the remaining candidates are unaffected, so this only drops the candidate's pairwise counts.
+    /// Once withdrawn, the candidate is unknown to the tally.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        if let Some(id) = self.candidates.remove(candidate) {
+            self.running_total.remove_candidate(id);
+            self.generation += 1;
+        }
+    }
+
     /// Add a vote.
     pub fn add(&mut self, vote: &[T]) -> Result<(), TallyError> {
         self.add_weighted(vote, C::one())
@@ -136,7 +278,7 @@ where
             self.check_vote(vote)?;
         }
 
-        let selection = self.unranked_mapped_candidates(&vote);
+        let selection = self.unranked_mapped_candidates(vote);
 
         self.add_ranked_candidate_ids(selection, weight);
 
@@ -160,27 +302,211 @@ where
             self.check_ranked_vote(vote)?;
         }
 
-        let selection = self.ranked_mapped_candidates(&vote);
+        let selection = self.ranked_mapped_candidates(vote);
 
         self.add_ranked_candidate_ids(selection, weight);
 
         Ok(())
     }
 
-    // Internal function that takes a ranked list of candidate-ids and adds them to the tally.
-    fn add_ranked_candidate_ids(&mut self, selection: Vec<(usize, u32)>, weight: C) {
-        for (i, (candidate_1, rank_1)) in selection.iter().enumerate() {
-            let mut j = i + 1;
-            while let Some((candidate_2, rank_2)) = selection.get(j) {
-                if rank_1 < rank_2 {
-                    *self.running_total.entry((*candidate_1, *candidate_2)).or_insert(C::zero()) += weight;
+    /// Get the id of a candidate, for adding votes with [`add_ids()`](#method.add_ids).
+    ///
+    /// Ids are assigned as candidates are added, and do not change while the candidate is in the tally.
+    pub fn candidate_id(&self, candidate: &T) -> Option<CandidateId> {
+        // OK to unwrap since we can only have u32 candidates.
+        self.candidates.get(candidate).map(|id| CandidateId((*id).try_into().unwrap()))
+    }
+
+    /// Get every candidate and their id, in order of id, for translating votes to ids ahead of time.
+    pub fn candidate_ids(&self) -> Vec<(T, CandidateId)> {
+        let mut ids: Vec<(T, CandidateId)> = self
+            .candidates
+            .iter()
+            .map(|(candidate, id)| (candidate.clone(), CandidateId((*id).try_into().unwrap())))
+            .collect();
+        ids.sort_by_key(|(_, id)| *id);
+        ids
+    }
+
+    /// Add a vote given as candidate ids, in order of preference.
+    ///
+    /// Candidates are neither hashed nor cloned, so this is much faster than [`add()`](#method.add) for large candidates such as long strings.
+    /// Ids come from [`candidate_id()`](#method.candidate_id) or [`candidate_ids()`](#method.candidate_ids),
+    /// or from the [`CandidateSet`](../intern/struct.CandidateSet.html) the tally was built from with [`from_candidate_set()`](#method.from_candidate_set).
+    ///
+    /// Unknown and duplicate ids are rejected like unknown and duplicate candidates. On an unchecked tally they are ignored instead,
+    /// since there is no candidate to add for an unknown id.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice".to_string(), "Bob".to_string()]);
+    ///    let alice = tally.candidate_id(&"Alice".to_string()).unwrap();
+    ///    let bob = tally.candidate_id(&"Bob".to_string()).unwrap();
+    ///    tally.add_ids(&[alice, bob])?;
+    ///    tally.add_ids(&[alice])?;
+    ///
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice".to_string()]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn add_ids(&mut self, vote: &[CandidateId]) -> Result<(), TallyError> {
+        self.add_ids_weighted(vote, C::one())
+    }
+
+    /// Add a weighted vote given as candidate ids, in order of preference.
+    pub fn add_ids_weighted(&mut self, vote: &[CandidateId], weight: C) -> Result<(), TallyError> {
+        let len = self.candidates.values().max().map_or(0, |id| id + 1);
+        let mut known = vec![false; len];
+        for id in self.candidates.values() {
+            known[*id] = true;
+        }
+
+        // Candidates that are not selected rank after all selected candidates.
+        let unselected: u32 = vote.len().try_into().unwrap(); // OK to unwrap since we can only have u32 candidates.
+        let mut ranks = vec![unselected; len];
+        for (position, id) in vote.iter().enumerate() {
+            let id = id.index();
+            if id >= len || !known[id] {
+                if self.check_votes {
+                    return Err(TallyError::UnknownCandidate { position });
                 }
-                if rank_2 < rank_1 {
-                    *self.running_total.entry((*candidate_2, *candidate_1)).or_insert(C::zero()) += weight;
+            } else if ranks[id] != unselected {
+                if self.check_votes {
+                    return Err(TallyError::VoteHasDuplicateCandidates { position });
                 }
-                j += 1;
+            } else {
+                ranks[id] = position as u32;
+            }
+        }
+        if self.check_votes {
+            self.truncation.check(vote.len(), self.candidates.len())?;
+        }
+
+        let selection = self.candidates.values().map(|id| (*id, ranks[*id])).collect();
+        self.add_ranked_candidate_ids(selection, weight);
+
+        Ok(())
+    }
+
+    /// Add pairwise preferences directly: `weight` votes that prefer `winner` over `loser`.
+    ///
+    /// This loads pre-aggregated pairwise data, such as the results of A/B comparisons or an imported pairwise matrix,
+    /// without making up ranked ballots. Pairwise preferences are not ballots, so they do not count towards a quorum,
+    /// and are not seen by [`Completion::Irv`](enum.Completion.html#variant.Irv).
+    ///
+    /// Unknown candidates are rejected with `TallyError::UnknownCandidate`, at position `0` for the winner and `1` for the loser,
+    /// and a candidate compared with themselves with `TallyError::VoteHasDuplicateCandidates`.
+    /// On an unchecked tally they are ignored instead.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+    ///    tally.add_pairwise(&"Alice", &"Bob", 30)?;
+    ///    tally.add_pairwise(&"Bob", &"Alice", 10)?;
+    ///    assert_eq!(tally.add_pairwise(&"Bob", &"Carlos", 5), Err(TallyError::UnknownCandidate { position: 1 }));
+    ///
+    ///    assert_eq!(tally.matrix().counts, vec![vec![0, 30], vec![10, 0]]);
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn add_pairwise(&mut self, winner: &T, loser: &T, weight: C) -> Result<(), TallyError> {
+        let error = match (self.candidates.get(winner), self.candidates.get(loser)) {
+            (Some(winner), Some(loser)) if winner != loser => {
+                self.running_total.add(*winner, *loser, weight);
+                self.generation += 1;
+                return Ok(());
+            }
+            (Some(_), Some(_)) => TallyError::VoteHasDuplicateCandidates { position: 1 },
+            (None, _) => TallyError::UnknownCandidate { position: 0 },
+            (_, None) => TallyError::UnknownCandidate { position: 1 },
+        };
+        if self.check_votes {
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    // Internal function that takes a ranked list of candidate-ids and adds them to the tally.
+    fn add_ranked_candidate_ids(&mut self, selection: Vec<(usize, u32)>, weight: C) {
+        self.turnout.add_valid(&weight);
+        self.generation += 1;
+        self.running_total.add_ballot(&selection, weight.clone());
+        if let Some(ballots) = &mut self.ballots {
+            ballots.push((selection, weight));
+        }
+    }
+
+    // Start recording ballots, for methods that need more than the pairwise totals.
+    pub(crate) fn record_ballots(&mut self) {
+        if self.ballots.is_none() {
+            self.ballots = Some(Vec::new());
+        }
+    }
+
+    // Whether ballots are being recorded, so that every ballot added from now on is recorded.
+    #[cfg(feature = "nightly")]
+    pub(crate) fn records_ballots(&self) -> bool {
+        self.ballots.is_some()
+    }
+
+    // The recorded ballots, in the order they were added.
+    #[cfg(feature = "nightly")]
+    pub(crate) fn ballots(&self) -> &[Ballot<C>] {
+        self.ballots.as_deref().unwrap_or(&[])
+    }
+
+    turnout_methods!(winners);
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// Candidates only known to the other tally are added, in the order the other tally added them.
+    /// Ballots recorded by the other tally are recorded in this one too.
+    /// The other tally's number of winners, quorum and vote checking settings are ignored.
+    pub fn merge(&mut self, other: Self) {
+        let mut candidates: Vec<(T, usize)> = other.candidates.into_iter().collect();
+        candidates.sort_by_key(|(_, id)| *id);
+
+        // Map the other tally's candidate ids to ids in this tally.
+        let mut ids = vec![None; candidates.last().map_or(0, |(_, id)| id + 1)];
+        for (candidate, other_id) in candidates {
+            if !self.candidates.contains_key(&candidate) {
+                self.add_candidate(candidate.clone());
+            }
+            ids[other_id] = self.candidates.get(&candidate).cloned();
+        }
+
+        for ((a, b), count) in other.running_total.iter() {
+            if let (Some(a), Some(b)) = (ids[a], ids[b]) {
+                self.running_total.add(a, b, count.clone());
+            }
+        }
+        if let Some(other) = other.ballots {
+            let ballots = self.ballots.get_or_insert_with(Vec::new);
+            for (selection, weight) in other {
+                let selection = selection
+                    .into_iter()
+                    .filter_map(|(id, rank)| ids.get(id).cloned().flatten().map(|id| (id, rank)))
+                    .collect();
+                ballots.push((selection, weight));
             }
         }
+        self.turnout.merge(other.turnout);
+        self.generation += 1;
     }
 
     /// Get total counts for this tally.
@@ -213,14 +539,96 @@ where
 
         for ((candidate1, candidate2), count) in self.running_total.iter() {
             // Ok to unwrap here since candidates must exist.
-            let candidate1 = candidates.get(candidate1).unwrap().clone();
-            let candidate2 = candidates.get(candidate2).unwrap().clone();
-            totals.push(((candidate1, candidate2), *count));
+            let candidate1 = candidates.get(&candidate1).unwrap().clone();
+            let candidate2 = candidates.get(&candidate2).unwrap().clone();
+            totals.push(((candidate1, candidate2), count.clone()));
         }
 
         totals
     }
 
+    /// Get total counts for this tally as a dense matrix.
+    ///
+    /// Candidates are indexed in the order they were added to the tally.
+    /// `matrix.counts[i][j]` is the number of votes where candidate `i` is preferred over candidate `j`.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+    ///    for _ in 0..30 { tally.add(&vec!["Alice", "Bob"]); }
+    ///    for _ in 0..10 { tally.add(&vec!["Bob", "Alice"]); }
+    ///
+    ///    let matrix = tally.matrix();
+    ///    assert_eq!(matrix.candidates, vec!["Alice", "Bob"]);
+    ///    assert_eq!(matrix.counts, vec![vec![0, 30], vec![10, 0]]);
+    ///    assert_eq!(matrix.get(&"Bob", &"Alice"), Some(&10));
+    /// ```
+    pub fn matrix(&self) -> PairwiseMatrix<T, C> {
+        let mut ids: Vec<(&T, usize)> = self.candidates.iter().map(|(candidate, id)| (candidate, *id)).collect();
+        ids.sort_by_key(|(_, id)| *id);
+
+        let zero = C::zero();
+        let counts = ids
+            .iter()
+            .map(|(_, a)| {
+                ids.iter()
+                    .map(|(_, b)| self.running_total.get(*a, *b).unwrap_or(&zero).clone())
+                    .collect()
+            })
+            .collect();
+
+        PairwiseMatrix {
+            candidates: ids.into_iter().map(|(candidate, _)| candidate.clone()).collect(),
+            counts: counts,
+        }
+    }
+
+    /// Get the margin of each head-to-head contest: how many more votes prefer the winner of the contest over the loser.
+    ///
+    /// Each pair of candidates appears once, as `((winner, loser), margin)`, so margins are never negative.
+    /// Tied contests have a margin of zero, with the candidate added to the tally first listed first.
+    /// Margins are sorted from largest to smallest, and contests with equal margins in the order their candidates were added.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..6 { tally.add(&vec!["Alice", "Bob", "Carlos"]); }
+    ///    for _ in 0..4 { tally.add(&vec!["Bob", "Alice", "Carlos"]); }
+    ///    for _ in 0..3 { tally.add(&vec!["Carlos", "Alice", "Bob"]); }
+    ///
+    ///    // Alice beats Bob 9 to 4, and Carlos 10 to 3. Bob beats Carlos 10 to 3.
+    ///    assert_eq!(
+    ///        tally.margins(),
+    ///        vec![(("Alice", "Carlos"), 7), (("Bob", "Carlos"), 7), (("Alice", "Bob"), 5)]
+    ///    );
+    /// ```
+    pub fn margins(&self) -> Vec<((T, T), C)> {
+        let mut ids: Vec<(&T, usize)> = self.candidates.iter().map(|(candidate, id)| (candidate, *id)).collect();
+        ids.sort_by_key(|(_, id)| *id);
+
+        let zero = C::zero();
+        let mut margins = Vec::with_capacity(ids.len() * ids.len().saturating_sub(1) / 2);
+        for (i, (candidate1, id1)) in ids.iter().enumerate() {
+            for (candidate2, id2) in ids[i + 1..].iter() {
+                let for1 = self.running_total.get(*id1, *id2).unwrap_or(&zero).clone();
+                let for2 = self.running_total.get(*id2, *id1).unwrap_or(&zero).clone();
+                if for1 >= for2 {
+                    margins.push((((*candidate1).clone(), (*candidate2).clone()), for1 - for2));
+                } else {
+                    margins.push((((*candidate2).clone(), (*candidate1).clone()), for2 - for1));
+                }
+            }
+        }
+
+        // A stable sort keeps contests with equal margins in the order their candidates were added.
+        margins.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        margins
+    }
+
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
     ///
@@ -241,11 +649,18 @@ where
     ///    //   Bob has a rank of 1
     ///    //   Carlos has a rank of 2
     /// ```
+    ///
+    /// Tied candidates, such as those in a cycle, are listed in a deterministic order that does not affect their rank:
+    /// by their Copeland score among themselves, counting two points for each pairwise win and one for each pairwise tie,
+    /// and then in the order they were added to the tally.
+    ///
+    /// The ranking is cached, and only recomputed after votes or candidates are added or withdrawn,
+    /// so it is cheap to call `ranked()` or [`winners()`](#method.winners) after every vote.
     pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
-        // Compute smith-sets using Tarjan's strongly connected components algorithm.
-        let graph = self.build_graph();
-        let smith_sets = tarjan_scc(&graph);
+        self.ranked.get_or_compute(self.generation, || self.compute_ranked())
+    }
 
+    fn compute_ranked(&self) -> Vec<RankedCandidate<T>> {
         // Invert the candidate map, cloned candidates will be moved into the winners list.
         let mut candidates = HashMap::<usize, T>::with_capacity(self.candidates.len());
         for (candidate, i) in self.candidates.iter() {
@@ -254,26 +669,173 @@ where
 
         // Add to ranked list.
         let mut ranked = Vec::<RankedCandidate<T>>::with_capacity(self.candidates.len());
-        for (rank, smith_set) in smith_sets.iter().enumerate() {
+        let mut rank = 0;
+        for smith_set in self.smith_sets() {
             // We need to add all members of a smith set at the same time,
             // even if it means more winners than needed. All members of a smith_set
-            // have the same rank.
-
-            // TODO: Check performance difference between cloning here and using a stable graph (where we can remove_node())
-            for graph_id in smith_set.iter() {
-                let candidate = graph.node_weight(*graph_id).unwrap(); // Safe to unwrap here since graph should always contain a node-weight at this graph-id.
-                ranked.push(RankedCandidate {
-                    candidate: candidate.clone(),
-                    rank,
-                });
+            // have the same rank, unless a completion rule ranks them.
+            for tier in self.complete(smith_set) {
+                for id in self.in_tie_order(tier) {
+                    ranked.push(RankedCandidate {
+                        candidate: candidates[&id].clone(),
+                        rank,
+                    });
+                }
+                rank += 1;
             }
         }
 
         ranked
     }
 
+    // Get the candidate ids in each Smith set, from the most preferred set to the least.
+    fn smith_sets(&self) -> Vec<Vec<usize>> {
+        // Compute smith-sets using Tarjan's strongly connected components algorithm.
+        let graph = self.build_graph();
+        tarjan_scc(&graph)
+            .iter()
+            .map(|smith_set| {
+                smith_set
+                    .iter()
+                    .map(|graph_id| {
+                        // Safe to unwrap here since graph should always contain a node-weight at this graph-id, and it is a candidate.
+                        let candidate = graph.node_weight(*graph_id).unwrap();
+                        *self.candidates.get(candidate).unwrap()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Rank the candidate ids of a Smith set with the completion rule, as tiers of tied candidates.
+    fn complete(&self, smith_set: Vec<usize>) -> Vec<Vec<usize>> {
+        if smith_set.len() < 2 {
+            return vec![smith_set];
+        }
+        let zero = C::zero();
+        let count = |a: usize, b: usize| self.running_total.get(a, b).unwrap_or(&zero).clone();
+        let others = |a: usize| smith_set.iter().cloned().filter(move |b| *b != a);
+        match self.completion {
+            Completion::Cycle => vec![smith_set],
+            Completion::Borda => {
+                let scored = smith_set.iter().map(|a| {
+                    let mut score = C::zero();
+                    for b in others(*a) {
+                        score += count(*a, b);
+                    }
+                    (*a, score)
+                });
+                into_tiers(scored.collect(), true)
+            }
+            Completion::Copeland => into_tiers(self.copeland_scores(&smith_set), true),
+            Completion::Minimax => {
+                let scored = smith_set.iter().map(|a| {
+                    let mut worst = C::zero();
+                    for b in others(*a) {
+                        let (wins, losses) = (count(*a, b), count(b, *a));
+                        if losses > wins && losses.clone() - wins.clone() > worst {
+                            worst = losses - wins;
+                        }
+                    }
+                    (*a, worst)
+                });
+                into_tiers(scored.collect(), false)
+            }
+            Completion::Irv => self.instant_runoff(smith_set),
+        }
+    }
+
+    // Get the Copeland score of each candidate id against the others: two points for each pairwise win, and one for each pairwise tie.
+    fn copeland_scores(&self, ids: &[usize]) -> Vec<(usize, usize)> {
+        let zero = C::zero();
+        let count = |a: usize, b: usize| self.running_total.get(a, b).unwrap_or(&zero);
+        ids.iter()
+            .map(|a| {
+                let mut score = 0;
+                for b in ids.iter().filter(|b| *b != a) {
+                    let (wins, losses) = (count(*a, *b), count(*b, *a));
+                    if wins > losses {
+                        score += 2;
+                    } else if wins == losses {
+                        score += 1;
+                    }
+                }
+                (*a, score)
+            })
+            .collect()
+    }
+
+    // Order tied candidate ids deterministically: by Copeland score among themselves, highest first, then by id.
+    fn in_tie_order(&self, tier: Vec<usize>) -> Vec<usize> {
+        if tier.len() < 2 {
+            return tier;
+        }
+        let mut scored = self.copeland_scores(&tier);
+        scored.sort_by(|(a_id, a), (b_id, b)| b.cmp(a).then(a_id.cmp(b_id)));
+        scored.into_iter().map(|(id, _)| id).collect()
+    }
+
+    // Rank candidate ids by instant-runoff over the recorded ballots, as tiers of tied candidates.
+    fn instant_runoff(&self, mut remaining: Vec<usize>) -> Vec<Vec<usize>> {
+        let ballots = match &self.ballots {
+            Some(ballots) => ballots,
+            None => return vec![remaining],
+        };
+
+        let mut eliminated: Vec<Vec<usize>> = Vec::new();
+        while remaining.len() > 1 {
+            let mut counts: Vec<C> = vec![C::zero(); remaining.len()];
+            for (selection, weight) in ballots.iter() {
+                // Find the remaining candidate ranked first, if only one candidate is.
+                let mut first: Option<(usize, u32)> = None;
+                let mut tied = false;
+                for (id, rank) in selection.iter() {
+                    let i = match remaining.iter().position(|remaining| remaining == id) {
+                        Some(i) => i,
+                        None => continue,
+                    };
+                    match first {
+                        Some((_, best)) if *rank == best => tied = true,
+                        Some((_, best)) if *rank > best => {}
+                        _ => {
+                            first = Some((i, *rank));
+                            tied = false;
+                        }
+                    }
+                }
+                if let (Some((i, _)), false) = (first, tied) {
+                    counts[i] += weight.clone();
+                }
+            }
+
+            let mut fewest = &counts[0];
+            for count in counts.iter() {
+                if count < fewest {
+                    fewest = count;
+                }
+            }
+            let losers: Vec<usize> = remaining
+                .iter()
+                .zip(counts.iter())
+                .filter(|(_, count)| *count == fewest)
+                .map(|(id, _)| *id)
+                .collect();
+            if losers.len() == remaining.len() {
+                break;
+            }
+            remaining.retain(|id| !losers.contains(id));
+            eliminated.push(losers);
+        }
+
+        let mut tiers = vec![remaining];
+        tiers.extend(eliminated.into_iter().rev());
+        tiers
+    }
+
     /// Get a ranked list of winners. Winners with the same rank are tied.
     /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    /// Candidates in a cycle are tied, unless they are ranked by a completion rule set with [`with_completion()`](#method.with_completion).
     ///
     /// # Example
     /// ```
@@ -312,92 +874,358 @@ where
     ///    //   Carlos has a rank of 1
     /// ```
     pub fn winners(&self) -> RankedWinners<T> {
-        RankedWinners::from_ranked(self.ranked(), self.num_winners)
+        self.winners_n(self.num_winners)
     }
 
-    /// Build a graph representing all pairwise competitions between all candidates.
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
     ///
-    /// Each candidate is assigned a node, vertexes between nodes contain a tuple of counts.
-    /// Vertexes are directional, leading from the more preferred candidate to the less prefered candidate.
-    /// The first element of tuple is the number votes where the first candidate is prefered to the second.
-    /// The second element of the tuple is the number of votes where the second candidate is prefered to the first.
-    /// The first element in the tuple is always greater than or equal to the second element in the tuple.
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.turnout.decide(RankedWinners::from_ranked(self.ranked(), num_winners))
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
     ///
-    /// If both candidates are equally prefered, two vertexes are created, one going in each direction.
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
+    }
+
+    /// Get the Smith set: the smallest set of candidates that are each preferred over every candidate outside the set.
     ///
-    /// <img src="https://raw.githubusercontent.com/phayes/tallystick/master/docs/pairwise-graph.png" height="320px">
-    /// Image Source: [https://arxiv.org/pdf/1804.02973.pdf](https://arxiv.org/pdf/1804.02973.pdf)
-    pub fn build_graph(&self) -> Graph<T, (C, C)> {
-        let mut graph = Graph::<T, (C, C)>::with_capacity(self.candidates.len(), self.candidates.len() ^ 2);
+    /// The Smith set is the set of candidates ranked first by this tally, unless a [`Completion`](enum.Completion.html) rule ranks them.
+    /// Candidates are returned in the order they were added.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos", "Dave"]);
+    ///    tally.add(&vec!["Alice", "Bob", "Carlos", "Dave"]);
+    ///    tally.add(&vec!["Bob", "Carlos", "Alice", "Dave"]);
+    ///    tally.add(&vec!["Carlos", "Alice", "Bob", "Dave"]);
+    ///
+    ///    // Alice, Bob and Carlos form a cycle, but all of them beat Dave.
+    ///    assert_eq!(tally.smith_set(), vec!["Alice", "Bob", "Carlos"]);
+    ///    assert_eq!(tally.schwartz_set(), vec!["Alice", "Bob", "Carlos"]);
+    /// ```
+    pub fn smith_set(&self) -> Vec<T> {
+        let ids = self.smith_sets().into_iter().next().unwrap_or_default();
+        let smith_set: Vec<T> = self
+            .candidates
+            .iter()
+            .filter(|(_, id)| ids.contains(id))
+            .map(|(candidate, _)| candidate.clone())
+            .collect();
+        self.in_candidate_order(smith_set)
+    }
 
-        // Add all candidates
-        let mut graph_ids = HashMap::<usize, NodeIndex>::new();
-        for (candidate, candidate_id) in self.candidates.iter() {
-            graph_ids.insert(*candidate_id, graph.add_node(candidate.clone()));
-        }
+    /// Get the Schwartz set: the union of the smallest sets of candidates that are not beaten by any candidate outside their set.
+    ///
+    /// Unlike the Smith set, pairwise ties do not join candidates together, so the Schwartz set is always a subset of the Smith set.
+    /// Candidates are returned in the order they were added.
+    pub fn schwartz_set(&self) -> Vec<T> {
+        let mut ids: Vec<(&T, usize)> = self.candidates.iter().map(|(candidate, id)| (candidate, *id)).collect();
+        ids.sort_by_key(|(_, id)| *id);
 
-        let zero = C::zero();
-        for ((candidate_1, candidate_2), votecount_1) in self.running_total.iter() {
-            let votecount_2 = self.running_total.get(&(*candidate_2, *candidate_1)).unwrap_or(&zero);
+        let graph = self.defeat_graph(&ids);
 
-            // Only add if candidate_1 vs candidate_2 votecount is larger than candidate_2 vs candidate_1 votecount
-            // Otherwise we will catch it when we come around to it again.
-            if votecount_1 >= votecount_2 {
-                let candidate_1_id = graph_ids.get(candidate_1).unwrap(); // Safe to unwrap since graph-ids contain all candidates.
-                let candidate_2_id = graph_ids.get(candidate_2).unwrap();
-                graph.add_edge(*candidate_2_id, *candidate_1_id, (*votecount_1, *votecount_2));
+        // A strongly connected component is part of the Schwartz set if none of its members are beaten from outside it.
+        let mut in_schwartz = vec![false; ids.len()];
+        for component in tarjan_scc(&graph).iter() {
+            let unbeaten = component
+                .iter()
+                .all(|node| graph.neighbors(*node).all(|winner| component.contains(&winner)));
+            if unbeaten {
+                for node in component.iter() {
+                    in_schwartz[node.index()] = true;
+                }
             }
         }
 
-        graph
+        ids.into_iter()
+            .zip(in_schwartz)
+            .filter(|(_, in_schwartz)| *in_schwartz)
+            .map(|((candidate, _), _)| candidate.clone())
+            .collect()
     }
 
-    /// Get a list of all candidates seen by this tally.
-    /// Candidates are returned in no particular order.
-    pub fn candidates(&self) -> Vec<T> {
-        self.candidates.iter().map(|(k, _v)| k.clone()).collect()
+    /// Get the majority cycles: sets of candidates that each beat one another in a circle of strict pairwise wins,
+    /// such as Alice beating Bob, Bob beating Carlos, and Carlos beating Alice.
+    ///
+    /// Cycles are why there may be no Condorcet winner. Each set is the largest group of candidates joined by cycles,
+    /// together with the pairwise defeats between them. Pairwise ties do not form cycles.
+    /// Sets are returned in the order their first candidate was added, and candidates and defeats in the order candidates were added.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos", "Dave"]);
+    ///    tally.add(&vec!["Alice", "Bob", "Carlos", "Dave"])?;
+    ///    tally.add(&vec!["Bob", "Carlos", "Alice", "Dave"])?;
+    ///    tally.add(&vec!["Carlos", "Alice", "Bob", "Dave"])?;
+    ///
+    ///    let cycles = tally.cycles();
+    ///    assert_eq!(cycles.len(), 1);
+    ///    assert_eq!(cycles[0].candidates, vec!["Alice", "Bob", "Carlos"]);
+    ///    assert_eq!(
+    ///        cycles[0].defeats,
+    ///        vec![(("Alice", "Bob"), (2, 1)), (("Bob", "Carlos"), (2, 1)), (("Carlos", "Alice"), (2, 1))]
+    ///    );
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn cycles(&self) -> Vec<MajorityCycle<T, C>> {
+        let mut ids: Vec<(&T, usize)> = self.candidates.iter().map(|(candidate, id)| (candidate, *id)).collect();
+        ids.sort_by_key(|(_, id)| *id);
+
+        // Every strongly connected component of the strict defeats with more than one candidate contains a cycle.
+        let graph = self.defeat_graph(&ids);
+        let mut components: Vec<Vec<usize>> = tarjan_scc(&graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let mut indexes: Vec<usize> = component.iter().map(|node| node.index()).collect();
+                indexes.sort_unstable();
+                indexes
+            })
+            .collect();
+        components.sort();
+
+        let zero = C::zero();
+        components
+            .into_iter()
+            .map(|indexes| {
+                let mut defeats = Vec::new();
+                for i in indexes.iter() {
+                    for j in indexes.iter() {
+                        let (winner, a) = ids[*i];
+                        let (loser, b) = ids[*j];
+                        let wins = self.running_total.get(a, b).unwrap_or(&zero);
+                        let losses = self.running_total.get(b, a).unwrap_or(&zero);
+                        if wins > losses {
+                            defeats.push(((winner.clone(), loser.clone()), (wins.clone(), losses.clone())));
+                        }
+                    }
+                }
+                MajorityCycle {
+                    candidates: indexes.iter().map(|i| ids[*i].0.clone()).collect(),
+                    defeats: defeats,
+                }
+            })
+            .collect()
     }
 
-    /// Check the validity of a vote
-    ///
-    /// This will ensure all candidates are valid, and there are no duplicate candidates.
-    pub fn check_vote(&self, vote: &[T]) -> Result<(), TallyError> {
-        // Check to make sure all candidates exists
-        for candidate in vote {
-            if self.candidates.get(candidate).is_none() {
-                return Err(TallyError::UnknownCandidate);
+    // Build a graph of strict defeats between the given candidates, with edges leading from the loser to the winner.
+    // Each candidate's node has the same index as the candidate in `ids`.
+    fn defeat_graph(&self, ids: &[(&T, usize)]) -> Graph<usize, ()> {
+        let mut graph = Graph::<usize, ()>::with_capacity(ids.len(), ids.len());
+        let nodes: Vec<NodeIndex> = ids.iter().map(|(_, id)| graph.add_node(*id)).collect();
+        let zero = C::zero();
+        for (i, (_, a)) in ids.iter().enumerate() {
+            for (j, (_, b)) in ids.iter().enumerate() {
+                let wins = self.running_total.get(*a, *b).unwrap_or(&zero);
+                let losses = self.running_total.get(*b, *a).unwrap_or(&zero);
+                if wins > losses {
+                    graph.add_edge(nodes[j], nodes[i], ());
+                }
             }
         }
-        crate::util::check_duplicates_transitive_vote(vote)?;
+        graph
+    }
 
-        Ok(())
+    // Sort candidates in the order they were added to the tally.
+    fn in_candidate_order(&self, mut candidates: Vec<T>) -> Vec<T> {
+        candidates.sort_by_key(|candidate| self.candidates.get(candidate).cloned());
+        candidates
     }
 
-    /// Check the validity of a ranked vote
+    /// Get the Condorcet winner: the candidate that is preferred over every other candidate in a head-to-head contest.
     ///
-    /// This will ensure all candidates are valid, and there are no duplicate candidates.
-    pub fn check_ranked_vote(&self, vote: &[(T, u32)]) -> Result<(), TallyError> {
-        // Check to make sure all candidates exists
-        for (candidate, _rank) in vote {
-            if self.candidates.get(candidate).is_none() {
-                return Err(TallyError::UnknownCandidate);
-            }
-        }
+    /// Returns `None` if there is no Condorcet winner, for example when the candidates' preferences form a cycle.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+    ///    tally.add(&vec!["Alice", "Bob", "Carlos"]);
+    ///    tally.add(&vec!["Bob", "Alice", "Carlos"]);
+    ///    tally.add(&vec!["Alice", "Carlos", "Bob"]);
+    ///
+    ///    assert_eq!(tally.condorcet_winner(), Some("Alice"));
+    ///    assert_eq!(tally.condorcet_loser(), Some("Carlos"));
+    /// ```
+    pub fn condorcet_winner(&self) -> Option<T> {
+        self.find_dominant(|wins, losses| wins > losses)
+    }
+
+    /// Get the Condorcet loser: the candidate that every other candidate is preferred over in a head-to-head contest.
+    ///
+    /// Returns `None` if there is no Condorcet loser.
+    pub fn condorcet_loser(&self) -> Option<T> {
+        self.find_dominant(|wins, losses| wins < losses)
+    }
+
+    // Find the candidate whose head-to-head wins and losses against every other candidate satisfy `dominates`.
+    fn find_dominant<F>(&self, dominates: F) -> Option<T>
+    where
+        F: Fn(&C, &C) -> bool,
+    {
+        let zero = C::zero();
+        let count = |a: usize, b: usize| self.running_total.get(a, b).unwrap_or(&zero);
+        self.candidates
+            .iter()
+            .find(|(_, a)| {
+                self.candidates
+                    .values()
+                    .all(|b| *a == b || dominates(count(**a, *b), count(*b, **a)))
+            })
+            .map(|(candidate, _)| candidate.clone())
+    }
+
+    /// Get the pairwise margin of victory of the Condorcet winner: their smallest lead over another candidate in a head-to-head contest.
+    ///
+    /// Adding [`ballots_to_add()`](../struct.MarginOfVictory.html#method.ballots_to_add) ballots that prefer the runner-up
+    /// over the winner ties that contest, so the winner is no longer a Condorcet winner and this tally's winners change.
+    /// Since every Condorcet method elects the Condorcet winner, the margin is also a lower bound for Condorcet methods
+    /// such as Schulze: at least this many ballots are needed to alter their winner.
+    ///
+    /// Returns `None` if there is no Condorcet winner.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..6 { tally.add(&vec!["Alice", "Bob", "Carlos"]); }
+    ///    for _ in 0..4 { tally.add(&vec!["Bob", "Alice", "Carlos"]); }
+    ///    for _ in 0..3 { tally.add(&vec!["Carlos", "Alice", "Bob"]); }
+    ///
+    ///    // Alice beats Bob 9 to 4, and Carlos 10 to 3.
+    ///    let margin = tally.margin_of_victory().unwrap();
+    ///    assert_eq!(margin.winner, "Alice");
+    ///    assert_eq!(margin.runner_up, "Bob");
+    ///    assert_eq!(margin.margin, 5);
+    /// ```
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        let smith_sets = self.smith_sets();
+        if smith_sets.len() < 2 || smith_sets[0].len() != 1 {
+            return None;
+        }
+        let winner_id = smith_sets[0][0];
+        let winner = self.candidates.iter().find(|(_, id)| **id == winner_id)?.0.clone();
+
+        // Visit opponents in the order they were added, so ties for the closest opponent are broken consistently.
+        let mut opponents: Vec<(&T, usize)> = self.candidates.iter().map(|(candidate, id)| (candidate, *id)).collect();
+        opponents.sort_by_key(|(_, id)| *id);
+
+        let zero = C::zero();
+        let mut closest: Option<(&T, C)> = None;
+        for (opponent, id) in opponents.into_iter().filter(|(_, id)| *id != winner_id) {
+            let wins = self.running_total.get(winner_id, id).unwrap_or(&zero).clone();
+            let losses = self.running_total.get(id, winner_id).unwrap_or(&zero).clone();
+            let lead = wins - losses;
+            if closest.as_ref().is_none_or(|(_, closest)| lead < *closest) {
+                closest = Some((opponent, lead));
+            }
+        }
+
+        let (runner_up, lead) = closest?;
+        Some(MarginOfVictory::new(winner, runner_up.clone(), lead))
+    }
+
+    /// Build a graph representing all pairwise competitions between all candidates.
+    ///
+    /// Each candidate is assigned a node, vertexes between nodes contain a tuple of counts.
+    /// Vertexes are directional, leading from the more preferred candidate to the less prefered candidate.
+    /// The first element of tuple is the number votes where the first candidate is prefered to the second.
+    /// The second element of the tuple is the number of votes where the second candidate is prefered to the first.
+    /// The first element in the tuple is always greater than or equal to the second element in the tuple.
+    ///
+    /// If both candidates are equally prefered, two vertexes are created, one going in each direction.
+    ///
+    /// <img src="https://raw.githubusercontent.com/phayes/tallystick/master/docs/pairwise-graph.png" height="320px">
+    /// Image Source: [https://arxiv.org/pdf/1804.02973.pdf](https://arxiv.org/pdf/1804.02973.pdf)
+    pub fn build_graph(&self) -> Graph<T, (C, C)> {
+        let mut graph = Graph::<T, (C, C)>::with_capacity(self.candidates.len(), self.candidates.len() ^ 2);
+
+        // Add all candidates
+        let mut graph_ids = HashMap::<usize, NodeIndex>::new();
+        for (candidate, candidate_id) in self.candidates.iter() {
+            graph_ids.insert(*candidate_id, graph.add_node(candidate.clone()));
+        }
+
+        let zero = C::zero();
+        for ((candidate_1, candidate_2), votecount_1) in self.running_total.iter() {
+            let votecount_2 = self.running_total.get(candidate_2, candidate_1).unwrap_or(&zero);
+
+            // Only add if candidate_1 vs candidate_2 votecount is larger than candidate_2 vs candidate_1 votecount
+            // Otherwise we will catch it when we come around to it again.
+            if votecount_1 >= votecount_2 {
+                let candidate_1_id = graph_ids.get(&candidate_1).unwrap(); // Safe to unwrap since graph-ids contain all candidates.
+                let candidate_2_id = graph_ids.get(&candidate_2).unwrap();
+                graph.add_edge(*candidate_2_id, *candidate_1_id, (votecount_1.clone(), votecount_2.clone()));
+            }
+        }
+
+        graph
+    }
+
+    /// Get a list of all candidates seen by this tally.
+    /// Candidates are returned in no particular order.
+    pub fn candidates(&self) -> Vec<T> {
+        self.candidates.iter().map(|(k, _v)| k.clone()).collect()
+    }
+
+    /// Check the validity of a vote
+    ///
+    /// This will ensure all candidates are valid, there are no duplicate candidates, and the vote meets the truncation policy.
+    pub fn check_vote(&self, vote: &[T]) -> Result<(), TallyError> {
+        // Check to make sure all candidates exists
+        for (position, candidate) in vote.iter().enumerate() {
+            if !self.candidates.contains_key(candidate) {
+                return Err(TallyError::UnknownCandidate { position });
+            }
+        }
+        crate::util::check_duplicates_transitive_vote(vote)?;
+        self.truncation.check(vote.len(), self.candidates.len())?;
+
+        Ok(())
+    }
+
+    /// Check the validity of a ranked vote
+    ///
+    /// This will ensure all candidates are valid, there are no duplicate candidates, and the vote meets the truncation policy.
+    pub fn check_ranked_vote(&self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        // Check to make sure all candidates exists
+        for (position, (candidate, _rank)) in vote.iter().enumerate() {
+            if !self.candidates.contains_key(candidate) {
+                return Err(TallyError::UnknownCandidate { position });
+            }
+        }
         crate::util::check_duplicates_ranked_vote(vote)?;
+        self.truncation.check(vote.len(), self.candidates.len())?;
 
         Ok(())
     }
 
     // Return an internal representation of candidates
     fn unranked_mapped_candidates(&mut self, selection: &[T]) -> Vec<(usize, u32)> {
-        let mut mapped = Vec::<(usize, u32)>::new();
-        for (candidate, candidate_id) in self.candidates.iter() {
-            let index = selection.iter().position(|ref r| *r == candidate);
-            let rank = match index {
-                Some(i) => i,
-                None => selection.len(),
-            };
-            mapped.push((*candidate_id, rank.try_into().unwrap())); // OK to unwrap since we can only have u32 candidates.
+        // Look up each selected candidate once, rather than searching the selection for every candidate.
+        // Candidates that are not selected rank after all selected candidates.
+        let unselected: u32 = selection.len().try_into().unwrap(); // OK to unwrap since we can only have u32 candidates.
+        let ranks = self.selection_ranks(selection.iter().enumerate().map(|(i, c)| (c, i as u32)), unselected);
+
+        let mut mapped = Vec::<(usize, u32)>::with_capacity(self.candidates.len());
+        for candidate_id in self.candidates.values() {
+            mapped.push((*candidate_id, ranks[*candidate_id]));
         }
 
         mapped
@@ -405,17 +1233,19 @@ where
 
     // Return an internal representation of candidates
     fn ranked_mapped_candidates(&mut self, selection: &[(T, u32)]) -> Vec<(usize, u32)> {
-        let mut mapped = Vec::<(usize, u32)>::new();
+        // Ranks are looked up by candidate id, with `u32::MAX` marking candidates that are not on the vote.
+        let ranks = self.selection_ranks(selection.iter().map(|(c, rank)| (c, *rank)), u32::MAX);
+
+        let mut mapped = Vec::<(usize, u32)>::with_capacity(self.candidates.len());
         let mut trailing_candidates = Vec::<usize>::new();
         let mut max_rank = 0;
-        for (candidate, candidate_id) in self.candidates.iter() {
-            let ranked_candidate = selection.iter().find(|ref r| &(r.0) == candidate);
-            match ranked_candidate {
-                Some(rc) => {
-                    max_rank = std::cmp::max(max_rank, rc.1);
-                    mapped.push((*candidate_id, rc.1));
+        for candidate_id in self.candidates.values() {
+            match ranks[*candidate_id] {
+                u32::MAX => trailing_candidates.push(*candidate_id),
+                rank => {
+                    max_rank = std::cmp::max(max_rank, rank);
+                    mapped.push((*candidate_id, rank));
                 }
-                None => trailing_candidates.push(*candidate_id),
             };
         }
 
@@ -426,11 +1256,444 @@ where
 
         mapped
     }
+
+    // The rank of each candidate on a vote, indexed by candidate id, with `missing` for candidates not on the vote.
+    // Unknown candidates on an unchecked vote are ignored, and a candidate listed more than once takes its first rank.
+    fn selection_ranks<'a, I>(&self, selection: I, missing: u32) -> Vec<u32>
+    where
+        I: DoubleEndedIterator<Item = (&'a T, u32)>,
+        T: 'a,
+    {
+        let len = self.candidates.values().max().map_or(0, |id| id + 1);
+        let mut ranks = vec![missing; len];
+        for (candidate, rank) in selection.rev() {
+            if let Some(candidate_id) = self.candidates.get(candidate) {
+                ranks[*candidate_id] = rank;
+            }
+        }
+        ranks
+    }
+}
+
+impl<T, C> CondorcetTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                 // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd, // Count type
+{
+    /// Add a vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any pairwise total, or the total weight of valid ballots, would overflow the count type.
+    pub fn checked_add(&mut self, vote: &[T]) -> Result<(), TallyError> {
+        self.checked_add_weighted(vote, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any pairwise total, or the total weight of valid ballots, would overflow the count type.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, vote: &[T], weight: C) -> Result<(), TallyError> {
+        if self.check_votes {
+            self.check_vote(vote)?;
+        }
+
+        let selection = self.unranked_mapped_candidates(vote);
+        self.checked_add_ranked_candidate_ids(selection, weight)
+    }
+
+    /// Add a ranked vote with a weight, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any pairwise total, or the total weight of valid ballots, would overflow the count type.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        if self.check_votes {
+            self.check_ranked_vote(vote)?;
+        }
+
+        let selection = self.ranked_mapped_candidates(vote);
+        self.checked_add_ranked_candidate_ids(selection, weight)
+    }
+
+    // Check every pairwise total before adding any of them, so an overflowing vote is not partially added.
+    fn checked_add_ranked_candidate_ids(&mut self, selection: Vec<(usize, u32)>, weight: C) -> Result<(), TallyError> {
+        for (i, (candidate_1, rank_1)) in selection.iter().enumerate() {
+            for (candidate_2, rank_2) in selection[i + 1..].iter() {
+                let pair = match rank_1.cmp(rank_2) {
+                    std::cmp::Ordering::Less => (*candidate_1, *candidate_2),
+                    std::cmp::Ordering::Greater => (*candidate_2, *candidate_1),
+                    std::cmp::Ordering::Equal => continue,
+                };
+                if let Some(total) = self.running_total.get(pair.0, pair.1) {
+                    total.checked_add(&weight).ok_or(TallyError::CountOverflow)?;
+                }
+            }
+        }
+        self.turnout.check_add_valid(&weight)?;
+
+        self.add_ranked_candidate_ids(selection, weight);
+        Ok(())
+    }
+}
+
+impl<T, C> CondorcetTally<T, C>
+where
+    T: Eq + Clone + Hash,                                             // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Signed, // Count type
+{
+    /// Get the signed margin of every ordered pair of candidates: how many more votes prefer the first candidate over the second
+    /// than the other way around.
+    ///
+    /// For a pair `((T1, T2), C)`, `C` is the number of votes preferring `T1` over `T2` less the number preferring `T2` over `T1`,
+    /// so every margin appears twice, once negated. Margins need a signed count type, such as `i64` or `f64`.
+    /// Pairs are listed in the order their candidates were added.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::CondorcetTally;
+    ///
+    ///    let mut tally = CondorcetTally::<&str, i64>::with_candidates(1, vec!["Alice", "Bob"]);
+    ///    for _ in 0..30 { tally.add(&vec!["Alice", "Bob"])?; }
+    ///    for _ in 0..10 { tally.add(&vec!["Bob", "Alice"])?; }
+    ///
+    ///    assert_eq!(tally.margin_totals(), vec![(("Alice", "Bob"), 20), (("Bob", "Alice"), -20)]);
+    ///    assert_eq!(tally.margin_matrix().counts, vec![vec![0, 20], vec![-20, 0]]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn margin_totals(&self) -> Vec<((T, T), C)> {
+        let matrix = self.margin_matrix();
+        let mut margins = Vec::with_capacity(matrix.candidates.len() * matrix.candidates.len().saturating_sub(1));
+        for (i, row) in matrix.counts.into_iter().enumerate() {
+            for (j, margin) in row.into_iter().enumerate() {
+                if i != j {
+                    margins.push(((matrix.candidates[i].clone(), matrix.candidates[j].clone()), margin));
+                }
+            }
+        }
+        margins
+    }
+
+    /// Get the signed margins of [`margin_totals()`](#method.margin_totals) as a dense matrix.
+    ///
+    /// Candidates are indexed in the order they were added to the tally, as for [`matrix()`](#method.matrix).
+    /// `matrix.counts[i][j]` is the number of votes preferring candidate `i` over candidate `j` less the number preferring `j` over `i`.
+    pub fn margin_matrix(&self) -> PairwiseMatrix<T, C> {
+        let matrix = self.matrix();
+        let counts = (0..matrix.candidates.len())
+            .map(|i| {
+                (0..matrix.candidates.len())
+                    .map(|j| matrix.counts[i][j].clone() - matrix.counts[j][i].clone())
+                    .collect()
+            })
+            .collect();
+        PairwiseMatrix {
+            candidates: matrix.candidates,
+            counts: counts,
+        }
+    }
+}
+
+impl<T, C> CondorcetTally<T, C>
+where
+    T: Eq + Clone + Hash + Display,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Display, // Count type
+{
+    /// Export the pairwise graph in the Graphviz DOT format, with the winners highlighted.
+    ///
+    /// See [`PairwiseMatrix::to_dot()`](struct.PairwiseMatrix.html#method.to_dot) for the layout of the graph.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+    ///    for _ in 0..30 { tally.add(&vec!["Alice", "Bob"]); }
+    ///    for _ in 0..10 { tally.add(&vec!["Bob", "Alice"]); }
+    ///
+    ///    let dot = tally.to_dot();
+    ///    assert!(dot.contains("\"Alice\" [style=bold];"));
+    ///    assert!(dot.contains("\"Alice\" -> \"Bob\" [label=\"30-10\"];"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.matrix().to_dot(&self.winners().all())
+    }
+}
+
+/// A majority cycle, as returned by [`CondorcetTally::cycles()`](struct.CondorcetTally.html#method.cycles).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MajorityCycle<T, C> {
+    /// The candidates joined by the cycle.
+    pub candidates: Vec<T>,
+
+    /// The pairwise defeats between the candidates, as `((winner, loser), (wins, losses))`,
+    /// where `wins` is the number of votes preferring the winner over the loser and `losses` the number preferring the loser.
+    pub defeats: Vec<((T, T), (C, C))>,
+}
+
+/// Pairwise preference counts as a dense matrix, along with the candidates it is indexed by.
+///
+/// See [`CondorcetTally::matrix()`](struct.CondorcetTally.html#method.matrix).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairwiseMatrix<T, C> {
+    /// The candidates, in the order they index the matrix.
+    pub candidates: Vec<T>,
+
+    /// `counts[i][j]` is the number of votes where `candidates[i]` is preferred over `candidates[j]`.
+    pub counts: Vec<Vec<C>>,
+}
+
+impl<T: PartialEq, C> PairwiseMatrix<T, C> {
+    /// Get the index of a candidate in the matrix.
+    pub fn index_of(&self, candidate: &T) -> Option<usize> {
+        self.candidates.iter().position(|c| c == candidate)
+    }
+
+    /// Get the number of votes where `candidate1` is preferred over `candidate2`.
+    pub fn get(&self, candidate1: &T, candidate2: &T) -> Option<&C> {
+        Some(&self.counts[self.index_of(candidate1)?][self.index_of(candidate2)?])
+    }
+}
+
+impl<T: PartialEq + Display, C: PartialOrd + Display> PairwiseMatrix<T, C> {
+    /// Render the matrix as a graph in the Graphviz DOT format.
+    ///
+    /// Each head-to-head contest is an edge from the preferred candidate to the other, labelled with both candidates' votes.
+    /// Tied contests are drawn as dashed, undirected edges. Candidates in `highlight`, usually the winners, are drawn in bold.
+    pub fn to_dot(&self, highlight: &[T]) -> String {
+        let mut dot = String::from("digraph {\n");
+        for candidate in self.candidates.iter() {
+            if highlight.contains(candidate) {
+                dot.push_str(&format!("    {} [style=bold];\n", dot_id(candidate)));
+            } else {
+                dot.push_str(&format!("    {};\n", dot_id(candidate)));
+            }
+        }
+
+        for i in 0..self.candidates.len() {
+            for j in i + 1..self.candidates.len() {
+                let (cij, cji) = (&self.counts[i][j], &self.counts[j][i]);
+                let (a, b) = (dot_id(&self.candidates[i]), dot_id(&self.candidates[j]));
+                if cij > cji {
+                    dot.push_str(&format!("    {} -> {} [label=\"{}-{}\"];\n", a, b, cij, cji));
+                } else if cji > cij {
+                    dot.push_str(&format!("    {} -> {} [label=\"{}-{}\"];\n", b, a, cji, cij));
+                } else {
+                    dot.push_str(&format!(
+                        "    {} -> {} [label=\"{}-{}\", dir=none, style=dashed];\n",
+                        a, b, cij, cji
+                    ));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Pairwise counts between candidate ids, stored as a flat row-major matrix.
+// Candidate ids are dense, so indexing the matrix is much cheaper than hashing pairs of ids.
+// Pairs that no vote has distinguished are not `seen`, so they are left out of totals and the pairwise graph.
+#[derive(Clone, Debug)]
+pub(crate) struct PairwiseCounts<C> {
+    stride: usize,
+    counts: Vec<C>,
+    seen: Vec<bool>,
+}
+
+impl<C: Clone + AddAssign + Num> PairwiseCounts<C> {
+    pub(crate) fn new() -> Self {
+        PairwiseCounts {
+            stride: 0,
+            counts: Vec::new(),
+            seen: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_capacity(candidates: usize) -> Self {
+        let mut counts = Self::new();
+        counts.reserve(candidates);
+        counts
+    }
+
+    // Make room for candidate ids up to `candidates - 1`.
+    // The matrix at least doubles when it grows, so adding candidates one at a time stays cheap.
+    pub(crate) fn reserve(&mut self, candidates: usize) {
+        if candidates <= self.stride {
+            return;
+        }
+        let stride = std::cmp::max(candidates, self.stride * 2);
+        let mut counts = vec![C::zero(); stride * stride];
+        let mut seen = vec![false; stride * stride];
+        for ((a, b), count) in self.iter() {
+            counts[a * stride + b] = count.clone();
+            seen[a * stride + b] = true;
+        }
+        self.stride = stride;
+        self.counts = counts;
+        self.seen = seen;
+    }
+
+    // Get the number of votes where candidate `a` is preferred over candidate `b`.
+    pub(crate) fn get(&self, a: usize, b: usize) -> Option<&C> {
+        if a >= self.stride || b >= self.stride || !self.seen[a * self.stride + b] {
+            return None;
+        }
+        Some(&self.counts[a * self.stride + b])
+    }
+
+    pub(crate) fn add(&mut self, a: usize, b: usize, weight: C) {
+        self.reserve(std::cmp::max(a, b) + 1);
+        self.counts[a * self.stride + b] += weight;
+        self.seen[a * self.stride + b] = true;
+    }
+
+    // Add a ballot, given as the rank of each candidate on it.
+    // Each candidate's row gains the weight for every candidate ranked below them,
+    // so the work is a pass over contiguous rows rather than a loop over pairs.
+    pub(crate) fn add_ballot(&mut self, selection: &[(usize, u32)], weight: C) {
+        let len = selection.iter().map(|(id, _)| id + 1).max().unwrap_or(0);
+        self.reserve(len);
+
+        // Candidates not on the ballot have rank 0 here, but have no row, and no candidate is ranked above rank 0.
+        let mut ranks = vec![0; len];
+        for (id, rank) in selection.iter() {
+            ranks[*id] = *rank;
+        }
+        for (id, rank) in selection.iter() {
+            let row = id * self.stride;
+            C::add_row(
+                &mut self.counts[row..row + len],
+                &mut self.seen[row..row + len],
+                *rank,
+                &ranks,
+                &weight,
+            );
+        }
+    }
+
+    // Drop every count involving a candidate.
+    pub(crate) fn remove_candidate(&mut self, id: usize) {
+        if id >= self.stride {
+            return;
+        }
+        for other in 0..self.stride {
+            for i in [id * self.stride + other, other * self.stride + id] {
+                self.counts[i] = C::zero();
+                self.seen[i] = false;
+            }
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.seen.iter().filter(|seen| **seen).count()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = ((usize, usize), &C)> {
+        let stride = self.stride;
+        self.counts
+            .iter()
+            .zip(self.seen.iter())
+            .enumerate()
+            .filter(|(_, (_, seen))| **seen)
+            .map(move |(i, (count, _))| ((i / stride, i % stride), count))
+    }
+}
+
+// Add a weight to the counts in one candidate's row of the pairwise matrix, for each candidate ranked below them.
+trait AddRow: Sized {
+    fn add_row(counts: &mut [Self], seen: &mut [bool], rank: u32, ranks: &[u32], weight: &Self);
+}
+
+#[cfg(feature = "nightly")]
+impl<C: Clone + AddAssign + Num> AddRow for C {
+    default fn add_row(counts: &mut [C], seen: &mut [bool], rank: u32, ranks: &[u32], weight: &C) {
+        add_row_branching(counts, seen, rank, ranks, weight);
+    }
+}
+
+#[cfg(not(feature = "nightly"))]
+impl<C: Clone + AddAssign + Num> AddRow for C {
+    fn add_row(counts: &mut [C], seen: &mut [bool], rank: u32, ranks: &[u32], weight: &C) {
+        add_row_branching(counts, seen, rank, ranks, weight);
+    }
+}
+
+// Counts that are `Copy`, such as integers and floats, add zero rather than branching for candidates ranked above this one,
+// so the loop has no branches and is vectorized.
+#[cfg(feature = "nightly")]
+impl<C: Copy + AddAssign + Num> AddRow for C {
+    fn add_row(counts: &mut [C], seen: &mut [bool], rank: u32, ranks: &[u32], weight: &C) {
+        let (weight, zero) = (*weight, C::zero());
+        for ((count, seen), other) in counts.iter_mut().zip(seen.iter_mut()).zip(ranks.iter()) {
+            let preferred = rank < *other;
+            *count += if preferred { weight } else { zero };
+            *seen |= preferred;
+        }
+    }
+}
+
+fn add_row_branching<C: Clone + AddAssign>(counts: &mut [C], seen: &mut [bool], rank: u32, ranks: &[u32], weight: &C) {
+    for ((count, seen), other) in counts.iter_mut().zip(seen.iter_mut()).zip(ranks.iter()) {
+        if rank < *other {
+            *count += weight.clone();
+            *seen = true;
+        }
+    }
+}
+
+// Group scored candidate ids into tiers of equal scores, from the best score to the worst.
+// Candidates within a tier are ordered by id.
+fn into_tiers<S: PartialOrd>(mut scored: Vec<(usize, S)>, higher_first: bool) -> Vec<Vec<usize>> {
+    scored.sort_by(|(a_id, a), (b_id, b)| {
+        let order = if higher_first { b.partial_cmp(a) } else { a.partial_cmp(b) };
+        order.unwrap_or(std::cmp::Ordering::Equal).then(a_id.cmp(b_id))
+    });
+    let mut tiers: Vec<Vec<usize>> = Vec::new();
+    let mut prev: Option<S> = None;
+    for (id, score) in scored {
+        match (&prev, tiers.last_mut()) {
+            (Some(prev), Some(tier)) if *prev == score => tier.push(id),
+            _ => tiers.push(vec![id]),
+        }
+        prev = Some(score);
+    }
+    tiers
+}
+
+// Quote a candidate as a DOT identifier.
+fn dot_id<T: Display>(candidate: &T) -> String {
+    format!("\"{}\"", candidate.to_string().replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl<T, C> Shard for CondorcetTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
+{
+    fn shard(&self) -> Self {
+        CondorcetTally {
+            running_total: PairwiseCounts::with_capacity(self.candidates.values().max().map_or(0, |id| id + 1)),
+            num_winners: self.num_winners,
+            candidates: self.candidates.clone(),
+            next_id: self.next_id,
+            check_votes: self.check_votes,
+            truncation: self.truncation,
+            turnout: self.turnout.shard(),
+            generation: 0,
+            ranked: Cache::new(),
+            completion: self.completion,
+            ballots: self.ballots.as_ref().map(|_| Vec::new()),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        CondorcetTally::merge(self, shard);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Quorum;
     use maplit::hashset;
     use std::collections::HashSet;
     use std::iter::FromIterator;
@@ -439,9 +1702,9 @@ mod tests {
     fn condorcet_basic() -> Result<(), TallyError> {
         // Election between Alice, Bob, and Carol
         let mut tally = DefaultCondorcetTally::with_candidates(2, vec!["Alice", "Bob", "Carol"]);
-        tally.add(&vec!["Alice", "Bob", "Carol"])?;
-        tally.add(&vec!["Alice", "Bob", "Carol"])?;
-        tally.add(&vec!["Alice", "Bob", "Carol"])?;
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Alice", "Bob", "Carol"])?;
 
         let totals = tally.totals();
         let totals = HashSet::from_iter(totals.iter().cloned()); // As a hashset.
@@ -455,13 +1718,13 @@ mod tests {
 
         // Test a non-transitive voting paradox
         let mut tally = DefaultCondorcetTally::with_candidates(2, vec!["Alice", "Bob", "Carol"]);
-        tally.add(&vec!["Alice", "Bob", "Carol"])?;
-        tally.add(&vec!["Bob", "Carol", "Alice"])?;
-        tally.add(&vec!["Carol", "Alice", "Bob"])?;
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Bob", "Carol", "Alice"])?;
+        tally.add(&["Carol", "Alice", "Bob"])?;
 
         let winners = tally.winners();
-        assert_eq!(winners.is_empty(), false);
-        assert_eq!(winners.check_overflow(), true);
+        assert!(!winners.is_empty());
+        assert!(winners.check_overflow());
         assert_eq!(winners.all().len(), 3);
         assert_eq!(winners.overflow().unwrap().len(), 3);
         assert_eq!(winners.rank(&"Alice").unwrap(), 0);
@@ -471,14 +1734,176 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn pairwise_counts_grow() {
+        let mut counts = PairwiseCounts::<u64>::with_capacity(2);
+        counts.add(0, 1, 3);
+        counts.add(1, 0, 1);
+        counts.add(4, 0, 2);
+        counts.add(0, 1, 1);
+
+        assert_eq!(counts.get(0, 1), Some(&4));
+        assert_eq!(counts.get(1, 0), Some(&1));
+        assert_eq!(counts.get(4, 0), Some(&2));
+        assert_eq!(counts.get(0, 4), None);
+        assert_eq!(counts.get(9, 0), None);
+        assert_eq!(counts.len(), 3);
+
+        counts.remove_candidate(0);
+        assert_eq!(counts.len(), 0);
+    }
+
+    #[test]
+    fn condorcet_add_ids() -> Result<(), TallyError> {
+        let candidates = vec!["Alice", "Bob", "Carol"];
+        let mut tally = DefaultCondorcetTally::with_candidates(1, candidates.clone());
+        let mut expected = DefaultCondorcetTally::with_candidates(1, candidates);
+        tally.withdraw_candidate(&"Alice");
+        expected.withdraw_candidate(&"Alice");
+        tally.add_candidate("Dave");
+        expected.add_candidate("Dave");
+        assert_eq!(
+            tally.candidate_ids(),
+            vec![("Bob", CandidateId(1)), ("Carol", CandidateId(2)), ("Dave", CandidateId(3))]
+        );
+
+        let bob = tally.candidate_id(&"Bob").unwrap();
+        let carol = tally.candidate_id(&"Carol").unwrap();
+        let dave = tally.candidate_id(&"Dave").unwrap();
+        tally.add_ids(&[dave, bob])?;
+        tally.add_ids_weighted(&[carol, bob, dave], 2)?;
+        expected.add(&["Dave", "Bob"])?;
+        expected.add_weighted(&["Carol", "Bob", "Dave"], 2)?;
+        assert_eq!(tally.matrix(), expected.matrix());
+
+        assert_eq!(
+            tally.add_ids(&[bob, CandidateId(0)]),
+            Err(TallyError::UnknownCandidate { position: 1 })
+        );
+        assert_eq!(
+            tally.add_ids(&[bob, CandidateId(9)]),
+            Err(TallyError::UnknownCandidate { position: 1 })
+        );
+        assert_eq!(
+            tally.add_ids(&[bob, carol, bob]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 2 })
+        );
+        assert_eq!(tally.matrix(), expected.matrix());
+
+        // An unchecked tally ignores unknown ids, and counts duplicates at their first rank.
+        let mut tally = tally.unchecked();
+        tally.add_ids(&[CandidateId(9), bob, carol, bob])?;
+        expected.add(&["Bob", "Carol"])?;
+        assert_eq!(tally.matrix(), expected.matrix());
+
+        // A tally built from a candidate set shares its ids.
+        let mut set = CandidateSet::with_candidates(vec!["Alice", "Bob"]);
+        let carol = set.intern("Carol");
+        let mut tally = DefaultCondorcetTally::from_candidate_set(1, &set);
+        assert_eq!(tally.candidate_id(&"Carol"), Some(carol));
+        tally.add_ids(&set.lookup_vote(&["Carol", "Alice"])?)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Carol"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn pairwise_counts_add_ballot() {
+        use num_bigint::BigUint;
+
+        // Candidate 1 is not on the ballot, and candidates 2 and 3 are tied.
+        let selection = vec![(4, 0), (2, 1), (3, 1), (0, 2)];
+        let mut expected = vec![(4, 2), (4, 3), (4, 0), (2, 0), (3, 0)];
+        expected.sort();
+
+        // Integer counts take the vectorized path, and big integers the branching path.
+        let mut counts = PairwiseCounts::<u64>::new();
+        counts.add_ballot(&selection, 2);
+        let mut pairs: Vec<(usize, usize)> = counts
+            .iter()
+            .map(|(pair, count)| {
+                assert_eq!(*count, 2);
+                pair
+            })
+            .collect();
+        pairs.sort();
+        assert_eq!(pairs, expected);
+
+        let mut counts = PairwiseCounts::<BigUint>::new();
+        counts.add_ballot(&selection, BigUint::from(2u32));
+        let mut pairs: Vec<(usize, usize)> = counts
+            .iter()
+            .map(|(pair, count)| {
+                assert_eq!(*count, BigUint::from(2u32));
+                pair
+            })
+            .collect();
+        pairs.sort();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn condorcet_unchecked_mapping() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]).unchecked();
+        tally.add(&["Dave", "Bob", "Alice", "Bob"])?;
+        tally.ranked_add(&[("Carol", 0), ("Erin", 1), ("Alice", 2)])?;
+
+        // Unknown candidates are ignored, and a repeated candidate keeps its first rank.
+        let totals = HashSet::from_iter(tally.totals().iter().cloned());
+        assert_eq!(
+            totals,
+            hashset![
+                (("Bob", "Alice"), 1),
+                (("Bob", "Carol"), 1),
+                (("Alice", "Carol"), 1),
+                (("Carol", "Alice"), 1),
+                (("Carol", "Bob"), 1),
+                (("Alice", "Bob"), 1)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_checked() -> Result<(), TallyError> {
+        let mut tally = CondorcetTally::<&str, u8>::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.checked_add_weighted(&["Alice", "Bob", "Carol"], 250)?;
+        tally.checked_add(&["Carol", "Bob", "Alice"])?;
+        assert_eq!(tally.checked_add_weighted(&["Bob", "Alice"], 10), Err(TallyError::CountOverflow));
+        assert_eq!(
+            tally.checked_ranked_add_weighted(&[("Carol", 0), ("Alice", 1)], 6),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(
+            tally.checked_add_weighted(&["Dave"], 1),
+            Err(TallyError::UnknownCandidate { position: 0 })
+        );
+
+        let totals = HashSet::from_iter(tally.totals().iter().cloned());
+        assert_eq!(
+            totals,
+            hashset![
+                (("Alice", "Bob"), 250),
+                (("Alice", "Carol"), 250),
+                (("Bob", "Carol"), 250),
+                (("Bob", "Alice"), 1),
+                (("Carol", "Alice"), 1),
+                (("Carol", "Bob"), 1)
+            ]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn condorcet_wikipedia() -> Result<(), TallyError> {
         // From: https://en.wikipedia.org/wiki/Condorcet_method
         let mut tally = DefaultCondorcetTally::with_candidates(4, vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"]);
-        tally.add_weighted(&vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"], 42)?;
-        tally.add_weighted(&vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26)?;
-        tally.add_weighted(&vec!["Chattanooga", "Knoxville", "Nashville", "Memphis"], 15)?;
-        tally.add_weighted(&vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17)?;
+        tally.add_weighted(&["Memphis", "Nashville", "Chattanooga", "Knoxville"], 42)?;
+        tally.add_weighted(&["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26)?;
+        tally.add_weighted(&["Chattanooga", "Knoxville", "Nashville", "Memphis"], 15)?;
+        tally.add_weighted(&["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17)?;
 
         let candidates = tally.candidates();
         let candidates = HashSet::from_iter(candidates.iter().cloned()); // As a hashset
@@ -519,11 +1944,11 @@ mod tests {
 
         // Example 1:
         let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["a", "b", "c", "d"]);
-        tally.add_weighted(&vec!["a", "c", "d", "b"], 8)?;
-        tally.add_weighted(&vec!["b", "a", "d", "c"], 2)?;
-        tally.add_weighted(&vec!["c", "d", "b", "a"], 4)?;
-        tally.add_weighted(&vec!["d", "b", "a", "c"], 4)?;
-        tally.add_weighted(&vec!["d", "c", "b", "a"], 3)?;
+        tally.add_weighted(&["a", "c", "d", "b"], 8)?;
+        tally.add_weighted(&["b", "a", "d", "c"], 2)?;
+        tally.add_weighted(&["c", "d", "b", "a"], 4)?;
+        tally.add_weighted(&["d", "b", "a", "c"], 4)?;
+        tally.add_weighted(&["d", "c", "b", "a"], 3)?;
 
         let graph = tally.build_graph();
         assert_eq!(graph.node_count(), 4);
@@ -544,4 +1969,326 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn condorcet_quorum() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]).with_quorum(Quorum::Turnout(4));
+        tally.add(&["Alice", "Bob"])?;
+        tally.ranked_add(&[("Bob", 0), ("Alice", 1)])?;
+        tally.add_weighted(&["Alice", "Bob"], 1)?;
+        assert!(!tally.quorum_met());
+        assert!(tally.winners().is_empty());
+        assert_eq!(tally.ranked()[0], ("Alice", 0));
+
+        tally.add_blank();
+        assert!(tally.quorum_met());
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_cached() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+        tally.add(&["Alice", "Bob"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.ranked(), tally.ranked());
+
+        // Each change to the tally invalidates the cached ranking.
+        tally.add_weighted(&["Bob", "Alice"], 2)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+        tally.add_candidate("Carol");
+        assert_eq!(tally.ranked().len(), 3);
+        tally.withdraw_candidate(&"Carol");
+        assert_eq!(tally.ranked().len(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_withdraw() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Alice", "Carol", "Bob"])?;
+        tally.add(&["Carol", "Bob", "Alice"])?;
+        tally.withdraw_candidate(&"Alice");
+        assert_eq!(tally.winners().into_unranked(), vec!["Carol"]);
+        assert_eq!(tally.add(&["Alice", "Bob"]), Err(TallyError::UnknownCandidate { position: 0 }));
+
+        // A new candidate does not take the id of an existing one.
+        tally.add_candidate("Dave");
+        tally.add(&["Dave", "Bob", "Carol"])?;
+        tally.add(&["Dave", "Bob", "Carol"])?;
+        let totals = tally.totals();
+        assert!(totals.contains(&(("Dave", "Carol"), 2)));
+        assert!(totals.contains(&(("Bob", "Carol"), 3)));
+        assert_eq!(tally.winners().into_unranked(), vec!["Dave"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_truncation() -> Result<(), TallyError> {
+        let candidates = vec!["Alice", "Bob", "Carol"];
+        let mut tally = DefaultCondorcetTally::with_candidates(1, candidates.clone()).with_truncation(Truncation::Minimum(2));
+        tally.add(&["Alice", "Bob"])?;
+        tally.ranked_add(&[("Bob", 0), ("Carol", 0)])?;
+        assert_eq!(tally.add(&["Carol"]), Err(TallyError::TruncatedBallot { rankings: 1, required: 2 }));
+        assert_eq!(
+            tally.ranked_add(&[("Carol", 0)]),
+            Err(TallyError::TruncatedBallot { rankings: 1, required: 2 })
+        );
+
+        let mut tally = DefaultCondorcetTally::with_candidates(1, candidates)
+            .with_truncation(Truncation::RequireFull)
+            .unchecked();
+        tally.add(&["Carol"])?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_margin_of_victory() -> Result<(), TallyError> {
+        // Alice leads Bob and Carol by 1 each, so the runner-up is the first one added.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Carol", "Bob"]);
+        tally.add(&["Alice", "Carol", "Bob"])?;
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Bob", "Carol", "Alice"])?;
+        let margin = tally.margin_of_victory().unwrap();
+        assert_eq!(margin.runner_up, "Carol");
+        assert_eq!(margin.margin, 1);
+        assert_eq!(margin.ballots_to_change(), 1);
+
+        // A cycle has no Condorcet winner.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Bob", "Carol", "Alice"])?;
+        tally.add(&["Carol", "Alice", "Bob"])?;
+        assert_eq!(tally.margin_of_victory(), None);
+        assert_eq!(
+            tally.margins(),
+            vec![(("Alice", "Bob"), 1), (("Carol", "Alice"), 1), (("Bob", "Carol"), 1)]
+        );
+
+        // Tied contests list the candidate added first, first.
+        let tally = DefaultCondorcetTally::with_candidates(1, vec!["Bob", "Alice"]);
+        assert_eq!(tally.margins(), vec![(("Bob", "Alice"), 0)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_winner_loser() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Bob", "Carol", "Alice"])?;
+        tally.add(&["Carol", "Alice", "Bob"])?;
+        assert_eq!(tally.condorcet_winner(), None);
+        assert_eq!(tally.condorcet_loser(), None);
+
+        // Break the cycle. Alice now beats Bob and Carol, and Bob still beats Carol.
+        tally.add_weighted(&["Alice", "Bob", "Carol"], 2)?;
+        assert_eq!(tally.condorcet_winner(), Some("Alice"));
+        assert_eq!(tally.condorcet_loser(), Some("Carol"));
+
+        // Candidates without any votes are tied, so neither wins nor loses.
+        tally.add_candidate("Dave");
+        assert_eq!(tally.condorcet_winner(), None);
+        assert_eq!(tally.condorcet_loser(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_smith_schwartz() -> Result<(), TallyError> {
+        // Alice and Bob tie head-to-head, and both beat Carol.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Bob", "Alice", "Carol"])?;
+        assert_eq!(tally.smith_set(), vec!["Alice", "Bob"]);
+        assert_eq!(tally.schwartz_set(), vec!["Alice", "Bob"]);
+
+        // Alice ties Bob and beats Carol, while Bob ties Carol. The tie pulls Carol into the Smith set, but not the Schwartz set.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        tally.add(&["Alice", "Bob", "Carol"])?;
+        tally.add(&["Carol", "Bob", "Alice"])?;
+        tally.add(&["Alice", "Carol", "Bob"])?;
+        tally.add(&["Bob", "Alice", "Carol"])?;
+        assert_eq!(tally.smith_set(), vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(tally.schwartz_set(), vec!["Alice", "Bob"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_recorded_ballots() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]).with_completion(Completion::Irv);
+        tally.add(&["Carlos", "Alice", "Bob"])?;
+
+        // Dave does not take the id of Carlos, so the recorded ballot is not counted for Dave.
+        tally.withdraw_candidate(&"Carlos");
+        tally.add_candidate("Dave");
+        assert_eq!(tally.candidate_id(&"Dave"), Some(CandidateId(3)));
+        assert!(tally.ballots.as_ref().unwrap()[0].0.iter().all(|(id, _)| *id != 3));
+
+        // Recorded ballots are merged into a tally that was not recording them, without the withdrawn candidate.
+        let mut merged = DefaultCondorcetTally::with_candidates(1, vec!["Bob", "Alice"]);
+        merged.merge(tally);
+        let ballots = merged.ballots.as_ref().unwrap();
+        assert_eq!(ballots.len(), 1);
+        let mut selection = ballots[0].0.clone();
+        selection.sort();
+        assert_eq!(selection, vec![(0, 2), (1, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_completion() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos", "Dave"]).with_completion(Completion::Irv);
+        for _ in 0..4 {
+            tally.add(&["Alice", "Bob", "Carlos", "Dave"])?;
+        }
+        let mut shard = tally.shard();
+        for _ in 0..3 {
+            shard.add(&["Bob", "Carlos", "Alice", "Dave"])?;
+        }
+        tally.merge(shard);
+        tally.add_weighted(&["Carlos", "Alice", "Bob"], 2)?;
+
+        // Carlos is eliminated first, and his ballots elect Alice. Dave is outside the cycle.
+        assert_eq!(tally.ranked(), vec![("Alice", 0), ("Bob", 1), ("Carlos", 2), ("Dave", 3)]);
+
+        // Alice and Bob each have 10 votes over the others in the cycle.
+        let tally = tally.with_completion(Completion::Borda);
+        let mut winners = tally.winners().into_unranked();
+        winners.sort();
+        assert_eq!(winners, vec!["Alice", "Bob"]);
+        assert_eq!(tally.ranked()[2], ("Carlos", 1));
+
+        // Every candidate in the cycle has one win.
+        let tally = tally.with_completion(Completion::Copeland);
+        assert_eq!(tally.winners().len(), 3);
+        assert_eq!(tally.ranked()[3], ("Dave", 1));
+
+        // Without a cycle, completion rules make no difference.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]).with_completion(Completion::Minimax);
+        tally.add_weighted(&["Bob", "Alice", "Carlos"], 2)?;
+        tally.add(&["Carlos", "Bob", "Alice"])?;
+        assert_eq!(tally.ranked(), vec![("Bob", 0), ("Alice", 1), ("Carlos", 2)]);
+        assert_eq!(tally.margin_of_victory().map(|margin| margin.margin), Some(1));
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_cycles() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos", "Dave", "Eve", "Frank"]);
+        tally.add_weighted(&["Alice", "Bob", "Carlos", "Dave", "Eve", "Frank"], 2)?;
+        tally.add_weighted(&["Bob", "Carlos", "Alice", "Eve", "Frank", "Dave"], 2)?;
+        tally.add_weighted(&["Carlos", "Alice", "Bob", "Frank", "Dave", "Eve"], 2)?;
+
+        // Two separate cycles, one beating the other.
+        let cycles = tally.cycles();
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].candidates, vec!["Alice", "Bob", "Carlos"]);
+        assert_eq!(cycles[1].candidates, vec!["Dave", "Eve", "Frank"]);
+        assert_eq!(cycles[1].defeats[0], (("Dave", "Eve"), (4, 2)));
+        assert_eq!(tally.smith_set(), vec!["Alice", "Bob", "Carlos"]);
+
+        // Ties do not form cycles, even though they join the Smith set.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+        tally.add(&["Alice", "Bob"])?;
+        tally.add(&["Bob", "Alice"])?;
+        assert!(tally.cycles().is_empty());
+        assert_eq!(tally.smith_set(), vec!["Alice", "Bob"]);
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_margin_matrix() -> Result<(), TallyError> {
+        let mut tally = CondorcetTally::<&str, f64>::with_candidates(1, vec!["Alice", "Bob", "Carlos"]);
+        tally.add_weighted(&["Alice", "Bob", "Carlos"], 1.5)?;
+        tally.add(&["Carlos", "Bob"])?;
+
+        let matrix = tally.margin_matrix();
+        assert_eq!(matrix.get(&"Alice", &"Bob"), Some(&0.5));
+        assert_eq!(matrix.get(&"Carlos", &"Alice"), Some(&-0.5));
+        assert_eq!(matrix.get(&"Bob", &"Bob"), Some(&0.0));
+
+        // Every margin is the negation of its reverse, and pairs without votes have a margin of zero.
+        let margins = tally.margin_totals();
+        assert_eq!(margins.len(), 6);
+        for ((a, b), margin) in margins.iter() {
+            assert_eq!(matrix.get(b, a), Some(&-margin));
+        }
+        tally.add_candidate("Dave");
+        assert_eq!(tally.margin_matrix().get(&"Dave", &"Alice"), Some(&0.0));
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_add_pairwise() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carlos"]).with_quorum(Quorum::Turnout(1));
+        tally.add_pairwise(&"Bob", &"Alice", 3)?;
+        tally.add_pairwise(&"Bob", &"Carlos", 2)?;
+        tally.add_pairwise(&"Carlos", &"Bob", 1)?;
+        assert_eq!(
+            tally.add_pairwise(&"Dave", &"Bob", 1),
+            Err(TallyError::UnknownCandidate { position: 0 })
+        );
+        assert_eq!(
+            tally.add_pairwise(&"Bob", &"Bob", 1),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+
+        // Pairwise preferences are counted, but are not ballots.
+        assert_eq!(tally.condorcet_winner(), Some("Bob"));
+        assert!(!tally.quorum_met());
+        tally.add(&["Alice", "Bob", "Carlos"])?;
+        assert_eq!(tally.matrix().get(&"Bob", &"Alice"), Some(&3));
+        assert_eq!(tally.matrix().get(&"Alice", &"Bob"), Some(&1));
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+
+        // An unchecked tally ignores unknown candidates.
+        let mut tally = tally.unchecked();
+        tally.add_pairwise(&"Dave", &"Alice", 10)?;
+        assert_eq!(tally.candidates().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_tie_order() -> Result<(), TallyError> {
+        let defeats = [("A", "B"), ("A", "C"), ("B", "C"), ("B", "D"), ("C", "D"), ("D", "A")];
+
+        // Whatever order the tally hashes candidates in, the cycle is listed by Copeland score, then in the order candidates were added.
+        for _ in 0..8 {
+            let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["D", "C", "B", "A"]);
+            for (winner, loser) in defeats.iter() {
+                tally.add_pairwise(winner, loser, 1)?;
+            }
+            assert_eq!(tally.ranked(), vec![("B", 0), ("A", 0), ("D", 0), ("C", 0)]);
+            assert_eq!(tally.winners().into_unranked(), vec!["B", "A", "D", "C"]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn condorcet_dot() -> Result<(), TallyError> {
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob \"Bobby\"", "Carol"]);
+        tally.add(&["Alice", "Bob \"Bobby\"", "Carol"])?;
+        tally.add(&["Alice", "Carol", "Bob \"Bobby\""])?;
+
+        let expected = r#"digraph {
+    "Alice" [style=bold];
+    "Bob \"Bobby\"";
+    "Carol";
+    "Alice" -> "Bob \"Bobby\"" [label="2-0"];
+    "Alice" -> "Carol" [label="2-0"];
+    "Bob \"Bobby\"" -> "Carol" [label="1-1", dir=none, style=dashed];
+}
+"#;
+        assert_eq!(tally.to_dot(), expected);
+
+        Ok(())
+    }
 }
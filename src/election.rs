@@ -0,0 +1,688 @@
+use hashbrown::HashMap;
+use hashbrown::HashSet;
+use num_traits::FromPrimitive;
+use num_traits::Num;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+use super::approval::ApprovalTally;
+use super::borda;
+use super::condorcet::CondorcetTally;
+use super::irv;
+use super::plurality::PluralityTally;
+use super::schulze;
+use super::stv;
+use super::util::split_ranked_vote;
+use super::Quota;
+use super::RankedWinners;
+use super::TallyError;
+use super::Transfer;
+
+/// A tally method, and its configuration.
+///
+/// Every method counts ranked ballots, listing candidates in order of preference.
+/// Methods that do not rank candidates read the ranking as follows:
+/// - `Plurality` counts each ballot for its first preference.
+/// - `Approval` counts each ballot as approving every candidate it lists.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Method<C> {
+    /// Plurality voting. See [`plurality`](../plurality/index.html).
+    Plurality,
+
+    /// Approval voting. See [`approval`](../approval/index.html).
+    Approval,
+
+    /// The standard Borda count. See [`borda::Variant::Borda`](../borda/enum.Variant.html#variant.Borda).
+    Borda,
+
+    /// Condorcet voting. See [`condorcet`](../condorcet/index.html).
+    Condorcet,
+
+    /// The Schulze method with the given variant. See [`schulze`](../schulze/index.html).
+    Schulze(schulze::Variant),
+
+    /// Single transferable vote with the given quota. See [`stv`](../stv/index.html).
+    Stv(Quota<C>),
+
+    /// Instant-runoff voting, which always elects a single winner.
+    Irv,
+}
+
+impl<C> Method<C>
+where
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    // Run a tally with this method over weighted ballots.
+    pub(crate) fn tally<T>(&self, num_winners: usize, candidates: &[T], ballots: &[(Vec<T>, C)]) -> Result<RankedWinners<T>, TallyError>
+    where
+        T: Eq + Clone + Hash,
+    {
+        let winners = match self {
+            Method::Plurality => {
+                let mut tally = PluralityTally::with_capacity(num_winners, candidates.len());
+                for (ballot, weight) in ballots.iter() {
+                    if let Some(first) = ballot.first() {
+                        tally.add_weighted_ref(first, weight.clone());
+                    }
+                }
+                tally.winners()
+            }
+            Method::Approval => {
+                let mut tally = ApprovalTally::with_capacity(num_winners, candidates.len());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted_ref(ballot, weight.clone())?;
+                }
+                tally.winners()
+            }
+            Method::Borda => {
+                let mut tally = borda::BordaTally::with_capacity(num_winners, borda::Variant::Borda, candidates.len());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted_ref(ballot, weight.clone())?;
+                }
+                tally.winners()
+            }
+            Method::Condorcet => {
+                let mut tally = CondorcetTally::with_candidates(num_winners, candidates.to_vec());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted(ballot, weight.clone())?;
+                }
+                tally.winners()
+            }
+            Method::Schulze(variant) => {
+                let mut tally = schulze::SchulzeTally::with_candidates(num_winners, *variant, candidates.to_vec());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted(ballot, weight.clone())?;
+                }
+                tally.winners()
+            }
+            Method::Stv(quota) => {
+                let mut tally = stv::Tally::with_candidates(num_winners, quota.clone(), candidates.to_vec());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted(ballot.clone(), weight.clone())?;
+                }
+                tally.winners()
+            }
+            Method::Irv => {
+                let mut tally = irv::Tally::with_candidates(Transfer::Meek, candidates.to_vec());
+                for (ballot, weight) in ballots.iter() {
+                    tally.add_weighted_ref(ballot, weight.clone())?;
+                }
+                tally.tally_winners()
+            }
+        };
+
+        Ok(winners)
+    }
+
+    // Run a tally with this method over weighted ranked ballots, which may rank candidates equally.
+    // Approval, Condorcet, Schulze and Borda count equal ranks directly, and the other methods split each ballot into strict orderings.
+    pub(crate) fn tally_ranked<T>(
+        &self,
+        num_winners: usize,
+        candidates: &[T],
+        ballots: &[(Vec<(T, u32)>, C)],
+    ) -> Result<RankedWinners<T>, TallyError>
+    where
+        T: Eq + Clone + Hash,
+    {
+        let winners = match self {
+            Method::Approval => {
+                let mut tally = ApprovalTally::with_capacity(num_winners, candidates.len());
+                for (index, (ballot, weight)) in ballots.iter().enumerate() {
+                    let approved: Vec<T> = ballot.iter().map(|(candidate, _)| candidate.clone()).collect();
+                    tally.add_weighted(approved, weight.clone()).map_err(|e| e.on_ballot(index))?;
+                }
+                tally.winners()
+            }
+            Method::Borda => {
+                let mut tally = borda::BordaTally::with_capacity(num_winners, borda::Variant::Borda, candidates.len());
+                for (index, (ballot, weight)) in ballots.iter().enumerate() {
+                    tally.ranked_add_weighted(ballot, weight.clone()).map_err(|e| e.on_ballot(index))?;
+                }
+                tally.winners()
+            }
+            Method::Condorcet => {
+                let mut tally = CondorcetTally::with_candidates(num_winners, candidates.to_vec());
+                for (index, (ballot, weight)) in ballots.iter().enumerate() {
+                    tally.ranked_add_weighted(ballot, weight.clone()).map_err(|e| e.on_ballot(index))?;
+                }
+                tally.winners()
+            }
+            Method::Schulze(variant) => {
+                let mut tally = schulze::SchulzeTally::with_candidates(num_winners, *variant, candidates.to_vec());
+                for (index, (ballot, weight)) in ballots.iter().enumerate() {
+                    tally.ranked_add_weighted(ballot, weight.clone()).map_err(|e| e.on_ballot(index))?;
+                }
+                tally.winners()
+            }
+            _ => {
+                let mut strict = Vec::with_capacity(ballots.len());
+                for (index, (ballot, weight)) in ballots.iter().enumerate() {
+                    strict.extend(split_ranked_vote(ballot, weight.clone()).map_err(|e| e.on_ballot(index))?);
+                }
+                return self.tally(num_winners, candidates, &strict);
+            }
+        };
+
+        Ok(winners)
+    }
+}
+
+/// How to break a tie for the last seat, when more candidates are tied for it than there are seats left.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TieBreak<T> {
+    /// Leave ties unbroken, returning every tied candidate as a winner.
+    /// See [`RankedWinners::overflow()`](../struct.RankedWinners.html#method.overflow).
+    #[default]
+    Keep,
+
+    /// Break ties in favour of the candidate listed first.
+    ///
+    /// Tied candidates that are not listed lose to those that are, and stay tied with each other.
+    Order(Vec<T>),
+}
+
+impl<T: Clone + Eq + Hash> TieBreak<T> {
+    /// Break a tie for the last seat among the winners.
+    ///
+    /// Candidates that win the tie are given ranks in the order the tie was broken, and candidates that lose it are removed.
+    pub fn resolve(&self, mut winners: RankedWinners<T>) -> RankedWinners<T> {
+        let order = match self {
+            TieBreak::Keep => return winners,
+            TieBreak::Order(order) => order,
+        };
+        let overflow_rank = match winners.winners.last() {
+            Some(last) if winners.check_overflow() => last.rank,
+            _ => return winners,
+        };
+
+        let split = winners.winners.iter().position(|ranked| ranked.rank == overflow_rank).unwrap_or(0);
+        let seats = winners.num_winners.saturating_sub(split);
+        let mut tied: Vec<T> = winners.drain(split..).map(|ranked| ranked.candidate).collect();
+
+        // A stable sort keeps unlisted candidates in their original order, after the listed candidates.
+        tied.sort_by_key(|candidate| order.iter().position(|c| c == candidate).unwrap_or(order.len()));
+        let listed = tied.iter().filter(|candidate| order.contains(candidate)).count();
+        for (i, candidate) in tied.into_iter().enumerate() {
+            if i >= seats && (i < listed || listed >= seats) {
+                break;
+            }
+            winners.push(candidate, overflow_rank + std::cmp::min(i, listed));
+        }
+
+        winners
+    }
+}
+/// A tally configured by an [`ElectionBuilder`](struct.ElectionBuilder.html), that counts ballots with any method.
+///
+/// Ballots rank candidates in order of preference, and are checked against the tally's candidates as they are added.
+pub struct MethodTally<T, C = u64>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    method: Method<C>,
+    num_winners: usize,
+    candidates: Vec<T>,
+    standing: HashSet<T>, // The candidates, for checking ballots against
+    tie_break: TieBreak<T>,
+    ballots: Vec<(Vec<T>, C)>,
+}
+
+impl<T, C> MethodTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn new(method: Method<C>, num_winners: usize, candidates: Vec<T>, tie_break: TieBreak<T>) -> Self {
+        MethodTally {
+            method: method,
+            num_winners: num_winners,
+            standing: candidates.iter().cloned().collect(),
+            candidates: candidates,
+            tie_break: tie_break,
+            ballots: Vec::new(),
+        }
+    }
+
+    /// Get the method used to count the tally.
+    pub fn method(&self) -> &Method<C> {
+        &self.method
+    }
+
+    /// Get the candidates standing in the tally.
+    pub fn candidates(&self) -> &[T] {
+        &self.candidates
+    }
+
+    /// Add a ballot.
+    ///
+    /// Returns an error if the ballot has duplicate candidates or candidates that are not standing.
+    pub fn add(&mut self, ballot: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted(ballot, C::one())
+    }
+
+    /// Add a weighted ballot.
+    pub fn add_weighted(&mut self, ballot: Vec<T>, weight: C) -> Result<(), TallyError> {
+        for (position, candidate) in ballot.iter().enumerate() {
+            if !self.standing.contains(candidate) {
+                return Err(TallyError::UnknownCandidate { position });
+            }
+        }
+        crate::util::check_duplicates_transitive_vote(&ballot)?;
+
+        self.ballots.push((ballot, weight));
+        Ok(())
+    }
+
+    /// Get a ranked list of winners, with any tie for the last seat broken by the tally's tie-break.
+    pub fn winners(&self) -> Result<RankedWinners<T>, TallyError> {
+        let winners = self.method.tally(self.num_winners, &self.candidates, &self.ballots)?;
+        Ok(self.tie_break.resolve(winners))
+    }
+}
+
+/// A builder for a [`MethodTally`](struct.MethodTally.html), for tallies configured at runtime.
+///
+/// The configuration is checked when the tally is built, rather than when it is counted.
+///
+/// # Example
+/// ```
+///    use tallystick::election::{ElectionBuilder, Method, TieBreak};
+///    use tallystick::schulze::Variant;
+///    use tallystick::TallyError;
+///
+///    let mut tally = ElectionBuilder::<&str>::new()
+///        .method(Method::Schulze(Variant::Winning))
+///        .winners(1)
+///        .candidates(vec!["Alice", "Bob", "Carlos"])
+///        .tie_break(TieBreak::Order(vec!["Bob", "Alice", "Carlos"]))
+///        .build()?;
+///
+///    tally.add(vec!["Alice", "Carlos"])?;
+///    tally.add(vec!["Bob", "Carlos"])?;
+///
+///    // Alice and Bob are tied, and the tie is broken in Bob's favour.
+///    assert_eq!(tally.winners()?.into_unranked(), vec!["Bob"]);
+///    # Ok::<(), TallyError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ElectionBuilder<T, C = u64> {
+    method: Option<Method<C>>,
+    num_winners: usize,
+    candidates: Vec<T>,
+    tie_break: TieBreak<T>,
+}
+
+impl<T, C> Default for ElectionBuilder<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> ElectionBuilder<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    /// Create a new builder, for a tally with one winner.
+    pub fn new() -> Self {
+        ElectionBuilder {
+            method: None,
+            num_winners: 1,
+            candidates: Vec::new(),
+            tie_break: TieBreak::Keep,
+        }
+    }
+
+    /// Set the method used to count the tally. A method is required.
+    pub fn method(mut self, method: Method<C>) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Set the number of winners.
+    pub fn winners(mut self, num_winners: usize) -> Self {
+        self.num_winners = num_winners;
+        self
+    }
+
+    /// Set the candidates standing in the tally.
+    pub fn candidates(mut self, candidates: Vec<T>) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Set how to break a tie for the last seat. By default ties are kept.
+    pub fn tie_break(mut self, tie_break: TieBreak<T>) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Build the tally.
+    ///
+    /// Returns `TallyError::InvalidConfig` if no method is set, if there are no winners or candidates,
+    /// if a candidate is listed twice, or if more than one winner is wanted from an IRV tally.
+    pub fn build(self) -> Result<MethodTally<T, C>, TallyError> {
+        let method = self.method.ok_or(TallyError::InvalidConfig("no tally method"))?;
+        if self.num_winners == 0 {
+            return Err(TallyError::InvalidConfig("no winners"));
+        }
+        if self.candidates.is_empty() {
+            return Err(TallyError::InvalidConfig("no candidates"));
+        }
+        if crate::util::check_duplicates_transitive_vote(&self.candidates).is_err() {
+            return Err(TallyError::InvalidConfig("duplicate candidates"));
+        }
+        if method == Method::Irv && self.num_winners != 1 {
+            return Err(TallyError::InvalidConfig("instant-runoff voting elects a single winner"));
+        }
+
+        Ok(MethodTally::new(method, self.num_winners, self.candidates, self.tie_break))
+    }
+}
+
+// A district or race, tallied with its own method and candidates.
+struct District<D, T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    district: D,
+    tally: MethodTally<T, C>,
+}
+
+/// An election made up of many districts, or races, each with its own tally method and candidates.
+///
+/// Ballots for all districts can be added from a single stream. Each ballot is checked against the candidates in its district as it is added.
+///
+/// # Example
+/// ```
+///    use tallystick::election::{Election, Method};
+///    use tallystick::schulze::Variant;
+///    use tallystick::TallyError;
+///
+///    let mut election = Election::<&str, &str>::new();
+///    election.add_district("Mayor", Method::Schulze(Variant::Winning), 1, vec!["Alice", "Bob"])?;
+///    election.add_district("Council", Method::Plurality, 2, vec!["Carlos", "Dave", "Erin"])?;
+///
+///    election.add_ballots(vec![
+///        ("Mayor", vec!["Alice", "Bob"]),
+///        ("Council", vec!["Carlos"]),
+///        ("Mayor", vec!["Alice"]),
+///        ("Council", vec!["Dave"]),
+///        ("Council", vec!["Dave"]),
+///    ])?;
+///
+///    let results = election.results()?;
+///    assert_eq!(results.winners(&"Mayor").unwrap().clone().into_unranked(), vec!["Alice"]);
+///    assert_eq!(results.winners(&"Council").unwrap().clone().into_unranked(), vec!["Dave", "Carlos"]);
+///    # Ok::<(), TallyError>(())
+/// ```
+pub struct Election<D, T, C = u64>
+where
+    D: Eq + Clone + Hash,                                    // District
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    districts: Vec<District<D, T, C>>,
+    index: HashMap<D, usize>, // Map districts to their position in `districts`
+}
+
+impl<D, T, C> Default for Election<D, T, C>
+where
+    D: Eq + Clone + Hash,                                    // District
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D, T, C> Election<D, T, C>
+where
+    D: Eq + Clone + Hash,                                    // District
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    /// Create a new election with no districts.
+    pub fn new() -> Self {
+        Election {
+            districts: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Add a district to the election, with the method used to tally it, the number of winners, and its candidates.
+    ///
+    /// Adding a district that already exists replaces it, discarding any ballots already added to it.
+    /// The district is checked as [`ElectionBuilder::build()`](struct.ElectionBuilder.html#method.build) checks a tally,
+    /// and ties are kept. To break ties, build the tally and add it with [`add_district_tally()`](#method.add_district_tally).
+    pub fn add_district(&mut self, district: D, method: Method<C>, num_winners: usize, candidates: Vec<T>) -> Result<(), TallyError> {
+        let tally = ElectionBuilder::new()
+            .method(method)
+            .winners(num_winners)
+            .candidates(candidates)
+            .build()?;
+        self.add_district_tally(district, tally);
+        Ok(())
+    }
+
+    /// Add a district to the election, counted by a tally built with an [`ElectionBuilder`](struct.ElectionBuilder.html).
+    ///
+    /// Adding a district that already exists replaces it, discarding any ballots already added to it.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::election::{Election, ElectionBuilder, Method, TieBreak};
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut election = Election::<&str, &str>::new();
+    ///    let tally = ElectionBuilder::new()
+    ///        .method(Method::Plurality)
+    ///        .candidates(vec!["Alice", "Bob"])
+    ///        .tie_break(TieBreak::Order(vec!["Bob", "Alice"]))
+    ///        .build()?;
+    ///    election.add_district_tally("Mayor", tally);
+    ///
+    ///    election.add(&"Mayor", vec!["Alice"])?;
+    ///    election.add(&"Mayor", vec!["Bob"])?;
+    ///
+    ///    let results = election.results()?;
+    ///    assert_eq!(results.winners(&"Mayor").unwrap().clone().into_unranked(), vec!["Bob"]);
+    ///    # Ok::<(), TallyError>(())
+    /// ```
+    pub fn add_district_tally(&mut self, district: D, tally: MethodTally<T, C>) {
+        let new = District {
+            district: district.clone(),
+            tally: tally,
+        };
+        match self.index.get(&district) {
+            Some(&position) => self.districts[position] = new,
+            None => {
+                self.index.insert(district, self.districts.len());
+                self.districts.push(new);
+            }
+        }
+    }
+
+    /// Get all districts in the election, in the order they were added.
+    pub fn districts(&self) -> Vec<D> {
+        self.districts.iter().map(|d| d.district.clone()).collect()
+    }
+
+    /// Add a ballot to a district.
+    ///
+    /// Returns `TallyError::UnknownDistrict` if there is no such district, or an error if the ballot
+    /// has duplicate candidates or candidates that are not standing in the district.
+    pub fn add(&mut self, district: &D, ballot: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted(district, ballot, C::one())
+    }
+
+    /// Add a weighted ballot to a district.
+    pub fn add_weighted(&mut self, district: &D, ballot: Vec<T>, weight: C) -> Result<(), TallyError> {
+        let position = *self.index.get(district).ok_or(TallyError::UnknownDistrict)?;
+        self.districts[position].tally.add_weighted(ballot, weight)
+    }
+
+    /// Add a stream of ballots, each for a district.
+    ///
+    /// Ballots are added until an invalid ballot is found, which is returned as a `TallyError::InvalidBallot` with the index of the ballot in the stream.
+    pub fn add_ballots<I>(&mut self, ballots: I) -> Result<(), TallyError>
+    where
+        I: IntoIterator<Item = (D, Vec<T>)>,
+    {
+        for (index, (district, ballot)) in ballots.into_iter().enumerate() {
+            self.add(&district, ballot).map_err(|e| e.on_ballot(index))?;
+        }
+        Ok(())
+    }
+
+    /// Tally every district, returning the winners of each.
+    pub fn results(&self) -> Result<ElectionResult<D, T>, TallyError> {
+        let mut districts = Vec::with_capacity(self.districts.len());
+        for district in self.districts.iter() {
+            districts.push((district.district.clone(), district.tally.winners()?));
+        }
+
+        Ok(ElectionResult { districts: districts })
+    }
+}
+
+/// The results of an election, with the winners of each district.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElectionResult<D, T: Clone + Eq> {
+    /// Winners for each district, in the order districts were added to the election.
+    pub districts: Vec<(D, RankedWinners<T>)>,
+}
+
+impl<D: Eq, T: Clone + Eq> ElectionResult<D, T> {
+    /// Get the winners of a district.
+    pub fn winners(&self, district: &D) -> Option<&RankedWinners<T>> {
+        self.districts.iter().find(|(d, _)| d == district).map(|(_, winners)| winners)
+    }
+
+    /// Get every winner in the election, with the district they won.
+    pub fn all_winners(&self) -> Vec<(&D, &T)> {
+        self.districts
+            .iter()
+            .flat_map(|(district, winners)| winners.iter().map(move |ranked| (district, &ranked.candidate)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn election_test() -> Result<(), TallyError> {
+        let mut election = Election::<u32, &str>::new();
+        election.add_district(1, Method::Irv, 1, vec!["Alice", "Bob", "Carol"])?;
+        election.add_district(2, Method::Stv(Quota::Droop), 2, vec!["Alice", "Dave", "Erin"])?;
+        election.add_district(3, Method::Borda, 1, vec!["Frank", "Grace"])?;
+
+        for _ in 0..4 {
+            election.add(&1, vec!["Alice", "Bob"])?;
+            election.add(&2, vec!["Alice", "Dave"])?;
+        }
+        for _ in 0..3 {
+            election.add(&1, vec!["Bob", "Alice"])?;
+            election.add(&2, vec!["Erin"])?;
+        }
+        election.add_weighted(&1, vec!["Carol", "Bob"], 2)?;
+        election.add(&3, vec!["Grace", "Frank"])?;
+        election.add(&3, vec!["Grace"])?;
+
+        assert_eq!(election.add(&4, vec!["Alice"]), Err(TallyError::UnknownDistrict));
+        assert_eq!(election.add(&3, vec!["Alice"]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(
+            election.add_ballots(vec![(3, vec!["Frank"]), (3, vec!["Frank", "Frank"])]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 }.on_ballot(1))
+        );
+        assert_eq!(election.districts(), vec![1, 2, 3]);
+
+        // Districts are checked like built tallies, and an invalid district is not added.
+        assert_eq!(
+            election.add_district(4, Method::Irv, 2, vec!["Alice", "Bob"]),
+            Err(TallyError::InvalidConfig("instant-runoff voting elects a single winner"))
+        );
+        assert!(election.add_district(4, Method::Plurality, 0, vec!["Alice"]).is_err());
+        assert!(election.add_district(4, Method::Plurality, 1, vec![]).is_err());
+        assert!(election.add_district(4, Method::Plurality, 1, vec!["Alice", "Alice"]).is_err());
+        assert_eq!(election.districts(), vec![1, 2, 3]);
+
+        // Carol is eliminated and transfers to Bob, who beats Alice 5 to 4.
+        let results = election.results()?;
+        assert_eq!(results.winners(&1).unwrap().clone().into_unranked(), vec!["Bob"]);
+        assert_eq!(results.winners(&2).unwrap().len(), 2);
+        assert!(results.winners(&2).unwrap().contains(&"Alice"));
+        assert!(results.winners(&2).unwrap().contains(&"Erin"));
+        assert_eq!(results.winners(&3).unwrap().clone().into_unranked(), vec!["Grace"]);
+        assert_eq!(results.all_winners().len(), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn election_builder_test() -> Result<(), TallyError> {
+        let builder = ElectionBuilder::<&str>::new().candidates(vec!["Alice", "Bob", "Carol", "Dave"]);
+        assert_eq!(builder.clone().build().err(), Some(TallyError::InvalidConfig("no tally method")));
+        assert!(builder.clone().method(Method::Irv).winners(2).build().is_err());
+        assert!(builder.clone().method(Method::Borda).winners(0).build().is_err());
+        assert!(ElectionBuilder::<&str>::new()
+            .method(Method::Plurality)
+            .candidates(vec!["Alice", "Alice"])
+            .build()
+            .is_err());
+
+        // Three candidates are tied for two seats.
+        let builder = builder.method(Method::Plurality).winners(2);
+        let mut tally = builder.clone().build()?;
+        for candidate in ["Alice", "Bob", "Carol"].iter() {
+            tally.add(vec![candidate])?;
+        }
+        assert_eq!(tally.add(vec!["Erin"]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(tally.winners()?.len(), 3);
+
+        let tie_break = TieBreak::Order(vec!["Carol", "Alice"]);
+        let mut tally = builder.clone().tie_break(tie_break.clone()).build()?;
+        for candidate in ["Alice", "Bob", "Carol"].iter() {
+            tally.add(vec![candidate])?;
+        }
+        let winners = tally.winners()?;
+        assert_eq!(winners.rank(&"Carol"), Some(0));
+        assert_eq!(winners.rank(&"Alice"), Some(1));
+        assert!(!winners.contains(&"Bob"));
+
+        // Unlisted candidates stay tied with each other.
+        let mut tally = builder.build()?;
+        for candidate in ["Alice", "Bob", "Carol"].iter() {
+            tally.add(vec![candidate])?;
+        }
+        let winners = TieBreak::Order(vec!["Carol", "Dave"]).resolve(tally.winners()?);
+        assert_eq!(winners.rank(&"Carol"), Some(0));
+        assert_eq!(winners.overflow().map(|overflow| overflow.len()), Some(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn election_fractional_weights() -> Result<(), TallyError> {
+        let mut tally = ElectionBuilder::<&str, f64>::new()
+            .method(Method::Stv(Quota::Droop))
+            .candidates(vec!["Alice", "Bob", "Carol"])
+            .build()?;
+        tally.add_weighted(vec!["Alice", "Bob"], 1.5)?;
+        tally.add_weighted(vec!["Bob"], 0.5)?;
+        tally.add_weighted(vec!["Carol", "Bob"], 0.75)?;
+        assert_eq!(tally.winners()?.into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+}
@@ -1,27 +1,304 @@
-use failure::Fail;
+use std::error::Error;
+use std::fmt;
 
 /// TallyError enum contains a list of all errors that may occur during a tally.
-#[derive(Debug, Fail)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TallyError {
     /// A vote contains duplicate candidates.
-    #[fail(display = "tallystick: vote contains duplicate candidates")]
-    VoteHasDuplicateCandidates,
+    VoteHasDuplicateCandidates {
+        /// Position in the vote of the repeated candidate.
+        position: usize,
+    },
 
     /// A vote contains an unknown candidate.
-    #[fail(display = "tallystick: vote contains unknown candidate")]
-    UnknownCandidate,
+    UnknownCandidate {
+        /// Position in the vote of the unknown candidate.
+        position: usize,
+    },
+
+    /// A vote gives a candidate a score outside the tally's range of scores.
+    ScoreOutOfRange {
+        /// Position in the vote of the candidate with the out of range score.
+        position: usize,
+
+        /// The out of range score.
+        score: String,
+    },
+
+    /// Adding a vote would overflow the count type.
+    CountOverflow,
+
+    /// The weight of a vote with equally ranked candidates cannot be split evenly between them in the count type.
+    IndivisibleWeight,
+
+    /// A vote ranks so many candidates equally that it cannot be split between every ordering of them.
+    TooManyTies {
+        /// Maximum number of orderings a vote may be split between.
+        max: usize,
+    },
+
+    /// A ballot does not rank enough candidates for the tally's truncation policy.
+    TruncatedBallot {
+        /// Number of candidates ranked on the ballot.
+        rankings: usize,
+
+        /// Number of candidates the ballot is required to rank.
+        required: usize,
+    },
+
+    /// A ballot approves more candidates than the tally allows.
+    TooManyApprovals {
+        /// Number of candidates approved on the ballot.
+        approvals: usize,
+
+        /// Maximum number of candidates a ballot may approve.
+        max: usize,
+    },
+
+    /// A ranked ballot was added to a tally that needs scores.
+    UnscoredBallot,
+
+    /// A voter has already cast a ballot.
+    DuplicateVoter,
+
+    /// A ballot is for a district that is not part of the election.
+    UnknownDistrict,
+
+    /// A ballot in a batch of ballots is invalid.
+    InvalidBallot {
+        /// Index of the offending ballot within the batch.
+        index: usize,
+
+        /// The reason the ballot is invalid.
+        error: Box<TallyError>,
+    },
+
+    /// A tally or election is configured incorrectly.
+    InvalidConfig(&'static str),
+
+    /// Ballots stored outside of memory could not be read or written.
+    Io {
+        /// The kind of I/O error.
+        kind: std::io::ErrorKind,
+
+        /// A description of the I/O error.
+        message: String,
+    },
+}
+
+impl TallyError {
+    /// Attach the index of the offending ballot to this error.
+    pub fn on_ballot(self, index: usize) -> Self {
+        TallyError::InvalidBallot {
+            index,
+            error: Box::new(self),
+        }
+    }
+
+    /// Get the position in the vote of the candidate that caused this error, if there is one.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            TallyError::VoteHasDuplicateCandidates { position } => Some(*position),
+            TallyError::UnknownCandidate { position } => Some(*position),
+            TallyError::ScoreOutOfRange { position, .. } => Some(*position),
+            TallyError::InvalidBallot { error, .. } => error.position(),
+            _ => None,
+        }
+    }
+
+    /// Get the candidate that caused this error from the vote that was rejected, if there is one.
+    ///
+    /// Candidates can be of any type, so the error records the candidate's position rather than the candidate.
+    /// When validating a batch of ballots, [`Diagnostic`](validate/struct.Diagnostic.html) also reports the candidate.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::condorcet::DefaultCondorcetTally;
+    ///
+    ///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+    ///    let vote = vec!["Alice", "Carlos"];
+    ///    let error = tally.add(&vote).unwrap_err();
+    ///    assert_eq!(error.candidate(&vote), Some(&"Carlos"));
+    /// ```
+    pub fn candidate<'a, T>(&self, vote: &'a [T]) -> Option<&'a T> {
+        self.position().and_then(|position| vote.get(position))
+    }
+
+    /// Get the index of the offending ballot, if this error came from a batch of ballots.
+    pub fn ballot(&self) -> Option<usize> {
+        match self {
+            TallyError::InvalidBallot { index, .. } => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TallyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TallyError::VoteHasDuplicateCandidates { position } => {
+                write!(f, "tallystick: vote contains duplicate candidates at position {}", position)
+            }
+            TallyError::UnknownCandidate { position } => write!(f, "tallystick: vote contains unknown candidate at position {}", position),
+            TallyError::ScoreOutOfRange { position, score } => {
+                write!(f, "tallystick: vote contains out of range score {} at position {}", score, position)
+            }
+            TallyError::CountOverflow => write!(f, "tallystick: vote count overflowed the count type"),
+            TallyError::IndivisibleWeight => write!(
+                f,
+                "tallystick: vote weight cannot be split evenly between equally ranked candidates, use a fractional count type such as f64"
+            ),
+            TallyError::TooManyTies { max } => write!(
+                f,
+                "tallystick: vote ranks too many candidates equally to split between at most {} orderings",
+                max
+            ),
+            TallyError::TruncatedBallot { rankings, required } => {
+                write!(
+                    f,
+                    "tallystick: vote ranks {} candidates but at least {} are required",
+                    rankings, required
+                )
+            }
+            TallyError::TooManyApprovals { approvals, max } => {
+                write!(
+                    f,
+                    "tallystick: vote approves {} candidates but at most {} are allowed",
+                    approvals, max
+                )
+            }
+            TallyError::UnscoredBallot => write!(f, "tallystick: vote is ranked, but the tally needs scores"),
+            TallyError::DuplicateVoter => write!(f, "tallystick: voter has already cast a ballot"),
+            TallyError::UnknownDistrict => write!(f, "tallystick: vote is for an unknown district"),
+            TallyError::InvalidBallot { index, error } => write!(f, "{} (ballot {})", error, index),
+            TallyError::InvalidConfig(reason) => write!(f, "tallystick: invalid configuration: {}", reason),
+            TallyError::Io { message, .. } => write!(f, "tallystick: error reading or writing ballots: {}", message),
+        }
+    }
+}
+
+impl Error for TallyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TallyError::InvalidBallot { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// TieError is returned when a single winner is asked for, but there is not exactly one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TieError<T> {
+    /// There are no winners, because there were no candidates or no decision was made.
+    NoWinner,
+
+    /// The top rank is shared by several tied candidates.
+    Tied(Vec<T>),
+}
+
+impl<T> fmt::Display for TieError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TieError::NoWinner => write!(f, "tallystick: there is no winner"),
+            TieError::Tied(candidates) => write!(f, "tallystick: {} candidates are tied for the win", candidates.len()),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for TieError<T> {}
+
+impl From<std::io::Error> for TallyError {
+    fn from(error: std::io::Error) -> Self {
+        TallyError::Io {
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
 }
 
 /// ParseError enum contains a list of all errors that may occur during vote parsing.
-#[derive(Debug, Fail)]
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum ParseError {
-    #[fail(display = "tallystick: error parsing numeric value {}", 0)]
     /// Unable to parse this numeric value
     ParseError(String),
 
-    #[fail(display = "tallystick: error reading vote data: {}", 0)]
     /// Unable to read cursor
     ReadError(std::io::Error),
+
+    /// A vote names a candidate that is not accepted.
+    UnknownCandidate(String),
+
+    /// A vote file does not have what was expected in its place, such as a number or a candidate name.
+    Expected(&'static str),
+
+    /// A line in a vote file could not be parsed.
+    InvalidLine {
+        /// Line number of the offending line, counting from 1.
+        line: usize,
+
+        /// Column of the offending text within the line, counting characters from 1.
+        column: usize,
+
+        /// The offending line, as it was read.
+        text: String,
+
+        /// The reason the line could not be parsed.
+        error: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    /// Attach the location of the offending line to this error.
+    pub fn on_line(self, line: usize, column: usize, text: &str) -> Self {
+        ParseError::InvalidLine {
+            line,
+            column,
+            text: text.to_string(),
+            error: Box::new(self),
+        }
+    }
+
+    /// Get the line number of the offending line, if known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidLine { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Get the column of the offending text within its line, if known.
+    pub fn column(&self) -> Option<usize> {
+        match self {
+            ParseError::InvalidLine { column, .. } => Some(*column),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::ParseError(value) => write!(f, "tallystick: error parsing numeric value {}", value),
+            ParseError::ReadError(error) => write!(f, "tallystick: error reading vote data: {}", error),
+            ParseError::UnknownCandidate(candidate) => write!(f, "tallystick: vote contains unknown candidate {}", candidate),
+            ParseError::Expected(expected) => write!(f, "tallystick: expected {}", expected),
+            ParseError::InvalidLine { line, column, text, error } => {
+                write!(f, "{} (line {}, column {}: {})", error, line, column, text)
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::ReadError(error) => Some(error),
+            ParseError::InvalidLine { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
 }
 
 impl From<std::io::Error> for ParseError {
@@ -29,3 +306,47 @@ impl From<std::io::Error> for ParseError {
         ParseError::ReadError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tally_error_context() {
+        let error = TallyError::UnknownCandidate { position: 2 }.on_ballot(5);
+        assert_eq!(error.position(), Some(2));
+        assert_eq!(error.candidate(&["Alice", "Bob", "Carlos"]), Some(&"Carlos"));
+        assert_eq!(error.candidate(&["Alice"]), None);
+        assert_eq!(error.ballot(), Some(5));
+        assert_eq!(
+            error.to_string(),
+            "tallystick: vote contains unknown candidate at position 2 (ballot 5)"
+        );
+        assert!(error.source().is_some());
+
+        let error = TallyError::VoteHasDuplicateCandidates { position: 1 };
+        assert_eq!(error.ballot(), None);
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn parse_error_context() {
+        let error = ParseError::ParseError("x".to_string()).on_line(4, 9, "Alice * x");
+        assert_eq!((error.line(), error.column()), (Some(4), Some(9)));
+        assert_eq!(
+            error.to_string(),
+            "tallystick: error parsing numeric value x (line 4, column 9: Alice * x)"
+        );
+        assert!(error.source().is_some());
+        assert_eq!(ParseError::ParseError("x".to_string()).line(), None);
+    }
+
+    #[test]
+    fn tie_error_display() {
+        assert_eq!(TieError::<&str>::NoWinner.to_string(), "tallystick: there is no winner");
+        assert_eq!(
+            TieError::Tied(vec!["Alice", "Bob"]).to_string(),
+            "tallystick: 2 candidates are tied for the win"
+        );
+    }
+}
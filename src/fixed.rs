@@ -0,0 +1,339 @@
+use num_traits::{CheckedAdd, CheckedMul, FromPrimitive, Num, One, Zero};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign};
+
+use super::Numeric;
+
+const OVERFLOW_PANIC: &str = "tallystick::fixed::Fixed: arithmetic overflow";
+
+/// A rounding rule applied when a fixed-point result has more decimal places than the type can hold.
+///
+/// Rounding only happens on multiplication, division and conversion. Addition and subtraction are always exact.
+pub trait Rounding: Copy + Eq + Ord + Hash + Default + fmt::Debug {
+    /// Divide `numer` by `denom` and round the quotient to an integer. `denom` is never zero.
+    fn div(numer: i128, denom: i128) -> i128;
+
+    /// Round a float to an integer.
+    fn round_f64(value: f64) -> f64;
+}
+
+/// Discard any digits past the last decimal place. This is the rule used by most STV statutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Truncate;
+
+impl Rounding for Truncate {
+    fn div(numer: i128, denom: i128) -> i128 {
+        numer / denom
+    }
+
+    fn round_f64(value: f64) -> f64 {
+        value.trunc()
+    }
+}
+
+/// Round to the nearest value in the last decimal place, with halves rounded away from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct RoundHalfUp;
+
+impl Rounding for RoundHalfUp {
+    fn div(numer: i128, denom: i128) -> i128 {
+        let quotient = numer / denom;
+        let remainder = numer % denom;
+        if remainder.abs() * 2 >= denom.abs() {
+            quotient + numer.signum() * denom.signum()
+        } else {
+            quotient
+        }
+    }
+
+    fn round_f64(value: f64) -> f64 {
+        value.round()
+    }
+}
+
+/// A decimal fixed-point number with `PLACES` digits after the decimal point.
+///
+/// Some statutes specify STV counts to a fixed number of decimal places (commonly five) with a specific rounding rule.
+/// Using `Fixed` as the count type reproduces those counts exactly. `R` selects the rounding rule, and defaults to [`Truncate`](struct.Truncate.html).
+///
+/// Values are stored as an `i64` scaled by `10^PLACES`, so `PLACES` must be no greater than 18.
+///
+/// # Example
+/// ```
+///    use tallystick::fixed::{Fixed, RoundHalfUp};
+///
+///    let third: Fixed<5> = Fixed::from_integer(1) / Fixed::from_integer(3);
+///    assert_eq!(third.to_string(), "0.33333");
+///
+///    let two_thirds: Fixed<5, RoundHalfUp> = Fixed::from_integer(2) / Fixed::from_integer(3);
+///    assert_eq!(two_thirds.to_string(), "0.66667");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed<const PLACES: u32, R = Truncate> {
+    raw: i64,
+    rounding: PhantomData<R>,
+}
+
+impl<const PLACES: u32, R: Rounding> Fixed<PLACES, R> {
+    /// The scale factor, `10^PLACES`.
+    pub const SCALE: i64 = 10i64.pow(PLACES);
+
+    /// Create a fixed-point number from its raw scaled representation.
+    /// For example `Fixed::<2>::from_raw(150)` is `1.50`.
+    pub fn from_raw(raw: i64) -> Self {
+        Fixed {
+            raw: raw,
+            rounding: PhantomData,
+        }
+    }
+
+    /// Get the raw scaled representation of this number.
+    pub fn raw(&self) -> i64 {
+        self.raw
+    }
+
+    /// Create a fixed-point number from an integer.
+    ///
+    /// # Panics
+    /// Panics if the integer can't be represented with `PLACES` decimal places.
+    pub fn from_integer(value: i64) -> Self {
+        Self::from_raw(value.checked_mul(Self::SCALE).expect(OVERFLOW_PANIC))
+    }
+
+    fn from_wide(value: i128) -> Option<Self> {
+        i64::try_from(value).ok().map(Self::from_raw)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> fmt::Display for Fixed<PLACES, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.raw < 0 { "-" } else { "" };
+        let whole = (self.raw / Self::SCALE).unsigned_abs();
+        if PLACES == 0 {
+            write!(f, "{}{}", sign, whole)
+        } else {
+            let fraction = (self.raw % Self::SCALE).unsigned_abs();
+            write!(f, "{}{}.{:0width$}", sign, whole, fraction, width = PLACES as usize)
+        }
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> fmt::Debug for Fixed<PLACES, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fixed({})", self)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Add for Fixed<PLACES, R> {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.checked_add(&other).expect(OVERFLOW_PANIC)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Sub for Fixed<PLACES, R> {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        Self::from_raw(self.raw.checked_sub(other.raw).expect(OVERFLOW_PANIC))
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Mul for Fixed<PLACES, R> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(&other).expect(OVERFLOW_PANIC)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Div for Fixed<PLACES, R> {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if other.raw == 0 {
+            panic!("tallystick::fixed::Fixed: attempt to divide by zero");
+        }
+        let numer = self.raw as i128 * Self::SCALE as i128;
+        Self::from_wide(R::div(numer, other.raw as i128)).expect(OVERFLOW_PANIC)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Rem for Fixed<PLACES, R> {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        Self::from_raw(self.raw % other.raw)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> AddAssign for Fixed<PLACES, R> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> SubAssign for Fixed<PLACES, R> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> CheckedAdd for Fixed<PLACES, R> {
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        self.raw.checked_add(other.raw).map(Self::from_raw)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> CheckedMul for Fixed<PLACES, R> {
+    fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let product = self.raw as i128 * other.raw as i128;
+        Self::from_wide(R::div(product, Self::SCALE as i128))
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Zero for Fixed<PLACES, R> {
+    fn zero() -> Self {
+        Self::from_raw(0)
+    }
+    fn is_zero(&self) -> bool {
+        self.raw == 0
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> One for Fixed<PLACES, R> {
+    fn one() -> Self {
+        Self::from_raw(Self::SCALE)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Num for Fixed<PLACES, R> {
+    type FromStrRadixErr = ParseFixedError;
+
+    /// Parse a decimal string such as `"12.345"`. Digits past `PLACES` are rounded using `R`.
+    /// Only a radix of 10 is supported.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseFixedError> {
+        let error = || ParseFixedError(s.to_string());
+        if radix != 10 {
+            return Err(error());
+        }
+
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (whole, fraction) = match digits.find('.') {
+            Some(i) => (&digits[..i], &digits[i + 1..]),
+            None => (digits, ""),
+        };
+        if (whole.is_empty() && fraction.is_empty()) || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let mut numer: i128 = 0;
+        for c in whole.chars().chain(fraction.chars()) {
+            numer = numer
+                .checked_mul(10)
+                .and_then(|n| n.checked_add(c.to_digit(10).unwrap() as i128))
+                .ok_or_else(error)?;
+        }
+        if negative {
+            numer = -numer;
+        }
+
+        let raw = match (fraction.len() as u32).cmp(&PLACES) {
+            Ordering::Equal => numer,
+            Ordering::Less => numer.checked_mul(10i128.pow(PLACES - fraction.len() as u32)).ok_or_else(error)?,
+            Ordering::Greater => {
+                let extra = 10i128.checked_pow(fraction.len() as u32 - PLACES).ok_or_else(error)?;
+                R::div(numer, extra)
+            }
+        };
+        Self::from_wide(raw).ok_or_else(error)
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> FromPrimitive for Fixed<PLACES, R> {
+    fn from_i64(n: i64) -> Option<Self> {
+        n.checked_mul(Self::SCALE).map(Self::from_raw)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        i64::try_from(n).ok().and_then(Self::from_i64)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        let scaled = R::round_f64(n * Self::SCALE as f64);
+        if scaled.is_finite() && scaled >= i64::MIN as f64 && scaled < i64::MAX as f64 {
+            Some(Self::from_raw(scaled as i64))
+        } else {
+            None
+        }
+    }
+}
+
+impl<const PLACES: u32, R: Rounding> Numeric for Fixed<PLACES, R> {
+    fn floor(self) -> Self {
+        Self::from_raw(self.raw - self.raw.rem_euclid(Self::SCALE))
+    }
+    fn fraction() -> bool {
+        true
+    }
+    fn max_value() -> Self {
+        Self::from_raw(i64::MAX)
+    }
+}
+
+/// Error returned when a string can't be parsed as a [`Fixed`](struct.Fixed.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixedError(String);
+
+impl fmt::Display for ParseFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tallystick: error parsing fixed-point value {}", self.0)
+    }
+}
+
+impl Error for ParseFixedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_arithmetic() {
+        type F = Fixed<5>;
+        type H = Fixed<5, RoundHalfUp>;
+
+        assert_eq!(F::from_integer(1) / F::from_integer(3), F::from_raw(33_333));
+        assert_eq!(F::from_integer(2) / F::from_integer(3), F::from_raw(66_666));
+        assert_eq!(H::from_integer(2) / H::from_integer(3), H::from_raw(66_667));
+        assert_eq!(F::from_raw(150_000) * F::from_raw(33_333), F::from_raw(49_999));
+        assert_eq!(H::from_raw(150_000) * H::from_raw(33_333), H::from_raw(50_000));
+        assert_eq!(F::from_raw(123_456) - F::one(), F::from_raw(23_456));
+        assert_eq!(F::from_raw(-150_000).floor(), F::from_integer(-2));
+        assert_eq!(F::from_raw(150_000).floor(), F::one());
+        assert_eq!(F::from_usize(7), Some(F::from_integer(7)));
+        assert_eq!(F::from_f64(0.123_459), Some(F::from_raw(12_345)));
+        assert_eq!(F::max_value().checked_add(&F::one()), None);
+
+        assert_eq!(F::from_raw(-1_234_567).to_string(), "-12.34567");
+        assert_eq!(Fixed::<0>::from_integer(3).to_string(), "3");
+    }
+
+    #[test]
+    fn fixed_parse() {
+        type F = Fixed<2>;
+        type H = Fixed<2, RoundHalfUp>;
+
+        assert_eq!(F::from_str_radix("12", 10), Ok(F::from_raw(1200)));
+        assert_eq!(F::from_str_radix("12.3", 10), Ok(F::from_raw(1230)));
+        assert_eq!(F::from_str_radix("-0.125", 10), Ok(F::from_raw(-12)));
+        assert_eq!(H::from_str_radix("-0.125", 10), Ok(H::from_raw(-13)));
+        assert_eq!(F::from_str_radix(".5", 10), Ok(F::from_raw(50)));
+        assert!(F::from_str_radix("1.2.3", 10).is_err());
+        assert!(F::from_str_radix("", 10).is_err());
+        assert!(F::from_str_radix("ff", 16).is_err());
+    }
+}
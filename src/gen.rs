@@ -0,0 +1,215 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// A model that generates random ranked ballots, listing candidates in order of preference.
+pub trait BallotGenerator<T> {
+    /// Generate a single ballot.
+    fn ballot<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T>;
+
+    /// Generate many ballots.
+    fn ballots<R: Rng + ?Sized>(&self, rng: &mut R, num_ballots: usize) -> Vec<Vec<T>> {
+        (0..num_ballots).map(|_| self.ballot(rng)).collect()
+    }
+}
+
+/// The impartial culture model, where every ranking of the candidates is equally likely.
+///
+/// # Example
+/// ```
+///    use rand::SeedableRng;
+///    use tallystick::gen::{BallotGenerator, ImpartialCulture};
+///
+///    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+///    let model = ImpartialCulture::new(vec!["Alice", "Bob", "Carlos"]);
+///    let ballots = model.ballots(&mut rng, 100);
+///    assert_eq!(ballots.len(), 100);
+///    assert!(ballots.iter().all(|ballot| ballot.len() == 3));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImpartialCulture<T: Clone> {
+    candidates: Vec<T>,
+    min_rankings: usize,
+}
+
+impl<T: Clone> ImpartialCulture<T> {
+    /// Create a model where every ballot ranks every candidate.
+    pub fn new(candidates: Vec<T>) -> Self {
+        let min_rankings = candidates.len();
+        ImpartialCulture {
+            candidates: candidates,
+            min_rankings: min_rankings,
+        }
+    }
+
+    /// Truncate ballots, so that each ballot ranks a random number of candidates, at least `min_rankings`.
+    pub fn truncated(mut self, min_rankings: usize) -> Self {
+        self.min_rankings = std::cmp::min(min_rankings, self.candidates.len());
+        self
+    }
+}
+
+impl<T: Clone> BallotGenerator<T> for ImpartialCulture<T> {
+    fn ballot<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        let mut ballot = self.candidates.clone();
+        ballot.shuffle(rng);
+        ballot.truncate(rng.gen_range(self.min_rankings, self.candidates.len() + 1));
+        ballot
+    }
+}
+
+/// The Mallows model, where ballots are scattered around a reference ranking.
+///
+/// The dispersion is between `0.0` and `1.0`. With a dispersion of `0.0` every ballot is the reference ranking,
+/// and with a dispersion of `1.0` every ranking is equally likely, as in the impartial culture model.
+///
+/// # Example
+/// ```
+///    use rand::SeedableRng;
+///    use tallystick::gen::{BallotGenerator, Mallows};
+///
+///    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+///    let model = Mallows::new(vec!["Alice", "Bob", "Carlos"], 0.0);
+///    assert_eq!(model.ballot(&mut rng), vec!["Alice", "Bob", "Carlos"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mallows<T: Clone> {
+    reference: Vec<T>,
+    dispersion: f64,
+}
+
+impl<T: Clone> Mallows<T> {
+    /// Create a model around a reference ranking, with the given dispersion.
+    ///
+    /// The dispersion is clamped between `0.0` and `1.0`.
+    pub fn new(reference: Vec<T>, dispersion: f64) -> Self {
+        Mallows {
+            reference: reference,
+            dispersion: dispersion.clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl<T: Clone> BallotGenerator<T> for Mallows<T> {
+    // Uses the repeated insertion model: the i'th reference candidate is inserted j places
+    // above the bottom of the ballot with probability proportional to dispersion^j.
+    fn ballot<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        let mut ballot: Vec<T> = Vec::with_capacity(self.reference.len());
+        for (i, candidate) in self.reference.iter().enumerate() {
+            let weights: Vec<f64> = (0..=i).map(|j| self.dispersion.powi(j as i32)).collect();
+            let total: f64 = weights.iter().sum();
+
+            let mut choice = rng.gen::<f64>() * total;
+            let mut shift = 0;
+            for (j, weight) in weights.iter().enumerate() {
+                shift = j;
+                if choice < *weight {
+                    break;
+                }
+                choice -= weight;
+            }
+            ballot.insert(i - shift, candidate.clone());
+        }
+        ballot
+    }
+}
+
+/// A spatial model, where candidates and voters have positions, and voters rank candidates from nearest to furthest.
+///
+/// Voters are placed uniformly at random in the unit square, cube, or hypercube.
+///
+/// # Example
+/// ```
+///    use rand::SeedableRng;
+///    use tallystick::gen::{BallotGenerator, Spatial};
+///
+///    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+///    let model = Spatial::new(vec![("Left", vec![0.0]), ("Centre", vec![0.5]), ("Right", vec![1.0])]);
+///
+///    // Every voter is nearer to the centre than to one of the wings.
+///    let ballots = model.ballots(&mut rng, 100);
+///    assert!(ballots.iter().all(|ballot| ballot[2] != "Centre"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spatial<T: Clone> {
+    candidates: Vec<(T, Vec<f64>)>,
+    dimensions: usize,
+}
+
+impl<T: Clone> Spatial<T> {
+    /// Create a model with candidates at the given positions.
+    ///
+    /// Every position should have the same number of dimensions.
+    pub fn new(candidates: Vec<(T, Vec<f64>)>) -> Self {
+        let dimensions = candidates.iter().map(|(_, position)| position.len()).max().unwrap_or(0);
+        Spatial {
+            candidates: candidates,
+            dimensions: dimensions,
+        }
+    }
+
+    /// Create a model with candidates placed uniformly at random.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R, candidates: Vec<T>, dimensions: usize) -> Self {
+        let candidates = candidates
+            .into_iter()
+            .map(|candidate| (candidate, random_position(rng, dimensions)))
+            .collect();
+        Spatial {
+            candidates: candidates,
+            dimensions: dimensions,
+        }
+    }
+
+    /// Get the candidates and their positions.
+    pub fn candidates(&self) -> &[(T, Vec<f64>)] {
+        &self.candidates
+    }
+}
+
+impl<T: Clone> BallotGenerator<T> for Spatial<T> {
+    fn ballot<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        let voter = random_position(rng, self.dimensions);
+        let mut distances: Vec<(f64, &T)> = self
+            .candidates
+            .iter()
+            .map(|(candidate, position)| {
+                let distance = position.iter().zip(voter.iter()).map(|(c, v)| (c - v) * (c - v)).sum();
+                (distance, candidate)
+            })
+            .collect();
+        distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        distances.into_iter().map(|(_, candidate)| candidate.clone()).collect()
+    }
+}
+
+fn random_position<R: Rng + ?Sized>(rng: &mut R, dimensions: usize) -> Vec<f64> {
+    (0..dimensions).map(|_| rng.gen::<f64>()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn gen_test() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let candidates = vec![1, 2, 3, 4];
+
+        let ballots = ImpartialCulture::new(candidates.clone()).truncated(2).ballots(&mut rng, 200);
+        assert!(ballots.iter().all(|ballot| ballot.len() >= 2 && ballot.len() <= 4));
+        assert!(ballots.iter().any(|ballot| ballot.len() == 2));
+
+        // With low dispersion, the reference ranking is the most common ballot.
+        let ballots = Mallows::new(candidates.clone(), 0.1).ballots(&mut rng, 200);
+        let reference = ballots.iter().filter(|ballot| **ballot == candidates).count();
+        assert!(reference > 100);
+        let mut sorted = ballots[0].clone();
+        sorted.sort();
+        assert_eq!(sorted, candidates);
+
+        let model = Spatial::random(&mut rng, candidates.clone(), 2);
+        assert_eq!(model.candidates().len(), 4);
+        assert!(model.ballots(&mut rng, 10).iter().all(|ballot| ballot.len() == 4));
+    }
+}
@@ -0,0 +1,227 @@
+use hashbrown::HashMap;
+use std::convert::TryInto;
+use std::fmt;
+use std::hash::Hash;
+
+use super::errors::TallyError;
+use super::result::RankedCandidate;
+use super::result::RankedWinners;
+
+/// A candidate interned by a [`CandidateSet`](struct.CandidateSet.html).
+///
+/// Ids are numbered from `0` in the order candidates were interned. They are cheap to copy, hash and compare,
+/// so any tally can count them in place of the candidates themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandidateId(pub u32);
+
+impl CandidateId {
+    /// Get the id as an index, for indexing dense per-candidate storage.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for CandidateId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An interner mapping candidates to [`CandidateId`](struct.CandidateId.html)s.
+///
+/// Each candidate is hashed and cloned once, when it is interned. Votes are then translated to ids and added to any tally
+/// over `CandidateId`, and the results translated back to candidates. This avoids repeatedly hashing and cloning
+/// large candidates, such as long strings, while counting.
+///
+/// Condorcet, Schulze and ranked pairs tallies can instead be built from the set with `from_candidate_set()`,
+/// such as [`CondorcetTally::from_candidate_set()`](../condorcet/struct.CondorcetTally.html#method.from_candidate_set).
+/// They take the set's ids directly with `add_ids()`, and report winners as candidates.
+///
+/// # Example
+/// ```
+///    use tallystick::intern::CandidateSet;
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///
+///    let candidates = CandidateSet::with_candidates(vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()]);
+///    let mut tally = DefaultCondorcetTally::with_candidates(1, candidates.ids());
+///
+///    let ballots = vec![
+///        vec!["Alice".to_string(), "Bob".to_string(), "Carol".to_string()],
+///        vec!["Bob".to_string(), "Alice".to_string(), "Carol".to_string()],
+///        vec!["Alice".to_string(), "Carol".to_string(), "Bob".to_string()],
+///    ];
+///    for ballot in ballots.iter() {
+///        tally.add(&candidates.lookup_vote(ballot)?)?;
+///    }
+///
+///    let winners = candidates.resolve_winners(tally.winners());
+///    assert_eq!(winners.into_unranked(), vec!["Alice".to_string()]);
+/// # Ok::<(), tallystick::TallyError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CandidateSet<T: Eq + Hash> {
+    candidates: Vec<T>,
+    ids: HashMap<T, CandidateId>,
+}
+
+impl<T: Eq + Hash + Clone> Default for CandidateSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone> CandidateSet<T> {
+    /// Create a new, empty candidate set.
+    pub fn new() -> Self {
+        CandidateSet {
+            candidates: Vec::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Create a candidate set with the provided candidates, interned in order.
+    pub fn with_candidates(candidates: Vec<T>) -> Self {
+        let mut set = CandidateSet {
+            candidates: Vec::with_capacity(candidates.len()),
+            ids: HashMap::with_capacity(candidates.len()),
+        };
+        for candidate in candidates {
+            set.intern(candidate);
+        }
+        set
+    }
+
+    /// Intern a candidate, returning its id. A candidate that is already interned keeps its id.
+    ///
+    /// # Panics
+    /// Panics if more than `u32::MAX` candidates are interned.
+    pub fn intern(&mut self, candidate: T) -> CandidateId {
+        if let Some(id) = self.ids.get(&candidate) {
+            return *id;
+        }
+        let id = CandidateId(self.candidates.len().try_into().expect("too many candidates to intern"));
+        self.candidates.push(candidate.clone());
+        self.ids.insert(candidate, id);
+        id
+    }
+
+    /// Intern every candidate in a vote, returning the vote as ids.
+    pub fn intern_vote(&mut self, vote: &[T]) -> Vec<CandidateId> {
+        vote.iter().map(|candidate| self.intern(candidate.clone())).collect()
+    }
+
+    /// Get the id of a candidate, if it has been interned.
+    pub fn id(&self, candidate: &T) -> Option<CandidateId> {
+        self.ids.get(candidate).cloned()
+    }
+
+    /// Translate a vote to ids, without interning new candidates.
+    ///
+    /// Returns `TallyError::UnknownCandidate` if the vote contains a candidate that has not been interned.
+    pub fn lookup_vote(&self, vote: &[T]) -> Result<Vec<CandidateId>, TallyError> {
+        vote.iter()
+            .enumerate()
+            .map(|(position, candidate)| self.id(candidate).ok_or(TallyError::UnknownCandidate { position }))
+            .collect()
+    }
+
+    /// Get the candidate with an id.
+    pub fn get(&self, id: CandidateId) -> Option<&T> {
+        self.candidates.get(id.index())
+    }
+
+    /// Get the ids of all candidates, in the order they were interned.
+    pub fn ids(&self) -> Vec<CandidateId> {
+        (0..self.candidates.len()).map(|i| CandidateId(i as u32)).collect()
+    }
+
+    /// Get all candidates, in the order they were interned.
+    pub fn candidates(&self) -> &[T] {
+        &self.candidates
+    }
+
+    /// Get the number of interned candidates.
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Check if no candidates have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    /// Translate a list of ids back to candidates.
+    ///
+    /// # Panics
+    /// Panics if an id was not interned by this set.
+    pub fn resolve(&self, ids: &[CandidateId]) -> Vec<T> {
+        ids.iter().map(|id| self.candidates[id.index()].clone()).collect()
+    }
+
+    /// Translate winners counted by id back to candidates.
+    ///
+    /// # Panics
+    /// Panics if an id was not interned by this set.
+    pub fn resolve_winners(&self, winners: RankedWinners<CandidateId>) -> RankedWinners<T> {
+        RankedWinners::with_winners(self.resolve_ranked(winners.winners), winners.num_winners)
+    }
+
+    /// Translate a ranking counted by id back to candidates.
+    ///
+    /// # Panics
+    /// Panics if an id was not interned by this set.
+    pub fn resolve_ranked(&self, ranked: Vec<RankedCandidate<CandidateId>>) -> Vec<RankedCandidate<T>> {
+        ranked
+            .into_iter()
+            .map(|ranked| RankedCandidate {
+                candidate: self.candidates[ranked.candidate.index()].clone(),
+                rank: ranked.rank,
+            })
+            .collect()
+    }
+
+    /// Translate totals counted by id back to candidates.
+    ///
+    /// # Panics
+    /// Panics if an id was not interned by this set.
+    pub fn resolve_totals<C>(&self, totals: Vec<(CandidateId, C)>) -> Vec<(T, C)> {
+        totals
+            .into_iter()
+            .map(|(id, total)| (self.candidates[id.index()].clone(), total))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plurality::DefaultPluralityTally;
+
+    #[test]
+    fn intern_plurality() {
+        let mut candidates = CandidateSet::new();
+        let mut tally = DefaultPluralityTally::new(1);
+        for candidate in ["Alice", "Bob", "Alice", "Carol", "Alice", "Bob"].iter() {
+            tally.add(candidates.intern(candidate.to_string()));
+        }
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates.id(&"Bob".to_string()), Some(CandidateId(1)));
+        assert_eq!(candidates.get(CandidateId(2)), Some(&"Carol".to_string()));
+        assert_eq!(candidates.id(&"Dave".to_string()), None);
+        assert_eq!(
+            candidates.lookup_vote(&["Carol".to_string(), "Dave".to_string()]),
+            Err(TallyError::UnknownCandidate { position: 1 })
+        );
+
+        assert_eq!(
+            candidates.resolve_winners(tally.winners()).into_unranked(),
+            vec!["Alice".to_string()]
+        );
+        assert_eq!(
+            candidates.resolve_totals(tally.totals()),
+            vec![("Alice".to_string(), 3), ("Bob".to_string(), 2), ("Carol".to_string(), 1)]
+        );
+    }
+}
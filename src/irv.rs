@@ -1,20 +1,30 @@
+use super::cache::Cache;
+use super::quorum::{turnout_methods, Turnout};
+use super::shard::Shard;
+use super::Exhausted;
+use super::Flow;
 use super::Numeric;
 use super::RankedCandidate;
 use super::RankedWinners;
+use super::TallyCheckpoint;
+use super::TallyError;
+use super::Truncation;
 use crate::Transfer;
 use crate::VoteTree;
+use hashbrown::HashMap;
 use hashbrown::HashSet;
-use num_traits::cast::NumCast;
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
 use num_traits::Num;
-use std::cmp::Ord;
+use std::cmp::Ordering;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
 #[derive(Debug)]
 struct WeightedVote<T, C>
 where
-    T: Eq + Clone + Hash,                                       // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast + Numeric, // vote count type
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // vote count type
 {
     weight: C,
     remaining: Vec<T>,
@@ -22,24 +32,44 @@ where
 
 pub type DefaultTally<T> = Tally<T, u64>;
 
+// The outcome of a count.
+#[derive(Clone)]
+struct Count<T: Clone + Eq, C> {
+    ranked: Vec<RankedCandidate<T>>,
+    exhausted: Vec<Exhausted<C>>,
+    flows: Vec<Flow<T, C>>,
+    batches: Vec<(usize, Vec<T>)>,
+}
+
+#[derive(Clone)]
 pub struct Tally<T, C>
 where
-    T: Eq + Clone + Hash,                                       // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast + Numeric, // vote count type
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // vote count type
 {
     running_total: VoteTree<T, C>,
     transfer: Transfer,
+    turnout: Turnout<C>,
+    truncation: Truncation,
+    batch_elimination: bool,
+    generation: u64, // Bumped whenever votes or candidates change, invalidating the cached count
+    counted: Cache<Count<T, C>>,
 }
 
 impl<T, C> Tally<T, C>
 where
-    T: Eq + Clone + Hash,                                             // Candidate
-    C: Copy + PartialOrd + Ord + AddAssign + Num + NumCast + Numeric, // vote count type
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // vote count type
 {
     pub fn new(transfer: Transfer) -> Self {
         Tally {
             running_total: VoteTree::new(),
             transfer: transfer,
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            batch_elimination: false,
+            generation: 0,
+            counted: Cache::new(),
         }
     }
 
@@ -48,30 +78,173 @@ where
         Tally {
             running_total: VoteTree::with_candidates(candidates),
             transfer: transfer,
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            batch_elimination: false,
+            generation: 0,
+            counted: Cache::new(),
+        }
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    /// A full ranking is checked against every candidate the tally knows so far, so use [`with_candidates()`](#method.with_candidates) to require a ranking of all candidates.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Eliminate every candidate who cannot win in a single round, as many statutes allow.
+    ///
+    /// Each round eliminates the longest run of candidates with the fewest votes whose votes together are fewer than
+    /// those of the next candidate up, since even with every one of their votes that candidate would stay ahead of them.
+    /// Batch-eliminated candidates are still ranked by their votes. See [`batch_eliminated()`](#method.batch_eliminated).
+    pub fn with_batch_elimination(mut self) -> Self {
+        self.batch_elimination = true;
+        self.generation += 1;
+        self
+    }
+
+    pub fn add_weighted(&mut self, selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.add_weighted_ref(&selection, weight)
+    }
+
+    pub fn add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted_ref(&selection, C::one())
+    }
+
+    pub fn add_ref(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        self.add_weighted_ref(selection, C::one())
+    }
+
+    pub fn add_weighted_ref(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.truncation
+            .check(selection.len(), self.running_total.num_candidates_with(selection))?;
+        self.turnout.add_valid(&weight);
+        self.running_total.add(selection, weight);
+        self.generation += 1;
+
+        Ok(())
+    }
+
+    /// Add a ranked vote.
+    ///
+    /// A ranked vote is a list of tuples of (candidate, rank), where rank is ascending.
+    /// Two candidates with the same rank are equal in preference, and the vote is split equally between them.
+    pub fn ranked_add(&mut self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.ranked_add_weighted(vote, C::one())
+    }
+
+    /// Add a ranked vote with a weight.
+    ///
+    /// Equally ranked candidates split the vote, so that when one of them is eliminated their share
+    /// transfers to the others before moving on to lower preferences.
+    /// Integer count types return `TallyError::IndivisibleWeight` if the weight cannot be split evenly,
+    /// so ballots with ties and a weight of one need a fractional count type such as `f64`.
+    /// A vote with so many ties that it would be split into more than 5040 orderings returns `TallyError::TooManyTies`.
+    pub fn ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        let split = crate::util::split_ranked_vote(vote, weight.clone())?;
+        if let Some((selection, _)) = split.first() {
+            self.truncation
+                .check(selection.len(), self.running_total.num_candidates_with(selection))?;
+        }
+        self.turnout.add_valid(&weight);
+        for (selection, part) in split {
+            self.running_total.add(&selection, part);
         }
+        self.generation += 1;
+
+        Ok(())
     }
 
-    pub fn add_weighted(&mut self, selection: Vec<T>, weight: C) {
-        self.running_total.add(&selection, weight);
+    turnout_methods!(tally_winners);
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
     }
 
-    pub fn add(&mut self, selection: Vec<T>) {
-        // TODO: split add and add_ref in VoteTree
-        self.running_total.add(&selection, C::one());
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
     }
 
-    pub fn add_ref(&mut self, selection: &[T]) {
-        // TODO: split add and add_ref in VoteTree
-        self.running_total.add(selection, C::one());
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// The other tally's transfer type and quorum are ignored.
+    pub fn merge(&mut self, other: Self) {
+        self.running_total.merge(&other.running_total);
+        self.turnout.merge(other.turnout);
+        self.generation += 1;
     }
 
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    /// Ballots that ranked the candidate count for their next preference instead.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.running_total.remove_candidate(candidate);
+        self.generation += 1;
+    }
+
+    /// Get every candidate in the order they finished. The winner has a rank of `0`.
+    ///
+    /// The count is cached, and only rerun after votes are added or candidates withdrawn,
+    /// so it is cheap to call `tally_ranked()` or [`tally_winners()`](#method.tally_winners) after every vote.
     pub fn tally_ranked(&self) -> Vec<RankedCandidate<T>> {
+        self.count().ranked
+    }
+
+    /// Get the votes transferred in each round of the count, from each eliminated candidate to each continuing candidate,
+    /// or to exhausted ballots.
+    ///
+    /// Flows are ordered by round, counting from `0`, then by the final ranking of the candidates, with exhausted ballots last.
+    /// They are the data behind a Sankey diagram of the count.
+    pub fn flows(&self) -> Vec<Flow<T, C>> {
+        self.count().flows
+    }
+
+    /// Get the ballots that exhausted in each round of the count.
+    ///
+    /// There is one entry for each round in which candidates were eliminated, in the order the rounds were counted.
+    pub fn exhausted(&self) -> Vec<Exhausted<C>> {
+        self.count().exhausted
+    }
+
+    /// Get the total ballots that exhausted over all rounds of the count.
+    pub fn total_exhausted(&self) -> Exhausted<C> {
+        Exhausted::total(&self.exhausted())
+    }
+
+    /// Get the candidates eliminated together by batch elimination, with the round they were eliminated in,
+    /// counting from `0` as in [`flows()`](#method.flows).
+    ///
+    /// Candidates in a batch are ordered from fewest votes to most. Only rounds that eliminated more than the candidates
+    /// with the fewest votes are listed, so this is always empty without [`with_batch_elimination()`](#method.with_batch_elimination).
+    pub fn batch_eliminated(&self) -> Vec<(usize, Vec<T>)> {
+        self.count().batches
+    }
+
+    // The count is cached, and only rerun after votes or candidates change.
+    fn count(&self) -> Count<T, C> {
+        self.counted.get_or_compute(self.generation, || self.compute_count())
+    }
+
+    // Run the count, returning the ranked candidates, the ballots exhausted in each round, and the votes transferred in each round.
+    fn compute_count(&self) -> Count<T, C> {
         let max = C::max_value();
+        let mut exhausted = Vec::new();
+        let mut flows = Vec::new();
+        let mut already_exhausted = Exhausted::new();
 
         let candidates = self.running_total.candidates();
         let mut inverse_ranked = Vec::<RankedCandidate<T>>::with_capacity(candidates.len());
         let mut inverse_rank = 0;
+        let mut round = 0;
         let mut eliminated = HashSet::new();
+        let mut batches = Vec::new();
 
         loop {
             // First Eagerly assign tally passing through eliminated candidates
@@ -88,7 +261,24 @@ where
 
             // If there are no more valid candidates, return early
             if score.is_empty() {
-                return inverse_ranked;
+                return Count {
+                    ranked: inverse_ranked,
+                    exhausted: exhausted,
+                    flows: flows,
+                    batches: batches,
+                };
+            }
+
+            // Stop as soon as a candidate holds a strict majority of the continuing ballots,
+            // since no further elimination can change the winner. The remaining candidates are ranked by their votes.
+            let continuing = score.values().fold(C::zero(), |total, count| total + count.clone());
+            let leading = score
+                .values()
+                .fold(C::zero(), |leading, count| if *count > leading { count.clone() } else { leading });
+            // Compared against the rest of the continuing votes rather than doubled, so that the check itself cannot overflow.
+            if leading.clone() > continuing - leading {
+                push_by_score(score.into_iter().collect(), &mut inverse_ranked, &mut inverse_rank);
+                break;
             }
 
             // Check for case where all remaining candidates are tied
@@ -114,28 +304,49 @@ where
             }
 
             // Calculate the worst performing candidates and add them to the reverse ranking
-            let min_score = score.values().min().unwrap_or(&max);
-            let mut loosers = Vec::<T>::new();
-            for (cand, count) in score.iter() {
-                if count == min_score {
-                    loosers.push(cand.clone());
-                }
-            }
+            let min_score = score
+                .values()
+                .fold(None, |min: Option<&C>, count| match min {
+                    Some(min) if min <= count => Some(min),
+                    _ => Some(count),
+                })
+                .unwrap_or(&max);
+            let mut loosers: Vec<(T, C)> = score
+                .iter()
+                .filter(|(_, count)| *count == min_score)
+                .map(|(cand, count)| (cand.clone(), count.clone()))
+                .collect();
             if loosers.is_empty() {
                 // No one left
                 break;
             }
+            if self.batch_elimination {
+                let batch = batch_eliminable(&score);
+                if batch.len() > loosers.len() {
+                    batches.push((round, batch.iter().map(|(cand, _)| cand.clone()).collect()));
+                    loosers = batch;
+                }
+            }
 
             // Remove all loosers
-            for looser in loosers.drain(..) {
-                inverse_ranked.push(RankedCandidate {
-                    candidate: looser.clone(),
-                    rank: inverse_rank,
-                });
-                eliminated.insert(looser);
+            let mut newly_eliminated = HashSet::new();
+            for (looser, _) in loosers.iter() {
+                eliminated.insert(looser.clone());
+                newly_eliminated.insert(looser.clone());
             }
+            push_by_score(loosers, &mut inverse_ranked, &mut inverse_rank);
+            self.running_total.transfers(&eliminated, &newly_eliminated, round, &mut flows);
+
+            // Record the ballots newly exhausted by this round's eliminations
+            let (ballots, weight) = self.running_total.exhausted(&eliminated);
+            exhausted.push(Exhausted {
+                ballots: ballots - already_exhausted.ballots,
+                weight: weight.clone() - already_exhausted.weight,
+            });
+            already_exhausted = Exhausted { ballots, weight };
 
             inverse_rank += 1;
+            round += 1;
         }
 
         let num_ranked = inverse_ranked.len();
@@ -146,28 +357,124 @@ where
                 rank: num_ranked - inversed.rank - 1,
             });
         }
-        ranked
+
+        // Order flows by round, then by the final ranking, with exhausted ballots last.
+        let position = |candidate: Option<&T>| match candidate {
+            Some(candidate) => ranked.iter().position(|r| r.candidate == *candidate).unwrap_or(0),
+            None => ranked.len(),
+        };
+        flows.sort_by_key(|flow: &Flow<T, C>| (flow.round, position(Some(&flow.from)), position(flow.to.as_ref())));
+
+        Count {
+            ranked: ranked,
+            exhausted: exhausted,
+            flows: flows,
+            batches: batches,
+        }
     }
 
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
     pub fn tally_winners(&self) -> RankedWinners<T> {
-        RankedWinners::from_ranked(self.tally_ranked(), 1)
+        self.turnout.decide(RankedWinners::from_ranked(self.tally_ranked(), 1))
+    }
+}
+
+impl<T, C> Tally<T, C>
+where
+    T: Eq + Clone + Hash,                                                           // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric + CheckedAdd, // vote count type
+{
+    /// Add a new vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the total weight of valid ballots would overflow the count type.
+    pub fn checked_add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.checked_add_weighted(selection, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the total weight of valid ballots would overflow the count type.
+    /// No candidate ever holds more than every valid ballot, so this also keeps each candidate's count in every round from overflowing.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.turnout.check_add_valid(&weight)?;
+        self.add_weighted(selection, weight)
+    }
+}
+
+impl<T, C> Shard for Tally<T, C>
+where
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // vote count type
+{
+    fn shard(&self) -> Self {
+        Tally {
+            running_total: VoteTree::with_candidates(self.running_total.candidates()),
+            transfer: self.transfer,
+            turnout: self.turnout.shard(),
+            truncation: self.truncation,
+            batch_elimination: self.batch_elimination,
+            generation: 0,
+            counted: Cache::new(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        Tally::merge(self, shard);
     }
 }
 
+// Add candidates to the reverse ranking from fewest votes to most, with tied candidates sharing a rank.
+// The inverse rank is left at the rank of the candidates with the most votes.
+fn push_by_score<T: Clone + Eq, C: PartialOrd>(
+    mut candidates: Vec<(T, C)>,
+    inverse_ranked: &mut Vec<RankedCandidate<T>>,
+    inverse_rank: &mut usize,
+) {
+    candidates.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    for i in 0..candidates.len() {
+        if i > 0 && candidates[i].1 != candidates[i - 1].1 {
+            *inverse_rank += 1;
+        }
+        inverse_ranked.push(RankedCandidate {
+            candidate: candidates[i].0.clone(),
+            rank: *inverse_rank,
+        });
+    }
+}
+
+// The candidates who cannot win: the longest run of candidates with the fewest votes
+// whose votes together are fewer than those of the next candidate up. Ordered from fewest votes to most.
+fn batch_eliminable<T: Clone + Eq + Hash, C: Clone + PartialOrd + AddAssign + Num>(score: &HashMap<T, C>) -> Vec<(T, C)> {
+    let mut sorted: Vec<(T, C)> = score.iter().map(|(cand, count)| (cand.clone(), count.clone())).collect();
+    sorted.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let mut total = C::zero();
+    let mut batch = 0;
+    for i in 0..sorted.len().saturating_sub(1) {
+        total += sorted[i].1.clone();
+        if total < sorted[i + 1].1 {
+            batch = i + 1;
+        }
+    }
+    sorted.truncate(batch);
+    sorted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::errors::TallyError;
+    use crate::Quorum;
 
     #[test]
     fn irv_wikipedia_1() -> Result<(), TallyError> {
         // See: https://en.wikipedia.org/wiki/Instant-runoff_voting
 
         let mut tally = DefaultTally::new(Transfer::Meek);
-        tally.add_weighted(vec!["Memphis", "Nashville", "Chattanooga", "Chattanooga"], 42);
-        tally.add_weighted(vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26);
-        tally.add_weighted(vec!["Chattanooga", "Knoxville", "Nashville", "Nashville"], 15);
-        tally.add_weighted(vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17);
+        tally.add_weighted(vec!["Memphis", "Nashville", "Chattanooga", "Chattanooga"], 42)?;
+        tally.add_weighted(vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26)?;
+        tally.add_weighted(vec!["Chattanooga", "Knoxville", "Nashville", "Nashville"], 15)?;
+        tally.add_weighted(vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17)?;
 
         // Verify winners
         let winners = tally.tally_winners();
@@ -184,6 +491,183 @@ mod tests {
         assert_eq!(ranked[3].candidate, "Chattanooga");
         assert_eq!(ranked[3].rank, 3);
 
+        // Verify transfers
+        let flow = |round, from, to, weight| Flow {
+            round: round,
+            from: from,
+            to: to,
+            weight: weight,
+        };
+        assert_eq!(
+            tally.flows(),
+            vec![
+                flow(0, "Chattanooga", Some("Knoxville"), 15),
+                flow(1, "Nashville", Some("Knoxville"), 26),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_exhausted() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek);
+        for _ in 0..5 {
+            tally.add(vec!["Alice"])?;
+        }
+        for _ in 0..4 {
+            tally.add(vec!["Bob", "Alice"])?;
+        }
+        tally.add(vec!["Cir"])?;
+        tally.add_weighted(vec!["Cir"], 2)?;
+        tally.add(vec![])?;
+
+        // Cir's ballots exhaust when Cir is eliminated, leaving Alice with a majority. The blank ballot is not exhausted.
+        assert_eq!(tally.exhausted(), vec![Exhausted { ballots: 2, weight: 3 }]);
+        assert_eq!(tally.total_exhausted(), Exhausted { ballots: 2, weight: 3 });
+        assert!(tally.tally_winners().contains(&"Alice"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_checked() -> Result<(), TallyError> {
+        let mut tally = Tally::<&str, u8>::new(Transfer::Meek);
+        tally.checked_add_weighted(vec!["Alice", "Bob"], 200)?;
+        tally.checked_add(vec!["Bob"])?;
+        assert_eq!(tally.checked_add_weighted(vec!["Bob"], 60), Err(TallyError::CountOverflow));
+        assert_eq!(tally.tally_winners().into_unranked(), vec!["Alice"]);
+        Ok(())
+    }
+
+    #[test]
+    fn irv_quorum() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek).with_quorum(Quorum::Turnout(10));
+        tally.add_weighted(vec!["Alice", "Bob"], 6)?;
+        tally.add(vec!["Bob"])?;
+        tally.add_ref(&["Bob", "Alice"])?;
+        assert!(tally.tally_winners().is_empty());
+
+        tally.add_blank_weighted(2);
+        assert!(tally.quorum_met());
+        assert!(tally.tally_winners().contains(&"Alice"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_withdraw() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek);
+        tally.add_weighted(vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"], 42)?;
+        tally.add_weighted(vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26)?;
+        tally.add_weighted(vec!["Chattanooga", "Knoxville", "Nashville", "Memphis"], 15)?;
+        tally.add_weighted(vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17)?;
+        tally.withdraw_candidate(&"Nashville");
+
+        let mut expected = DefaultTally::new(Transfer::Meek);
+        expected.add_weighted(vec!["Memphis", "Chattanooga", "Knoxville"], 42)?;
+        expected.add_weighted(vec!["Chattanooga", "Knoxville", "Memphis"], 26 + 15)?;
+        expected.add_weighted(vec!["Knoxville", "Chattanooga", "Memphis"], 17)?;
+
+        assert_eq!(tally.tally_ranked(), expected.tally_ranked());
+        assert!(tally.tally_winners().contains(&"Chattanooga"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_equal_ranks() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek);
+        tally.add_weighted(vec!["Alice"], 4)?;
+        tally.add_weighted(vec!["Bob"], 3)?;
+        tally.add_weighted(vec!["Cir", "Bob"], 2)?;
+        tally.ranked_add_weighted(&[("Cir", 0), ("Bob", 0)], 2)?;
+
+        // Cir has 3, Bob has 4. Cir is eliminated and all of their votes go to Bob.
+        assert_eq!(tally.tally_ranked()[0], RankedCandidate { candidate: "Bob", rank: 0 });
+
+        // An integer count cannot split a single vote, so unit weight ties need a fractional count type.
+        assert_eq!(tally.ranked_add(&[("Alice", 0), ("Bob", 0)]), Err(TallyError::IndivisibleWeight));
+        assert_eq!(
+            tally.ranked_add(&[("Alice", 0), ("Alice", 1)]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+
+        let mut tally = Tally::<&str, f64>::new(Transfer::Meek);
+        tally.add_weighted(vec!["Alice"], 4.0)?;
+        tally.add_weighted(vec!["Bob"], 3.0)?;
+        tally.add_weighted(vec!["Cir", "Bob"], 2.0)?;
+        tally.ranked_add(&[("Cir", 0), ("Bob", 0)])?;
+        tally.ranked_add(&[("Cir", 0), ("Bob", 0)])?;
+        assert_eq!(tally.tally_ranked()[0], RankedCandidate { candidate: "Bob", rank: 0 });
+
+        // A large tie is rejected rather than split into every ordering.
+        let tied: Vec<(&str, u32)> = vec!["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L"]
+            .into_iter()
+            .map(|candidate| (candidate, 0))
+            .collect();
+        assert_eq!(tally.ranked_add(&tied), Err(TallyError::TooManyTies { max: 5040 }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_truncation() -> Result<(), TallyError> {
+        let candidates = vec!["Alice", "Bob", "Cir"];
+        let mut tally = DefaultTally::with_candidates(Transfer::Meek, candidates).with_truncation(Truncation::RequireFull);
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.ranked_add_weighted(&[("Bob", 0), ("Cir", 1), ("Alice", 1)], 2)?;
+        assert_eq!(
+            tally.add(vec!["Alice", "Bob"]),
+            Err(TallyError::TruncatedBallot { rankings: 2, required: 3 })
+        );
+        assert_eq!(
+            tally.add_ref(&["Alice", "Bob", "Dave"]),
+            Err(TallyError::TruncatedBallot { rankings: 3, required: 4 })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_cached() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek);
+        tally.add(vec!["Alice", "Bob"])?;
+        assert_eq!(tally.tally_winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.flows(), tally.flows());
+
+        // Each change to the tally invalidates the cached count.
+        tally.add_weighted(vec!["Bob", "Alice"], 2)?;
+        assert_eq!(tally.tally_winners().into_unranked(), vec!["Bob"]);
+        tally.withdraw_candidate(&"Bob");
+        assert_eq!(tally.tally_winners().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn irv_batch_elimination() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(Transfer::Meek);
+        tally.add_weighted(vec!["Alice"], 40)?;
+        tally.add_weighted(vec!["Bob"], 35)?;
+        tally.add_weighted(vec!["Carol", "Bob"], 10)?;
+        tally.add_weighted(vec!["Dave", "Bob"], 8)?;
+        tally.add_weighted(vec!["Erin", "Alice"], 7)?;
+
+        // Without batch elimination, one candidate is eliminated each round until Bob has a majority.
+        assert_eq!(tally.flows().len(), 3);
+        assert!(tally.batch_eliminated().is_empty());
+
+        // Carol, Dave and Erin together have fewer votes than Bob, so none of them can win.
+        let tally = tally.with_batch_elimination();
+        assert_eq!(tally.batch_eliminated(), vec![(0, vec!["Erin", "Dave", "Carol"])]);
+        assert_eq!(tally.flows().len(), 3);
+        assert!(tally.flows().iter().all(|flow| flow.round == 0));
+        assert_eq!(tally.exhausted().len(), 1);
+
+        let ranked: Vec<(&str, usize)> = tally.tally_ranked().into_iter().map(|r| (r.candidate, r.rank)).collect();
+        assert_eq!(ranked, vec![("Bob", 0), ("Alice", 1), ("Carol", 2), ("Dave", 3), ("Erin", 4)]);
+
         Ok(())
     }
 }
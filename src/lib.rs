@@ -6,6 +6,12 @@
 //!
 //! To enable nightly support, add `features=["nightly"]` to your `Cargo.toml` entry for `tallystick`.
 //!
+//! ## Count types
+//!
+//! Tallies are generic over the count type `C`, which needs to be [`Clone`] and [`FromPrimitive`](num_traits::FromPrimitive) rather than
+//! `Copy` and `NumCast`, so arbitrary-precision types such as `num_bigint::BigUint` and `num_rational::BigRational` can be used.
+//! Neither implements `NumCast`. Since 0.5, code generic over a tally's count type needs the new bounds.
+//!
 
 #![warn(missing_docs)]
 #![allow(clippy::redundant_field_names, clippy::cognitive_complexity)]
@@ -15,6 +21,24 @@
 /// Utilities for parsing common vote formats
 pub mod util;
 
+/// Validation of ballots before they are counted, reporting every invalid ballot and why it is invalid.
+pub mod validate;
+
+/// Voter tracking, so that each voter's ballot is counted only once.
+pub mod voters;
+
+/// Ballots stored once and added to tallies of any method, for comparing methods on the same votes.
+pub mod ballotbox;
+
+/// Candidate metadata such as party and group, for aggregating results by party.
+pub mod metadata;
+
+/// Candidate interning, mapping candidates to small integer ids once so that tallies count ids instead of hashing and cloning candidates.
+pub mod intern;
+
+/// Sharded ballot ingestion, counting ballots on several threads in separate shards of a tally and merging them for the result.
+pub mod shard;
+
 /// Plurality voting is an electoral system in which each voter is allowed to vote for only one candidate
 /// and the candidate who polls the most among their counterparts (a plurality) is elected. It may be called
 /// first-past-the-post (FPTP), single-choice voting, simple plurality, or relative/simple majority.
@@ -41,6 +65,13 @@ pub mod plurality;
 /// candidates. The winner is the most-approved candidate.
 pub mod approval;
 
+/// Approximate plurality and approval tallies for streams of votes with unbounded numbers of candidates,
+/// counted in fixed memory with a count-min sketch. `sketch`
+///
+/// Requires the `sketch` feature to be enabled
+#[cfg(feature = "sketch")]
+pub mod sketch;
+
 /// Score voting or "range voting" is an electoral system in which voters give each candidate a score,
 /// the scores are summed, and the candidate with the highest total is elected. It has been described
 /// by various other names including "evaluative voting", "utilitarian voting", and "the point system".
@@ -64,6 +95,12 @@ pub mod stv;
 /// The Condorcet method isn't guarunteed to produce a single-winner due to the non-transitive nature of group choice.
 pub mod condorcet;
 
+/// Ranked pairs (RP), or the Tideman method, is a Condorcet method that ranks candidates by locking in head-to-head majorities,
+/// from the strongest to the weakest, skipping any majority that would create a cycle.
+///
+/// The majorities considered and the resulting lock graph are exposed, so a result can be independently re-derived and visualized.
+pub mod rankedpairs;
+
 /// The Schulze method is an voting system that selects a single winner using votes that express preferences. `nightly`
 ///
 /// Requires the `nightly` feature to be enabled
@@ -107,11 +144,85 @@ pub mod borda;
 #[allow(missing_docs)]
 pub mod irv;
 
+/// Elections made up of many districts or races, each tallied with its own method. `nightly`
+///
+/// Requires the `nightly` feature to be enabled
+#[cfg(feature = "nightly")]
+pub mod election;
+
+/// Decimal fixed-point count type, for reproducing statutory counts that specify a number of decimal places. `fixed_point`
+///
+/// Requires the `fixed_point` feature to be enabled
+#[cfg(feature = "fixed_point")]
+pub mod fixed;
+
+/// Random ballot generators, for benchmarking and testing code that uses tallystick. `test-util`
+///
+/// Requires the `test-util` feature to be enabled
+#[cfg(feature = "test-util")]
+pub mod gen;
+
+/// Monte Carlo simulation of elections, sampling ballots from a model and tallying them. `test-util`
+///
+/// Requires the `test-util` feature to be enabled
+#[cfg(feature = "test-util")]
+pub mod simulate;
+
+/// Reports of election results, for exporting and publishing them.
+pub mod report;
+
+/// Bindings exposing the plurality, approval, score, condorcet and instant-runoff tallies to JavaScript. `wasm`
+///
+/// Requires the `wasm` feature to be enabled. The instant-runoff tally also requires the `nightly` feature.
+///
+/// Candidates are strings and counts are numbers. Build the library as a `cdylib`, then generate the JavaScript package with `wasm-bindgen`:
+///
+/// ```sh
+/// cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+/// wasm-bindgen --out-dir pkg target/wasm32-unknown-unknown/release/tallystick.wasm
+/// ```
+///
+/// ```js
+/// import { CondorcetTally } from "tallystick";
+///
+/// const tally = new CondorcetTally(1, ["Alice", "Bob", "Carol"]);
+/// tally.add(["Alice", "Bob", "Carol"]);
+/// tally.addWeighted(["Bob", "Carol", "Alice"], 2);
+/// console.log(tally.winners()); // [{ candidate: "Bob", rank: 0 }]
+/// ```
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Bindings exposing the tallies to Python as the `tallystick` module. `python`
+///
+/// Requires the `python` feature to be enabled. The schulze, STV and instant-runoff tallies also require the `nightly` feature.
+///
+/// Candidates are strings and counts are floats. Build the module with `maturin build --features python`,
+/// which builds the library as a `cdylib`:
+///
+/// ```python
+/// import tallystick
+///
+/// tally = tallystick.PluralityTally()
+/// tally.add_many(df["first_choice"].tolist())
+/// print(tally.winners(), tally.totals())
+/// ```
+#[cfg(feature = "python")]
+pub mod python;
+
 // Common Data Structures
 // ----------------------
 mod result;
+pub use crate::result::BallotSummary;
+pub use crate::result::CountedCandidates;
+pub use crate::result::Exhausted;
+pub use crate::result::Flow;
 pub use crate::result::RankedCandidate;
 pub use crate::result::RankedWinners;
+pub use crate::result::Round;
+pub use crate::result::Standings;
+pub use crate::result::TalliedCandidate;
+pub use crate::result::TalliedWinners;
 
 // TODO: Remove dead code
 #[cfg(feature = "nightly")]
@@ -132,11 +243,32 @@ mod quota;
 #[cfg(feature = "nightly")]
 pub use crate::quota::Quota;
 
+mod quorum;
+pub use crate::quorum::MinimumSupport;
+pub use crate::quorum::Quorum;
+
+mod truncation;
+pub use crate::truncation::Truncation;
+
+mod checkpoint;
+pub use crate::checkpoint::TallyCheckpoint;
+
+mod margin;
+pub use crate::margin::MarginOfVictory;
+
 mod traits;
 pub use crate::traits::Numeric;
 
+mod cache;
+
 mod errors;
 pub use crate::errors::TallyError;
+pub use crate::errors::TieError;
+
+// A ranked selection stored by a tally.
+// Most ballots rank only a few candidates, so selections are stored inline rather than allocated individually.
+#[cfg(feature = "nightly")]
+pub(crate) type Selection<T> = smallvec::SmallVec<[T; 8]>;
 
 // Common Utility Functions
 // ------------------------
@@ -146,8 +278,8 @@ pub use crate::errors::TallyError;
 #[cfg(feature = "nightly")]
 pub(crate) fn check_duplicate<T: PartialEq>(slice: &[T]) -> Result<(), TallyError> {
     for i in 1..slice.len() {
-        if slice[i..].contains(&slice[i - 1]) {
-            return Err(TallyError::VoteHasDuplicateCandidates);
+        if let Some(offset) = slice[i..].iter().position(|c| c == &slice[i - 1]) {
+            return Err(TallyError::VoteHasDuplicateCandidates { position: i + offset });
         }
     }
     Ok(())
@@ -0,0 +1,134 @@
+use num_traits::Num;
+
+/// The margin of victory of a finished tally: how close the last winner came to losing their seat.
+///
+/// The margin is measured between the last winner and the runner-up, the strongest candidate that did not win.
+/// From it, [`ballots_to_add()`](#method.ballots_to_add) and [`ballots_to_change()`](#method.ballots_to_change)
+/// give the minimum number of ballots that would have to be added, or changed, to close the margin, so that the runner-up at least ties with the winner.
+/// If that closes the margin exactly, one more ballot is needed to alter the winners.
+/// These numbers drive the sample sizes of risk-limiting audits: the smaller the margin, the more ballots an audit must check.
+///
+/// A margin of zero means the last seat is already tied.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    for _ in 0..50 { tally.add("Alice") }
+///    for _ in 0..45 { tally.add("Bob") }
+///    for _ in 0..5 { tally.add("Carol") }
+///
+///    let margin = tally.margin_of_victory().unwrap();
+///    assert_eq!(margin.winner, "Alice");
+///    assert_eq!(margin.runner_up, "Bob");
+///    assert_eq!(margin.margin, 5);
+///
+///    // Five more ballots for Bob would tie the election, as would changing three of Alice's ballots to Bob.
+///    assert_eq!(margin.ballots_to_add(), 5);
+///    assert_eq!(margin.ballots_to_change(), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarginOfVictory<T, C> {
+    /// The last winner, whose seat is the easiest to take.
+    pub winner: T,
+
+    /// The strongest candidate that did not win.
+    pub runner_up: T,
+
+    /// The lead of the winner over the runner-up.
+    pub margin: C,
+
+    /// The most a single ballot can add to the runner-up's lead or take away from the winner's lead.
+    /// This is `1` for tallies that count a vote per ballot, the maximum score for score tallies,
+    /// and the widest gap in points a ballot can put between two candidates for borda tallies.
+    pub per_ballot: C,
+}
+
+impl<T, C: Clone + Num + PartialOrd> MarginOfVictory<T, C> {
+    pub(crate) fn new(winner: T, runner_up: T, margin: C) -> Self {
+        MarginOfVictory {
+            winner: winner,
+            runner_up: runner_up,
+            margin: margin,
+            per_ballot: C::one(),
+        }
+    }
+
+    pub(crate) fn with_per_ballot(mut self, per_ballot: C) -> Self {
+        self.per_ballot = per_ballot;
+        self
+    }
+
+    /// Get the minimum number of unit-weight ballots that would need to be added to the tally to close the margin.
+    ///
+    /// Adding this many ballots for the runner-up ties them with the winner, or puts them ahead if the margin is not a multiple of `per_ballot`.
+    pub fn ballots_to_add(&self) -> C {
+        ceil_div(self.margin.clone(), self.per_ballot.clone())
+    }
+
+    /// Get the minimum number of unit-weight ballots that would need to change to close the margin.
+    ///
+    /// Each changed ballot takes a vote from the winner and gives it to the runner-up, closing the margin twice as fast as adding ballots.
+    pub fn ballots_to_change(&self) -> C {
+        let two = C::one() + C::one();
+        ceil_div(self.margin.clone(), self.per_ballot.clone() * two)
+    }
+}
+
+// Build a margin from totals sorted from highest to lowest.
+pub(crate) fn from_totals<T, C>(mut totals: Vec<(T, C)>, num_winners: usize) -> Option<MarginOfVictory<T, C>>
+where
+    C: Clone + Num + PartialOrd,
+{
+    if num_winners == 0 || totals.len() <= num_winners {
+        return None;
+    }
+    totals.truncate(num_winners + 1);
+    let (runner_up, runner_up_count) = totals.pop()?;
+    let (winner, winner_count) = totals.pop()?;
+    Some(MarginOfVictory::new(winner, runner_up, winner_count - runner_up_count))
+}
+
+// Divide, rounding up. Works for both integer and fractional count types.
+fn ceil_div<C: Clone + Num + PartialOrd>(numerator: C, denominator: C) -> C {
+    if denominator <= C::zero() {
+        return C::zero();
+    }
+    let remainder = numerator.clone() % denominator.clone();
+    let quotient = (numerator - remainder.clone()) / denominator;
+    if remainder.is_zero() {
+        quotient
+    } else {
+        quotient + C::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn margin_rounding() {
+        let margin = MarginOfVictory::new("Alice", "Bob", 7u64);
+        assert_eq!(margin.ballots_to_add(), 7);
+        assert_eq!(margin.ballots_to_change(), 4);
+
+        let margin = MarginOfVictory::new("Alice", "Bob", 7u64).with_per_ballot(5);
+        assert_eq!(margin.ballots_to_add(), 2);
+        assert_eq!(margin.ballots_to_change(), 1);
+
+        let margin = MarginOfVictory::new("Alice", "Bob", 2.5f64);
+        assert_eq!(margin.ballots_to_add(), 3.0);
+        assert_eq!(margin.ballots_to_change(), 2.0);
+
+        let margin = MarginOfVictory::new("Alice", "Bob", 0u64);
+        assert_eq!(margin.ballots_to_change(), 0);
+
+        assert_eq!(
+            from_totals(vec![("Alice", 3u64), ("Bob", 2), ("Carol", 1)], 2),
+            Some(MarginOfVictory::new("Bob", "Carol", 1))
+        );
+        assert_eq!(from_totals(vec![("Alice", 3u64), ("Bob", 2)], 2), None);
+    }
+}
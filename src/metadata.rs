@@ -0,0 +1,196 @@
+use hashbrown::HashMap;
+use num_traits::Num;
+use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+use super::RankedWinners;
+
+/// Information about a candidate: their party, group, and any other tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    /// The party the candidate stands for.
+    pub party: Option<String>,
+
+    /// A group the candidate belongs to, such as an electoral alliance or a list.
+    pub group: Option<String>,
+
+    /// Arbitrary tags, as name-value pairs.
+    pub tags: BTreeMap<String, String>,
+}
+
+impl Metadata {
+    /// Create new empty metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the candidate's party.
+    pub fn party(mut self, party: &str) -> Self {
+        self.party = Some(party.to_string());
+        self
+    }
+
+    /// Set the candidate's group.
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    /// Add a tag to the candidate's metadata.
+    pub fn tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+/// Metadata for the candidates in an election, used to aggregate results by party, group, or tag.
+///
+/// # Example
+/// ```
+///    use tallystick::metadata::{CandidateMetadata, Metadata};
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut metadata = CandidateMetadata::new();
+///    metadata.insert("Alice", Metadata::new().party("Greens"));
+///    metadata.insert("Bob", Metadata::new().party("Reds"));
+///    metadata.insert("Carlos", Metadata::new().party("Greens"));
+///
+///    let mut tally = DefaultPluralityTally::new(2);
+///    for _ in 0..5 { tally.add("Alice") }
+///    for _ in 0..6 { tally.add("Bob") }
+///    for _ in 0..3 { tally.add("Carlos") }
+///
+///    assert_eq!(metadata.party_totals(&tally.totals()), vec![("Greens".to_string(), 8), ("Reds".to_string(), 6)]);
+///    assert_eq!(metadata.party_seats(&tally.winners()), vec![("Greens".to_string(), 1), ("Reds".to_string(), 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CandidateMetadata<T: Eq + Hash> {
+    candidates: HashMap<T, Metadata>,
+}
+
+impl<T: Eq + Hash + Clone> Default for CandidateMetadata<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone> CandidateMetadata<T> {
+    /// Create a new, empty set of candidate metadata.
+    pub fn new() -> Self {
+        CandidateMetadata {
+            candidates: HashMap::new(),
+        }
+    }
+
+    /// Set the metadata for a candidate, returning any metadata it replaces.
+    pub fn insert(&mut self, candidate: T, metadata: Metadata) -> Option<Metadata> {
+        self.candidates.insert(candidate, metadata)
+    }
+
+    /// Get the metadata for a candidate.
+    pub fn get(&self, candidate: &T) -> Option<&Metadata> {
+        self.candidates.get(candidate)
+    }
+
+    /// Get the party of a candidate.
+    pub fn party(&self, candidate: &T) -> Option<&str> {
+        self.get(candidate).and_then(|metadata| metadata.party.as_deref())
+    }
+
+    /// Get all candidates standing for a party, in no particular order.
+    pub fn party_candidates(&self, party: &str) -> Vec<T> {
+        self.candidates
+            .iter()
+            .filter(|(_, metadata)| metadata.party.as_deref() == Some(party))
+            .map(|(candidate, _)| candidate.clone())
+            .collect()
+    }
+
+    /// Sum candidate totals by party. Candidates without a party are left out.
+    ///
+    /// Totals are returned from highest to lowest, with equal totals ordered by party name.
+    pub fn party_totals<C>(&self, totals: &[(T, C)]) -> Vec<(String, C)>
+    where
+        C: Clone + Num + PartialOrd + AddAssign,
+    {
+        self.totals_by(totals, |metadata| metadata.party.as_deref())
+    }
+
+    /// Sum candidate totals by group. Candidates without a group are left out.
+    ///
+    /// Totals are returned from highest to lowest, with equal totals ordered by group name.
+    pub fn group_totals<C>(&self, totals: &[(T, C)]) -> Vec<(String, C)>
+    where
+        C: Clone + Num + PartialOrd + AddAssign,
+    {
+        self.totals_by(totals, |metadata| metadata.group.as_deref())
+    }
+
+    /// Sum candidate totals by the value of a tag. Candidates without the tag are left out.
+    ///
+    /// Totals are returned from highest to lowest, with equal totals ordered by tag value.
+    pub fn tag_totals<C>(&self, tag: &str, totals: &[(T, C)]) -> Vec<(String, C)>
+    where
+        C: Clone + Num + PartialOrd + AddAssign,
+    {
+        self.totals_by(totals, |metadata| metadata.tags.get(tag).map(|value| value.as_str()))
+    }
+
+    /// Count the seats won by each party. Winners without a party are left out.
+    ///
+    /// Seats are returned from most to fewest, with equal seats ordered by party name.
+    /// If winners are tied for the last seat, each tied winner is counted.
+    pub fn party_seats(&self, winners: &RankedWinners<T>) -> Vec<(String, usize)> {
+        let totals: Vec<(T, usize)> = winners.iter().map(|ranked| (ranked.candidate.clone(), 1)).collect();
+        self.party_totals(&totals)
+    }
+
+    fn totals_by<C, F>(&self, totals: &[(T, C)], key: F) -> Vec<(String, C)>
+    where
+        C: Clone + Num + PartialOrd + AddAssign,
+        F: Fn(&Metadata) -> Option<&str>,
+    {
+        let mut aggregate: BTreeMap<String, C> = BTreeMap::new();
+        for (candidate, count) in totals.iter() {
+            if let Some(name) = self.get(candidate).and_then(&key) {
+                *aggregate.entry(name.to_string()).or_insert_with(C::zero) += count.clone();
+            }
+        }
+
+        // BTreeMap is sorted by name, and a stable sort keeps equal totals in that order.
+        let mut aggregate: Vec<(String, C)> = aggregate.into_iter().collect();
+        aggregate.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        aggregate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_totals() {
+        let mut metadata = CandidateMetadata::new();
+        metadata.insert("Alice", Metadata::new().party("Greens").group("Left").tag("region", "North"));
+        metadata.insert("Bob", Metadata::new().party("Reds").group("Left").tag("region", "South"));
+        metadata.insert("Carol", Metadata::new().party("Blues").tag("region", "North"));
+        metadata.insert("Dave", Metadata::new());
+
+        assert_eq!(metadata.party(&"Alice"), Some("Greens"));
+        assert_eq!(metadata.party(&"Dave"), None);
+        assert_eq!(metadata.party_candidates("Reds"), vec!["Bob"]);
+
+        let totals = vec![("Alice", 2.5), ("Bob", 1.0), ("Carol", 2.5), ("Dave", 10.0), ("Eve", 4.0)];
+        assert_eq!(
+            metadata.party_totals(&totals),
+            vec![("Blues".to_string(), 2.5), ("Greens".to_string(), 2.5), ("Reds".to_string(), 1.0)]
+        );
+        assert_eq!(metadata.group_totals(&totals), vec![("Left".to_string(), 3.5)]);
+        assert_eq!(
+            metadata.tag_totals("region", &totals),
+            vec![("North".to_string(), 5.0), ("South".to_string(), 1.0)]
+        );
+    }
+}
@@ -1,12 +1,22 @@
 use hashbrown::HashMap;
-use num_traits::cast::NumCast;
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
 use num_traits::Num;
+use num_traits::ToPrimitive;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
+use super::errors::TallyError;
+use super::margin::{self, MarginOfVictory};
+use super::quorum::{turnout_methods, MinimumSupport, Turnout};
+use super::result::BallotSummary;
 use super::result::CountedCandidates;
 use super::result::RankedCandidate;
 use super::result::RankedWinners;
+use super::result::Standings;
+use super::result::TalliedWinners;
+use super::shard::Shard;
+use super::TallyCheckpoint;
 
 /// A plurality tally using `u64` integers to count votes.
 /// `DefaultPluralityTally` is generally preferred over `PluralityTally`.
@@ -49,19 +59,21 @@ pub type DefaultPluralityTally<T> = PluralityTally<T, u64>;
 ///    tally.add("Carol");                // A vote for Carol with an implicit weight of `1.0`
 ///    let winners = tally.winners();
 /// ```
+#[derive(Clone)]
 pub struct PluralityTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Count type
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
 {
-    running_total: HashMap<T, C>,
-    num_winners: usize,
+    pub(crate) running_total: HashMap<T, C>,
+    pub(crate) num_winners: usize,
+    turnout: Turnout<C>,
 }
 
 impl<T, C> PluralityTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Count type
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
 {
     /// Create a new `PluralityTally` with the given number of winners.
     ///
@@ -71,6 +83,7 @@ where
         PluralityTally {
             running_total: HashMap::new(),
             num_winners: num_winners,
+            turnout: Turnout::new(),
         }
     }
 
@@ -79,9 +92,18 @@ where
         PluralityTally {
             running_total: HashMap::with_capacity(expected_candidates),
             num_winners: num_winners,
+            turnout: Turnout::new(),
         }
     }
 
+    /// Require winners to have a minimum level of support.
+    ///
+    /// Winners without the minimum support are left out of [`winners()`](#method.winners), leaving their seats unfilled.
+    pub fn with_minimum_support(mut self, support: MinimumSupport<C>) -> Self {
+        self.turnout.support = Some(support);
+        self
+    }
+
     /// Add a new vote
     pub fn add(&mut self, vote: T) {
         self.add_weighted(vote, C::one());
@@ -95,13 +117,26 @@ where
     /// Add a weighted vote.
     /// By default takes a weight as a `usize` integer, but can be customized by using `PluralityTally` with a custom vote type.
     pub fn add_weighted(&mut self, vote: T, weight: C) {
-        *self.running_total.entry(vote).or_insert(C::zero()) += weight;
+        self.turnout.add_valid(&weight);
+        self.add_to_total(vote, weight);
     }
 
     /// Add a weighted vote by reference.
     pub fn add_weighted_ref(&mut self, vote: &T, weight: C) {
-        if self.running_total.contains_key(&vote) {
-            if let Some(x) = self.running_total.get_mut(&vote) {
+        self.turnout.add_valid(&weight);
+        self.add_to_total_ref(vote, weight);
+    }
+
+    // Add to a candidate's total without counting a ballot.
+    // Tallies that total points or approvals in a plurality tally count their own ballots.
+    pub(crate) fn add_to_total(&mut self, vote: T, weight: C) {
+        *self.running_total.entry(vote).or_insert(C::zero()) += weight;
+    }
+
+    // Add to a candidate's total by reference, without counting a ballot.
+    pub(crate) fn add_to_total_ref(&mut self, vote: &T, weight: C) {
+        if self.running_total.contains_key(vote) {
+            if let Some(x) = self.running_total.get_mut(vote) {
                 *x += weight;
             }
         } else {
@@ -109,6 +144,73 @@ where
         }
     }
 
+    turnout_methods!(winners);
+
+    /// Add a spoiled ballot, which was cast but rejected for the given reason, such as `"overvote"`.
+    ///
+    /// Spoiled ballots count towards turnout, but not towards any candidate. Ballots with the same reason are totalled together.
+    pub fn add_spoiled(&mut self, reason: &str) {
+        self.add_spoiled_weighted(reason, C::one());
+    }
+
+    /// Add a weighted spoiled ballot.
+    pub fn add_spoiled_weighted(&mut self, reason: &str, weight: C) {
+        self.turnout.add_spoiled(reason, weight);
+    }
+
+    /// Get the total weight of spoiled ballots for each reason, in the order each reason was first recorded.
+    pub fn spoiled(&self) -> Vec<(String, C)> {
+        self.turnout.spoiled.clone()
+    }
+
+    /// Get a summary of the valid, blank and spoiled ballots cast.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    tally.add("Alice");
+    ///    tally.add("Bob");
+    ///    tally.add_blank();
+    ///    tally.add_spoiled("overvote");
+    ///
+    ///    let summary = tally.ballot_summary();
+    ///    assert_eq!((summary.valid, summary.blank, summary.spoiled), (2, 1, 1));
+    ///    assert_eq!(summary.turnout(), 4);
+    /// ```
+    pub fn ballot_summary(&self) -> BallotSummary<C> {
+        self.turnout.summary()
+    }
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// The other tally's number of winners, quorum and minimum support are ignored.
+    pub fn merge(&mut self, other: Self) {
+        self.turnout.merge(other.turnout);
+        for (candidate, total) in other.running_total {
+            *self.running_total.entry(candidate).or_insert(C::zero()) += total;
+        }
+    }
+
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot. Votes for the candidate are discarded.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.running_total.remove(candidate);
+    }
+
     /// Get a list of all candidates seen by this tally.
     /// Candidates are returned in no particular order.
     pub fn candidates(&self) -> Vec<T> {
@@ -117,6 +219,8 @@ where
 
     /// Get a ranked list of winners. Winners with the same rank are tied.
     /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    /// If a minimum support is set, winners without it are left out and their seats are unfilled.
     ///
     /// # Example
     /// ```
@@ -142,7 +246,43 @@ where
     ///    //   Cir has a rank of 1
     /// ```
     pub fn winners(&self) -> RankedWinners<T> {
-        self.get_counted().into_ranked(self.num_winners)
+        self.winners_n(self.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add_weighted("Bob", 2);
+    ///    tally.add("Carol");
+    ///
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    ///    assert_eq!(tally.winners_n(2).into_unranked(), vec!["Alice", "Bob"]);
+    ///    assert_eq!(tally.ranked_top(2), vec![("Alice", 0), ("Bob", 1)]);
+    /// ```
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        let winners = self.turnout.decide(self.get_counted().into_ranked(num_winners));
+        self.turnout
+            .supported(winners, |candidate| self.running_total.get(candidate).cloned())
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
     }
 
     /// Get vote totals for this tally.
@@ -166,6 +306,55 @@ where
         self.get_counted().into_vec()
     }
 
+    /// Get a ranked list of winners with their vote totals. Winners with the same rank are tied.
+    ///
+    /// This is [`winners()`](#method.winners) joined with [`totals()`](#method.totals).
+    pub fn winners_with_totals(&self) -> TalliedWinners<T, C> {
+        self.winners().with_totals(&self.totals())
+    }
+
+    /// Get the margin of victory: the lead of the last winner over the runner-up.
+    ///
+    /// The margin is exact: adding [`ballots_to_add()`](../struct.MarginOfVictory.html#method.ballots_to_add) votes
+    /// for the runner-up, or changing [`ballots_to_change()`](../struct.MarginOfVictory.html#method.ballots_to_change)
+    /// votes from the winner to the runner-up, ties the runner-up with the winner or puts them ahead.
+    /// Quorum and minimum support are not taken into account.
+    ///
+    /// Returns `None` if there are no more candidates than seats, so every candidate wins.
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        margin::from_totals(self.totals(), self.num_winners)
+    }
+
+    /// Get the total weight of votes for candidates. Blank ballots and votes for withdrawn candidates are not included.
+    pub fn total_votes(&self) -> C {
+        let mut total = C::zero();
+        for count in self.running_total.values() {
+            total += count.clone();
+        }
+        total
+    }
+
+    /// Check if the leading candidate has a majority: more than half of all votes for candidates.
+    ///
+    /// The check is exact for fractional counts, and false if there are no votes or the lead is tied.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::PluralityTally;
+    ///
+    ///    let mut tally = PluralityTally::<&str, f64>::new(1);
+    ///    tally.add_weighted("Alice", 2.5);
+    ///    tally.add_weighted("Bob", 2.5);
+    ///    assert!(!tally.has_majority());
+    ///
+    ///    tally.add_weighted("Alice", 0.1);
+    ///    assert!(tally.has_majority());
+    /// ```
+    pub fn has_majority(&self) -> bool {
+        let total = self.total_votes();
+        self.running_total.values().any(|count| count.clone() + count.clone() > total)
+    }
+
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
     ///
@@ -190,17 +379,135 @@ where
         self.get_counted().into_ranked(0).into_vec()
     }
 
+    /// Get each candidate with their vote count, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, C> {
+        self.get_counted()
+    }
+
     // Get the running total as CountedCandidates.
     pub(crate) fn get_counted(&self) -> CountedCandidates<T, C> {
         let mut counted = CountedCandidates::new();
         for (candidate, votecount) in self.running_total.iter() {
-            counted.push(candidate.clone(), *votecount);
+            counted.push(candidate.clone(), votecount.clone());
         }
 
         counted
     }
 }
 
+impl<T, C> PluralityTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                 // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd, // Count type
+{
+    /// Add a new vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the candidate's total, or the total weight of valid ballots, would overflow the count type.
+    pub fn checked_add(&mut self, vote: T) -> Result<(), TallyError> {
+        self.checked_add_weighted(vote, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the candidate's total, or the total weight of valid ballots, would overflow the count type.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, vote: T, weight: C) -> Result<(), TallyError> {
+        self.check_add_weighted_ref(&vote, &weight)?;
+        self.turnout.check_add_valid(&weight)?;
+        self.add_weighted(vote, weight);
+        Ok(())
+    }
+
+    /// Add a weighted vote by reference, checking for overflow.
+    pub fn checked_add_weighted_ref(&mut self, vote: &T, weight: C) -> Result<(), TallyError> {
+        self.check_add_weighted_ref(vote, &weight)?;
+        self.turnout.check_add_valid(&weight)?;
+        self.add_weighted_ref(vote, weight);
+        Ok(())
+    }
+
+    // Check that adding a weighted vote would not overflow, without adding it.
+    pub(crate) fn check_add_weighted_ref(&self, vote: &T, weight: &C) -> Result<(), TallyError> {
+        match self.running_total.get(vote) {
+            Some(total) => total.checked_add(weight).map(|_| ()).ok_or(TallyError::CountOverflow),
+            None => Ok(()),
+        }
+    }
+
+    // Check that adding several weighted votes from one ballot would not overflow, without adding them.
+    // Weights for a candidate that appears more than once are summed before they are checked against its total.
+    pub(crate) fn check_add_ballot<'a, I>(&self, votes: I) -> Result<(), TallyError>
+    where
+        I: IntoIterator<Item = (&'a T, C)>,
+        T: 'a,
+    {
+        let mut summed: HashMap<&T, C> = HashMap::new();
+        for (vote, weight) in votes {
+            match summed.get_mut(vote) {
+                Some(sum) => *sum = sum.checked_add(&weight).ok_or(TallyError::CountOverflow)?,
+                None => {
+                    summed.insert(vote, weight);
+                }
+            }
+        }
+        for (vote, weight) in summed.iter() {
+            self.check_add_weighted_ref(vote, weight)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, C> PluralityTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                  // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + ToPrimitive, // Count type
+{
+    /// Get each candidate's share of all votes for candidates, as a percentage, from highest to lowest.
+    ///
+    /// Shares are of [`total_votes()`](#method.total_votes), so blank ballots are not included. Shares are all zero if there are no votes.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add("Bob");
+    ///    assert_eq!(tally.shares(), vec![("Alice", 75.0), ("Bob", 25.0)]);
+    /// ```
+    pub fn shares(&self) -> Vec<(T, f64)> {
+        let total = self.total_votes().to_f64().unwrap_or(0.0);
+        self.totals()
+            .into_iter()
+            .map(|(candidate, count)| {
+                let share = match count.to_f64() {
+                    Some(count) if total > 0.0 => count / total * 100.0,
+                    _ => 0.0,
+                };
+                (candidate, share)
+            })
+            .collect()
+    }
+}
+
+impl<T, C> Shard for PluralityTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
+{
+    fn shard(&self) -> Self {
+        PluralityTally {
+            running_total: HashMap::new(),
+            num_winners: self.num_winners,
+            turnout: self.turnout.shard(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        PluralityTally::merge(self, shard);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,10 +528,26 @@ mod tests {
         assert_eq!(tally.ranked(), vec![("Alice", 0), ("Bob", 1), ("Cir", 2)]);
 
         let winners = tally.winners();
-        assert_eq!(winners.contains(&"Alice"), true);
-        assert_eq!(winners.contains(&"Bob"), true);
-        assert_eq!(winners.contains(&"Cir"), false);
-        assert_eq!(winners.contains(&"Rando"), false);
+        assert!(winners.contains(&"Alice"));
+        assert!(winners.contains(&"Bob"));
+        assert!(!winners.contains(&"Cir"));
+        assert!(!winners.contains(&"Rando"));
+
+        let tallied = tally.winners_with_totals();
+        assert_eq!(tallied.len(), 2);
+        assert_eq!(tallied[0].candidate, "Alice");
+        assert_eq!(tallied[0].total, 3);
+        assert_eq!(tallied.total(&"Bob"), Some(&2));
+        assert_eq!(tallied.total(&"Cir"), None);
+        assert_eq!(tallied.into_ranked(), winners);
+
+        assert_eq!(tally.total_votes(), 6);
+        assert!(!tally.has_majority());
+        let shares = tally.shares();
+        assert_eq!(shares[0], ("Alice", 50.0));
+        assert!((shares.iter().map(|(_, share)| share).sum::<f64>() - 100.0).abs() < 1e-9);
+        tally.add("Alice");
+        assert!(tally.has_majority());
 
         // Election for the most popular integer
         let mut tally = DefaultPluralityTally::new(1);
@@ -239,11 +562,11 @@ mod tests {
 
         let winners = tally.winners();
 
-        assert_eq!(winners.contains(&99), true);
-        assert_eq!(winners.contains(&100), false);
-        assert_eq!(winners.contains(&1), false);
-        assert_eq!(winners.contains(&2), false);
-        assert_eq!(winners.contains(&1000), false);
+        assert!(winners.contains(&99));
+        assert!(!winners.contains(&100));
+        assert!(!winners.contains(&1));
+        assert!(!winners.contains(&2));
+        assert!(!winners.contains(&1000));
 
         // Create an election with capacity
         let mut tally = DefaultPluralityTally::with_capacity(1, 2);
@@ -253,7 +576,45 @@ mod tests {
         tally.add_ref(&candidate_id_2);
 
         let winners = tally.winners();
-        assert_eq!(winners.contains(&candidate_id_1), true);
-        assert_eq!(winners.contains(&candidate_id_2), true);
+        assert!(winners.contains(&candidate_id_1));
+        assert!(winners.contains(&candidate_id_2));
+    }
+
+    #[test]
+    fn plurality_checked() {
+        let mut tally = PluralityTally::<&str, u8>::new(1);
+        tally.checked_add_weighted("Alice", 250).unwrap();
+        tally.checked_add("Bob").unwrap();
+        assert_eq!(tally.checked_add_weighted("Alice", 10), Err(TallyError::CountOverflow));
+        assert_eq!(tally.checked_add_weighted_ref(&"Alice", 6), Err(TallyError::CountOverflow));
+        tally.checked_add_weighted_ref(&"Alice", 4).unwrap();
+        assert_eq!(tally.totals(), vec![("Alice", 254), ("Bob", 1)]);
+
+        // Bob's total fits, but the total weight of valid ballots would not.
+        assert_eq!(tally.checked_add("Bob"), Err(TallyError::CountOverflow));
+        assert_eq!(tally.totals(), vec![("Alice", 254), ("Bob", 1)]);
+    }
+
+    #[test]
+    fn plurality_bigint_test() {
+        use num_bigint::BigUint;
+
+        let mut tally = PluralityTally::<&str, BigUint>::new(1);
+        tally.add_weighted("Alice", BigUint::from(u64::MAX) * 2u32);
+        tally.add_weighted("Bob", BigUint::from(u64::MAX));
+        tally.add("Bob");
+
+        assert_eq!(tally.totals()[0], ("Alice", BigUint::from(u64::MAX) * 2u32));
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    }
+
+    #[test]
+    fn plurality_withdraw() {
+        let mut tally = DefaultPluralityTally::new(1);
+        tally.add_weighted("Alice", 3);
+        tally.add_weighted("Bob", 2);
+        tally.withdraw_candidate(&"Alice");
+        tally.withdraw_candidate(&"Rando");
+        assert_eq!(tally.totals(), vec![("Bob", 2)]);
     }
 }
@@ -0,0 +1,306 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+use crate::approval::ApprovalTally;
+use crate::condorcet::CondorcetTally;
+use crate::errors::TallyError;
+use crate::plurality::PluralityTally;
+use crate::score::ScoreTally;
+use crate::{RankedCandidate, RankedWinners};
+
+#[cfg(feature = "nightly")]
+use crate::schulze::{self, SchulzeTally};
+#[cfg(feature = "nightly")]
+use crate::{irv, stv, Quota, Transfer};
+
+fn tally_error(error: TallyError) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+// Ranked candidates as a list of `(candidate, rank)` tuples.
+fn ranked_to_py(ranked: Vec<RankedCandidate<String>>) -> Vec<(String, usize)> {
+    ranked.into_iter().map(|r| (r.candidate, r.rank)).collect()
+}
+
+fn winners_to_py(winners: RankedWinners<String>) -> Vec<(String, usize)> {
+    ranked_to_py(winners.into_vec())
+}
+
+// Totals as a `{candidate: total}` dict.
+fn totals_to_py(totals: Vec<(String, f64)>) -> BTreeMap<String, f64> {
+    totals.into_iter().collect()
+}
+
+/// A plurality tally for Python, with string candidates and float weights. `python`
+#[pyclass(name = "PluralityTally")]
+pub struct PyPluralityTally {
+    tally: PluralityTally<String, f64>,
+}
+
+#[pymethods]
+impl PyPluralityTally {
+    /// Create a new plurality tally with the given number of winners.
+    #[new]
+    #[pyo3(signature = (num_winners = 1))]
+    pub fn new(num_winners: usize) -> Self {
+        PyPluralityTally {
+            tally: PluralityTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote for a candidate, with an optional weight.
+    #[pyo3(signature = (candidate, weight = 1.0))]
+    pub fn add(&mut self, candidate: String, weight: f64) {
+        self.tally.add_weighted(candidate, weight);
+    }
+
+    /// Add many votes at once, such as a column of a data frame.
+    pub fn add_many(&mut self, candidates: Vec<String>) {
+        for candidate in candidates {
+            self.tally.add(candidate);
+        }
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+
+    /// Get the vote totals, as a `{candidate: total}` dict.
+    pub fn totals(&self) -> BTreeMap<String, f64> {
+        totals_to_py(self.tally.totals())
+    }
+}
+
+/// An approval tally for Python, with string candidates and float weights. `python`
+#[pyclass(name = "ApprovalTally")]
+pub struct PyApprovalTally {
+    tally: ApprovalTally<String, f64>,
+}
+
+#[pymethods]
+impl PyApprovalTally {
+    /// Create a new approval tally with the given number of winners.
+    #[new]
+    #[pyo3(signature = (num_winners = 1))]
+    pub fn new(num_winners: usize) -> Self {
+        PyApprovalTally {
+            tally: ApprovalTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote approving a list of candidates, with an optional weight.
+    #[pyo3(signature = (selection, weight = 1.0))]
+    pub fn add(&mut self, selection: Vec<String>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(selection, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+
+    /// Get the approval totals, as a `{candidate: total}` dict.
+    pub fn totals(&self) -> BTreeMap<String, f64> {
+        totals_to_py(self.tally.totals())
+    }
+}
+
+/// A score tally for Python, with string candidates and float scores. `python`
+#[pyclass(name = "ScoreTally")]
+pub struct PyScoreTally {
+    tally: ScoreTally<String, f64>,
+}
+
+#[pymethods]
+impl PyScoreTally {
+    /// Create a new score tally with the given number of winners.
+    #[new]
+    #[pyo3(signature = (num_winners = 1))]
+    pub fn new(num_winners: usize) -> Self {
+        PyScoreTally {
+            tally: ScoreTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote, given as a `{candidate: score}` dict, with an optional weight.
+    #[pyo3(signature = (scores, weight = 1.0))]
+    pub fn add(&mut self, scores: BTreeMap<String, f64>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(scores.into_iter().collect(), weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+
+    /// Get the score totals, as a `{candidate: total}` dict.
+    pub fn totals(&self) -> BTreeMap<String, f64> {
+        totals_to_py(self.tally.totals())
+    }
+}
+
+/// A condorcet tally for Python, with string candidates and float weights. `python`
+#[pyclass(name = "CondorcetTally")]
+pub struct PyCondorcetTally {
+    tally: CondorcetTally<String, f64>,
+}
+
+#[pymethods]
+impl PyCondorcetTally {
+    /// Create a new condorcet tally with the given candidates and number of winners.
+    #[new]
+    #[pyo3(signature = (candidates, num_winners = 1))]
+    pub fn new(candidates: Vec<String>, num_winners: usize) -> Self {
+        PyCondorcetTally {
+            tally: CondorcetTally::with_candidates(num_winners, candidates),
+        }
+    }
+
+    /// Add a vote, given as a list of candidates in order of preference, with an optional weight.
+    #[pyo3(signature = (vote, weight = 1.0))]
+    pub fn add(&mut self, vote: Vec<String>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(&vote, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+
+    /// Get the pairwise totals, as a `{(candidate1, candidate2): votes}` dict of votes preferring candidate1 over candidate2.
+    pub fn totals(&self) -> BTreeMap<(String, String), f64> {
+        self.tally.totals().into_iter().collect()
+    }
+}
+
+/// A schulze tally for Python, with string candidates and float weights. `python` `nightly`
+#[cfg(feature = "nightly")]
+#[pyclass(name = "SchulzeTally")]
+pub struct PySchulzeTally {
+    tally: SchulzeTally<String, f64>,
+}
+
+#[cfg(feature = "nightly")]
+#[pymethods]
+impl PySchulzeTally {
+    /// Create a new schulze tally with the given candidates and number of winners.
+    ///
+    /// The variant is one of `"winning"`, `"margin"` or `"ratio"`.
+    #[new]
+    #[pyo3(signature = (candidates, num_winners = 1, variant = "winning"))]
+    pub fn new(candidates: Vec<String>, num_winners: usize, variant: &str) -> PyResult<Self> {
+        let variant = match variant {
+            "winning" => schulze::Variant::Winning,
+            "margin" => schulze::Variant::Margin,
+            "ratio" => schulze::Variant::Ratio,
+            _ => return Err(PyValueError::new_err(format!("unknown schulze variant {}", variant))),
+        };
+        Ok(PySchulzeTally {
+            tally: SchulzeTally::with_candidates(num_winners, variant, candidates),
+        })
+    }
+
+    /// Add a vote, given as a list of candidates in order of preference, with an optional weight.
+    #[pyo3(signature = (vote, weight = 1.0))]
+    pub fn add(&mut self, vote: Vec<String>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(&vote, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+}
+
+/// A single transferable vote tally for Python, with string candidates and float weights. `python` `nightly`
+#[cfg(feature = "nightly")]
+#[pyclass(name = "StvTally")]
+pub struct PyStvTally {
+    tally: stv::Tally<String, f64>,
+}
+
+#[cfg(feature = "nightly")]
+#[pymethods]
+impl PyStvTally {
+    /// Create a new STV tally with the given number of winners.
+    ///
+    /// The quota is one of `"droop"`, `"hagenbach"`, `"hare"` or `"imperiali"`.
+    #[new]
+    #[pyo3(signature = (num_winners, quota = "droop"))]
+    pub fn new(num_winners: usize, quota: &str) -> PyResult<Self> {
+        let quota = match quota {
+            "droop" => Quota::Droop,
+            "hagenbach" => Quota::Hagenbach,
+            "hare" => Quota::Hare,
+            "imperiali" => Quota::Imperiali,
+            _ => return Err(PyValueError::new_err(format!("unknown quota {}", quota))),
+        };
+        Ok(PyStvTally {
+            tally: stv::Tally::new(num_winners, quota),
+        })
+    }
+
+    /// Add a vote, given as a list of candidates in order of preference, with an optional weight.
+    #[pyo3(signature = (vote, weight = 1.0))]
+    pub fn add(&mut self, vote: Vec<String>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(vote, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.winners())
+    }
+}
+
+/// An instant-runoff tally for Python, with string candidates and float weights. `python` `nightly`
+#[cfg(feature = "nightly")]
+#[pyclass(name = "IrvTally")]
+pub struct PyIrvTally {
+    tally: irv::Tally<String, f64>,
+}
+
+#[cfg(feature = "nightly")]
+#[pymethods]
+impl PyIrvTally {
+    /// Create a new instant-runoff tally with the given candidates.
+    #[new]
+    pub fn new(candidates: Vec<String>) -> Self {
+        PyIrvTally {
+            tally: irv::Tally::with_candidates(Transfer::Meek, candidates),
+        }
+    }
+
+    /// Add a vote, given as a list of candidates in order of preference, with an optional weight.
+    #[pyo3(signature = (vote, weight = 1.0))]
+    pub fn add(&mut self, vote: Vec<String>, weight: f64) -> PyResult<()> {
+        self.tally.add_weighted(vote, weight).map_err(tally_error)
+    }
+
+    /// Get the winner, as a list of `(candidate, rank)` tuples.
+    pub fn winners(&self) -> Vec<(String, usize)> {
+        winners_to_py(self.tally.tally_winners())
+    }
+
+    /// Get every candidate in the order they finished, as a list of `(candidate, rank)` tuples.
+    pub fn ranked(&self) -> Vec<(String, usize)> {
+        ranked_to_py(self.tally.tally_ranked())
+    }
+}
+
+/// The `tallystick` Python module. `python`
+#[pymodule]
+pub fn tallystick(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyPluralityTally>()?;
+    module.add_class::<PyApprovalTally>()?;
+    module.add_class::<PyScoreTally>()?;
+    module.add_class::<PyCondorcetTally>()?;
+    #[cfg(feature = "nightly")]
+    {
+        module.add_class::<PySchulzeTally>()?;
+        module.add_class::<PyStvTally>()?;
+        module.add_class::<PyIrvTally>()?;
+    }
+    Ok(())
+}
@@ -0,0 +1,315 @@
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
+use num_traits::Num;
+
+use super::result::BallotSummary;
+use super::result::RankedWinners;
+use super::TallyError;
+
+/// A quorum is the minimum participation required for a tally to reach a decision.
+///
+/// When a tally's quorum is not met, `winners()` reports no decision by returning an empty set of winners.
+/// A quorum is set with `with_quorum()`, and can be set before or after votes are added.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::Quorum;
+///
+///    // At least 5 ballots must be cast, blank ballots included.
+///    let mut tally = DefaultPluralityTally::new(1).with_quorum(Quorum::Turnout(5));
+///    tally.add("Alice");
+///    tally.add("Bob");
+///    tally.add("Alice");
+///    tally.add_blank();
+///    assert!(!tally.quorum_met());
+///    assert!(tally.winners().is_empty());
+///
+///    tally.add_blank();
+///    assert!(tally.quorum_met());
+///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Quorum<C> {
+    /// Minimum turnout. The total weight of all ballots cast, including blank and spoiled ballots, must be at least this value.
+    Turnout(C),
+
+    /// Minimum valid votes. The total weight of non-blank ballots must be at least this value.
+    ValidVotes(C),
+}
+
+impl<C: Clone + Num + PartialOrd> Quorum<C> {
+    /// Check if this quorum is met given the total weight of valid and blank ballots.
+    pub fn is_met(&self, valid: &C, blank: &C) -> bool {
+        match self {
+            Quorum::Turnout(min) => valid.clone() + blank.clone() >= *min,
+            Quorum::ValidVotes(min) => valid >= min,
+        }
+    }
+}
+
+/// The minimum support a candidate must receive to be declared a winner.
+///
+/// When a winning candidate does not have the minimum support, their seat is left unfilled:
+/// `winners()` does not include them, and [`RankedWinners::unfilled()`](struct.RankedWinners.html#method.unfilled) reports the empty seat.
+/// It applies to tallies where a candidate's support is a count of votes for them, so only plurality and approval tallies
+/// have `with_minimum_support()`. Score and Borda tallies total points rather than votes, and ranked tallies transfer votes between rounds,
+/// so they do not support a minimum.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::MinimumSupport;
+///
+///    // Winners must have at least 40% of the vote.
+///    let mut tally = DefaultPluralityTally::new(2).with_minimum_support(MinimumSupport::Percent(40));
+///    for _ in 0..6 { tally.add("Alice") }
+///    for _ in 0..3 { tally.add("Bob") }
+///    tally.add("Carlos");
+///
+///    let winners = tally.winners();
+///    assert_eq!(winners.unfilled(), 1);
+///    assert_eq!(winners.into_unranked(), vec!["Alice"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinimumSupport<C> {
+    /// Minimum number of votes. The total weight of votes for the candidate must be at least this value.
+    Votes(C),
+
+    /// Minimum percentage of valid votes. The total weight of votes for the candidate must be at least this percentage of the total weight of non-blank ballots.
+    Percent(C),
+}
+
+impl<C: Clone + Num + PartialOrd + FromPrimitive> MinimumSupport<C> {
+    /// Check if a candidate with the given support meets this minimum, given the total weight of valid ballots.
+    pub fn is_met(&self, support: &C, valid: &C) -> bool {
+        match self {
+            MinimumSupport::Votes(min) => support >= min,
+            MinimumSupport::Percent(percent) => {
+                let hundred = C::from_u8(100).unwrap();
+                if valid.is_zero() {
+                    return true;
+                }
+                // Fractional count types compare the share of the vote directly.
+                let truncates = (C::one() / (C::one() + C::one())).is_zero();
+                if !truncates {
+                    return support.clone() / valid.clone() * hundred >= *percent;
+                }
+
+                // Integer count types divide before multiplying, so large totals do not overflow.
+                // The support needed is percent * valid / 100, split into whole hundreds of valid votes and the rest.
+                let whole = percent.clone() * (valid.clone() / hundred.clone());
+                let rest = percent.clone() * (valid.clone() % hundred.clone());
+                if *support < whole {
+                    return false;
+                }
+                let needed = (rest + hundred.clone() - C::one()) / hundred;
+                support.clone() - whole >= needed
+            }
+        }
+    }
+}
+
+// The quorum and blank ballot methods of a tally with a `turnout` field, so every tally counts turnout the same way.
+// `$winners` names the tally's method for getting its winners, which returns none when the quorum is not met.
+macro_rules! turnout_methods {
+    ($winners:ident) => {
+        /// Require a quorum for this tally to reach a decision.
+        ///
+        #[doc = concat!("If the quorum is not met, [`", stringify!($winners), "()`](#method.", stringify!($winners), ") returns no winners.")]
+        pub fn with_quorum(mut self, quorum: $crate::Quorum<C>) -> Self {
+            self.turnout.quorum = Some(quorum);
+            self
+        }
+
+        /// Add a blank ballot, for a voter who abstains.
+        ///
+        /// Blank ballots count towards turnout, but not towards any candidate.
+        pub fn add_blank(&mut self) {
+            self.add_blank_weighted(num_traits::One::one());
+        }
+
+        /// Add a weighted blank ballot.
+        pub fn add_blank_weighted(&mut self, weight: C) {
+            self.turnout.add_blank(weight);
+        }
+
+        /// Get the total weight of blank ballots.
+        pub fn blank(&self) -> C {
+            self.turnout.blank.clone()
+        }
+
+        /// Check if the quorum for this tally has been met. Always true if no quorum is set.
+        pub fn quorum_met(&self) -> bool {
+            self.turnout.is_met()
+        }
+    };
+}
+pub(crate) use turnout_methods;
+
+// Running turnout for a tally, used to record blank and spoiled ballots and check the quorum.
+#[derive(Debug, Clone)]
+pub(crate) struct Turnout<C> {
+    pub(crate) valid: C,
+    pub(crate) blank: C,
+    pub(crate) spoiled: Vec<(String, C)>, // Spoiled ballots by reason, in the order each reason was first seen
+    pub(crate) quorum: Option<Quorum<C>>,
+    pub(crate) support: Option<MinimumSupport<C>>,
+}
+
+impl<C: Clone + Num + PartialOrd + FromPrimitive> Turnout<C> {
+    pub(crate) fn new() -> Self {
+        Turnout {
+            valid: C::zero(),
+            blank: C::zero(),
+            spoiled: Vec::new(),
+            quorum: None,
+            support: None,
+        }
+    }
+
+    // Valid votes are always counted, so a quorum or minimum support can be set after votes are added.
+    pub(crate) fn add_valid(&mut self, weight: &C) {
+        self.valid = self.valid.clone() + weight.clone();
+    }
+
+    pub(crate) fn add_blank(&mut self, weight: C) {
+        self.blank = self.blank.clone() + weight;
+    }
+
+    pub(crate) fn add_spoiled(&mut self, reason: &str, weight: C) {
+        match self.spoiled.iter_mut().find(|(r, _)| r == reason) {
+            Some((_, total)) => *total = total.clone() + weight,
+            None => self.spoiled.push((reason.to_string(), weight)),
+        }
+    }
+
+    // The total weight of spoiled ballots, for all reasons.
+    pub(crate) fn spoiled_total(&self) -> C {
+        self.spoiled.iter().fold(C::zero(), |total, (_, weight)| total + weight.clone())
+    }
+
+    pub(crate) fn summary(&self) -> BallotSummary<C> {
+        BallotSummary {
+            valid: self.valid.clone(),
+            blank: self.blank.clone(),
+            spoiled: self.spoiled_total(),
+            spoiled_reasons: self.spoiled.clone(),
+        }
+    }
+
+    // An empty turnout with the same quorum and minimum support, for counting a shard of the ballots.
+    pub(crate) fn shard(&self) -> Self {
+        Turnout {
+            valid: C::zero(),
+            blank: C::zero(),
+            spoiled: Vec::new(),
+            quorum: self.quorum.clone(),
+            support: self.support.clone(),
+        }
+    }
+
+    // Add the turnout counted by another tally. The other tally's quorum and minimum support are ignored.
+    pub(crate) fn merge(&mut self, other: Turnout<C>) {
+        self.valid = self.valid.clone() + other.valid;
+        self.blank = self.blank.clone() + other.blank;
+        for (reason, weight) in other.spoiled {
+            self.add_spoiled(&reason, weight);
+        }
+    }
+
+    pub(crate) fn is_met(&self) -> bool {
+        match &self.quorum {
+            // Spoiled ballots were cast, so they count towards turnout like blank ballots.
+            Some(quorum) => quorum.is_met(&self.valid, &(self.blank.clone() + self.spoiled_total())),
+            None => true,
+        }
+    }
+
+    // Report no decision if the quorum is not met.
+    pub(crate) fn decide<T: Clone + Eq>(&self, winners: RankedWinners<T>) -> RankedWinners<T> {
+        if self.is_met() {
+            winners
+        } else {
+            RankedWinners::new(winners.num_winners)
+        }
+    }
+
+    // Leave seats unfilled for winners without the minimum support.
+    pub(crate) fn supported<T, F>(&self, mut winners: RankedWinners<T>, support_for: F) -> RankedWinners<T>
+    where
+        T: Clone + Eq,
+        F: Fn(&T) -> Option<C>,
+    {
+        if let Some(support) = &self.support {
+            winners
+                .winners
+                .retain(|ranked| support_for(&ranked.candidate).is_some_and(|count| support.is_met(&count, &self.valid)));
+        }
+        winners
+    }
+}
+
+impl<C: Clone + Num + PartialOrd + FromPrimitive + CheckedAdd> Turnout<C> {
+    // Check that counting a valid ballot would not overflow the total weight of valid ballots.
+    pub(crate) fn check_add_valid(&self, weight: &C) -> Result<(), TallyError> {
+        self.valid.checked_add(weight).map(|_| ()).ok_or(TallyError::CountOverflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quorum_test() {
+        assert!(Quorum::Turnout(10).is_met(&6, &4));
+        assert!(!Quorum::Turnout(10).is_met(&6, &3));
+        assert!(Quorum::ValidVotes(6).is_met(&6, &0));
+        assert!(!Quorum::ValidVotes(6).is_met(&5, &100));
+
+        let mut turnout = Turnout::new();
+        assert!(turnout.is_met());
+        turnout.add_valid(&1.0);
+        assert_eq!(turnout.valid, 1.0);
+        turnout.quorum = Some(Quorum::ValidVotes(3.5));
+        turnout.add_valid(&2.0);
+        turnout.add_blank(1.0);
+        assert!(!turnout.is_met());
+        turnout.add_valid(&0.5);
+        assert!(turnout.is_met());
+
+        let mut turnout = Turnout::new();
+        turnout.quorum = Some(Quorum::Turnout(5));
+        turnout.add_valid(&1);
+        turnout.add_spoiled("overvote", 2);
+        turnout.add_spoiled("unsigned", 1);
+        assert!(!turnout.is_met());
+        turnout.add_spoiled("overvote", 1);
+        assert!(turnout.is_met());
+        let summary = turnout.summary();
+        assert_eq!(summary.spoiled, 4);
+        assert_eq!(
+            summary.spoiled_reasons,
+            vec![("overvote".to_string(), 3), ("unsigned".to_string(), 1)]
+        );
+        assert_eq!(summary.turnout(), 5);
+    }
+
+    #[test]
+    fn minimum_support_test() {
+        assert!(MinimumSupport::Votes(3).is_met(&3, &10));
+        assert!(!MinimumSupport::Votes(3).is_met(&2, &10));
+        assert!(MinimumSupport::Percent(10).is_met(&1, &10));
+        assert!(!MinimumSupport::Percent(10).is_met(&9, &91));
+        assert!(MinimumSupport::Percent(12.5).is_met(&1.0, &8.0));
+        assert!(!MinimumSupport::Percent(12.5).is_met(&0.9, &8.0));
+        assert!(MinimumSupport::Percent(0).is_met(&0, &0));
+
+        // Large integer totals do not overflow.
+        assert!(MinimumSupport::Percent(50u64).is_met(&(u64::MAX / 2 + 1), &u64::MAX));
+        assert!(!MinimumSupport::Percent(50u64).is_met(&(u64::MAX / 2), &u64::MAX));
+        assert!(MinimumSupport::Percent(33u16).is_met(&83, &250));
+        assert!(!MinimumSupport::Percent(33u16).is_met(&82, &250));
+    }
+}
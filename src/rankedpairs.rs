@@ -0,0 +1,566 @@
+use num_traits::FromPrimitive;
+use num_traits::Num;
+use petgraph::Graph;
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::ops::AddAssign;
+
+use super::cache::Cache;
+use super::condorcet::CondorcetTally;
+use super::condorcet::MajorityCycle;
+use super::condorcet::PairwiseMatrix;
+use super::errors::TallyError;
+use super::intern::{CandidateId, CandidateSet};
+use super::margin::MarginOfVictory;
+use super::quorum::Quorum;
+use super::shard::Shard;
+use super::RankedCandidate;
+use super::RankedWinners;
+use super::Standings;
+use super::TallyCheckpoint;
+use super::Truncation;
+
+// The pairwise matrix, and every majority in it in the order they were considered for locking.
+type Locked<T, C> = (PairwiseMatrix<T, C>, Vec<Majority<T, C>>);
+
+/// Ranked pairs variants.
+///
+/// Each variant represents a different way to measure the strength of a majority.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// The strength of a majority is measured by the number of votes for it. You should use this variant if you are unsure.
+    ///
+    /// Majorities with the same number of votes for them are sorted by the fewest votes against them.
+    Winning,
+
+    /// The strength of a majority is measured by the difference between the votes for it and against it.
+    ///
+    /// Majorities with the same margin are sorted by the most votes for them.
+    Margin,
+}
+
+/// A ranked pairs tally using `u64` integers to count votes.
+/// `DefaultRankedPairsTally` is generally preferred over `RankedPairsTally`, except when using vote weights that contains fractions.
+/// Since this is an alias, refer to [`RankedPairsTally`](struct.RankedPairsTally.html) for method documentation.
+///
+/// # Example
+/// ```
+///    use tallystick::rankedpairs::DefaultRankedPairsTally;
+///    use tallystick::rankedpairs::Variant;
+///
+///    let mut tally = DefaultRankedPairsTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob", "Carlos"]);
+///    for _ in 0..5 { tally.add(&vec!["Alice", "Bob", "Carlos"]); }
+///    for _ in 0..4 { tally.add(&vec!["Bob", "Carlos", "Alice"]); }
+///    for _ in 0..3 { tally.add(&vec!["Carlos", "Alice", "Bob"]); }
+///
+///    // Alice beats Bob, Bob beats Carlos, and Carlos beats Alice. The weakest majority, Carlos over Alice, is not locked in.
+///    let winners = tally.winners().into_unranked();
+///    assert_eq!(winners, vec!["Alice"]);
+/// ```
+pub type DefaultRankedPairsTally<T> = RankedPairsTally<T, u64>;
+
+/// A generic ranked pairs tally.
+///
+/// Ranked pairs sorts the head-to-head majorities from strongest to weakest, and locks each one in unless it would
+/// create a cycle with the majorities already locked. The winner is the candidate no locked majority beats.
+///
+/// Majorities are sorted by their strength, as measured by the tally's [`Variant`](enum.Variant.html).
+/// Majorities of equal strength are considered in the order their candidates were added to the tally.
+///
+/// Generics:
+/// - `T`: The candidate type.
+/// - `C`: The count type. `u64` is recommended, but can be modified to use a different type for counting votes (eg `f64` for fractional vote weights).
+#[derive(Clone)]
+pub struct RankedPairsTally<T, C = u64>
+where
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
+{
+    variant: Variant,
+    condorcet: CondorcetTally<T, C>,
+    locked: Cache<Locked<T, C>>,
+}
+
+impl<T, C> RankedPairsTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
+{
+    /// Create a new `RankedPairsTally` with the given number of winners.
+    ///
+    /// If there is a tie, the number of winners might be more than `num_winners`.
+    pub fn new(num_winners: usize, variant: Variant) -> Self {
+        RankedPairsTally {
+            variant: variant,
+            condorcet: CondorcetTally::new(num_winners),
+            locked: Cache::new(),
+        }
+    }
+
+    /// Create a new `RankedPairsTally` with the given number of winners, and the given candidates.
+    pub fn with_candidates(num_winners: usize, variant: Variant, candidates: Vec<T>) -> Self {
+        RankedPairsTally {
+            variant: variant,
+            condorcet: CondorcetTally::with_candidates(num_winners, candidates),
+            locked: Cache::new(),
+        }
+    }
+
+    /// Create a new `RankedPairsTally` with the given number of winners, and the candidates in a [`CandidateSet`](../intern/struct.CandidateSet.html).
+    ///
+    /// See [`CondorcetTally::from_candidate_set()`](../condorcet/struct.CondorcetTally.html#method.from_candidate_set).
+    pub fn from_candidate_set(num_winners: usize, variant: Variant, candidates: &CandidateSet<T>) -> Self {
+        Self::with_candidates(num_winners, variant, candidates.candidates().to_vec())
+    }
+
+    /// Make this tally an unchecked tally, forgoing vote validity checking
+    ///
+    /// When using an unchecked tally, all vote adding methods will return Ok(), so you may elide checking for errors.
+    pub fn unchecked(mut self) -> Self {
+        self.condorcet = self.condorcet.unchecked();
+        self
+    }
+
+    /// Require a quorum for this tally to reach a decision.
+    ///
+    /// If the quorum is not met, [`winners()`](#method.winners) returns no winners.
+    pub fn with_quorum(mut self, quorum: Quorum<C>) -> Self {
+        self.condorcet = self.condorcet.with_quorum(quorum);
+        self
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.condorcet = self.condorcet.with_truncation(truncation);
+        self
+    }
+
+    /// Add a candidate to the tally.
+    pub fn add_candidate(&mut self, candidate: T) {
+        self.condorcet.add_candidate(candidate);
+    }
+
+    /// Add some candidates to the tally.
+    pub fn add_candidates(&mut self, candidates: Vec<T>) {
+        self.condorcet.add_candidates(candidates);
+    }
+
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.condorcet.withdraw_candidate(candidate);
+    }
+
+    /// Add a vote.
+    pub fn add(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        self.condorcet.add(selection)
+    }
+
+    /// Add a weighted vote.
+    pub fn add_weighted(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.condorcet.add_weighted(selection, weight)
+    }
+
+    /// Add a new ranked vote
+    ///
+    /// A ranked vote is a list of tuples of (candidate, rank), where rank is ascending.
+    /// Two candidates with the same rank are equal in preference.
+    pub fn ranked_add(&mut self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.condorcet.ranked_add(vote)
+    }
+
+    /// Add a ranked weighted vote.
+    pub fn ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        self.condorcet.ranked_add_weighted(vote, weight)
+    }
+
+    /// Get the id of a candidate, for adding votes with [`add_ids()`](#method.add_ids).
+    pub fn candidate_id(&self, candidate: &T) -> Option<CandidateId> {
+        self.condorcet.candidate_id(candidate)
+    }
+
+    /// Get every candidate and their id, in order of id, for translating votes to ids ahead of time.
+    pub fn candidate_ids(&self) -> Vec<(T, CandidateId)> {
+        self.condorcet.candidate_ids()
+    }
+
+    /// Add a vote given as candidate ids, in order of preference.
+    ///
+    /// See [`CondorcetTally::add_ids()`](../condorcet/struct.CondorcetTally.html#method.add_ids).
+    pub fn add_ids(&mut self, vote: &[CandidateId]) -> Result<(), TallyError> {
+        self.condorcet.add_ids(vote)
+    }
+
+    /// Add a weighted vote given as candidate ids, in order of preference.
+    pub fn add_ids_weighted(&mut self, vote: &[CandidateId], weight: C) -> Result<(), TallyError> {
+        self.condorcet.add_ids_weighted(vote, weight)
+    }
+
+    /// Add a blank ballot, for a voter who abstains.
+    pub fn add_blank(&mut self) {
+        self.condorcet.add_blank();
+    }
+
+    /// Add a weighted blank ballot.
+    pub fn add_blank_weighted(&mut self, weight: C) {
+        self.condorcet.add_blank_weighted(weight);
+    }
+
+    /// Get the total weight of blank ballots.
+    pub fn blank(&self) -> C {
+        self.condorcet.blank()
+    }
+
+    /// Check if the quorum for this tally has been met. Always true if no quorum is set.
+    pub fn quorum_met(&self) -> bool {
+        self.condorcet.quorum_met()
+    }
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// See [`CondorcetTally::merge()`](../condorcet/struct.CondorcetTally.html#method.merge).
+    pub fn merge(&mut self, other: Self) {
+        self.condorcet.merge(other.condorcet);
+    }
+
+    /// Get a list of all candidates seen by this tally.
+    /// Candidates are returned in no particular order.
+    pub fn candidates(&self) -> Vec<T> {
+        self.condorcet.candidates()
+    }
+
+    /// Get total counts for this tally.
+    /// For a pairwise comparison `((T1, T2), C)`, `C` is the number of votes where candidate `T1` is preferred over candidate `T2`.
+    pub fn totals(&self) -> Vec<((T, T), C)> {
+        self.condorcet.totals()
+    }
+
+    /// Get a lower bound on the margin of victory, from the pairwise contests of the Condorcet winner.
+    ///
+    /// A ranked pairs tally elects the Condorcet winner, so at least [`ballots_to_add()`](../struct.MarginOfVictory.html#method.ballots_to_add)
+    /// ballots are needed to alter its winner. Fewer ballots can never change it, though more may be needed.
+    /// See [`CondorcetTally::margin_of_victory()`](../condorcet/struct.CondorcetTally.html#method.margin_of_victory).
+    ///
+    /// Returns `None` if there is no Condorcet winner.
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        self.condorcet.margin_of_victory()
+    }
+
+    /// Get the margin of each head-to-head contest, from largest to smallest.
+    ///
+    /// See [`CondorcetTally::margins()`](../condorcet/struct.CondorcetTally.html#method.margins).
+    pub fn margins(&self) -> Vec<((T, T), C)> {
+        self.condorcet.margins()
+    }
+
+    /// Get the majority cycles that keep there from being a Condorcet winner.
+    ///
+    /// See [`CondorcetTally::cycles()`](../condorcet/struct.CondorcetTally.html#method.cycles).
+    pub fn cycles(&self) -> Vec<MajorityCycle<T, C>> {
+        self.condorcet.cycles()
+    }
+
+    /// Get total counts for this tally as a dense matrix.
+    pub fn matrix(&self) -> PairwiseMatrix<T, C> {
+        self.condorcet.matrix()
+    }
+
+    /// Get every head-to-head majority, in the order they were considered, and whether each was locked in.
+    ///
+    /// Together with the sorting rule, this is enough to independently re-derive the result.
+    /// Tied head-to-head contests have no majority and are left out.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::rankedpairs::DefaultRankedPairsTally;
+    ///    use tallystick::rankedpairs::Variant;
+    ///
+    ///    let mut tally = DefaultRankedPairsTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..5 { tally.add(&vec!["Alice", "Bob", "Carlos"]); }
+    ///    for _ in 0..4 { tally.add(&vec!["Bob", "Carlos", "Alice"]); }
+    ///    for _ in 0..3 { tally.add(&vec!["Carlos", "Alice", "Bob"]); }
+    ///
+    ///    for majority in tally.majorities() {
+    ///        println!("{} over {}, {} to {}, locked: {}", majority.winner, majority.loser, majority.votes_for, majority.votes_against, majority.locked);
+    ///    }
+    ///    // Prints:
+    ///    //   Bob over Carlos, 9 to 3, locked: true
+    ///    //   Alice over Bob, 8 to 4, locked: true
+    ///    //   Carlos over Alice, 7 to 5, locked: false
+    /// ```
+    pub fn majorities(&self) -> Vec<Majority<T, C>> {
+        self.lock().1
+    }
+
+    /// Get the graph of locked majorities.
+    ///
+    /// Each candidate is a node, and each locked majority is an edge leading from the winner to the loser.
+    /// Edge weights are the votes for and against the majority. The graph never contains a cycle.
+    pub fn lock_graph(&self) -> Graph<T, (C, C)> {
+        let (matrix, majorities) = self.lock();
+        let mut graph = Graph::<T, (C, C)>::with_capacity(matrix.candidates.len(), majorities.len());
+        let nodes: Vec<_> = matrix
+            .candidates
+            .iter()
+            .map(|candidate| graph.add_node(candidate.clone()))
+            .collect();
+        for majority in majorities.into_iter().filter(|majority| majority.locked) {
+            // Ok to unwrap here since majorities are only made of candidates in the matrix.
+            let winner = matrix.index_of(&majority.winner).unwrap();
+            let loser = matrix.index_of(&majority.loser).unwrap();
+            graph.add_edge(nodes[winner], nodes[loser], (majority.votes_for, majority.votes_against));
+        }
+        graph
+    }
+
+    /// Get a ranked list of all candidates. Candidates with the same rank are tied.
+    /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
+    ///
+    /// Candidates that no remaining locked majority beats share the top rank, and are then removed to rank the rest.
+    pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
+        let (matrix, majorities) = self.lock();
+        let n = matrix.candidates.len();
+
+        let mut beaten_by = vec![Vec::new(); n];
+        for majority in majorities.iter().filter(|majority| majority.locked) {
+            let winner = matrix.index_of(&majority.winner).unwrap();
+            let loser = matrix.index_of(&majority.loser).unwrap();
+            beaten_by[loser].push(winner);
+        }
+
+        let mut ranks: Vec<Option<usize>> = vec![None; n];
+        let mut rank = 0;
+        while ranks.iter().any(|r| r.is_none()) {
+            // The locked graph is acyclic, so there is always an unbeaten candidate among those remaining.
+            let unbeaten: Vec<usize> = (0..n)
+                .filter(|i| ranks[*i].is_none() && beaten_by[*i].iter().all(|winner| ranks[*winner].is_some()))
+                .collect();
+            for i in unbeaten {
+                ranks[i] = Some(rank);
+            }
+            rank += 1;
+        }
+
+        let mut ranked: Vec<RankedCandidate<T>> = matrix
+            .candidates
+            .into_iter()
+            .zip(ranks)
+            .map(|(candidate, rank)| RankedCandidate {
+                candidate: candidate,
+                rank: rank.unwrap_or(0),
+            })
+            .collect();
+        ranked.sort_by_key(|ranked| ranked.rank);
+        ranked
+    }
+
+    /// Get a ranked list of winners. Winners with the same rank are tied.
+    /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    pub fn winners(&self) -> RankedWinners<T> {
+        self.winners_n(self.condorcet.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.condorcet
+            .turnout
+            .decide(RankedWinners::from_ranked(self.ranked(), num_winners))
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
+    }
+
+    // Locked majorities are cached, and only recomputed after votes or candidates change.
+    fn lock(&self) -> Locked<T, C> {
+        self.locked.get_or_compute(self.condorcet.generation, || self.compute_lock())
+    }
+
+    // Sort the majorities from strongest to weakest, and lock in each one that does not create a cycle.
+    fn compute_lock(&self) -> Locked<T, C> {
+        let matrix = self.condorcet.matrix();
+        let n = matrix.candidates.len();
+
+        let mut pairs = Vec::<(usize, usize)>::new();
+        for i in 0..n {
+            for j in 0..n {
+                if matrix.counts[i][j] > matrix.counts[j][i] {
+                    pairs.push((i, j));
+                }
+            }
+        }
+
+        // A stable sort keeps majorities of equal strength in candidate order.
+        let cmp = |a: &C, b: &C| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        pairs.sort_by(|(a_win, a_lose), (b_win, b_lose)| {
+            let (a_for, a_against) = (&matrix.counts[*a_win][*a_lose], &matrix.counts[*a_lose][*a_win]);
+            let (b_for, b_against) = (&matrix.counts[*b_win][*b_lose], &matrix.counts[*b_lose][*b_win]);
+            match self.variant {
+                Variant::Winning => cmp(b_for, a_for).then(cmp(a_against, b_against)),
+                Variant::Margin => {
+                    // Votes for a majority always outnumber the votes against it, so the margin is never negative.
+                    let a_margin = a_for.clone() - a_against.clone();
+                    let b_margin = b_for.clone() - b_against.clone();
+                    cmp(&b_margin, &a_margin).then(cmp(b_for, a_for))
+                }
+            }
+        });
+
+        let mut locked = vec![vec![false; n]; n];
+        let mut majorities = Vec::with_capacity(pairs.len());
+        for (winner, loser) in pairs {
+            let lock = !reachable(&locked, loser, winner);
+            if lock {
+                locked[winner][loser] = true;
+            }
+            majorities.push(Majority {
+                winner: matrix.candidates[winner].clone(),
+                loser: matrix.candidates[loser].clone(),
+                votes_for: matrix.counts[winner][loser].clone(),
+                votes_against: matrix.counts[loser][winner].clone(),
+                locked: lock,
+            });
+        }
+
+        (matrix, majorities)
+    }
+}
+
+// Check if there is a path from one candidate to another in a graph of locked majorities.
+fn reachable(locked: &[Vec<bool>], from: usize, to: usize) -> bool {
+    let mut visited = vec![false; locked.len()];
+    let mut stack = vec![from];
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        stack.extend((0..locked.len()).filter(|next| locked[node][*next] && !visited[*next]));
+    }
+    false
+}
+
+/// A head-to-head majority, where more voters prefer one candidate over the other.
+///
+/// See [`RankedPairsTally::majorities()`](struct.RankedPairsTally.html#method.majorities).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Majority<T, C> {
+    /// The preferred candidate.
+    pub winner: T,
+
+    /// The other candidate.
+    pub loser: T,
+
+    /// The number of votes preferring the winner over the loser.
+    pub votes_for: C,
+
+    /// The number of votes preferring the loser over the winner.
+    pub votes_against: C,
+
+    /// Whether the majority was locked in. A majority is skipped if it would create a cycle with stronger majorities.
+    pub locked: bool,
+}
+
+impl<T, C> Shard for RankedPairsTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Count type
+{
+    fn shard(&self) -> Self {
+        RankedPairsTally {
+            variant: self.variant,
+            condorcet: self.condorcet.shard(),
+            locked: Cache::new(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        RankedPairsTally::merge(self, shard);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranked_pairs_wikipedia() -> Result<(), TallyError> {
+        // See: https://en.wikipedia.org/wiki/Ranked_pairs#Example
+        let mut tally =
+            DefaultRankedPairsTally::with_candidates(1, Variant::Winning, vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"]);
+        tally.add_weighted(&["Memphis", "Nashville", "Chattanooga", "Knoxville"], 42)?;
+        tally.add_weighted(&["Nashville", "Chattanooga", "Knoxville", "Memphis"], 26)?;
+        tally.add_weighted(&["Chattanooga", "Knoxville", "Nashville", "Memphis"], 15)?;
+        tally.add_weighted(&["Knoxville", "Chattanooga", "Nashville", "Memphis"], 17)?;
+
+        let ranked: Vec<&str> = tally.ranked().into_iter().map(|ranked| ranked.candidate).collect();
+        assert_eq!(ranked, vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"]);
+        assert!(tally.majorities().iter().all(|majority| majority.locked));
+        assert_eq!(tally.lock_graph().edge_count(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ranked_pairs_cycle() -> Result<(), TallyError> {
+        let mut tally = DefaultRankedPairsTally::with_candidates(1, Variant::Margin, vec!["Alice", "Bob", "Carol"]);
+        tally.add_weighted(&["Alice", "Bob", "Carol"], 5)?;
+        tally.add_weighted(&["Bob", "Carol", "Alice"], 4)?;
+        tally.add_weighted(&["Carol", "Alice", "Bob"], 3)?;
+
+        let majorities = tally.majorities();
+        let considered: Vec<(&str, &str, bool)> = majorities.iter().map(|m| (m.winner, m.loser, m.locked)).collect();
+        assert_eq!(
+            considered,
+            vec![("Bob", "Carol", true), ("Alice", "Bob", true), ("Carol", "Alice", false)]
+        );
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.lock_graph().edge_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ranked_pairs_cached() -> Result<(), TallyError> {
+        let mut tally = DefaultRankedPairsTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob"]);
+        tally.add(&["Alice", "Bob"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.majorities(), tally.majorities());
+
+        // Each change to the tally invalidates the cached majorities.
+        tally.add_weighted(&["Bob", "Alice"], 2)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+        tally.add_candidate("Carol");
+        assert_eq!(tally.ranked().len(), 3);
+
+        Ok(())
+    }
+}
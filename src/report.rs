@@ -0,0 +1,591 @@
+use std::fmt::{self, Display};
+#[cfg(any(feature = "csv", feature = "json"))]
+use std::io::{self, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+use crate::result::Exhausted;
+use crate::result::{group_thousands, BallotSummary, Flow, RankedCandidate, RankedWinners, Round};
+
+#[cfg(feature = "report-html")]
+mod html;
+
+/// The version of the JSON schema that reports are serialized with.
+///
+/// The version is bumped whenever a field is removed or changes meaning. New fields may be added without bumping the version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The results of a tally, gathered together for exporting and publishing.
+///
+/// A report always has winners. Totals, a full ranking, rounds and pairwise totals are included when the tally method produces them,
+/// and a summary of the ballots cast when the tally records it.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::report::ElectionReport;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    tally.add("Alice");
+///    tally.add("Bob");
+///    tally.add("Alice");
+///
+///    let report = ElectionReport::new("plurality", tally.winners()).with_totals(tally.totals());
+///    assert_eq!(report.totals, vec![("Alice", 2), ("Bob", 1)]);
+/// ```
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ElectionReport<T: Clone + Eq, C> {
+    /// The name of the tally method, such as `"stv"`.
+    pub method: String,
+
+    /// The winners.
+    pub winners: RankedWinners<T>,
+
+    /// Every candidate, ranked from first to last, if the method ranks all candidates.
+    pub ranked: Vec<RankedCandidate<T>>,
+
+    /// The vote totals for each candidate, if the method counts totals.
+    pub totals: Vec<(T, C)>,
+
+    /// Each round of the count, if the method counts in rounds.
+    pub rounds: Vec<Round<T, C>>,
+
+    /// Votes transferred in each round of the count, if the method transfers votes.
+    pub flows: Vec<Flow<T, C>>,
+
+    /// Pairwise totals of votes preferring the first candidate to the second, if the method compares candidates pairwise.
+    pub pairwise: Vec<((T, T), C)>,
+
+    /// The valid, blank and spoiled ballots cast, if the tally records them.
+    pub ballots: Option<BallotSummary<C>>,
+}
+
+impl<T: Clone + Eq, C> ElectionReport<T, C> {
+    /// Create a new report of the winners of a tally.
+    pub fn new(method: &str, winners: RankedWinners<T>) -> Self {
+        ElectionReport {
+            method: method.to_string(),
+            winners: winners,
+            ranked: Vec::new(),
+            totals: Vec::new(),
+            rounds: Vec::new(),
+            flows: Vec::new(),
+            pairwise: Vec::new(),
+            ballots: None,
+        }
+    }
+
+    /// Include a ranking of every candidate.
+    pub fn with_ranked(mut self, ranked: Vec<RankedCandidate<T>>) -> Self {
+        self.ranked = ranked;
+        self
+    }
+
+    /// Include the vote totals for each candidate.
+    pub fn with_totals(mut self, totals: Vec<(T, C)>) -> Self {
+        self.totals = totals;
+        self
+    }
+
+    /// Include each round of the count.
+    pub fn with_rounds(mut self, rounds: Vec<Round<T, C>>) -> Self {
+        self.rounds = rounds;
+        self
+    }
+
+    /// Include the votes transferred in each round of the count.
+    pub fn with_flows(mut self, flows: Vec<Flow<T, C>>) -> Self {
+        self.flows = flows;
+        self
+    }
+
+    /// Include pairwise totals.
+    pub fn with_pairwise(mut self, pairwise: Vec<((T, T), C)>) -> Self {
+        self.pairwise = pairwise;
+        self
+    }
+
+    /// Include a summary of the valid, blank and spoiled ballots cast.
+    pub fn with_ballots(mut self, ballots: BallotSummary<C>) -> Self {
+        self.ballots = Some(ballots);
+        self
+    }
+}
+
+/// Reports are displayed as plain text, with a heading for each part of the report that is present.
+///
+/// Ranks and rounds are numbered from `1`, and the digits of whole-number totals are grouped in thousands.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::report::ElectionReport;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    tally.add_weighted("Alice", 1234);
+///    tally.add_weighted("Bob", 56);
+///
+///    let report = ElectionReport::new("plurality", tally.winners()).with_totals(tally.totals());
+///    assert_eq!(
+///        report.to_string(),
+///        "Method: plurality\nWinners:\n  1. Alice\nTotals:\n  Alice: 1,234\n  Bob: 56\n"
+///    );
+/// ```
+impl<T: Clone + Eq + Display, C: Display> Display for ElectionReport<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Method: {}", self.method)?;
+
+        writeln!(f, "Winners:")?;
+        for ranked in self.winners.iter() {
+            writeln!(f, "  {}. {}", ranked.rank + 1, ranked.candidate)?;
+        }
+        if self.winners.check_overflow() {
+            writeln!(f, "  More candidates tied for the last seat than there were seats left.")?;
+        }
+
+        if !self.ranked.is_empty() {
+            writeln!(f, "Ranking:")?;
+            for ranked in self.ranked.iter() {
+                writeln!(f, "  {}. {}", ranked.rank + 1, ranked.candidate)?;
+            }
+        }
+
+        if !self.totals.is_empty() {
+            writeln!(f, "Totals:")?;
+            for (candidate, total) in self.totals.iter() {
+                writeln!(f, "  {}: {}", candidate, group_thousands(&total.to_string()))?;
+            }
+        }
+
+        if !self.rounds.is_empty() {
+            writeln!(f, "Rounds:")?;
+            let join = |candidates: &[T]| candidates.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ");
+            for (index, round) in self.rounds.iter().enumerate() {
+                let totals: Vec<String> = round
+                    .totals
+                    .iter()
+                    .map(|(candidate, total)| format!("{} {}", candidate, group_thousands(&total.to_string())))
+                    .collect();
+                write!(f, "  {}. {}", index + 1, totals.join(", "))?;
+                if !round.elected.is_empty() {
+                    write!(f, "; elected {}", join(&round.elected))?;
+                }
+                if !round.eliminated.is_empty() {
+                    write!(f, "; eliminated {}", join(&round.eliminated))?;
+                }
+                if round.exhausted.ballots > 0 {
+                    write!(f, "; {} exhausted", round.exhausted.ballots)?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        if !self.pairwise.is_empty() {
+            writeln!(f, "Pairwise:")?;
+            for ((candidate1, candidate2), total) in self.pairwise.iter() {
+                writeln!(f, "  {} over {}: {}", candidate1, candidate2, group_thousands(&total.to_string()))?;
+            }
+        }
+
+        if let Some(ballots) = &self.ballots {
+            writeln!(f, "Ballots:")?;
+            writeln!(f, "  Valid: {}", group_thousands(&ballots.valid.to_string()))?;
+            writeln!(f, "  Blank: {}", group_thousands(&ballots.blank.to_string()))?;
+            writeln!(f, "  Spoiled: {}", group_thousands(&ballots.spoiled.to_string()))?;
+            for (reason, total) in ballots.spoiled_reasons.iter() {
+                writeln!(f, "    {}: {}", reason, group_thousands(&total.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<T: Clone + Eq + Display, C: Display> ElectionReport<T, C> {
+    /// Write the winners as CSV, with a `rank,candidate` header. `csv`
+    ///
+    /// Ranks are numbered from `1`, as they would be in a canvass report.
+    pub fn write_winners_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.winners.write_csv(writer)
+    }
+
+    /// Write the vote totals as CSV, with a `candidate,total` header. `csv`
+    pub fn write_totals_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record(["candidate", "total"])?;
+        for (candidate, total) in self.totals.iter() {
+            csv.write_record([candidate.to_string(), total.to_string()])?;
+        }
+        csv.flush()
+    }
+
+    /// Write the rounds as CSV, with one row per round and one column per candidate. `csv`
+    ///
+    /// The header is `round`, then each candidate in the order they appear in the first round, then `exhausted`, `elected` and `eliminated`.
+    /// A candidate's cell is empty once they are no longer continuing. Candidates elected or eliminated in the same round are separated by `; `.
+    ///
+    /// # Example
+    /// The example counts an STV tally, so it only runs with the `nightly` feature.
+    /// ```
+    ///    # #[cfg(feature = "nightly")]
+    ///    # {
+    ///    use tallystick::stv::DefaultTally;
+    ///    use tallystick::report::ElectionReport;
+    ///    use tallystick::Quota;
+    ///
+    ///    let mut tally = DefaultTally::new(1, Quota::Droop);
+    ///    tally.add(vec!["Alice"])?;
+    ///    tally.add(vec!["Alice", "Bob"])?;
+    ///    tally.add(vec!["Bob", "Alice"])?;
+    ///    tally.add(vec!["Carol", "Bob"])?;
+    ///
+    ///    let report = ElectionReport::new("stv", tally.winners()).with_rounds(tally.rounds());
+    ///    let mut csv = Vec::new();
+    ///    report.write_rounds_csv(&mut csv)?;
+    ///    assert_eq!(
+    ///        String::from_utf8(csv).unwrap(),
+    ///        [
+    ///            "round,Alice,Bob,Carol,exhausted,elected,eliminated",
+    ///            "1,2,1,1,1,,Bob; Carol",
+    ///            "2,3,,,0,Alice,",
+    ///            "",
+    ///        ]
+    ///        .join("\n")
+    ///    );
+    ///    # }
+    ///    # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_rounds_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        let candidates: Vec<&T> = match self.rounds.first() {
+            Some(round) => round.totals.iter().map(|(c, _)| c).collect(),
+            None => Vec::new(),
+        };
+        let join = |candidates: &[T]| candidates.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; ");
+
+        let mut csv = csv::Writer::from_writer(writer);
+        let mut header = vec!["round".to_string()];
+        header.extend(candidates.iter().map(|c| c.to_string()));
+        header.extend(["exhausted", "elected", "eliminated"].iter().map(|h| h.to_string()));
+        csv.write_record(&header)?;
+
+        for (index, round) in self.rounds.iter().enumerate() {
+            let mut record = vec![(index + 1).to_string()];
+            for candidate in candidates.iter() {
+                let total = round.totals.iter().find(|(c, _)| c == *candidate);
+                record.push(total.map(|(_, total)| total.to_string()).unwrap_or_default());
+            }
+            record.push(round.exhausted.weight.to_string());
+            record.push(join(&round.elected));
+            record.push(join(&round.eliminated));
+            csv.write_record(&record)?;
+        }
+        csv.flush()
+    }
+
+    /// Write the votes transferred in each round as CSV, with a `round,from,to,weight` header, for drawing a Sankey diagram. `csv`
+    ///
+    /// Rounds are numbered from `1`, as they are by [`write_rounds_csv()`](#method.write_rounds_csv). Votes that exhausted have an empty `to` column.
+    pub fn write_flows_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record(["round", "from", "to", "weight"])?;
+        for flow in self.flows.iter() {
+            let to = flow.to.as_ref().map(|to| to.to_string()).unwrap_or_default();
+            csv.write_record([(flow.round + 1).to_string(), flow.from.to_string(), to, flow.weight.to_string()])?;
+        }
+        csv.flush()
+    }
+}
+
+/// Reports serialize to a stable, versioned schema, described here as JSON. `serde`
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "method": "stv",
+///   "num_winners": 2,
+///   "winners": [{ "candidate": "Alice", "rank": 0 }, { "candidate": "Bob", "rank": 1 }],
+///   "ties": [],
+///   "tie_for_last_seat": false,
+///   "ranked": [],
+///   "totals": [{ "candidate": "Alice", "total": 4 }],
+///   "rounds": [
+///     {
+///       "round": 1,
+///       "totals": [{ "candidate": "Alice", "total": 4 }],
+///       "elected": ["Alice"],
+///       "eliminated": [],
+///       "exhausted": { "ballots": 0, "weight": 0 }
+///     }
+///   ],
+///   "flows": [{ "round": 1, "from": "Alice", "to": "Bob", "weight": 1 }],
+///   "pairwise": [{ "for": "Alice", "against": "Bob", "total": 3 }],
+///   "ballots": {
+///     "valid": 4,
+///     "blank": 0,
+///     "spoiled": 1,
+///     "spoiled_reasons": [{ "reason": "overvote", "total": 1 }]
+///   }
+/// }
+/// ```
+///
+/// Ranks are numbered from `0`, and candidates that share a rank are tied. Rounds are numbered from `1`, and a flow whose `to` is `null` is of ballots that exhausted. `ties` lists each group of winners that share a rank.
+/// `tie_for_last_seat` is true if more candidates tied for the last seat than there were seats left, so there are more winners than `num_winners`.
+/// `ranked`, `totals`, `rounds`, `flows` and `pairwise` are empty if the tally method does not produce them, and `ballots` is `null` if the report has no summary of the ballots cast.
+#[cfg(feature = "serde")]
+impl<T: Clone + Eq + Serialize, C: Serialize> Serialize for ElectionReport<T, C> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut ties: Vec<Vec<&T>> = Vec::new();
+        let mut previous_rank = None;
+        for ranked in self.winners.iter() {
+            if previous_rank == Some(ranked.rank) {
+                if let Some(last) = ties.last_mut() {
+                    last.push(&ranked.candidate);
+                }
+            } else {
+                ties.push(vec![&ranked.candidate]);
+            }
+            previous_rank = Some(ranked.rank);
+        }
+        ties.retain(|tied| tied.len() > 1);
+
+        let schema = ReportSchema {
+            schema_version: SCHEMA_VERSION,
+            method: &self.method,
+            num_winners: self.winners.num_winners,
+            winners: &self.winners.winners,
+            ties: ties,
+            tie_for_last_seat: self.winners.check_overflow(),
+            ranked: &self.ranked,
+            totals: self.totals.iter().map(CandidateTotal::from).collect(),
+            rounds: self
+                .rounds
+                .iter()
+                .enumerate()
+                .map(|(index, round)| RoundSchema {
+                    round: index + 1,
+                    totals: round.totals.iter().map(CandidateTotal::from).collect(),
+                    elected: &round.elected,
+                    eliminated: &round.eliminated,
+                    exhausted: &round.exhausted,
+                })
+                .collect(),
+            flows: self
+                .flows
+                .iter()
+                .map(|flow| FlowSchema {
+                    round: flow.round + 1,
+                    from: &flow.from,
+                    to: flow.to.as_ref(),
+                    weight: &flow.weight,
+                })
+                .collect(),
+            pairwise: self
+                .pairwise
+                .iter()
+                .map(|((for_candidate, against), total)| PairwiseTotal {
+                    for_candidate: for_candidate,
+                    against: against,
+                    total: total,
+                })
+                .collect(),
+            ballots: self.ballots.as_ref().map(|ballots| BallotsSchema {
+                valid: &ballots.valid,
+                blank: &ballots.blank,
+                spoiled: &ballots.spoiled,
+                spoiled_reasons: ballots
+                    .spoiled_reasons
+                    .iter()
+                    .map(|(reason, total)| SpoiledTotal {
+                        reason: reason,
+                        total: total,
+                    })
+                    .collect(),
+            }),
+        };
+        schema.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: Clone + Eq + Serialize, C: Serialize> ElectionReport<T, C> {
+    /// Write the report as pretty-printed JSON, in the versioned schema documented on its `Serialize` implementation. `json`
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///    use tallystick::report::ElectionReport;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    tally.add("Alice");
+    ///    tally.add("Bob");
+    ///    tally.add("Alice");
+    ///
+    ///    let report = ElectionReport::new("plurality", tally.winners()).with_totals(tally.totals());
+    ///    let mut json = Vec::new();
+    ///    report.write_json(&mut json)?;
+    ///    let json: serde_json::Value = serde_json::from_slice(&json)?;
+    ///    assert_eq!(json["schema_version"], 1);
+    ///    assert_eq!(json["winners"][0]["candidate"], "Alice");
+    ///    assert_eq!(json["totals"][1]["total"], 1);
+    ///    # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_json<W: Write>(&self, writer: W) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+// The serialized form of a report. Field names and meanings are fixed for each schema version.
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct ReportSchema<'a, T: Clone + Eq, C> {
+    schema_version: u32,
+    method: &'a str,
+    num_winners: usize,
+    winners: &'a [RankedCandidate<T>],
+    ties: Vec<Vec<&'a T>>,
+    tie_for_last_seat: bool,
+    ranked: &'a [RankedCandidate<T>],
+    totals: Vec<CandidateTotal<'a, T, C>>,
+    rounds: Vec<RoundSchema<'a, T, C>>,
+    flows: Vec<FlowSchema<'a, T, C>>,
+    pairwise: Vec<PairwiseTotal<'a, T, C>>,
+    ballots: Option<BallotsSchema<'a, C>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct BallotsSchema<'a, C> {
+    valid: &'a C,
+    blank: &'a C,
+    spoiled: &'a C,
+    spoiled_reasons: Vec<SpoiledTotal<'a, C>>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SpoiledTotal<'a, C> {
+    reason: &'a str,
+    total: &'a C,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct CandidateTotal<'a, T, C> {
+    candidate: &'a T,
+    total: &'a C,
+}
+
+#[cfg(feature = "serde")]
+impl<'a, T, C> From<&'a (T, C)> for CandidateTotal<'a, T, C> {
+    fn from((candidate, total): &'a (T, C)) -> Self {
+        CandidateTotal {
+            candidate: candidate,
+            total: total,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct RoundSchema<'a, T, C> {
+    round: usize,
+    totals: Vec<CandidateTotal<'a, T, C>>,
+    elected: &'a [T],
+    eliminated: &'a [T],
+    exhausted: &'a Exhausted<C>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct FlowSchema<'a, T, C> {
+    round: usize,
+    from: &'a T,
+    to: Option<&'a T>,
+    weight: &'a C,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct PairwiseTotal<'a, T, C> {
+    #[serde(rename = "for")]
+    for_candidate: &'a T,
+    against: &'a T,
+    total: &'a C,
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_schema() {
+        let mut winners = RankedWinners::new(1);
+        winners.push("Alice", 0);
+        winners.push("Bob", 0);
+        let report = ElectionReport::new("stv", winners)
+            .with_totals(vec![("Alice", 2), ("Bob", 2), ("Carol", 1)])
+            .with_rounds(vec![Round {
+                totals: vec![("Alice", 2), ("Bob", 2), ("Carol", 1)],
+                elected: vec![],
+                eliminated: vec!["Carol"],
+                exhausted: Exhausted { ballots: 1, weight: 1 },
+            }])
+            .with_flows(vec![Flow {
+                round: 0,
+                from: "Carol",
+                to: None,
+                weight: 1,
+            }])
+            .with_pairwise(vec![(("Alice", "Bob"), 2)])
+            .with_ballots(BallotSummary {
+                valid: 5,
+                blank: 0,
+                spoiled: 1,
+                spoiled_reasons: vec![("overvote".to_string(), 1)],
+            });
+
+        let mut json = Vec::new();
+        report.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "schema_version": 1,
+                "method": "stv",
+                "num_winners": 1,
+                "winners": [{ "candidate": "Alice", "rank": 0 }, { "candidate": "Bob", "rank": 0 }],
+                "ties": [["Alice", "Bob"]],
+                "tie_for_last_seat": true,
+                "ranked": [],
+                "totals": [
+                    { "candidate": "Alice", "total": 2 },
+                    { "candidate": "Bob", "total": 2 },
+                    { "candidate": "Carol", "total": 1 }
+                ],
+                "rounds": [{
+                    "round": 1,
+                    "totals": [
+                        { "candidate": "Alice", "total": 2 },
+                        { "candidate": "Bob", "total": 2 },
+                        { "candidate": "Carol", "total": 1 }
+                    ],
+                    "elected": [],
+                    "eliminated": ["Carol"],
+                    "exhausted": { "ballots": 1, "weight": 1 }
+                }],
+                "flows": [{ "round": 1, "from": "Carol", "to": null, "weight": 1 }],
+                "pairwise": [{ "for": "Alice", "against": "Bob", "total": 2 }],
+                "ballots": {
+                    "valid": 5,
+                    "blank": 0,
+                    "spoiled": 1,
+                    "spoiled_reasons": [{ "reason": "overvote", "total": 1 }]
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use num_traits::ToPrimitive;
+use std::fmt::{Display, Write};
+
+use super::ElectionReport;
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:60em;margin:2em auto;padding:0 1em;color:#222}\
+table{border-collapse:collapse;margin:1em 0}\
+th,td{border:1px solid #ccc;padding:.3em .6em;text-align:left}\
+td.number{text-align:right}\
+.bar{background:#4a7bd0;height:1em}\
+.elected{color:#1a7f37;font-weight:bold}\
+.eliminated{color:#999;text-decoration:line-through}";
+
+impl<T: Clone + Eq + Display, C: Display + ToPrimitive> ElectionReport<T, C> {
+    /// Render the report as a self-contained HTML page, with no external stylesheets, scripts or images. `report-html`
+    ///
+    /// Requires the `report-html` feature to be enabled.
+    ///
+    /// The page has a table of winners, then a table of totals, a bar chart of each round, the pairwise matrix and a table of ballots cast,
+    /// for those parts of the report that are present.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///    use tallystick::report::ElectionReport;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    tally.add("Alice");
+    ///    tally.add("Bob <3");
+    ///    tally.add("Alice");
+    ///
+    ///    let report = ElectionReport::new("plurality", tally.winners()).with_totals(tally.totals());
+    ///    let html = report.to_html("Club president");
+    ///    assert!(html.contains("<title>Club president</title>"));
+    ///    assert!(html.contains("Bob &lt;3"));
+    /// ```
+    pub fn to_html(&self, title: &str) -> String {
+        let mut html = String::new();
+        // Writing to a String cannot fail.
+        self.write_html(&mut html, title).unwrap();
+        html
+    }
+
+    fn write_html(&self, html: &mut String, title: &str) -> std::fmt::Result {
+        write!(html, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n")?;
+        write!(
+            html,
+            "<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+            escape(title),
+            STYLE
+        )?;
+        write!(html, "<h1>{}</h1>\n<p>Counted by {}.</p>\n", escape(title), escape(&self.method))?;
+
+        writeln!(html, "<h2>Winners</h2>\n<table>\n<tr><th>Rank</th><th>Candidate</th></tr>")?;
+        for ranked in self.winners.iter() {
+            writeln!(
+                html,
+                "<tr><td class=\"number\">{}</td><td>{}</td></tr>",
+                ranked.rank + 1,
+                escape(&ranked.candidate)
+            )?;
+        }
+        writeln!(html, "</table>")?;
+        if self.winners.check_overflow() {
+            writeln!(html, "<p>More candidates tied for the last seat than there were seats left.</p>")?;
+        }
+
+        if !self.totals.is_empty() {
+            writeln!(html, "<h2>Totals</h2>\n<table>\n<tr><th>Candidate</th><th>Votes</th><th></th></tr>")?;
+            let max = max_total(self.totals.iter().map(|(_, total)| total));
+            for (candidate, total) in self.totals.iter() {
+                write!(html, "<tr><td>{}</td><td class=\"number\">{}</td>", escape(candidate), total)?;
+                writeln!(html, "<td>{}</td></tr>", bar(total, max))?;
+            }
+            writeln!(html, "</table>")?;
+        }
+
+        if !self.rounds.is_empty() {
+            writeln!(html, "<h2>Rounds</h2>")?;
+            let max = max_total(self.rounds.iter().flat_map(|round| round.totals.iter().map(|(_, total)| total)));
+            for (index, round) in self.rounds.iter().enumerate() {
+                writeln!(html, "<h3>Round {}</h3>\n<table>", index + 1)?;
+                for (candidate, total) in round.totals.iter() {
+                    let class = if round.elected.contains(candidate) {
+                        " class=\"elected\""
+                    } else if round.eliminated.contains(candidate) {
+                        " class=\"eliminated\""
+                    } else {
+                        ""
+                    };
+                    write!(
+                        html,
+                        "<tr><td{}>{}</td><td class=\"number\">{}</td>",
+                        class,
+                        escape(candidate),
+                        total
+                    )?;
+                    writeln!(html, "<td>{}</td></tr>", bar(total, max))?;
+                }
+                writeln!(html, "</table>")?;
+                if round.exhausted.ballots > 0 {
+                    writeln!(
+                        html,
+                        "<p>{} ballots exhausted, with a value of {}.</p>",
+                        round.exhausted.ballots, round.exhausted.weight
+                    )?;
+                }
+            }
+        }
+
+        if !self.pairwise.is_empty() {
+            let mut candidates: Vec<&T> = Vec::new();
+            for ((candidate1, candidate2), _) in self.pairwise.iter() {
+                for candidate in [candidate1, candidate2] {
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+
+            writeln!(html, "<h2>Pairwise matrix</h2>")?;
+            writeln!(
+                html,
+                "<p>Each cell is the number of votes preferring the row candidate to the column candidate.</p>"
+            )?;
+            write!(html, "<table>\n<tr><th></th>")?;
+            for candidate in candidates.iter() {
+                write!(html, "<th>{}</th>", escape(candidate))?;
+            }
+            writeln!(html, "</tr>")?;
+            for row in candidates.iter() {
+                write!(html, "<tr><th>{}</th>", escape(row))?;
+                for column in candidates.iter() {
+                    let total = self.pairwise.iter().find(|((c1, c2), _)| c1 == *row && c2 == *column);
+                    match total {
+                        Some((_, total)) => write!(html, "<td class=\"number\">{}</td>", total)?,
+                        None => write!(html, "<td></td>")?,
+                    }
+                }
+                writeln!(html, "</tr>")?;
+            }
+            writeln!(html, "</table>")?;
+        }
+
+        if let Some(ballots) = &self.ballots {
+            writeln!(html, "<h2>Ballots</h2>\n<table>")?;
+            writeln!(html, "<tr><td>Valid</td><td class=\"number\">{}</td></tr>", ballots.valid)?;
+            writeln!(html, "<tr><td>Blank</td><td class=\"number\">{}</td></tr>", ballots.blank)?;
+            writeln!(html, "<tr><td>Spoiled</td><td class=\"number\">{}</td></tr>", ballots.spoiled)?;
+            for (reason, total) in ballots.spoiled_reasons.iter() {
+                writeln!(
+                    html,
+                    "<tr><td>Spoiled: {}</td><td class=\"number\">{}</td></tr>",
+                    escape(reason),
+                    total
+                )?;
+            }
+            writeln!(html, "</table>")?;
+        }
+
+        write!(html, "</body>\n</html>\n")
+    }
+}
+
+// The largest total, used to scale bars. Totals that cannot be converted to floats are ignored.
+fn max_total<'a, C: ToPrimitive + 'a, I: Iterator<Item = &'a C>>(totals: I) -> f64 {
+    totals.filter_map(|total| total.to_f64()).fold(0.0, f64::max)
+}
+
+// A horizontal bar for a total, as a fraction of the largest total.
+fn bar<C: ToPrimitive>(total: &C, max: f64) -> String {
+    let width = match total.to_f64() {
+        Some(total) if max > 0.0 && total > 0.0 => total / max * 100.0,
+        _ => 0.0,
+    };
+    format!("<div class=\"bar\" style=\"width:{:.1}%\"></div>", width)
+}
+
+// Escape text for HTML.
+fn escape<T: Display + ?Sized>(text: &T) -> String {
+    let mut escaped = String::new();
+    for c in text.to_string().chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
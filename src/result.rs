@@ -1,7 +1,12 @@
 use derive_more::{From, Index, IndexMut};
+use hashbrown::HashMap;
 use num_traits::Num;
 use std::cmp::Ordering::Equal;
-use std::ops::RangeBounds;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::{Bound, RangeBounds};
+
+use super::errors::TieError;
 
 /// A RankedCandidate is candidate in an election, ranked ascending (starting from zero).
 /// A ranked-candidate with a lower rank beats a ranked-candidate with a higher rank.
@@ -25,15 +30,70 @@ impl<T: Eq + PartialEq + Clone> PartialEq<(T, usize)> for RankedCandidate<T> {
 /// `RankedWinners` is a ranked list of winning candidates, sorted according to rank.
 /// Ranks are in ascending order. A `0` ranked winner is more significant than a `3` ranked winner.
 /// Winners with the same rank are tied.
-// TODO: implement Index, IndexMut
-#[derive(Debug, Eq, PartialEq, From, Default, Clone)]
+///
+/// Winners can be indexed like a slice, so `winners[0]` is the first winner. Indexing out of bounds panics;
+/// use [`get()`](#method.get) for a checked lookup.
+///
+/// Winners are indexed by candidate, so [`contains()`](#method.contains) and [`rank()`](#method.rank) take constant time.
+#[derive(Index, IndexMut, Default, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "UnindexedWinners<T>", bound(deserialize = "T: serde::Deserialize<'de> + Hash"))
+)]
 pub struct RankedWinners<T: Clone + Eq + PartialEq> {
     /// Ranked winners
+    ///
+    /// Candidates added or replaced here directly are not indexed, so [`contains()`](#method.contains) and
+    /// [`rank()`](#method.rank) will not find them. Reordering or reranking winners is fine.
+    #[index]
+    #[index_mut]
     pub winners: Vec<RankedCandidate<T>>,
 
     /// Number of winners, this number could be less than winners.len() if there are ties in the lowest ranked winners.
     pub num_winners: usize,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    positions: HashMap<T, usize>, // Map winners to their position in `winners`
+}
+
+// Ranked winners as they are serialized, without their index.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UnindexedWinners<T: Clone + Eq + PartialEq> {
+    winners: Vec<RankedCandidate<T>>,
+    num_winners: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Clone + Eq + Hash> From<UnindexedWinners<T>> for RankedWinners<T> {
+    fn from(unindexed: UnindexedWinners<T>) -> Self {
+        RankedWinners::with_winners(unindexed.winners, unindexed.num_winners)
+    }
+}
+
+impl<T: Clone + Eq + Hash> From<(Vec<RankedCandidate<T>>, usize)> for RankedWinners<T> {
+    fn from((winners, num_winners): (Vec<RankedCandidate<T>>, usize)) -> Self {
+        RankedWinners::with_winners(winners, num_winners)
+    }
+}
+
+// The index is derived from the winners, so it is left out of comparisons and debug output.
+impl<T: Clone + Eq + PartialEq> PartialEq for RankedWinners<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.winners == other.winners && self.num_winners == other.num_winners
+    }
+}
+
+impl<T: Clone + Eq + PartialEq> Eq for RankedWinners<T> {}
+
+impl<T: Clone + Eq + PartialEq + fmt::Debug> fmt::Debug for RankedWinners<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RankedWinners")
+            .field("winners", &self.winners)
+            .field("num_winners", &self.num_winners)
+            .finish()
+    }
 }
 
 impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
@@ -47,14 +107,6 @@ impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
         self.winners.is_empty()
     }
 
-    /// Clears the winners, returning all winner-rank pairs as an iterator.
-    pub fn drain<R>(&mut self, range: R) -> std::vec::Drain<'_, RankedCandidate<T>>
-    where
-        R: RangeBounds<usize>,
-    {
-        self.winners.drain(range)
-    }
-
     /// Transform winners into a vector of winner-rank pairs.
     pub fn into_vec(self) -> Vec<RankedCandidate<T>> {
         self.winners
@@ -71,42 +123,152 @@ impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
     }
 
     /// Iterate over all winner->rank pairs.
-    pub fn iter(&self) -> IterWinners<'_, T> {
-        IterWinners { inner: self, pos: 0 }
+    ///
+    /// Winners can also be iterated over directly, by reference or by value.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(2);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add_weighted("Bob", 2);
+    ///    tally.add("Carol");
+    ///    let winners = tally.winners();
+    ///
+    ///    let top: Vec<&str> = winners.iter().filter(|r| r.rank == 0).map(|r| r.candidate).collect();
+    ///    assert_eq!(top, vec!["Alice"]);
+    ///
+    ///    for ranked in &winners {
+    ///        println!("{} has a rank of {}", ranked.candidate, ranked.rank);
+    ///    }
+    ///
+    ///    let candidates: Vec<&str> = winners.into_iter().map(|r| r.candidate).collect();
+    ///    assert_eq!(candidates, vec!["Alice", "Bob"]);
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, RankedCandidate<T>> {
+        self.winners.iter()
     }
 
-    /// Check if the given candidate exists in the set of ranked-winners.
-    pub fn contains(&self, candidate: &T) -> bool {
-        for ranked in self.iter() {
-            if candidate == &ranked.candidate {
-                return true;
-            }
-        }
+    /// Get the winner at a position, or `None` if the position is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&RankedCandidate<T>> {
+        self.winners.get(index)
+    }
 
-        false
+    /// Get a mutable reference to the winner at a position, or `None` if the position is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut RankedCandidate<T>> {
+        self.winners.get_mut(index)
     }
 
-    /// Get the rank of a single winner.
-    pub fn rank(&self, candidate: &T) -> Option<usize> {
-        for ranked in self.iter() {
-            if candidate == &ranked.candidate {
-                return Some(ranked.rank);
-            }
-        }
+    /// Get the winners with a rank, who are tied with each other. Empty if no winner has the rank.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(2);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add_weighted("Bob", 2);
+    ///    tally.add_weighted("Carol", 2);
+    ///    let winners = tally.winners();
+    ///
+    ///    assert_eq!(winners[0].candidate, "Alice");
+    ///    assert_eq!(winners.group(1).len(), 2);
+    ///    assert!(winners.group(2).is_empty());
+    ///    assert!(winners.get(3).is_none());
+    /// ```
+    pub fn group(&self, rank: usize) -> &[RankedCandidate<T>] {
+        // Winners are sorted by rank, so the winners with a rank are contiguous.
+        let start = self.winners.partition_point(|ranked| ranked.rank < rank);
+        let end = self.winners.partition_point(|ranked| ranked.rank <= rank);
+        &self.winners[start..end]
+    }
 
-        None
+    /// Get the winners sharing the top rank, or `None` if there are no winners.
+    ///
+    /// There is more than one top winner if they are tied.
+    pub fn top(&self) -> Option<&[RankedCandidate<T>]> {
+        self.winners.first().map(|first| self.group(first.rank))
+    }
+
+    /// Get the single winner, or an error if there are no winners or the top rank is tied.
+    ///
+    /// This does not consider `num_winners`, so for a multi-winner tally it is the winner ranked first.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///    use tallystick::TieError;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(1);
+    ///    assert_eq!(tally.winners().single_winner(), Err(TieError::NoWinner));
+    ///
+    ///    tally.add("Alice");
+    ///    assert_eq!(tally.winners().single_winner(), Ok("Alice"));
+    ///
+    ///    tally.add("Bob");
+    ///    match tally.winners().single_winner() {
+    ///        Err(TieError::Tied(tied)) => assert_eq!(tied.len(), 2),
+    ///        _ => unreachable!(),
+    ///    }
+    /// ```
+    pub fn single_winner(&self) -> Result<T, TieError<T>> {
+        match self.top() {
+            None => Err(TieError::NoWinner),
+            Some([winner]) => Ok(winner.candidate.clone()),
+            Some(tied) => Err(TieError::Tied(tied.iter().map(|ranked| ranked.candidate.clone()).collect())),
+        }
     }
 
     /// Get an unranked list of all winners, this consumes the winner list.
-    pub fn into_unranked(mut self) -> Vec<T> {
-        let mut all = Vec::new();
-        for ranked in self.drain(0..) {
+    pub fn into_unranked(self) -> Vec<T> {
+        let mut all = Vec::with_capacity(self.len());
+        for ranked in self.winners {
             all.push(ranked.candidate);
         }
 
         all
     }
 
+    /// Get the winners grouped by rank, this consumes the winner list.
+    ///
+    /// Each group holds the winners that share a rank, so a group with more than one winner is a tie.
+    /// Groups are in rank order, best first.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(2);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add_weighted("Bob", 2);
+    ///    tally.add_weighted("Carol", 2);
+    ///
+    ///    let mut groups = tally.winners().into_ranked_groups();
+    ///    groups[1].sort();
+    ///    assert_eq!(groups, vec![vec!["Alice"], vec!["Bob", "Carol"]]);
+    /// ```
+    pub fn into_ranked_groups(self) -> Vec<Vec<T>> {
+        let mut groups: Vec<Vec<T>> = Vec::new();
+        let mut prev_rank = None;
+        for ranked in self.winners {
+            match groups.last_mut() {
+                Some(group) if prev_rank == Some(ranked.rank) => group.push(ranked.candidate),
+                _ => groups.push(vec![ranked.candidate]),
+            }
+            prev_rank = Some(ranked.rank);
+        }
+
+        groups
+    }
+
+    /// Get the number of seats left unfilled.
+    ///
+    /// Seats are left unfilled when there are fewer winning candidates than wanted, for example when a winner does not have the minimum support.
+    pub fn unfilled(&self) -> usize {
+        self.num_winners.saturating_sub(self.len())
+    }
+
     /// Check if the actual number of winners is more than the wanted number of winners.
     /// This can happen if there is a tie.
     ///
@@ -147,18 +309,101 @@ impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
         RankedWinners {
             winners: Vec::new(),
             num_winners: num_winners,
+            positions: HashMap::new(),
         }
     }
+}
+
+impl<T: Clone + Eq + Hash> RankedWinners<T> {
+    /// Check if the given candidate exists in the set of ranked-winners.
+    pub fn contains(&self, candidate: &T) -> bool {
+        self.rank(candidate).is_some()
+    }
+
+    /// Get the rank of a single winner.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::plurality::DefaultPluralityTally;
+    ///
+    ///    let mut tally = DefaultPluralityTally::new(2);
+    ///    tally.add_weighted("Alice", 3);
+    ///    tally.add_weighted("Bob", 2);
+    ///    tally.add("Carol");
+    ///
+    ///    let mut winners = tally.winners();
+    ///    assert_eq!(winners.rank(&"Bob"), Some(1));
+    ///    assert!(!winners.contains(&"Carol"));
+    ///
+    ///    winners.drain(..1);
+    ///    assert!(!winners.contains(&"Alice"));
+    ///    assert_eq!(winners.rank(&"Bob"), Some(1));
+    /// ```
+    pub fn rank(&self, candidate: &T) -> Option<usize> {
+        let position = *self.positions.get(candidate)?;
+        match self.winners.get(position) {
+            Some(ranked) if ranked.candidate == *candidate => Some(ranked.rank),
+            // The winners were reordered directly, so the position is stale.
+            _ => self.iter().find(|ranked| candidate == &ranked.candidate).map(|ranked| ranked.rank),
+        }
+    }
+
+    /// Clears the winners, returning all winner-rank pairs as an iterator.
+    pub fn drain<R>(&mut self, range: R) -> std::vec::Drain<'_, RankedCandidate<T>>
+    where
+        R: RangeBounds<usize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.winners.len(),
+        };
+
+        // Index the winners that are left, in their positions after the drain.
+        self.positions.clear();
+        if start <= end && end <= self.winners.len() {
+            for (position, ranked) in self.winners[..start].iter().chain(self.winners[end..].iter()).enumerate() {
+                self.positions.entry(ranked.candidate.clone()).or_insert(position);
+            }
+        }
+        self.winners.drain(start..end)
+    }
+
+    // Create winners from a list of ranked candidates that are already sorted by rank.
+    pub(crate) fn with_winners(winners: Vec<RankedCandidate<T>>, num_winners: usize) -> Self {
+        let mut ranked = RankedWinners {
+            winners: winners,
+            num_winners: num_winners,
+            positions: HashMap::new(),
+        };
+        ranked.reindex();
+        ranked
+    }
 
     // Push a new winner onto the end of of the list of winners
     // Make sure to call sort() before passing the Winners back to the user.
     pub(crate) fn push(&mut self, candidate: T, rank: usize) {
+        self.positions.entry(candidate.clone()).or_insert(self.winners.len());
         self.winners.push((candidate, rank).into());
     }
 
     // Sort the winners by rank.
     pub(crate) fn sort(&mut self) {
-        self.winners.sort_by(|a, b| a.rank.cmp(&b.rank));
+        self.winners.sort_by_key(|a| a.rank);
+        self.reindex();
+    }
+
+    // Index every winner by their position.
+    fn reindex(&mut self) {
+        self.positions.clear();
+        for (position, ranked) in self.winners.iter().enumerate() {
+            self.positions.entry(ranked.candidate.clone()).or_insert(position);
+        }
     }
 
     // Create winners from a list of ranked candidates
@@ -178,57 +423,506 @@ impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
     }
 }
 
-// Iterator for Winners
-// TODO: Use some sort of a macro to auto-generate this.
-// ---------------------
+impl<T: Clone + Eq + PartialEq> RankedWinners<T> {
+    /// Join the winners with their totals, as returned by a tally's `totals()`.
+    ///
+    /// A winner without a total is given a total of zero.
+    pub fn with_totals<C: Clone + Num>(self, totals: &[(T, C)]) -> TalliedWinners<T, C> {
+        let winners = self
+            .winners
+            .into_iter()
+            .map(|ranked| {
+                let total = totals
+                    .iter()
+                    .find(|(candidate, _)| *candidate == ranked.candidate)
+                    .map(|(_, total)| total.clone())
+                    .unwrap_or_else(C::zero);
+                TalliedCandidate {
+                    candidate: ranked.candidate,
+                    rank: ranked.rank,
+                    total: total,
+                }
+            })
+            .collect();
+        TalliedWinners {
+            winners: winners,
+            num_winners: self.num_winners,
+        }
+    }
+}
+
+/// A ranked candidate with their final total, such as their number of votes or score.
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalliedCandidate<T: Clone + Eq + PartialEq, C> {
+    /// The Candidate
+    pub candidate: T,
+
+    /// Candidate rank. A lower rank beats a higher rank. A rank of zero is the best a candidate can get.
+    pub rank: usize,
+
+    /// The candidate's final total
+    pub total: C,
+}
+
+/// `TalliedWinners` is a ranked list of winning candidates with their final totals, sorted according to rank.
+///
+/// This is [`RankedWinners`](struct.RankedWinners.html) with each winner's total joined in,
+/// as returned by a tally's `winners_with_totals()`.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(2);
+///    tally.add_weighted("Alice", 3);
+///    tally.add_weighted("Bob", 2);
+///    tally.add("Carol");
+///
+///    for winner in tally.winners_with_totals().iter() {
+///       println!("{} has a rank of {} with {} votes", winner.candidate, winner.rank, winner.total);
+///    }
+///    // Prints:
+///    //   Alice has a rank of 0 with 3 votes
+///    //   Bob has a rank of 1 with 2 votes
+/// ```
+#[derive(Debug, Eq, PartialEq, Index, IndexMut, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TalliedWinners<T: Clone + Eq + PartialEq, C> {
+    /// Ranked winners with their totals
+    #[index]
+    #[index_mut]
+    pub winners: Vec<TalliedCandidate<T, C>>,
 
-/// Iterator for winners
-pub struct IterWinners<'a, T: Clone + Eq + PartialEq> {
-    inner: &'a RankedWinners<T>,
-    pos: usize,
+    /// The number of winners requested. The number of winners may be greater than this if there is a tie.
+    pub num_winners: usize,
 }
 
-impl<'a, T: Clone + Eq + PartialEq> Iterator for IterWinners<'a, T> {
-    type Item = &'a RankedCandidate<T>;
+impl<T: Clone + Eq + PartialEq, C> TalliedWinners<T, C> {
+    /// Get the number of winners.
+    pub fn len(&self) -> usize {
+        self.winners.len()
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.inner.winners.len() {
-            None
-        } else {
-            self.pos += 1;
-            self.inner.winners.get(self.pos - 1)
+    /// Check if there are no winners.
+    pub fn is_empty(&self) -> bool {
+        self.winners.is_empty()
+    }
+
+    /// Iterate over the winners and their totals.
+    pub fn iter(&self) -> std::slice::Iter<'_, TalliedCandidate<T, C>> {
+        self.winners.iter()
+    }
+
+    /// Get the winner at a position, or `None` if the position is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&TalliedCandidate<T, C>> {
+        self.winners.get(index)
+    }
+
+    /// Get the total of a candidate, or `None` if they are not a winner.
+    pub fn total(&self, candidate: &T) -> Option<&C> {
+        self.winners
+            .iter()
+            .find(|winner| winner.candidate == *candidate)
+            .map(|winner| &winner.total)
+    }
+
+    /// Transform into a vector of winners with their totals.
+    pub fn into_vec(self) -> Vec<TalliedCandidate<T, C>> {
+        self.winners
+    }
+
+    /// Drop the totals, leaving the ranked winners.
+    pub fn into_ranked(self) -> RankedWinners<T>
+    where
+        T: Hash,
+    {
+        RankedWinners::with_winners(
+            self.winners
+                .into_iter()
+                .map(|winner| RankedCandidate {
+                    candidate: winner.candidate,
+                    rank: winner.rank,
+                })
+                .collect(),
+            self.num_winners,
+        )
+    }
+}
+
+impl<T: Clone + Eq + PartialEq, C> IntoIterator for TalliedWinners<T, C> {
+    type Item = TalliedCandidate<T, C>;
+    type IntoIter = std::vec::IntoIter<TalliedCandidate<T, C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.winners.into_iter()
+    }
+}
+
+impl<'a, T: Clone + Eq + PartialEq, C> IntoIterator for &'a TalliedWinners<T, C> {
+    type Item = &'a TalliedCandidate<T, C>;
+    type IntoIter = std::slice::Iter<'a, TalliedCandidate<T, C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.winners.iter()
+    }
+}
+
+/// The winners of a tally, together with the complete standings of every candidate.
+///
+/// Candidates that did not win keep their place in the full ranking, so the runner-up is the best ranked of them.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    tally.add_weighted("Alice", 3);
+///    tally.add_weighted("Bob", 2);
+///    tally.add("Carol");
+///
+///    let standings = tally.standings();
+///    assert_eq!(standings.runner_up(), &[("Bob", 1)]);
+///    assert_eq!(standings.losers().len(), 2);
+///    assert_eq!(standings.winners.into_unranked(), vec!["Alice"]);
+/// ```
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(deserialize = "T: serde::Deserialize<'de> + Hash")))]
+pub struct Standings<T: Clone + Eq + PartialEq> {
+    /// The winners
+    pub winners: RankedWinners<T>,
+
+    /// Every candidate, ranked from first to last. Candidates with the same rank are tied.
+    pub ranked: Vec<RankedCandidate<T>>,
+}
+
+impl<T: Clone + Eq + Hash> Standings<T> {
+    /// Create standings from the winners of a tally and its full ranking.
+    pub fn new(winners: RankedWinners<T>, ranked: Vec<RankedCandidate<T>>) -> Self {
+        Standings {
+            winners: winners,
+            ranked: ranked,
         }
     }
+
+    /// Get the candidates that did not win, in ranked order.
+    pub fn losers(&self) -> Vec<&RankedCandidate<T>> {
+        self.ranked
+            .iter()
+            .filter(|ranked| !self.winners.contains(&ranked.candidate))
+            .collect()
+    }
+
+    /// Get the runner-up: the best ranked candidates that did not win. There is more than one if they are tied.
+    ///
+    /// Empty if every candidate won.
+    pub fn runner_up(&self) -> &[RankedCandidate<T>] {
+        let first = self.ranked.iter().position(|ranked| !self.winners.contains(&ranked.candidate));
+        match first {
+            Some(first) => {
+                let rank = self.ranked[first].rank;
+                let len = self.ranked[first..].iter().take_while(|ranked| ranked.rank == rank).count();
+                &self.ranked[first..first + len]
+            }
+            None => &[],
+        }
+    }
+}
+
+/// A summary of the ballots cast in a tally: valid, blank and spoiled ballots, as official result declarations report them.
+///
+/// A spoiled ballot is one that was cast but rejected, such as an overvote or an unsigned postal ballot.
+/// Like blank ballots, spoiled ballots count towards turnout but not towards any candidate.
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BallotSummary<C> {
+    /// Total weight of valid ballots.
+    pub valid: C,
+
+    /// Total weight of blank ballots.
+    pub blank: C,
+
+    /// Total weight of spoiled ballots, for all reasons.
+    pub spoiled: C,
+
+    /// Total weight of spoiled ballots for each reason, in the order each reason was first recorded.
+    pub spoiled_reasons: Vec<(String, C)>,
+}
+
+impl<C: Clone + Num> BallotSummary<C> {
+    /// Get the total weight of all ballots cast: valid, blank and spoiled.
+    pub fn turnout(&self) -> C {
+        self.valid.clone() + self.blank.clone() + self.spoiled.clone()
+    }
+}
+
+/// Ballots that exhausted during a round of a transferable-vote tally (STV or IRV).
+///
+/// A ballot exhausts when it has no continuing candidate left to transfer to. Ballots with no preferences at all are blank, not exhausted.
+#[derive(Debug, Eq, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exhausted<C> {
+    /// Number of ballots that exhausted.
+    pub ballots: usize,
+
+    /// Total weight of the exhausted ballots, at the value they held when they exhausted.
+    pub weight: C,
+}
+
+/// A round of a transferable-vote tally (STV or IRV).
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round<T, C> {
+    /// The votes held by each continuing candidate at the start of the round.
+    pub totals: Vec<(T, C)>,
+
+    /// Candidates elected in this round.
+    pub elected: Vec<T>,
+
+    /// Candidates eliminated in this round.
+    pub eliminated: Vec<T>,
+
+    /// Ballots that exhausted when votes were transferred in this round.
+    pub exhausted: Exhausted<C>,
+}
+
+/// Votes transferred from one candidate during a round of a transferable-vote tally (STV or IRV).
+///
+/// A list of flows is the data behind a Sankey diagram of the count.
+#[derive(Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flow<T, C> {
+    /// The round in which the votes were transferred, counting from `0`.
+    pub round: usize,
+
+    /// The elected or eliminated candidate the votes were transferred from.
+    pub from: T,
+
+    /// The continuing candidate the votes were transferred to, or `None` if the ballots exhausted.
+    pub to: Option<T>,
+
+    /// The value of the votes transferred.
+    pub weight: C,
 }
 
-#[derive(Debug, Eq, PartialEq, From, Index, IndexMut, Default)]
-pub(crate) struct CountedCandidates<T: Clone + Eq, C: Copy + Num + PartialOrd>(Vec<(T, C)>);
+#[cfg_attr(not(feature = "nightly"), allow(dead_code))]
+impl<T: PartialEq, C: Clone + Num> Flow<T, C> {
+    // Add a transfer to a list of flows, merging it with any flow between the same candidates in the same round.
+    pub(crate) fn add(flows: &mut Vec<Self>, round: usize, from: &T, to: Option<&T>, weight: C)
+    where
+        T: Clone,
+    {
+        let existing = flows
+            .iter_mut()
+            .find(|flow| flow.round == round && flow.from == *from && flow.to.as_ref() == to);
+        match existing {
+            Some(flow) => flow.weight = flow.weight.clone() + weight,
+            None => flows.push(Flow {
+                round: round,
+                from: from.clone(),
+                to: to.cloned(),
+                weight: weight,
+            }),
+        }
+    }
+}
 
-impl<T: Clone + Eq, C: Copy + Num + PartialOrd> CountedCandidates<T, C> {
-    // New empty list of counted candidates
+#[cfg_attr(not(feature = "nightly"), allow(dead_code))]
+impl<C: Clone + Num> Exhausted<C> {
     pub(crate) fn new() -> Self {
+        Exhausted {
+            ballots: 0,
+            weight: C::zero(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, ballots: usize, weight: C) {
+        self.ballots += ballots;
+        self.weight = self.weight.clone() + weight;
+    }
+
+    // Sum exhausted ballots over all rounds.
+    pub(crate) fn total(rounds: &[Self]) -> Self {
+        let mut total = Self::new();
+        for round in rounds {
+            total.add(round.ballots, round.weight.clone());
+        }
+        total
+    }
+}
+
+#[cfg(feature = "csv")]
+impl<T: Clone + Eq + PartialEq + fmt::Display> RankedWinners<T> {
+    /// Write the winners as CSV, with a `rank,candidate` header. `csv`
+    ///
+    /// Ranks are numbered from `1`, as they would be in a canvass report.
+    pub fn write_csv<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record(["rank", "candidate"])?;
+        for ranked in self.iter() {
+            csv.write_record([(ranked.rank + 1).to_string(), ranked.candidate.to_string()])?;
+        }
+        csv.flush()
+    }
+}
+
+impl<T: Clone + Eq + PartialEq> IntoIterator for RankedWinners<T> {
+    type Item = RankedCandidate<T>;
+    type IntoIter = std::vec::IntoIter<RankedCandidate<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.winners.into_iter()
+    }
+}
+
+impl<'a, T: Clone + Eq + PartialEq> IntoIterator for &'a RankedWinners<T> {
+    type Item = &'a RankedCandidate<T>;
+    type IntoIter = std::slice::Iter<'a, RankedCandidate<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.winners.iter()
+    }
+}
+
+/// Winners are displayed one per line as `1. Alice`, with ranks numbered from `1`. Tied winners share a number.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(2);
+///    tally.add_weighted("Alice", 3);
+///    tally.add_weighted("Bob", 2);
+///    tally.add_weighted("Carol", 2);
+///
+///    let mut winners = tally.winners();
+///    winners.winners.sort_by_key(|r| (r.rank, r.candidate));
+///    assert_eq!(winners.to_string(), "1. Alice\n2. Bob\n2. Carol");
+/// ```
+impl<T: Clone + Eq + PartialEq + fmt::Display> fmt::Display for RankedWinners<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, ranked) in self.winners.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}. {}", ranked.rank + 1, ranked.candidate)?;
+        }
+        Ok(())
+    }
+}
+
+/// Winners are displayed one per line as `1. Alice (1,234)`, with ranks numbered from `1` and totals in brackets.
+/// Tied winners share a number. The digits of whole-number totals are grouped in thousands.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(2);
+///    tally.add_weighted("Alice", 1234);
+///    tally.add_weighted("Bob", 56);
+///    assert_eq!(tally.winners_with_totals().to_string(), "1. Alice (1,234)\n2. Bob (56)");
+/// ```
+impl<T: Clone + Eq + PartialEq + fmt::Display, C: fmt::Display> fmt::Display for TalliedWinners<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, winner) in self.winners.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "{}. {} ({})",
+                winner.rank + 1,
+                winner.candidate,
+                group_thousands(&winner.total.to_string())
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// Group the digits of a number in thousands, as in "1,234,567.5". Anything that is not a plain decimal number is left alone.
+pub(crate) fn group_thousands(number: &str) -> String {
+    let unsigned = number.strip_prefix('-').unwrap_or(number);
+    let (whole, fraction) = match unsigned.find('.') {
+        Some(point) => unsigned.split_at(point),
+        None => (unsigned, ""),
+    };
+    let is_digits = |digits: &str| digits.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction.strip_prefix('.').unwrap_or(fraction)) {
+        return number.to_string();
+    }
+
+    let mut grouped = String::with_capacity(number.len() + whole.len() / 3);
+    if unsigned.len() < number.len() {
+        grouped.push('-');
+    }
+    for (index, digit) in whole.chars().enumerate() {
+        if index > 0 && (whole.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(fraction);
+    grouped
+}
+
+/// Candidates with their tallied counts, from which both totals and a ranking can be taken.
+///
+/// Candidates with higher counts rank better, and candidates with equal counts are tied.
+/// Indexing gives candidates in the order they were pushed; the `into_*` methods sort them from highest count to lowest.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    tally.add_weighted("Alice", 3);
+///    tally.add_weighted("Bob", 2);
+///    tally.add_weighted("Carol", 2);
+///
+///    let mut tallied = tally.counted().into_tallied();
+///    tallied.sort_by_key(|t| (t.rank, t.candidate));
+///    let rows: Vec<(&str, u64, usize)> = tallied.into_iter().map(|t| (t.candidate, t.total, t.rank)).collect();
+///    assert_eq!(rows, vec![("Alice", 3, 0), ("Bob", 2, 1), ("Carol", 2, 1)]);
+/// ```
+#[derive(Debug, Eq, PartialEq, From, Index, IndexMut, Default, Clone)]
+pub struct CountedCandidates<T: Clone + Eq, C: Clone + Num + PartialOrd>(Vec<(T, C)>);
+
+impl<T: Clone + Eq, C: Clone + Num + PartialOrd> CountedCandidates<T, C> {
+    /// Create a new, empty list of counted candidates.
+    pub fn new() -> Self {
         CountedCandidates(Vec::new())
     }
 
-    /// Get the number of winners.
-    pub(crate) fn len(&self) -> usize {
+    /// Get the number of candidates.
+    pub fn len(&self) -> usize {
         self.0.len()
     }
 
+    /// Check if there are no candidates.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Transform candidates into a vector of RankedWinners.
     /// Limit the number of winners by "num_winners", returned number may be over this if there is a tie
     /// Set num_winners to `0` for no limit.
-    pub(crate) fn into_ranked(mut self, num_winners: usize) -> RankedWinners<T> {
+    pub fn into_ranked(mut self, num_winners: usize) -> RankedWinners<T>
+    where
+        T: Hash,
+    {
         let mut ranked = RankedWinners::<T>::new(num_winners);
 
-        if self.len() == 0 {
+        if self.is_empty() {
             return ranked;
         }
 
         self.sort();
 
         let mut rank = 0;
-        let mut prev = self.0[0].1;
+        let mut prev = self.0[0].1.clone();
         for (candidate, score) in self.0.drain(0..) {
             if score != prev {
                 if num_winners != 0 && ranked.len() >= num_winners {
@@ -243,15 +937,33 @@ impl<T: Clone + Eq, C: Copy + Num + PartialOrd> CountedCandidates<T, C> {
         ranked
     }
 
-    // Transform into a vector
-    pub(crate) fn into_vec(mut self) -> Vec<(T, C)> {
+    /// Transform into a vector of candidates and their counts, from highest count to lowest.
+    pub fn into_vec(mut self) -> Vec<(T, C)> {
         self.sort();
         self.0
     }
 
-    // Push a new winner onto the end of of the list of winners
-    // Make sure to call sort() before passing the Winners back to the user.
-    pub(crate) fn push(&mut self, candidate: T, count: C) {
+    /// Transform into a vector of every candidate with their count and rank, from highest count to lowest.
+    pub fn into_tallied(mut self) -> Vec<TalliedCandidate<T, C>> {
+        self.sort();
+        let mut tallied: Vec<TalliedCandidate<T, C>> = Vec::with_capacity(self.len());
+        for (candidate, count) in self.0.into_iter() {
+            let rank = match tallied.last() {
+                Some(prev) if prev.total == count => prev.rank,
+                Some(prev) => prev.rank + 1,
+                None => 0,
+            };
+            tallied.push(TalliedCandidate {
+                candidate: candidate,
+                rank: rank,
+                total: count,
+            });
+        }
+        tallied
+    }
+
+    /// Add a candidate with their count.
+    pub fn push(&mut self, candidate: T, count: C) {
         self.0.push((candidate, count));
     }
 
@@ -1,20 +1,32 @@
 use hashbrown::HashMap;
-use num_traits::{Num, NumCast};
+use num_traits::{CheckedAdd, FromPrimitive, Num, ToPrimitive};
 use petgraph::Graph;
 
+use super::cache::Cache;
 use super::condorcet::CondorcetTally;
+use super::condorcet::MajorityCycle;
+use super::condorcet::PairwiseMatrix;
 use super::errors::TallyError;
+use super::intern::{CandidateId, CandidateSet};
 use super::plurality::PluralityTally;
+use super::quorum::Quorum;
 use super::result::CountedCandidates;
 use super::result::RankedCandidate;
 use super::result::RankedWinners;
+use super::result::Standings;
+use super::shard::Shard;
+use super::MarginOfVictory;
 use super::Numeric;
+use super::TallyCheckpoint;
+use super::Truncation;
+use std::fmt::Display;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
 /// Schulze Variants.
 ///
 /// Each variant represents a different way to measure the strength of a link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Variant {
     /// Strength of a link is measured by its support. You should use this variant if you are unsure.
     ///
@@ -30,7 +42,7 @@ pub enum Variant {
     ///
     /// When the strength of the link `ef` is measured by ratio, then its strength is the ratio `N[e,f] / N[f,e]` between its support `N[e,f]` and its opposition `N[f,e]`.
     ///
-    /// If Ratio is selected, tallystick will panic if an integer count type is used in the tally. This variant should only be used with a float tally.
+    /// If Ratio is selected, tallystick will panic if an integer count type is used in the tally. This variant should only be used with a float or rational tally.
     Ratio,
 }
 
@@ -57,6 +69,29 @@ pub enum Variant {
 /// ```
 pub type DefaultSchulzeTally<T> = SchulzeTally<T, u64>;
 
+/// A schulze tally using `num_rational::Rational64` to count votes. `rational`
+///
+/// Rational counts are exact, making this a good fit for `Variant::Ratio` and for fractional vote weights.
+/// Since this is an alias, refer to [`SchulzeTally`](struct.SchulzeTally.html) for method documentation.
+///
+/// # Example
+/// ```
+///    use num_rational::Rational64;
+///    use tallystick::schulze::RationalSchulzeTally;
+///    use tallystick::schulze::Variant;
+///
+///    let mut tally = RationalSchulzeTally::new(1, Variant::Ratio);
+///    tally.add_candidates(vec!["Alice", "Bob", "Carol"]);
+///    tally.add_weighted(&vec!["Alice", "Bob", "Carol"], Rational64::new(1, 3));
+///    tally.add_weighted(&vec!["Bob", "Carol", "Alice"], Rational64::new(1, 4));
+///    tally.add_weighted(&vec!["Carol", "Alice", "Bob"], Rational64::new(1, 6));
+///
+///    let winners = tally.winners().into_unranked();
+///    assert!(winners[0] == "Alice");
+/// ```
+#[cfg(feature = "rational")]
+pub type RationalSchulzeTally<T> = SchulzeTally<T, num_rational::Rational64>;
+
 /// A generic schulze tally.
 ///
 /// Generics:schulze
@@ -80,19 +115,26 @@ pub type DefaultSchulzeTally<T> = SchulzeTally<T, u64>;
 ///    let winners = tally.winners().into_unranked();
 ///    assert!(winners[0] == "Notorious RBG");
 /// ```
+#[derive(Clone)]
 pub struct SchulzeTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     variant: Variant,
     condorcet: CondorcetTally<T, C>,
+    counted: Cache<CountedCandidates<T, C>>,
+    tie_breaking: Option<TieBreaking<C>>,
+    tbrc: Cache<Vec<T>>,
 }
 
+// The seed of the random voter hierarchy, with how to weigh a ballot when drawing voters into it.
+type TieBreaking<C> = (u64, fn(&C) -> f64);
+
 impl<T, C> SchulzeTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     /// Create a new `SchulzeTally` with the given number of winners.
     ///
@@ -105,6 +147,9 @@ where
         SchulzeTally {
             variant: variant,
             condorcet: CondorcetTally::new(num_winners),
+            counted: Cache::new(),
+            tie_breaking: None,
+            tbrc: Cache::new(),
         }
     }
 
@@ -116,9 +161,19 @@ where
         SchulzeTally {
             variant: variant,
             condorcet: CondorcetTally::with_candidates(num_winners, candidates),
+            counted: Cache::new(),
+            tie_breaking: None,
+            tbrc: Cache::new(),
         }
     }
 
+    /// Create a new `SchulzeTally` with the given number of winners, and the candidates in a [`CandidateSet`](../intern/struct.CandidateSet.html).
+    ///
+    /// See [`CondorcetTally::from_candidate_set()`](../condorcet/struct.CondorcetTally.html#method.from_candidate_set).
+    pub fn from_candidate_set(num_winners: usize, variant: Variant, candidates: &CandidateSet<T>) -> Self {
+        Self::with_candidates(num_winners, variant, candidates.candidates().to_vec())
+    }
+
     /// Make this tally an unchecked tally, forgoing vote validity checking
     ///
     /// When using an unchecked tally, all vote adding methods will return Ok(), so you may elide checking for errors.
@@ -127,6 +182,23 @@ where
         self
     }
 
+    /// Require a quorum for this tally to reach a decision.
+    ///
+    /// If the quorum is not met, [`winners()`](#method.winners) returns no winners.
+    pub fn with_quorum(mut self, quorum: Quorum<C>) -> Self {
+        self.condorcet = self.condorcet.with_quorum(quorum);
+        self
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    /// The policy is checked along with the other vote checks, so it has no effect on an [`unchecked()`](#method.unchecked) tally.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.condorcet = self.condorcet.with_truncation(truncation);
+        self
+    }
+
     /// Add a candidate to the tally.
     pub fn add_candidate(&mut self, candidate: T) {
         self.condorcet.add_candidate(candidate);
@@ -137,6 +209,14 @@ where
         self.condorcet.add_candidates(candidates);
     }
 
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    /// Once withdrawn, the candidate is unknown to the tally.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.condorcet.withdraw_candidate(candidate);
+    }
+
     /// Add a vote.
     pub fn add(&mut self, selection: &[T]) -> Result<(), TallyError> {
         self.condorcet.add(selection)
@@ -166,6 +246,74 @@ where
         self.condorcet.ranked_add_weighted(vote, weight)
     }
 
+    /// Get the id of a candidate, for adding votes with [`add_ids()`](#method.add_ids).
+    pub fn candidate_id(&self, candidate: &T) -> Option<CandidateId> {
+        self.condorcet.candidate_id(candidate)
+    }
+
+    /// Get every candidate and their id, in order of id, for translating votes to ids ahead of time.
+    pub fn candidate_ids(&self) -> Vec<(T, CandidateId)> {
+        self.condorcet.candidate_ids()
+    }
+
+    /// Add a vote given as candidate ids, in order of preference.
+    ///
+    /// See [`CondorcetTally::add_ids()`](../condorcet/struct.CondorcetTally.html#method.add_ids).
+    pub fn add_ids(&mut self, vote: &[CandidateId]) -> Result<(), TallyError> {
+        self.condorcet.add_ids(vote)
+    }
+
+    /// Add a weighted vote given as candidate ids, in order of preference.
+    pub fn add_ids_weighted(&mut self, vote: &[CandidateId], weight: C) -> Result<(), TallyError> {
+        self.condorcet.add_ids_weighted(vote, weight)
+    }
+
+    /// Add pairwise preferences directly: `weight` votes that prefer `winner` over `loser`.
+    ///
+    /// See [`CondorcetTally::add_pairwise()`](../condorcet/struct.CondorcetTally.html#method.add_pairwise).
+    pub fn add_pairwise(&mut self, winner: &T, loser: &T, weight: C) -> Result<(), TallyError> {
+        self.condorcet.add_pairwise(winner, loser, weight)
+    }
+
+    /// Add a blank ballot, for a voter who abstains.
+    ///
+    /// Blank ballots count towards turnout, but not towards any candidate.
+    pub fn add_blank(&mut self) {
+        self.condorcet.add_blank();
+    }
+
+    /// Add a weighted blank ballot.
+    pub fn add_blank_weighted(&mut self, weight: C) {
+        self.condorcet.add_blank_weighted(weight);
+    }
+
+    /// Get the total weight of blank ballots.
+    pub fn blank(&self) -> C {
+        self.condorcet.blank()
+    }
+
+    /// Check if the quorum for this tally has been met. Always true if no quorum is set.
+    pub fn quorum_met(&self) -> bool {
+        self.condorcet.quorum_met()
+    }
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// See [`CondorcetTally::merge()`](../condorcet/struct.CondorcetTally.html#method.merge).
+    pub fn merge(&mut self, other: Self) {
+        self.condorcet.merge(other.condorcet);
+    }
+
     /// Get a list of all candidates seen by this tally.
     /// Candidates are returned in no particular order.
     pub fn candidates(&self) -> Vec<T> {
@@ -196,99 +344,200 @@ where
         self.condorcet.totals()
     }
 
+    /// Get the margin of each head-to-head contest, from largest to smallest.
+    ///
+    /// See [`CondorcetTally::margins()`](../condorcet/struct.CondorcetTally.html#method.margins).
+    pub fn margins(&self) -> Vec<((T, T), C)> {
+        self.condorcet.margins()
+    }
+
+    /// Get the majority cycles that keep there from being a Condorcet winner.
+    ///
+    /// See [`CondorcetTally::cycles()`](../condorcet/struct.CondorcetTally.html#method.cycles).
+    pub fn cycles(&self) -> Vec<MajorityCycle<T, C>> {
+        self.condorcet.cycles()
+    }
+
+    /// Get total counts for this tally as a dense matrix.
+    ///
+    /// See [`CondorcetTally::matrix()`](../condorcet/struct.CondorcetTally.html#method.matrix).
+    pub fn matrix(&self) -> PairwiseMatrix<T, C> {
+        self.condorcet.matrix()
+    }
+
     /// Computes the strongest path between all candidates.
     ///
     /// This is a well-known problem in graph theory sometimes called the widest path problem.
     /// This function uses a variant of the Floyd–Warshall algorithm.
+    /// With the `rayon` feature enabled, it runs in parallel for elections with many candidates.
     ///
     /// See: [https://en.wikipedia.org/wiki/Schulze_method#Implementations](https://en.wikipedia.org/wiki/Schulze_method#Implementations)
     pub fn strongest_paths(&self) -> Vec<((T, T), C)> {
-        let zero = C::zero();
-        let mut p = HashMap::<(usize, usize), C>::new();
-        for i in self.condorcet.candidates.values() {
-            for j in self.condorcet.candidates.values() {
+        let paths = self.compute_paths();
+        let candidates = self.candidates_by_id();
+
+        let n = paths.ids.len();
+        let mut strongest = Vec::<((T, T), C)>::with_capacity(n * n);
+        for (i, candidate1) in paths.ids.iter().enumerate() {
+            for (j, candidate2) in paths.ids.iter().enumerate() {
                 if i != j {
-                    let dij = self.condorcet.running_total.get(&(*i, *j)).unwrap_or(&zero);
-                    let dji = self.condorcet.running_total.get(&(*j, *i)).unwrap_or(&zero);
+                    // Ok to index here since candidates must exist.
+                    let pair = (candidates[candidate1].clone(), candidates[candidate2].clone());
+                    strongest.push((pair, paths.strength(i, j).clone()));
+                }
+            }
+        }
 
-                    if dij > dji {
-                        let strength = match self.variant {
-                            Variant::Winning => *dij,
-                            Variant::Margin => *dij - *dji,
-                            Variant::Ratio => {
-                                if dji != &zero {
-                                    *dij / *dji
-                                } else {
-                                    C::max_value()
-                                }
-                            }
-                        };
-                        p.insert((*i, *j), strength);
-                    } else {
-                        p.insert((*i, *j), zero);
+        strongest
+    }
+
+    /// Computes the strongest path between all candidates, along with the candidates on each path.
+    ///
+    /// Beatpaths explain the result: a candidate beats another if the strongest path from them is stronger than the strongest path back.
+    /// A pair of candidates with no path between them has a strength of zero and an empty path.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::schulze::DefaultSchulzeTally;
+    ///    use tallystick::schulze::Variant;
+    ///
+    ///    let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..5 { tally.add(&vec!["Alice", "Carlos", "Bob"]); }
+    ///    for _ in 0..4 { tally.add(&vec!["Bob", "Alice", "Carlos"]); }
+    ///    for _ in 0..3 { tally.add(&vec!["Carlos", "Bob", "Alice"]); }
+    ///
+    ///    // Bob beats Alice 7 to 5, but Alice beats Carlos 9 to 3 and Carlos beats Bob 8 to 4.
+    ///    let beatpath = tally.beatpaths().into_iter().find(|b| b.from == "Alice" && b.to == "Bob").unwrap();
+    ///    assert_eq!(beatpath.path, vec!["Alice", "Carlos", "Bob"]);
+    ///    assert_eq!(beatpath.strength, 8);
+    /// ```
+    pub fn beatpaths(&self) -> Vec<Beatpath<T, C>> {
+        let paths = self.compute_paths();
+        let candidates = self.candidates_by_id();
+
+        let n = paths.ids.len();
+        let mut beatpaths = Vec::<Beatpath<T, C>>::with_capacity(n * n);
+        for from in 0..n {
+            for to in (0..n).filter(|to| *to != from) {
+                let mut path = Vec::new();
+                if paths.next[from * n + to].is_some() {
+                    path.push(from);
+                    let mut current = from;
+                    // A path never visits a candidate twice, the length limit only guards against a malformed successor table.
+                    while current != to && path.len() <= n {
+                        match paths.next[current * n + to] {
+                            Some(next) => current = next,
+                            None => break,
+                        }
+                        path.push(current);
                     }
                 }
+
+                beatpaths.push(Beatpath {
+                    from: candidates[&paths.ids[from]].clone(),
+                    to: candidates[&paths.ids[to]].clone(),
+                    strength: paths.strength(from, to).clone(),
+                    path: path.iter().map(|i| candidates[&paths.ids[*i]].clone()).collect(),
+                });
             }
         }
 
-        for i in self.condorcet.candidates.values() {
-            for j in self.condorcet.candidates.values() {
+        beatpaths
+    }
+
+    // Floyd–Warshall widest paths over candidate ids, on flat matrices indexed by position in the sorted candidate ids.
+    // Computes the strength of the strongest path between each pair of candidates,
+    // and the next candidate along each strongest path, for pairs that have one.
+    fn compute_paths(&self) -> Paths<C> {
+        // Ok to unwrap since there is one set of paths per variant.
+        self.compute_paths_for(&[self.variant]).pop().unwrap()
+    }
+
+    // Strongest paths under each of the given variants, reading the pairwise totals once for all of them.
+    fn compute_paths_for(&self, variants: &[Variant]) -> Vec<Paths<C>> {
+        let mut ids: Vec<usize> = self.condorcet.candidates.values().cloned().collect();
+        ids.sort_unstable();
+        let n = ids.len();
+
+        let zero = C::zero();
+        let mut strengths = vec![vec![zero.clone(); n * n]; variants.len()];
+        let mut next = vec![None; n * n];
+        for (i, a) in ids.iter().enumerate() {
+            for (j, b) in ids.iter().enumerate() {
                 if i != j {
-                    for k in self.condorcet.candidates.values() {
-                        if i != k && j != k {
-                            //p[j,k] := max ( p[j,k], min ( p[j,i], p[i,k] ) )
-                            let pji = p.get(&(*j, *i)).unwrap_or(&zero);
-                            let pik = p.get(&(*i, *k)).unwrap_or(&zero);
-                            let pjk = p.get(&(*j, *k)).unwrap_or(&zero);
-
-                            let min = if pji < pik { pji } else { pik };
-                            let max = if pjk > min { *pjk } else { *min };
-                            p.insert((*j, *k), max);
+                    let dij = self.condorcet.running_total.get(*a, *b).unwrap_or(&zero);
+                    let dji = self.condorcet.running_total.get(*b, *a).unwrap_or(&zero);
+
+                    if dij > dji {
+                        for (variant, strength) in variants.iter().zip(strengths.iter_mut()) {
+                            strength[i * n + j] = match variant {
+                                Variant::Winning => dij.clone(),
+                                Variant::Margin => dij.clone() - dji.clone(),
+                                Variant::Ratio => {
+                                    if dji != &zero {
+                                        dij.clone() / dji.clone()
+                                    } else {
+                                        C::max_value()
+                                    }
+                                }
+                            };
                         }
+                        next[i * n + j] = Some(j);
                     }
                 }
             }
         }
 
-        let mut strongest = Vec::<((T, T), C)>::with_capacity(self.condorcet.running_total.len());
+        strengths
+            .into_iter()
+            .map(|mut strength| {
+                let mut next = next.clone();
+                C::relax_paths(&mut strength, &mut next, n);
+                Paths {
+                    ids: ids.clone(),
+                    strength: strength,
+                    next: next,
+                }
+            })
+            .collect()
+    }
 
-        // Invert the candidate map.
+    // Invert the candidate map.
+    fn candidates_by_id(&self) -> HashMap<usize, T> {
         let mut candidates = HashMap::<usize, T>::with_capacity(self.condorcet.candidates.len());
         for (candidate, i) in self.condorcet.candidates.iter() {
             candidates.insert(*i, candidate.clone());
         }
-
-        for ((candidate1, candidate2), strength) in p.iter() {
-            // Ok to unwrap here since candidates must exist.
-            let candidate1 = candidates.get(candidate1).unwrap().clone();
-            let candidate2 = candidates.get(candidate2).unwrap().clone();
-            strongest.push(((candidate1, candidate2), *strength));
-        }
-
-        strongest
+        candidates
     }
 
+    // Strongest paths are cached, and only recomputed after votes or candidates change.
     pub(crate) fn get_counted(&self) -> CountedCandidates<T, C> {
-        let mut strongest = self.strongest_paths();
+        self.counted.get_or_compute(self.condorcet.generation, || self.compute_counted())
+    }
 
-        // Convert strongest to a hashmap
-        let mut strongest_hash = HashMap::<(T, T), C>::with_capacity(strongest.len());
-        for ((candidate_1, candidate_2), strength) in strongest.drain(..) {
-            strongest_hash.insert((candidate_1, candidate_2), strength);
-        }
+    fn compute_counted(&self) -> CountedCandidates<T, C> {
+        self.count_paths(&self.compute_paths())
+    }
+
+    // Count how many candidates each candidate beats or ties by strongest path.
+    fn count_paths(&self, paths: &Paths<C>) -> CountedCandidates<T, C> {
+        let candidates = self.candidates_by_id();
 
         // Make a little plurality tally for counting up pairwise strength competition.
         let mut running_total = PluralityTally::with_capacity(self.condorcet.num_winners, self.condorcet.candidates.len());
 
-        let zero = C::zero();
-        for ((candidate_1, candidate_2), strength_1) in strongest_hash.iter() {
-            // Cloning here is dumb, but unable to construct a key tuple otherwise
-            let strength_2 = strongest_hash.get(&(candidate_2.clone(), candidate_1.clone())).unwrap_or(&zero);
-            if strength_1 >= strength_2 {
-                running_total.add_ref(candidate_1);
-            } else {
-                // Add it with a weight of zero
-                running_total.add_weighted_ref(candidate_1, C::zero());
+        let n = paths.ids.len();
+        for (i, id) in paths.ids.iter().enumerate() {
+            // Ok to index here since candidates must exist.
+            let candidate = &candidates[id];
+            for j in (0..n).filter(|j| *j != i) {
+                if paths.strength(i, j) >= paths.strength(j, i) {
+                    running_total.add_ref(candidate);
+                } else {
+                    // Add it with a weight of zero
+                    running_total.add_weighted_ref(candidate, C::zero());
+                }
             }
         }
 
@@ -297,14 +546,193 @@ where
 
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
+    ///
+    /// With [`with_tie_breaking()`](#method.with_tie_breaking), tied candidates are ranked by the TBRC, so no candidates are tied.
     pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
-        self.get_counted().into_ranked(0).into_vec()
+        self.rank_counted(self.get_counted())
+    }
+
+    // Rank counted candidates, breaking ties if asked to.
+    fn rank_counted(&self, counted: CountedCandidates<T, C>) -> Vec<RankedCandidate<T>> {
+        let ranked = counted.into_ranked(0).into_vec();
+        match self.tie_breaking_ranking() {
+            Some(tbrc) => break_ties(ranked, &tbrc),
+            None => ranked,
+        }
+    }
+
+    /// Compare the results of every variant, to see whether the choice of variant matters for these votes.
+    ///
+    /// The strongest paths of all variants are computed in a single pass over the pairwise totals.
+    /// `Variant::Ratio` is only compared if the count type is bounded and fractional, since it would otherwise panic.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::schulze::DefaultSchulzeTally;
+    ///    use tallystick::schulze::Variant;
+    ///
+    ///    let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob", "Carlos"]);
+    ///    for _ in 0..5 { tally.add(&vec!["Alice", "Bob"])?; }
+    ///    for _ in 0..4 { tally.add(&vec!["Bob", "Carlos", "Alice"])?; }
+    ///    for _ in 0..2 { tally.add(&vec!["Carlos", "Alice"])?; }
+    ///
+    ///    let comparison = tally.compare_variants();
+    ///    assert!(comparison.winners_agree());
+    ///    assert!(comparison.rankings.iter().all(|(variant, _)| *variant != Variant::Ratio));
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn compare_variants(&self) -> VariantComparison<T> {
+        let mut variants = vec![Variant::Winning, Variant::Margin];
+        if C::fraction() && C::max_value() != C::zero() {
+            variants.push(Variant::Ratio);
+        }
+
+        let rankings: Vec<(Variant, Vec<RankedCandidate<T>>)> = variants
+            .iter()
+            .zip(self.compute_paths_for(&variants))
+            .map(|(variant, paths)| (*variant, self.rank_counted(self.count_paths(&paths))))
+            .collect();
+
+        let winners = rankings
+            .iter()
+            .map(|(variant, ranked)| {
+                let winners = RankedWinners::from_ranked(ranked.clone(), self.condorcet.num_winners);
+                (*variant, self.condorcet.turnout.decide(winners))
+            })
+            .collect();
+
+        let mut differences = Vec::new();
+        for (candidate, _) in self.condorcet.candidate_ids() {
+            let ranks: Vec<(Variant, usize)> = rankings
+                .iter()
+                .filter_map(|(variant, ranked)| ranked.iter().find(|r| r.candidate == candidate).map(|r| (*variant, r.rank)))
+                .collect();
+            if ranks.iter().any(|(_, rank)| *rank != ranks[0].1) {
+                differences.push((candidate, ranks));
+            }
+        }
+
+        VariantComparison {
+            rankings: rankings,
+            winners: winners,
+            differences: differences,
+        }
     }
 
     /// Get a ranked list of winners. Winners with the same rank are tied.
-    /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// The number of winners might be greater than the requested `num_winners` if there is a tie,
+    /// unless ties are broken with [`with_tie_breaking()`](#method.with_tie_breaking).
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
     pub fn winners(&self) -> RankedWinners<T> {
-        self.get_counted().into_ranked(self.condorcet.num_winners)
+        self.winners_n(self.condorcet.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        let winners = match self.tie_breaking {
+            Some(_) => RankedWinners::from_ranked(self.ranked(), num_winners),
+            None => self.get_counted().into_ranked(num_winners),
+        };
+        self.condorcet.turnout.decide(winners)
+    }
+
+    /// Get the tie-breaking ranking of the candidates (TBRC), from first to last.
+    ///
+    /// Voters are put in a random hierarchy, seeded by [`with_tie_breaking()`](#method.with_tie_breaking).
+    /// A candidate is ranked above another if the highest voter in the hierarchy who is not indifferent between them prefers them.
+    /// Candidates that no voter tells apart are ordered at random.
+    /// Voters are drawn into the hierarchy in proportion to the weight of their ballot,
+    /// so a ballot of weight two is twice as likely to be drawn first as a ballot of weight one. Ballots without weight are left out.
+    ///
+    /// Returns `None` if ties are not broken.
+    pub fn tie_breaking_ranking(&self) -> Option<Vec<T>> {
+        let (seed, weigh) = self.tie_breaking?;
+        Some(
+            self.tbrc
+                .get_or_compute(self.condorcet.generation, || self.compute_tie_breaking_ranking(seed, weigh)),
+        )
+    }
+
+    fn compute_tie_breaking_ranking(&self, seed: u64, weigh: fn(&C) -> f64) -> Vec<T> {
+        let mut rng = SplitMix64(seed);
+
+        // Draw the voters into a random hierarchy, each in proportion to their weight:
+        // voters are ordered by `u^(1/weight)` for a uniform random `u`, compared as `ln(u) / weight`.
+        let ballots = self.condorcet.ballots();
+        let mut keys: Vec<(f64, usize)> = Vec::with_capacity(ballots.len());
+        for (i, (_, weight)) in ballots.iter().enumerate() {
+            let u = ((rng.next() >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+            let weight = weigh(weight);
+            if weight > 0.0 {
+                keys.push((u.ln() / weight, i));
+            }
+        }
+        keys.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal).then(a.1.cmp(&b.1)));
+        let hierarchy: Vec<usize> = keys.into_iter().map(|(_, i)| i).collect();
+
+        // Candidates in order of id, with where each voter ranks them. Unranked candidates come last.
+        let mut ids: Vec<(usize, T)> = self.condorcet.candidates.iter().map(|(c, id)| (*id, c.clone())).collect();
+        ids.sort_by_key(|(id, _)| *id);
+        let position: HashMap<usize, usize> = ids.iter().enumerate().map(|(i, (id, _))| (*id, i)).collect();
+        let mut ranks = vec![vec![u32::MAX; hierarchy.len()]; ids.len()];
+        for (level, ballot) in hierarchy.iter().enumerate() {
+            for (id, rank) in ballots[*ballot].0.iter() {
+                if let Some(i) = position.get(id) {
+                    ranks[*i][level] = *rank;
+                }
+            }
+        }
+
+        let mut candidates: Vec<(Vec<u32>, u64, T)> = ids
+            .into_iter()
+            .zip(ranks)
+            .map(|((_, candidate), ranks)| (ranks, rng.next(), candidate))
+            .collect();
+        candidates.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+        candidates.into_iter().map(|(_, _, candidate)| candidate).collect()
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
+    }
+
+    /// Get the Condorcet winner: the candidate that is preferred over every other candidate in a head-to-head contest.
+    /// When there is one, Schulze always elects them.
+    ///
+    /// Returns `None` if there is no Condorcet winner.
+    pub fn condorcet_winner(&self) -> Option<T> {
+        self.condorcet.condorcet_winner()
+    }
+
+    /// Get the Condorcet loser: the candidate that every other candidate is preferred over in a head-to-head contest.
+    /// When there is one, Schulze always ranks them last.
+    ///
+    /// Returns `None` if there is no Condorcet loser.
+    pub fn condorcet_loser(&self) -> Option<T> {
+        self.condorcet.condorcet_loser()
+    }
+
+    /// Get a lower bound on the margin of victory, from the pairwise contests of the Condorcet winner.
+    ///
+    /// A Schulze tally elects the Condorcet winner, so at least [`ballots_to_add()`](../struct.MarginOfVictory.html#method.ballots_to_add)
+    /// ballots are needed to alter its winner. Fewer ballots can never change it, though more may be needed.
+    /// See [`CondorcetTally::margin_of_victory()`](../condorcet/struct.CondorcetTally.html#method.margin_of_victory).
+    ///
+    /// Returns `None` if there is no Condorcet winner.
+    pub fn margin_of_victory(&self) -> Option<MarginOfVictory<T, C>> {
+        self.condorcet.margin_of_victory()
     }
 
     /// Build a graph representing all pairwise competitions between all candidates.
@@ -333,6 +761,253 @@ where
     }
 }
 
+impl<T, C> SchulzeTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                  // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + ToPrimitive, // Vote count type
+{
+    /// Break ties with the tie-breaking ranking of the candidates (TBRC), using a random voter hierarchy seeded by `seed`.
+    ///
+    /// Candidates tied by the Schulze method are ranked by the TBRC, so [`ranked()`](#method.ranked) has no ties
+    /// and [`winners()`](#method.winners) returns exactly `num_winners` winners, if there are that many candidates.
+    /// The same seed and the same ballots, added in the same order, always break ties the same way.
+    ///
+    /// The TBRC needs the ballots themselves, so the tally records every ballot.
+    /// Returns `TallyError::InvalidConfig` if votes have already been added, since those ballots were not recorded.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::schulze::DefaultSchulzeTally;
+    ///    use tallystick::schulze::Variant;
+    ///
+    ///    let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob"]).with_tie_breaking(7)?;
+    ///    tally.add(&vec!["Alice", "Bob"])?;
+    ///    tally.add(&vec!["Bob", "Alice"])?;
+    ///
+    ///    // Alice and Bob are tied, so the first voter of the hierarchy picks the winner.
+    ///    let tbrc = tally.tie_breaking_ranking().unwrap();
+    ///    assert_eq!(tally.winners().into_unranked(), vec![tbrc[0]]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_tie_breaking(mut self, seed: u64) -> Result<Self, TallyError> {
+        if !self.condorcet.records_ballots() && self.condorcet.turnout.valid != C::zero() {
+            return Err(TallyError::InvalidConfig("tie breaking must be set before votes are added"));
+        }
+        self.tie_breaking = Some((seed, |weight| weight.to_f64().unwrap_or(0.0)));
+        self.tbrc = Cache::new();
+        self.condorcet.record_ballots();
+        Ok(self)
+    }
+}
+
+impl<T, C> SchulzeTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                 // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd, // Vote count type
+{
+    /// Add a vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any pairwise total, or the total weight of valid ballots, would overflow the count type.
+    pub fn checked_add(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        self.condorcet.checked_add(selection)
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    pub fn checked_add_weighted(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.condorcet.checked_add_weighted(selection, weight)
+    }
+
+    /// Add a ranked weighted vote, checking for overflow.
+    pub fn checked_ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        self.condorcet.checked_ranked_add_weighted(vote, weight)
+    }
+}
+
+impl<T, C> SchulzeTally<T, C>
+where
+    T: Eq + Clone + Hash + Display,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Display, // Vote count type
+{
+    /// Export the pairwise graph in the Graphviz DOT format, with the winners highlighted.
+    ///
+    /// See [`PairwiseMatrix::to_dot()`](../condorcet/struct.PairwiseMatrix.html#method.to_dot) for the layout of the graph.
+    pub fn to_dot(&self) -> String {
+        self.matrix().to_dot(&self.winners().all())
+    }
+}
+
+// The Floyd–Warshall step of `compute_paths()`: relax every path through each candidate in turn.
+// Rows are independent while a candidate is the intermediate, so with the `rayon` feature they are relaxed in parallel
+// when the count type can be shared between threads.
+trait RelaxPaths: Sized {
+    fn relax_paths(strength: &mut [Self], next: &mut [Option<usize>], n: usize);
+}
+
+impl<C: Clone + PartialOrd> RelaxPaths for C {
+    default fn relax_paths(strength: &mut [C], next: &mut [Option<usize>], n: usize) {
+        relax_paths_sequential(strength, next, n);
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<C: Clone + PartialOrd + Send + Sync> RelaxPaths for C {
+    fn relax_paths(strength: &mut [C], next: &mut [Option<usize>], n: usize) {
+        use rayon::prelude::*;
+
+        // Spawning work costs more than it saves on small elections.
+        if n < PARALLEL_CANDIDATES {
+            return relax_paths_sequential(strength, next, n);
+        }
+        for i in 0..n {
+            let through: Vec<C> = strength[i * n..(i + 1) * n].to_vec();
+            strength
+                .par_chunks_mut(n)
+                .zip(next.par_chunks_mut(n))
+                .enumerate()
+                .for_each(|(j, (row, next_row))| relax_row(i, j, &through, row, next_row));
+        }
+    }
+}
+
+// The number of candidates from which strongest paths are computed in parallel.
+#[cfg(feature = "rayon")]
+const PARALLEL_CANDIDATES: usize = 32;
+
+fn relax_paths_sequential<C: Clone + PartialOrd>(strength: &mut [C], next: &mut [Option<usize>], n: usize) {
+    for i in 0..n {
+        // Paths from i are not changed while i is the intermediate candidate, so read them from a copy while updating the other rows.
+        let through: Vec<C> = strength[i * n..(i + 1) * n].to_vec();
+        for (j, (row, next_row)) in strength.chunks_mut(n).zip(next.chunks_mut(n)).enumerate() {
+            relax_row(i, j, &through, row, next_row);
+        }
+    }
+}
+
+// Relax the paths from candidate j through candidate i.
+fn relax_row<C: Clone + PartialOrd>(i: usize, j: usize, through: &[C], row: &mut [C], next_row: &mut [Option<usize>]) {
+    if j == i {
+        return;
+    }
+    //p[j,k] := max ( p[j,k], min ( p[j,i], p[i,k] ) )
+    let pji = row[i].clone();
+    let via = next_row[i];
+    for k in 0..through.len() {
+        if k != i && k != j {
+            let min = if pji < through[k] { &pji } else { &through[k] };
+            if *min > row[k] {
+                // The path through i is stronger, so the path to k now starts the same way as the path to i.
+                row[k] = min.clone();
+                next_row[k] = via;
+            }
+        }
+    }
+}
+
+// Strongest paths between candidates, as flat matrices indexed by position in the sorted candidate ids.
+struct Paths<C> {
+    ids: Vec<usize>,
+    strength: Vec<C>,
+    next: Vec<Option<usize>>,
+}
+
+impl<C> Paths<C> {
+    fn strength(&self, from: usize, to: usize) -> &C {
+        &self.strength[from * self.ids.len() + to]
+    }
+}
+
+/// A strongest path from one candidate to another.
+///
+/// See [`SchulzeTally::beatpaths()`](struct.SchulzeTally.html#method.beatpaths).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Beatpath<T, C> {
+    /// The candidate the path starts from.
+    pub from: T,
+
+    /// The candidate the path leads to.
+    pub to: T,
+
+    /// The strength of the path: the strength of its weakest link.
+    pub strength: C,
+
+    /// The candidates along the path, from `from` to `to`, each beating the next. Empty if there is no path.
+    pub path: Vec<T>,
+}
+
+/// The results of every Schulze variant for the same votes.
+///
+/// See [`SchulzeTally::compare_variants()`](struct.SchulzeTally.html#method.compare_variants).
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantComparison<T: Clone + Eq> {
+    /// The ranking of every candidate under each variant that was compared.
+    pub rankings: Vec<(Variant, Vec<RankedCandidate<T>>)>,
+
+    /// The winners under each variant that was compared.
+    pub winners: Vec<(Variant, RankedWinners<T>)>,
+
+    /// The candidates ranked differently by some variants, in order of candidate id, with their rank under each variant.
+    pub differences: Vec<(T, Vec<(Variant, usize)>)>,
+}
+
+impl<T: Clone + Eq + Hash> VariantComparison<T> {
+    /// Whether every variant ranks every candidate the same.
+    pub fn agree(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Whether every variant elects the same winners.
+    pub fn winners_agree(&self) -> bool {
+        // Tied winners may be listed in any order, so compare ranks rather than the lists themselves.
+        self.winners.windows(2).all(|pair| {
+            let (a, b) = (&pair[0].1, &pair[1].1);
+            a.len() == b.len() && a.iter().all(|w| b.rank(&w.candidate) == Some(w.rank))
+        })
+    }
+}
+
+// Rank tied candidates by their place in the tie-breaking ranking, so that every candidate has their own rank.
+fn break_ties<T: Clone + Eq>(mut ranked: Vec<RankedCandidate<T>>, tbrc: &[T]) -> Vec<RankedCandidate<T>> {
+    let place = |candidate: &T| tbrc.iter().position(|c| c == candidate);
+    ranked.sort_by_key(|r| (r.rank, place(&r.candidate)));
+    for (rank, candidate) in ranked.iter_mut().enumerate() {
+        candidate.rank = rank;
+    }
+    ranked
+}
+
+// A small seeded random number generator (SplitMix64), so that ties are broken the same way on every platform.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl<T, C> Shard for SchulzeTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn shard(&self) -> Self {
+        SchulzeTally {
+            variant: self.variant,
+            condorcet: self.condorcet.shard(),
+            counted: Cache::new(),
+            tie_breaking: self.tie_breaking,
+            tbrc: Cache::new(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        SchulzeTally::merge(self, shard);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,29 +1019,96 @@ mod tests {
         let mut tally = DefaultSchulzeTally::<&str>::new(1, Variant::Winning);
         tally.add_candidates(vec!["Notorious RBG", "Judge Judy", "Judge Dredd", "Abe Vigoda"]);
 
-        tally.add(&vec!["Notorious RBG", "Judge Judy"])?;
-        tally.add(&vec!["Judge Dredd"])?;
-        tally.add(&vec!["Abe Vigoda", "Notorious RBG"])?;
-        tally.add(&vec!["Notorious RBG", "Judge Dredd"])?;
+        tally.add(&["Notorious RBG", "Judge Judy"])?;
+        tally.add(&["Judge Dredd"])?;
+        tally.add(&["Abe Vigoda", "Notorious RBG"])?;
+        tally.add(&["Notorious RBG", "Judge Dredd"])?;
 
         assert_eq!(tally.winners().into_unranked()[0], "Notorious RBG");
 
         Ok(())
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn schulze_parallel_paths() {
+        // Pseudo-random pairwise strengths, enough candidates to be relaxed in parallel.
+        let n = PARALLEL_CANDIDATES + 8;
+        let mut seed: u64 = 42;
+        let mut strength = Vec::with_capacity(n * n);
+        let mut next = Vec::with_capacity(n * n);
+        for i in 0..n * n {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let link = if i / n != i % n && seed >> 62 == 0 { seed >> 54 } else { 0 };
+            strength.push(link);
+            next.push(if link > 0 { Some(i % n) } else { None });
+        }
+
+        let (mut sequential, mut sequential_next) = (strength.clone(), next.clone());
+        relax_paths_sequential(&mut sequential, &mut sequential_next, n);
+        u64::relax_paths(&mut strength, &mut next, n);
+
+        assert_eq!(strength, sequential);
+        assert_eq!(next, sequential_next);
+    }
+
+    #[test]
+    fn schulze_cached() -> Result<(), TallyError> {
+        let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Margin, vec!["Alice", "Bob"]);
+        tally.add(&["Alice", "Bob"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        // Each change to the tally invalidates the cached result.
+        tally.add(&["Bob", "Alice"])?;
+        tally.add(&["Bob", "Alice"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+        tally.add_candidate("Carol");
+        tally.add_weighted(&["Carol"], 5)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Carol"]);
+        tally.withdraw_candidate(&"Carol");
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn schulze_pairwise() -> Result<(), TallyError> {
+        // The pairwise matrix of https://en.wikipedia.org/wiki/Schulze_method, loaded without ballots.
+        let candidates = vec!["A", "B", "C", "D", "E"];
+        let matrix = [
+            [0, 20, 26, 30, 22],
+            [25, 0, 16, 33, 18],
+            [19, 29, 0, 17, 24],
+            [15, 12, 28, 0, 14],
+            [23, 27, 21, 31, 0],
+        ];
+        let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
+        for (i, row) in matrix.iter().enumerate() {
+            for (j, count) in row.iter().enumerate() {
+                if i != j {
+                    tally.add_pairwise(&candidates[i], &candidates[j], *count)?;
+                }
+            }
+        }
+
+        assert_eq!(tally.ranked(), vec![("E", 0), ("A", 1), ("C", 2), ("B", 3), ("D", 4)]);
+        Ok(())
+    }
+
     #[test]
     fn schulze_wikipedia() -> Result<(), TallyError> {
         // See: https://en.wikipedia.org/wiki/Schulze_method
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["A", "B", "C", "D", "E"]);
-        tally.add_weighted(&vec!["A", "C", "B", "E", "D"], 5)?;
-        tally.add_weighted(&vec!["A", "D", "E", "C", "B"], 5)?;
-        tally.add_weighted(&vec!["B", "E", "D", "A", "C"], 8)?;
-        tally.add_weighted(&vec!["C", "A", "B", "E", "D"], 3)?;
-        tally.add_weighted(&vec!["C", "A", "E", "B", "D"], 7)?;
-        tally.add_weighted(&vec!["C", "B", "A", "D", "E"], 2)?;
-        tally.add_weighted(&vec!["D", "C", "E", "B", "A"], 7)?;
-        tally.add_weighted(&vec!["E", "B", "A", "D", "C"], 8)?;
+        tally.add_weighted(&["A", "C", "B", "E", "D"], 5)?;
+        tally.add_weighted(&["A", "D", "E", "C", "B"], 5)?;
+        tally.add_weighted(&["B", "E", "D", "A", "C"], 8)?;
+        tally.add_weighted(&["C", "A", "B", "E", "D"], 3)?;
+        tally.add_weighted(&["C", "A", "E", "B", "D"], 7)?;
+        tally.add_weighted(&["C", "B", "A", "D", "E"], 2)?;
+        tally.add_weighted(&["D", "C", "E", "B", "A"], 7)?;
+        tally.add_weighted(&["E", "B", "A", "D", "C"], 8)?;
 
         // Verify totals
         let totals = tally.totals();
@@ -434,6 +1176,17 @@ mod tests {
             }
         }
 
+        // Verify beatpaths
+        let beatpaths = tally.beatpaths();
+        assert_eq!(beatpaths.len(), 20);
+        let path = |from, to| beatpaths.iter().find(|b| b.from == from && b.to == to).unwrap();
+        assert_eq!(path("A", "B").path, vec!["A", "D", "C", "B"]);
+        assert_eq!(path("A", "B").strength, 28);
+        assert_eq!(path("C", "D").path, vec!["C", "B", "D"]);
+        assert!(beatpaths
+            .iter()
+            .all(|b| b.path.first() == Some(&b.from) && b.path.last() == Some(&b.to)));
+
         // Verify ranking
         let ranked = tally.ranked();
         assert_eq!(ranked, vec![("E", 0), ("A", 1), ("C", 2), ("B", 3), ("D", 4)]);
@@ -447,37 +1200,37 @@ mod tests {
 
         // Original scenario
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["A", "B", "C"]);
-        tally.add_weighted(&vec!["B", "C", "A"], 9)?;
-        tally.add_weighted(&vec!["C", "A", "B"], 6)?;
-        tally.add_weighted(&vec!["A", "B", "C"], 5)?;
+        tally.add_weighted(&["B", "C", "A"], 9)?;
+        tally.add_weighted(&["C", "A", "B"], 6)?;
+        tally.add_weighted(&["A", "B", "C"], 5)?;
         assert_eq!(tally.winners().into_unranked()[0], "B");
 
         // Strategic vote change fully-betraying C with winning variant - betrayal works
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["A", "B", "C"]);
-        tally.add_weighted(&vec!["B", "C", "A"], 9)?;
-        tally.add_weighted(&vec!["A", "C", "B"], 6)?;
-        tally.add_weighted(&vec!["A", "B", "C"], 5)?;
+        tally.add_weighted(&["B", "C", "A"], 9)?;
+        tally.add_weighted(&["A", "C", "B"], 6)?;
+        tally.add_weighted(&["A", "B", "C"], 5)?;
         assert_eq!(tally.winners().into_unranked()[0], "A");
 
         // Strategic vote change fully-betraying C with margin variant - betrayal works
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Margin, vec!["A", "B", "C"]);
-        tally.add_weighted(&vec!["B", "C", "A"], 9)?;
-        tally.add_weighted(&vec!["A", "C", "B"], 6)?;
-        tally.add_weighted(&vec!["A", "B", "C"], 5)?;
+        tally.add_weighted(&["B", "C", "A"], 9)?;
+        tally.add_weighted(&["A", "C", "B"], 6)?;
+        tally.add_weighted(&["A", "B", "C"], 5)?;
         assert_eq!(tally.winners().into_unranked()[0], "A");
 
         // Strategic vote change partly-betraying C with winning - betrayal works
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["A", "B", "C"]);
-        tally.add_weighted(&vec!["B", "C", "A"], 9)?;
-        tally.ranked_add_weighted(&vec![("A", 0), ("C", 0), ("B", 1)], 6)?;
-        tally.add_weighted(&vec!["A", "B", "C"], 5)?;
+        tally.add_weighted(&["B", "C", "A"], 9)?;
+        tally.ranked_add_weighted(&[("A", 0), ("C", 0), ("B", 1)], 6)?;
+        tally.add_weighted(&["A", "B", "C"], 5)?;
         assert_eq!(tally.winners().into_unranked()[0], "A");
 
         // Strategic vote change partly-betraying C with margin - betrayal fails
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Margin, vec!["A", "B", "C"]);
-        tally.add_weighted(&vec!["B", "C", "A"], 9)?;
-        tally.ranked_add_weighted(&vec![("A", 0), ("C", 0), ("B", 1)], 6)?;
-        tally.add_weighted(&vec!["A", "B", "C"], 5)?;
+        tally.add_weighted(&["B", "C", "A"], 9)?;
+        tally.ranked_add_weighted(&[("A", 0), ("C", 0), ("B", 1)], 6)?;
+        tally.add_weighted(&["A", "B", "C"], 5)?;
         assert_eq!(tally.winners().into_unranked()[0], "B");
 
         Ok(())
@@ -497,7 +1250,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -524,7 +1277,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -554,7 +1307,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -580,7 +1333,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -601,11 +1354,11 @@ mod tests {
         // See Example 5: https://arxiv.org/pdf/1804.02973.pdf
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["a", "b", "c", "d"]);
-        tally.add_weighted(&vec!["a", "b", "c", "d"], 12)?;
-        tally.add_weighted(&vec!["a", "d", "b", "c"], 6)?;
-        tally.add_weighted(&vec!["b", "c", "d", "a"], 9)?;
-        tally.add_weighted(&vec!["c", "d", "a", "b"], 15)?;
-        tally.add_weighted(&vec!["d", "b", "a", "c"], 21)?;
+        tally.add_weighted(&["a", "b", "c", "d"], 12)?;
+        tally.add_weighted(&["a", "d", "b", "c"], 6)?;
+        tally.add_weighted(&["b", "c", "d", "a"], 9)?;
+        tally.add_weighted(&["c", "d", "a", "b"], 15)?;
+        tally.add_weighted(&["d", "b", "a", "c"], 21)?;
 
         // Verify ranking - "a" and "b" are tied.
         let ranked = tally.ranked();
@@ -628,7 +1381,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -667,7 +1420,7 @@ mod tests {
     ";
 
         let votes = Cursor::new(votes_raw);
-        let mut votes = util::read_votes(votes).unwrap();
+        let mut votes = util::read_votes(votes).unwrap().votes;
 
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone());
         for (vote, weight) in votes.drain(..) {
@@ -680,7 +1433,7 @@ mod tests {
 
         // Add additional votes
         tally.add_weighted(
-            &vec![
+            &[
                 "A".to_string(),
                 "E".to_string(),
                 "F".to_string(),
@@ -719,7 +1472,7 @@ mod tests {
     D > C > A * 4";
 
         let votes = Cursor::new(votes_raw);
-        let votes = util::read_votes(votes).unwrap();
+        let votes = util::read_votes(votes).unwrap().votes;
 
         // Margin
         let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Margin, candidates.clone());
@@ -727,6 +1480,7 @@ mod tests {
             match vote {
                 util::ParsedVote::Ranked(v) => tally.ranked_add_weighted(v, *weight)?,
                 util::ParsedVote::Unranked(v) => tally.add_weighted(v, *weight)?,
+                util::ParsedVote::Scored(_) => panic!("Unexpected scored vote"),
             }
         }
         assert_eq!(tally.winners().into_unranked()[0], "A".to_string());
@@ -737,22 +1491,221 @@ mod tests {
             match vote {
                 util::ParsedVote::Ranked(v) => tally.ranked_add_weighted(v, *weight)?,
                 util::ParsedVote::Unranked(v) => tally.add_weighted(v, *weight)?,
+                util::ParsedVote::Scored(_) => panic!("Unexpected scored vote"),
             }
         }
         assert_eq!(tally.winners().into_unranked()[0], "D".to_string());
 
         // Ratio
         let votes = Cursor::new(votes_raw);
-        let votes = util::read_votes(votes).unwrap(); // reparse votes as f64
+        let votes = util::read_votes(votes).unwrap().votes; // reparse votes as f64
         let mut tally = SchulzeTally::<_, f64>::with_candidates(1, Variant::Ratio, candidates.clone());
         for (vote, weight) in votes.iter() {
             match vote {
                 util::ParsedVote::Ranked(v) => tally.ranked_add_weighted(v, *weight)?,
                 util::ParsedVote::Unranked(v) => tally.add_weighted(v, *weight)?,
+                util::ParsedVote::Scored(_) => panic!("Unexpected scored vote"),
             }
         }
         assert_eq!(tally.winners().into_unranked()[0], "B".to_string());
 
         Ok(())
     }
+
+    #[test]
+    fn schulze_bigint() -> Result<(), TallyError> {
+        use num_bigint::BigUint;
+
+        let mut tally = SchulzeTally::<&str, BigUint>::new(1, Variant::Margin);
+        tally.add_candidates(vec!["Notorious RBG", "Judge Judy", "Judge Dredd", "Abe Vigoda"]);
+
+        tally.add(&["Notorious RBG", "Judge Judy"])?;
+        tally.add(&["Judge Dredd"])?;
+        tally.add(&["Abe Vigoda", "Notorious RBG"])?;
+        tally.add_weighted(&["Notorious RBG", "Judge Dredd"], BigUint::from(u64::MAX) * 3u32)?;
+
+        assert_eq!(tally.winners().into_unranked()[0], "Notorious RBG");
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rational")]
+    fn schulze_rational() -> Result<(), TallyError> {
+        // Same as example 10, with exact rational counts.
+        let candidates = vec!["A".to_string(), "B".to_string(), "C".to_string(), "D".to_string()];
+
+        let votes_raw = "
+    A > B > C > D * 6
+    A = B * 8
+    A = C * 8
+    A = C > D * 18
+    A = C = D * 8
+    B * 40
+    C > B > D * 4
+    C > D > A * 9
+    C = D * 8
+    D > A > B * 14
+    D > B > C * 11
+    D > C > A * 4";
+
+        for (variant, winner) in [(Variant::Margin, "A"), (Variant::Winning, "D"), (Variant::Ratio, "B")] {
+            let votes = util::read_votes(Cursor::new(votes_raw)).unwrap().votes;
+            let mut tally = RationalSchulzeTally::with_candidates(1, variant, candidates.clone());
+            for (vote, weight) in votes.iter() {
+                match vote {
+                    util::ParsedVote::Ranked(v) => tally.ranked_add_weighted(v, *weight)?,
+                    util::ParsedVote::Unranked(v) => tally.add_weighted(v, *weight)?,
+                    util::ParsedVote::Scored(_) => panic!("Unexpected scored vote"),
+                }
+            }
+            assert_eq!(tally.winners().into_unranked()[0], winner.to_string());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn schulze_tie_breaking() -> Result<(), TallyError> {
+        let candidates = vec!["Alice", "Bob", "Carlos", "Dave"];
+
+        // A single voter decides every tie, whatever the seed. Nobody tells Carlos and Dave apart.
+        let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates.clone()).with_tie_breaking(1)?;
+        tally.add(&["Bob", "Alice"])?;
+        let tbrc = tally.tie_breaking_ranking().unwrap();
+        assert_eq!(tbrc[..2], ["Bob", "Alice"]);
+
+        // Alice beats Bob, Bob beats Carlos, and Carlos beats Alice. Dave is last.
+        for seed in 0..8 {
+            let mut tally = DefaultSchulzeTally::with_candidates(2, Variant::Winning, candidates.clone()).with_tie_breaking(seed)?;
+            tally.add(&["Alice", "Bob", "Carlos", "Dave"])?;
+            tally.add(&["Bob", "Carlos", "Alice", "Dave"])?;
+            tally.add(&["Carlos", "Alice", "Bob", "Dave"])?;
+
+            let tbrc = tally.tie_breaking_ranking().unwrap();
+            assert_eq!(tbrc, tally.clone().with_tie_breaking(seed)?.tie_breaking_ranking().unwrap());
+
+            // The tie is broken by the TBRC, and Dave stays last.
+            let ranked: Vec<_> = tally.ranked().into_iter().map(|r| (r.candidate, r.rank)).collect();
+            let mut expected: Vec<_> = tbrc.iter().filter(|c| **c != "Dave").copied().collect();
+            expected.push("Dave");
+            assert_eq!(ranked, expected.into_iter().zip(0..).collect::<Vec<_>>());
+
+            let winners = tally.winners();
+            assert_eq!(winners.len(), 2);
+            assert_eq!(winners.into_unranked(), tbrc[..2].to_vec());
+        }
+
+        // Without tie breaking, all three tie.
+        let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, candidates);
+        tally.add(&["Alice", "Bob", "Carlos", "Dave"])?;
+        tally.add(&["Bob", "Carlos", "Alice", "Dave"])?;
+        tally.add(&["Carlos", "Alice", "Bob", "Dave"])?;
+        assert_eq!(tally.winners().len(), 3);
+        assert_eq!(tally.tie_breaking_ranking(), None);
+
+        // Ballots added before ties are broken would be left out of the hierarchy.
+        assert_eq!(
+            tally.with_tie_breaking(1).err(),
+            Some(TallyError::InvalidConfig("tie breaking must be set before votes are added"))
+        );
+
+        // Voters are drawn into the hierarchy in proportion to their weight.
+        let mut first = 0;
+        for seed in 0..100 {
+            let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Winning, vec!["Alice", "Bob"]).with_tie_breaking(seed)?;
+            tally.add_weighted(&["Alice", "Bob"], 9)?;
+            tally.add_weighted(&["Bob", "Alice"], 1)?;
+            tally.add_weighted(&["Bob", "Alice"], 0)?;
+            if tally.tie_breaking_ranking().unwrap()[0] == "Alice" {
+                first += 1;
+            }
+        }
+        assert!(first > 80, "Alice was first in {} of 100 hierarchies", first);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rational")]
+    fn schulze_compare_variants() -> Result<(), TallyError> {
+        // Example 10 again: each variant elects a different winner.
+        let votes_raw = "
+    A > B > C > D * 6
+    A = B * 8
+    A = C * 8
+    A = C > D * 18
+    A = C = D * 8
+    B * 40
+    C > B > D * 4
+    C > D > A * 9
+    C = D * 8
+    D > A > B * 14
+    D > B > C * 11
+    D > C > A * 4";
+
+        let votes = util::read_votes(Cursor::new(votes_raw)).unwrap().votes;
+        let count = |variant: Variant| -> Result<RationalSchulzeTally<&str>, TallyError> {
+            let mut tally = RationalSchulzeTally::with_candidates(1, variant, vec!["A", "B", "C", "D"]);
+            for (vote, weight) in votes.iter() {
+                match vote {
+                    util::ParsedVote::Ranked(v) => {
+                        let v: Vec<(&str, u32)> = v.iter().map(|(c, r)| (c.as_str(), *r)).collect();
+                        tally.ranked_add_weighted(&v, *weight)?
+                    }
+                    util::ParsedVote::Unranked(v) => {
+                        let v: Vec<&str> = v.iter().map(|c| c.as_str()).collect();
+                        tally.add_weighted(&v, *weight)?
+                    }
+                    util::ParsedVote::Scored(_) => panic!("Unexpected scored vote"),
+                }
+            }
+            Ok(tally)
+        };
+        let tally = count(Variant::Winning)?;
+
+        let comparison = tally.compare_variants();
+        let winners: Vec<(Variant, Vec<&str>)> = comparison.winners.iter().map(|(v, w)| (*v, w.clone().into_unranked())).collect();
+        assert_eq!(
+            winners,
+            vec![
+                (Variant::Winning, vec!["D"]),
+                (Variant::Margin, vec!["A"]),
+                (Variant::Ratio, vec!["B"])
+            ]
+        );
+        assert!(!comparison.winners_agree());
+        assert!(!comparison.agree());
+
+        // Each variant's ranking is the one the tally gives under that variant.
+        for (variant, ranked) in comparison.rankings.iter() {
+            let mut ranked: Vec<(&str, usize)> = ranked.iter().map(|r| (r.candidate, r.rank)).collect();
+            let mut expected: Vec<(&str, usize)> = count(*variant)?.ranked().into_iter().map(|r| (r.candidate, r.rank)).collect();
+            ranked.sort();
+            expected.sort();
+            assert_eq!(ranked, expected);
+        }
+        for (candidate, ranks) in comparison.differences.iter() {
+            assert_eq!(ranks.len(), 3);
+            assert!(
+                ranks.iter().any(|(_, rank)| *rank != ranks[0].1),
+                "{} is ranked the same",
+                candidate
+            );
+        }
+        assert!(comparison.differences.iter().any(|(c, _)| *c == "A"));
+
+        // Integer counts can't be compared by ratio. A Condorcet winner wins under every variant.
+        let mut tally = DefaultSchulzeTally::with_candidates(1, Variant::Margin, vec!["Alice", "Bob", "Carlos"]);
+        tally.add(&["Alice", "Bob", "Carlos"])?;
+        tally.add(&["Alice", "Carlos", "Bob"])?;
+        tally.add(&["Bob", "Alice", "Carlos"])?;
+        let comparison = tally.compare_variants();
+        assert_eq!(comparison.rankings.len(), 2);
+        assert!(comparison.winners_agree());
+        assert!(comparison.agree());
+        assert_eq!(comparison.winners[0].1.clone().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
 }
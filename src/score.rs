@@ -1,11 +1,22 @@
-use num_traits::cast::NumCast;
+use hashbrown::{HashMap, HashSet};
+use num_traits::FromPrimitive;
 use num_traits::Num;
+use num_traits::{CheckedAdd, CheckedMul};
+use std::fmt::Display;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
+use super::errors::TallyError;
 use super::plurality::PluralityTally;
+use super::quorum::{turnout_methods, Turnout};
+use super::result::CountedCandidates;
 use super::result::RankedCandidate;
 use super::result::RankedWinners;
+use super::result::Standings;
+use super::result::TalliedWinners;
+use super::shard::Shard;
+use super::MarginOfVictory;
+use super::TallyCheckpoint;
 
 /// A score tally using `u64` integers to count votes.
 /// `DefaultScoreTally` is generally preferred over `ScoreTally`.
@@ -17,16 +28,49 @@ use super::result::RankedWinners;
 ///
 ///    // An election for Judge
 ///    let mut tally = DefaultScoreTally::<&str>::new(1);
-///    tally.add(vec![("Judge Judy", 5), ("Notorious RBG", 2)]);
-///    tally.add(vec![("Judge Dredd", 5)]);
-///    tally.add(vec![("Abe Vigoda", 6), ("Notorious RBG", 3)]);
-///    tally.add(vec![("Judge Dredd", 1), ("Notorious RBG", 4)]);
+///    tally.add(vec![("Judge Judy", 5), ("Notorious RBG", 2)])?;
+///    tally.add(vec![("Judge Dredd", 5)])?;
+///    tally.add(vec![("Abe Vigoda", 6), ("Notorious RBG", 3)])?;
+///    tally.add(vec![("Judge Dredd", 1), ("Notorious RBG", 4)])?;
 ///
 ///    let winners = tally.winners().into_unranked();
 ///    assert!(winners[0] == "Notorious RBG");
+/// # Ok::<(), tallystick::TallyError>(())
 /// ```
 pub type DefaultScoreTally<T> = ScoreTally<T, u64>;
 
+/// Specifies how a score tally ranks candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Candidates are ranked by their total score, the sum of every score they were given.
+    #[default]
+    Total,
+
+    /// Candidates are ranked by their average score, their total score divided by the number of ballots that scored them.
+    /// Ballots that do not score a candidate do not count towards their average.
+    Average,
+
+    /// Candidates are ranked by their median score, the score at which half of the ballots that scored them gave a higher score.
+    /// When a candidate's ballots split evenly, the lower of the two middle scores is their median.
+    /// Ballots that do not score a candidate do not count towards their median.
+    Median,
+}
+
+/// Specifies how a score tally scales each ballot as it is added, to blunt the advantage of voters who give only extreme scores.
+///
+/// Ballots that give no positive score are left as they are. Integer scores are rounded down when scaled, so `f64` scores are recommended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization<C> {
+    /// Scale each ballot so that its highest score is the given score, usually the top of the tally's range.
+    Max(C),
+
+    /// Scale each ballot so that its scores add up to the given total.
+    Sum(C),
+}
+
+// The lowest and highest valid scores, with how to show an out of range score in an error.
+type Range<C> = (C, C, fn(&C) -> String);
+
 /// A generic score tally.
 ///
 /// Generics:
@@ -39,26 +83,36 @@ pub type DefaultScoreTally<T> = ScoreTally<T, u64>;
 ///
 ///    // An election for Judge using floats as the score type.
 ///    let mut tally = ScoreTally::<&str, f64>::new(1);
-///    tally.add(vec![("Judge Judy", 3.5), ("Notorious RBG", 2.5)]);
-///    tally.add(vec![("Judge Dredd", 0.5)]);
-///    tally.add(vec![("Abe Vigoda", 6.1), ("Notorious RBG", 3.2)]);
-///    tally.add(vec![("Judge Dredd", 1.0), ("Notorious RBG", 4.1)]);
+///    tally.add(vec![("Judge Judy", 3.5), ("Notorious RBG", 2.5)])?;
+///    tally.add(vec![("Judge Dredd", 0.5)])?;
+///    tally.add(vec![("Abe Vigoda", 6.1), ("Notorious RBG", 3.2)])?;
+///    tally.add(vec![("Judge Dredd", 1.0), ("Notorious RBG", 4.1)])?;
 ///
 ///    let winners = tally.winners().into_unranked();
 ///    assert!(winners[0] == "Notorious RBG");
+/// # Ok::<(), tallystick::TallyError>(())
 /// ```
+#[derive(Clone)]
 pub struct ScoreTally<T, C = u64>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     plurality: PluralityTally<T, C>,
+    ratings: PluralityTally<T, C>, // Weight of ballots scoring each candidate
+    variant: Variant,
+    min_ratings: Option<C>,
+    scores: Option<HashMap<T, Vec<(C, C)>>>, // Weight of ballots giving each score to each candidate, if recorded
+    normalization: Option<Normalization<C>>,
+    turnout: Turnout<C>,
+    range: Option<Range<C>>,        // Lowest and highest valid scores, if set
+    candidates: Option<HashSet<T>>, // Known candidates, if votes are checked against them
 }
 
 impl<T, C> ScoreTally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // Vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
 {
     /// Create a new `ScoreTally` with the given number of winners.
     ///
@@ -67,6 +121,14 @@ where
     pub fn new(num_winners: usize) -> Self {
         ScoreTally {
             plurality: PluralityTally::new(num_winners),
+            ratings: PluralityTally::new(num_winners),
+            variant: Variant::Total,
+            min_ratings: None,
+            scores: None,
+            normalization: None,
+            turnout: Turnout::new(),
+            range: None,
+            candidates: None,
         }
     }
 
@@ -74,35 +136,287 @@ where
     pub fn with_capacity(num_winners: usize, expected_candidates: usize) -> Self {
         ScoreTally {
             plurality: PluralityTally::with_capacity(num_winners, expected_candidates),
+            ratings: PluralityTally::with_capacity(num_winners, expected_candidates),
+            variant: Variant::Total,
+            min_ratings: None,
+            scores: None,
+            normalization: None,
+            turnout: Turnout::new(),
+            range: None,
+            candidates: None,
         }
     }
 
-    /// Add a new vote
-    pub fn add(&mut self, mut selection: Vec<(T, C)>) {
-        for (vote, score) in selection.drain(0..) {
-            self.plurality.add_weighted(vote, score);
+    /// Create a new `ScoreTally` with the given number of winners, and the provided candidates.
+    ///
+    /// Votes are then checked against the candidates:
+    /// a vote scoring an unknown candidate is rejected with `TallyError::UnknownCandidate`,
+    /// and a vote scoring a candidate more than once with `TallyError::VoteHasDuplicateCandidates`.
+    /// Candidates without any scores are included in the totals.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::DefaultScoreTally;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut tally = DefaultScoreTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+    ///    tally.add(vec![("Alice", 5), ("Bob", 3)])?;
+    ///    assert_eq!(tally.add(vec![("Bob", 1), ("Dave", 5)]), Err(TallyError::UnknownCandidate { position: 1 }));
+    ///    assert_eq!(
+    ///        tally.add(vec![("Bob", 1), ("Bob", 5)]),
+    ///        Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+    ///    );
+    ///    assert_eq!(tally.totals()[2], ("Carol", 0));
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_candidates(num_winners: usize, candidates: Vec<T>) -> Self {
+        let mut tally = Self::with_capacity(num_winners, candidates.len());
+        tally.add_candidates(candidates);
+        tally
+    }
+
+    /// Add a candidate to the tally.
+    ///
+    /// Once a candidate has been added, votes are checked against the known candidates,
+    /// as described for [`with_candidates()`](#method.with_candidates).
+    pub fn add_candidate(&mut self, candidate: T) {
+        self.plurality.running_total.entry(candidate.clone()).or_insert_with(C::zero);
+        self.candidates.get_or_insert_with(HashSet::new).insert(candidate);
+    }
+
+    /// Add some candidates to the tally.
+    pub fn add_candidates(&mut self, candidates: Vec<T>) {
+        for candidate in candidates {
+            self.add_candidate(candidate);
         }
     }
 
-    /// Add a vote by reference.
-    pub fn add_ref(&mut self, selection: &[(T, C)]) {
-        for (vote, score) in selection {
-            self.plurality.add_weighted_ref(vote, *score);
+    /// Set how candidates are ranked. Defaults to [`Variant::Total`](enum.Variant.html#variant.Total).
+    ///
+    /// Ranking by [`Variant::Median`](enum.Variant.html#variant.Median) records the scores each candidate is given.
+    /// Set it before adding votes, since scores added earlier are not recorded.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::{DefaultScoreTally, Variant};
+    ///
+    ///    let mut tally = DefaultScoreTally::new(1).with_variant(Variant::Average).with_minimum_ratings(2);
+    ///    tally.add(vec![("Alice", 5), ("Bob", 3)])?;
+    ///    tally.add(vec![("Alice", 4), ("Bob", 4)])?;
+    ///    tally.add(vec![("Bob", 4), ("Carol", 5)])?;
+    ///
+    ///    // Bob has the highest total, Alice the highest average, and Carol has too few ratings to qualify.
+    ///    assert_eq!(tally.totals()[0], ("Bob", 11));
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    ///    assert_eq!(tally.averages(), vec![("Alice", 4), ("Bob", 3)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        if variant == Variant::Median && self.scores.is_none() {
+            self.scores = Some(HashMap::new());
         }
+        self
+    }
+
+    /// Record the scores each candidate is given, for [`distribution()`](#method.distribution).
+    ///
+    /// Each distinct score given to a candidate is kept, so scores are best drawn from a small range.
+    /// Enable this before adding votes, since scores added earlier are not recorded.
+    pub fn with_distributions(mut self) -> Self {
+        if self.scores.is_none() {
+            self.scores = Some(HashMap::new());
+        }
+        self
+    }
+
+    /// Require candidates to be scored on at least `min` ballots to qualify.
+    ///
+    /// Candidates with fewer ratings are left out of [`winners()`](#method.winners) and [`ranked()`](#method.ranked),
+    /// which keeps a candidate with a single high score from topping an average.
+    /// Weighted ballots count their weight towards a candidate's ratings.
+    pub fn with_minimum_ratings(mut self, min: C) -> Self {
+        self.min_ratings = Some(min);
+        self
+    }
+
+    /// Scale each ballot as it is added, as described by [`Normalization`](enum.Normalization.html).
+    ///
+    /// Ballots are checked against the range of scores before they are scaled, and recorded scores are the scaled scores.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::{Normalization, ScoreTally};
+    ///
+    ///    let mut tally = ScoreTally::<&str, f64>::new(1).with_normalization(Normalization::Max(10.0));
+    ///    tally.add(vec![("Alice", 10.0), ("Bob", 8.0)])?;
+    ///    tally.add(vec![("Alice", 2.0), ("Bob", 5.0)])?;
+    ///
+    ///    // The second ballot is scaled to give Bob 10 and Alice 4.
+    ///    assert_eq!(tally.totals(), vec![("Bob", 18.0), ("Alice", 14.0)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_normalization(mut self, normalization: Normalization<C>) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    // Scale the scores of a ballot according to the normalization, if there is one.
+    fn normalize(&self, selection: &mut [(T, C)]) {
+        let zero = C::zero();
+        let (target, scale) = match &self.normalization {
+            Some(Normalization::Max(target)) => {
+                let mut max = zero.clone();
+                for (_, score) in selection.iter() {
+                    if *score > max {
+                        max = score.clone();
+                    }
+                }
+                (target, max)
+            }
+            Some(Normalization::Sum(target)) => {
+                let mut sum = zero.clone();
+                for (_, score) in selection.iter() {
+                    sum += score.clone();
+                }
+                (target, sum)
+            }
+            None => return,
+        };
+        if scale <= zero {
+            return;
+        }
+        for (_, score) in selection.iter_mut() {
+            *score = score.clone() * target.clone() / scale.clone();
+        }
+    }
+
+    /// Add a new vote
+    ///
+    /// The vote is checked with [`check_vote()`](#method.check_vote), and the tally is left unchanged if it is not valid.
+    pub fn add(&mut self, selection: Vec<(T, C)>) -> Result<(), TallyError> {
+        self.add_weighted(selection, C::one())
+    }
+
+    /// Add a vote by reference.
+    pub fn add_ref(&mut self, selection: &[(T, C)]) -> Result<(), TallyError> {
+        self.add_weighted_ref(selection, C::one())
     }
 
     /// Add a weighted vote.
     /// By default takes a weight as a `usize` integer, but can be customized by using `ApprovalTally` with a custom vote type.
-    pub fn add_weighted(&mut self, mut selection: Vec<(T, C)>, weight: C) {
-        for (vote, score) in selection.drain(0..) {
-            self.plurality.add_weighted(vote, weight * score);
-        }
+    pub fn add_weighted(&mut self, mut selection: Vec<(T, C)>, weight: C) -> Result<(), TallyError> {
+        self.check_vote(&selection)?;
+        self.normalize(&mut selection);
+        self.add_normalized(&selection, weight);
+        Ok(())
     }
 
     /// Add a weighted vote by reference.
-    pub fn add_weighted_ref(&mut self, selection: &[(T, C)], weight: C) {
+    pub fn add_weighted_ref(&mut self, selection: &[(T, C)], weight: C) -> Result<(), TallyError> {
+        if self.normalization.is_some() {
+            return self.add_weighted(selection.to_vec(), weight);
+        }
+        self.check_vote(selection)?;
+        self.add_normalized(selection, weight);
+        Ok(())
+    }
+
+    /// Check the validity of a vote, without adding it.
+    ///
+    /// If candidates have been added to the tally, a vote must score only known candidates, each at most once.
+    /// If a range has been set with [`with_range()`](#method.with_range), every score in the vote must be within it.
+    /// Otherwise every vote is valid.
+    pub fn check_vote(&self, selection: &[(T, C)]) -> Result<(), TallyError> {
+        if let Some(candidates) = &self.candidates {
+            for (position, (candidate, _)) in selection.iter().enumerate() {
+                if !candidates.contains(candidate) {
+                    return Err(TallyError::UnknownCandidate { position });
+                }
+            }
+            crate::util::check_duplicates_ranked_vote(selection)?;
+        }
+        if let Some((min, max, show)) = &self.range {
+            for (position, (_, score)) in selection.iter().enumerate() {
+                if score < min || score > max {
+                    return Err(TallyError::ScoreOutOfRange {
+                        position: position,
+                        score: show(score),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Add a weighted vote whose scores have already been normalized.
+    fn add_normalized(&mut self, selection: &[(T, C)], weight: C) {
+        self.turnout.add_valid(&weight);
         for (vote, score) in selection {
-            self.plurality.add_weighted_ref(vote, weight * *score);
+            self.record(vote, score, weight.clone());
+            self.ratings.add_to_total_ref(vote, weight.clone());
+            self.plurality.add_to_total_ref(vote, weight.clone() * score.clone());
+        }
+    }
+
+    // Record the weight of a ballot giving a score to a candidate, if scores are being recorded.
+    fn record(&mut self, candidate: &T, score: &C, weight: C) {
+        if let Some(scores) = &mut self.scores {
+            let distribution = scores.entry(candidate.clone()).or_insert_with(Vec::new);
+            match distribution.iter_mut().find(|(recorded, _)| recorded == score) {
+                Some((_, total)) => *total += weight,
+                None => distribution.push((score.clone(), weight)),
+            }
+        }
+    }
+
+    turnout_methods!(winners);
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// The other tally's number of winners, quorum and minimum support are ignored.
+    pub fn merge(&mut self, other: Self) {
+        self.turnout.merge(other.turnout);
+        self.plurality.merge(other.plurality);
+        self.ratings.merge(other.ratings);
+        if let Some(other) = other.candidates {
+            self.candidates.get_or_insert_with(HashSet::new).extend(other);
+        }
+        if let (Some(scores), Some(other)) = (&mut self.scores, other.scores) {
+            for (candidate, distribution) in other {
+                for (score, weight) in distribution {
+                    let recorded = scores.entry(candidate.clone()).or_insert_with(Vec::new);
+                    match recorded.iter_mut().find(|(recorded, _)| *recorded == score) {
+                        Some((_, total)) => *total += weight,
+                        None => recorded.push((score, weight)),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot. Ballots keep their scores of other candidates.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        self.plurality.withdraw_candidate(candidate);
+        self.ratings.withdraw_candidate(candidate);
+        if let Some(scores) = &mut self.scores {
+            scores.remove(candidate);
+        }
+        if let Some(candidates) = &mut self.candidates {
+            candidates.remove(candidate);
         }
     }
 
@@ -114,9 +428,30 @@ where
 
     /// Get a ranked list of winners. Winners with the same rank are tied.
     /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
     /// In score voting, the winning candidate(s) is the one with the highest total score.
     pub fn winners(&self) -> RankedWinners<T> {
-        self.plurality.winners()
+        self.winners_n(self.plurality.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.turnout.decide(RankedWinners::from_ranked(self.ranking(), num_winners))
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
     }
 
     /// Get vote totals for this tally.
@@ -128,8 +463,8 @@ where
     ///    use tallystick::score::DefaultScoreTally;
     ///
     ///    let mut tally = DefaultScoreTally::new(1);
-    ///    tally.add(vec![("Alice", 30), ("Bob", 10)]);
-    ///    tally.add(vec![("Bob", 10), ("Carol", 5)]);
+    ///    tally.add(vec![("Alice", 30), ("Bob", 10)])?;
+    ///    tally.add(vec![("Bob", 10), ("Carol", 5)])?;
     ///
     ///    for (candidate, score) in tally.totals().iter() {
     ///       println!("{} got a score of {}", candidate, score);
@@ -138,11 +473,48 @@ where
     ///    //   Alice got a score of 30
     ///    //   Bob got a score of 20
     ///    //   Carol got a score of 5
+    /// # Ok::<(), tallystick::TallyError>(())
     /// ```
     pub fn totals(&self) -> Vec<(T, C)> {
         self.plurality.totals()
     }
 
+    /// Get a ranked list of winners with their total scores. Winners with the same rank are tied.
+    ///
+    /// This is [`winners()`](#method.winners) joined with [`totals()`](#method.totals).
+    pub fn winners_with_totals(&self) -> TalliedWinners<T, C> {
+        self.winners().with_totals(&self.totals())
+    }
+
+    /// Get the margin of victory: the lead in total score of the last winner over the runner-up.
+    ///
+    /// `max_score` is the highest score a ballot can give a candidate. A single ballot can close the margin by at most
+    /// `max_score` when added, by scoring only the runner-up, and by twice that when changed, by swapping the two candidates' scores.
+    ///
+    /// Returns `None` if there are no more candidates than seats, so every candidate wins,
+    /// or if candidates are not ranked by [`Variant::Total`](enum.Variant.html#variant.Total), since a ballot can move an average or median by any amount.
+    /// The minimum number of ratings is not taken into account.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::DefaultScoreTally;
+    ///
+    ///    let mut tally = DefaultScoreTally::new(1);
+    ///    tally.add(vec![("Alice", 5), ("Bob", 2)])?;
+    ///    tally.add(vec![("Alice", 4), ("Bob", 5)])?;
+    ///
+    ///    let margin = tally.margin_of_victory(5).unwrap();
+    ///    assert_eq!(margin.margin, 2);
+    ///    assert_eq!(margin.ballots_to_add(), 1);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn margin_of_victory(&self, max_score: C) -> Option<MarginOfVictory<T, C>> {
+        if self.variant != Variant::Total {
+            return None;
+        }
+        Some(self.plurality.margin_of_victory()?.with_per_ballot(max_score))
+    }
+
     /// Get a ranked list of all candidates. Candidates with the same rank are tied.
     /// Candidates are ranked in ascending order. The highest ranked candidate has a rank of `0`.
     ///
@@ -151,8 +523,8 @@ where
     ///    use tallystick::score::DefaultScoreTally;
     ///
     ///    let mut tally = DefaultScoreTally::new(1);
-    ///    tally.add(vec![("Alice", 30), ("Bob", 10)]);
-    ///    tally.add(vec![("Bob", 10), ("Carol", 5)]);
+    ///    tally.add(vec![("Alice", 30), ("Bob", 10)])?;
+    ///    tally.add(vec![("Bob", 10), ("Carol", 5)])?;
     ///    
     ///    for ranked in tally.ranked().iter() {
     ///       println!("{} has a rank of {}", ranked.candidate, ranked.rank);
@@ -161,9 +533,293 @@ where
     ///    //   Alice has a rank of 0
     ///    //   Bob has a rank of 1
     ///    //   Carol has a rank of 2
+    /// # Ok::<(), tallystick::TallyError>(())
     /// ```
     pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
-        self.plurality.ranked()
+        self.ranking()
+    }
+
+    /// Get the weight of ballots scoring each candidate, from most ratings to fewest.
+    pub fn ratings(&self) -> Vec<(T, C)> {
+        self.ratings.totals()
+    }
+
+    /// Get the average score of each qualifying candidate, from highest average to lowest.
+    ///
+    /// Averages are divided in the score type, so are rounded down for integer scores. Use `f64` for exact averages.
+    /// Candidates are ordered by their exact average either way.
+    pub fn averages(&self) -> Vec<(T, C)> {
+        let zero = C::zero();
+        self.averaged()
+            .into_iter()
+            .map(|(candidate, total, ratings)| {
+                let average = if ratings == zero { zero.clone() } else { total / ratings };
+                (candidate, average)
+            })
+            .collect()
+    }
+
+    /// Get the distribution of scores a candidate was given: each score with the weight of ballots giving it, from lowest score to highest.
+    ///
+    /// Ballots that do not score the candidate are not included. The distribution is empty if scores are not recorded,
+    /// as set by [`with_distributions()`](#method.with_distributions), or the candidate was never scored.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::DefaultScoreTally;
+    ///
+    ///    let mut tally = DefaultScoreTally::new(1).with_distributions();
+    ///    tally.add(vec![("Alice", 5), ("Bob", 2)])?;
+    ///    tally.add(vec![("Alice", 3)])?;
+    ///    tally.add_weighted(vec![("Alice", 5), ("Bob", 0)], 2)?;
+    ///
+    ///    assert_eq!(tally.distribution(&"Alice"), vec![(3, 1), (5, 3)]);
+    ///    assert_eq!(tally.distribution(&"Bob"), vec![(0, 2), (2, 1)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn distribution(&self, candidate: &T) -> Vec<(C, C)> {
+        let mut distribution = match self.scores.as_ref().and_then(|scores| scores.get(candidate)) {
+            Some(distribution) => distribution.clone(),
+            None => return Vec::new(),
+        };
+        distribution.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        distribution
+    }
+
+    /// Get the median score of each qualifying candidate, from highest median to lowest.
+    ///
+    /// Medians are only known when the tally records the scores each candidate is given, by ranking candidates by
+    /// [`Variant::Median`](enum.Variant.html#variant.Median) or with [`with_distributions()`](#method.with_distributions),
+    /// and are otherwise empty.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::{DefaultScoreTally, Variant};
+    ///
+    ///    let mut tally = DefaultScoreTally::new(1).with_variant(Variant::Median);
+    ///    tally.add(vec![("Alice", 5), ("Bob", 3)])?;
+    ///    tally.add(vec![("Alice", 0), ("Bob", 3)])?;
+    ///    tally.add(vec![("Alice", 5), ("Bob", 4)])?;
+    ///    tally.add(vec![("Alice", 1), ("Bob", 4)])?;
+    ///
+    ///    // Alice has the higher total, but half of her ballots score her 1 or less.
+    ///    assert_eq!(tally.medians(), vec![("Bob", 3), ("Alice", 1)]);
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn medians(&self) -> Vec<(T, C)> {
+        self.get_medians().into_vec()
+    }
+
+    // Get the median score of each qualifying candidate with recorded scores.
+    fn get_medians(&self) -> CountedCandidates<T, C> {
+        let zero = C::zero();
+        let mut medians = CountedCandidates::new();
+        if let Some(scores) = &self.scores {
+            for (candidate, distribution) in scores.iter() {
+                if !self.qualifies(self.ratings.running_total.get(candidate).unwrap_or(&zero)) {
+                    continue;
+                }
+                if let Some(median) = median(distribution) {
+                    medians.push(candidate.clone(), median);
+                }
+            }
+        }
+        medians
+    }
+
+    // Get each qualifying candidate with their total score and ratings, from highest average to lowest.
+    fn averaged(&self) -> Vec<(T, C, C)> {
+        let zero = C::zero();
+        let mut averaged: Vec<(T, C, C)> = Vec::new();
+        for (candidate, total) in self.plurality.running_total.iter() {
+            let ratings = self.ratings.running_total.get(candidate).unwrap_or(&zero).clone();
+            if self.qualifies(&ratings) && ratings != zero {
+                averaged.push((candidate.clone(), total.clone(), ratings));
+            }
+        }
+        // Compare averages exactly, as a / b > c / d when a * d > c * b
+        averaged.sort_by(|(_, a, b), (_, c, d)| {
+            (c.clone() * b.clone())
+                .partial_cmp(&(a.clone() * d.clone()))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        averaged
+    }
+
+    // Check if a candidate with the given ratings is scored on enough ballots to qualify.
+    fn qualifies(&self, ratings: &C) -> bool {
+        match &self.min_ratings {
+            Some(min) => ratings >= min,
+            None => true,
+        }
+    }
+
+    // Rank the qualifying candidates according to the variant.
+    fn ranking(&self) -> Vec<RankedCandidate<T>> {
+        match self.variant {
+            Variant::Total => {
+                let zero = C::zero();
+                let mut counted = CountedCandidates::new();
+                for (candidate, total) in self.plurality.running_total.iter() {
+                    if self.qualifies(self.ratings.running_total.get(candidate).unwrap_or(&zero)) {
+                        counted.push(candidate.clone(), total.clone());
+                    }
+                }
+                counted.into_ranked(0).into_vec()
+            }
+            Variant::Average => {
+                let mut ranked: Vec<RankedCandidate<T>> = Vec::new();
+                let mut prev: Option<(C, C)> = None;
+                let mut rank = 0;
+                for (candidate, total, ratings) in self.averaged() {
+                    if let Some((prev_total, prev_ratings)) = prev {
+                        if prev_total * ratings.clone() != total.clone() * prev_ratings {
+                            rank += 1;
+                        }
+                    }
+                    ranked.push(RankedCandidate {
+                        candidate: candidate,
+                        rank: rank,
+                    });
+                    prev = Some((total, ratings));
+                }
+                ranked
+            }
+            Variant::Median => self.get_medians().into_ranked(0).into_vec(),
+        }
+    }
+
+    /// Get each candidate with their total score, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, C> {
+        self.plurality.counted()
+    }
+}
+
+impl<T, C> ScoreTally<T, C>
+where
+    T: Eq + Clone + Hash,                                              // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Display, // Vote count type
+{
+    /// Limit the scores a ballot may give to the range from `min` to `max`, inclusive.
+    ///
+    /// Votes that give any candidate a score outside the range are rejected with `TallyError::ScoreOutOfRange`,
+    /// which gives the position of the candidate in the vote and the offending score.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::score::DefaultScoreTally;
+    ///    use tallystick::TallyError;
+    ///
+    ///    let mut tally = DefaultScoreTally::new(1).with_range(0, 10);
+    ///    tally.add(vec![("Alice", 10), ("Bob", 0)])?;
+    ///
+    ///    let error = tally.add(vec![("Alice", 5), ("Bob", 11)]).unwrap_err();
+    ///    assert_eq!(error, TallyError::ScoreOutOfRange { position: 1, score: "11".to_string() });
+    ///    assert_eq!(error.to_string(), "tallystick: vote contains out of range score 11 at position 1");
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_range(mut self, min: C, max: C) -> Self {
+        self.range = Some((min, max, |score| score.to_string()));
+        self
+    }
+}
+
+impl<T, C> ScoreTally<T, C>
+where
+    T: Eq + Clone + Hash,                                                                        // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd + CheckedMul + Display, // Vote count type
+{
+    /// Add a new vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any candidate's total score, or the total weight of valid ballots, would overflow the count type.
+    pub fn checked_add(&mut self, selection: Vec<(T, C)>) -> Result<(), TallyError> {
+        self.checked_add_weighted(selection, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if any weighted score, any candidate's total score, or the total weight of valid ballots would overflow the count type.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, selection: Vec<(T, C)>, weight: C) -> Result<(), TallyError> {
+        self.checked_add_weighted_ref(&selection, weight)
+    }
+
+    /// Add a weighted vote by reference, checking for overflow.
+    ///
+    /// The vote is also checked with [`check_vote()`](#method.check_vote).
+    pub fn checked_add_weighted_ref(&mut self, selection: &[(T, C)], weight: C) -> Result<(), TallyError> {
+        self.check_vote(selection)?;
+        if self.normalization.is_some() {
+            let mut normalized = selection.to_vec();
+            self.normalize(&mut normalized);
+            self.check_add(&normalized, &weight)?;
+            self.add_normalized(&normalized, weight);
+        } else {
+            self.check_add(selection, &weight)?;
+            self.add_normalized(selection, weight);
+        }
+        Ok(())
+    }
+
+    // Check that adding a weighted ballot of already normalized scores does not overflow any count.
+    fn check_add(&self, selection: &[(T, C)], weight: &C) -> Result<(), TallyError> {
+        let mut points = Vec::with_capacity(selection.len());
+        for (vote, score) in selection {
+            points.push((vote, weight.checked_mul(score).ok_or(TallyError::CountOverflow)?));
+        }
+        self.plurality.check_add_ballot(points)?;
+        self.ratings
+            .check_add_ballot(selection.iter().map(|(vote, _)| (vote, weight.clone())))?;
+        self.turnout.check_add_valid(weight)
+    }
+}
+
+// Get the lower median score of a distribution of scores and their weights, or `None` if it has no weight.
+fn median<C>(distribution: &[(C, C)]) -> Option<C>
+where
+    C: Clone + PartialOrd + AddAssign + Num, // Vote count type
+{
+    let mut sorted = distribution.to_vec();
+    sorted.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mut total = C::zero();
+    for (_, weight) in sorted.iter() {
+        total += weight.clone();
+    }
+    if total == C::zero() {
+        return None;
+    }
+    let mut cumulative = C::zero();
+    for (score, weight) in sorted {
+        cumulative += weight;
+        if cumulative.clone() + cumulative.clone() >= total {
+            return Some(score);
+        }
+    }
+    None
+}
+
+impl<T, C> Shard for ScoreTally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // Vote count type
+{
+    fn shard(&self) -> Self {
+        ScoreTally {
+            plurality: self.plurality.shard(),
+            ratings: self.ratings.shard(),
+            variant: self.variant,
+            min_ratings: self.min_ratings.clone(),
+            scores: self.scores.as_ref().map(|_| HashMap::new()),
+            normalization: self.normalization.clone(),
+            turnout: self.turnout.shard(),
+            range: self.range.clone(),
+            candidates: self.candidates.clone(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        ScoreTally::merge(self, shard);
     }
 }
 
@@ -172,11 +828,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn score_basic() {
+    fn score_basic() -> Result<(), TallyError> {
         let mut tally = ScoreTally::new(1);
-        tally.add(vec![("Alice", 10), ("Bob", 4)]);
-        tally.add_ref(&vec![("Alice", 2), ("Bob", 2)]);
-        tally.add_weighted_ref(&vec![("Alice", 1), ("Bob", 1)], 5);
+        tally.add(vec![("Alice", 10), ("Bob", 4)])?;
+        tally.add_ref(&[("Alice", 2), ("Bob", 2)])?;
+        tally.add_weighted_ref(&[("Alice", 1), ("Bob", 1)], 5)?;
 
         let candidates = tally.candidates();
         assert_eq!(candidates.len(), 2);
@@ -188,21 +844,228 @@ mod tests {
         assert_eq!(ranked, vec![("Alice", 0), ("Bob", 1)]);
 
         let winners = tally.winners();
-        assert_eq!(winners.is_empty(), false);
-        assert_eq!(winners.check_overflow(), false);
+        assert!(!winners.is_empty());
+        assert!(!winners.check_overflow());
         assert_eq!(winners.overflow(), Option::None);
         assert_eq!(winners.all(), vec!["Alice"]);
+        Ok(())
+    }
+
+    #[test]
+    fn score_checked() {
+        let mut tally = ScoreTally::<&str, u8>::new(1);
+        tally.checked_add(vec![("Alice", 100), ("Bob", 50)]).unwrap();
+        assert_eq!(tally.checked_add_weighted(vec![("Bob", 10)], 30), Err(TallyError::CountOverflow));
+        assert_eq!(tally.checked_add(vec![("Bob", 1), ("Alice", 200)]), Err(TallyError::CountOverflow));
+        tally.checked_add_weighted(vec![("Bob", 10)], 20).unwrap();
+        assert_eq!(tally.totals(), vec![("Bob", 250), ("Alice", 100)]);
+
+        // Scores for a repeated candidate are summed before they are checked.
+        assert_eq!(
+            tally.checked_add(vec![("Alice", 100), ("Alice", 100)]),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(
+            tally.checked_add(vec![("Dave", 200), ("Dave", 100)]),
+            Err(TallyError::CountOverflow)
+        );
+        assert_eq!(tally.totals(), vec![("Bob", 250), ("Alice", 100)]);
+    }
+
+    #[test]
+    fn score_range() -> Result<(), TallyError> {
+        let mut tally = ScoreTally::<&str, i64>::new(1).with_range(-2, 2);
+        tally.checked_add(vec![("Alice", 2), ("Bob", -2)])?;
+        assert_eq!(
+            tally.checked_add(vec![("Alice", 1), ("Bob", -3)]),
+            Err(TallyError::ScoreOutOfRange {
+                position: 1,
+                score: "-3".to_string()
+            })
+        );
+
+        // The range applies to scores, not weighted scores, and a rejected vote leaves the tally unchanged.
+        tally.checked_add_weighted(vec![("Bob", 2)], 3)?;
+        assert_eq!(tally.totals(), vec![("Bob", 4), ("Alice", 2)]);
+
+        // Votes added without checking for overflow are checked against the range too.
+        assert_eq!(tally.add(vec![("Alice", 3)]).unwrap_err().position(), Some(0));
+        assert_eq!(
+            tally.add_weighted_ref(&[("Bob", 0), ("Alice", -5)], 2).unwrap_err().position(),
+            Some(1)
+        );
+        assert_eq!(tally.totals(), vec![("Bob", 4), ("Alice", 2)]);
+
+        // Votes can be checked without being added, and scores that cannot be checked for overflow are checked when added.
+        let mut tally = ScoreTally::<&str, f64>::new(1).with_range(0.0, 5.0);
+        assert_eq!(tally.check_vote(&[("Alice", 5.0)]), Ok(()));
+        assert_eq!(tally.check_vote(&[("Alice", 5.5)]).unwrap_err().position(), Some(0));
+        assert_eq!(tally.add_weighted(vec![("Alice", 5.5)], 2.0).unwrap_err().position(), Some(0));
+        tally.add(vec![("Alice", 5.0)])?;
+        assert_eq!(tally.totals(), vec![("Alice", 5.0)]);
+
+        // Without a range, any score is accepted.
+        let mut tally = DefaultScoreTally::new(1);
+        tally.checked_add(vec![("Alice", 1000)])?;
+        Ok(())
+    }
+
+    #[test]
+    fn score_candidates() -> Result<(), TallyError> {
+        let mut tally = DefaultScoreTally::with_candidates(1, vec!["Alice", "Bob"]).with_range(0, 5);
+        tally.checked_add(vec![("Alice", 4)])?;
+
+        // Candidates are checked before scores, and a rejected vote leaves the tally unchanged.
+        assert_eq!(
+            tally.checked_add(vec![("Alice", 9), ("Carol", 1)]),
+            Err(TallyError::UnknownCandidate { position: 1 })
+        );
+        assert_eq!(tally.totals(), vec![("Alice", 4), ("Bob", 0)]);
+
+        // Withdrawn candidates can no longer be scored, and merged candidates can.
+        let mut shard = tally.shard();
+        shard.add_candidate("Carol");
+        tally.merge(shard);
+        tally.withdraw_candidate(&"Alice");
+        tally.checked_add(vec![("Carol", 3), ("Bob", 2)])?;
+        assert_eq!(tally.check_vote(&[("Alice", 1)]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(tally.totals(), vec![("Carol", 3), ("Bob", 2)]);
+
+        // Votes added without checking for overflow are checked against the candidates too.
+        let mut tally = ScoreTally::<&str, f64>::with_candidates(1, vec!["Alice", "Bob"]);
+        assert_eq!(tally.add(vec![("Carol", 1.0)]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(
+            tally.add_weighted_ref(&[("Bob", 1.0), ("Bob", 2.0)], 2.0),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        tally.add_ref(&[("Bob", 1.5)])?;
+        assert_eq!(tally.candidates().len(), 2);
+        assert_eq!(tally.totals()[0], ("Bob", 1.5));
+        Ok(())
+    }
+
+    #[test]
+    fn score_distributions() -> Result<(), TallyError> {
+        let mut tally = ScoreTally::<&str, f64>::new(1).with_distributions();
+        tally.add(vec![("Alice", 0.5), ("Bob", 1.0)])?;
+        tally.add_weighted(vec![("Alice", 1.0), ("Bob", 0.75)], 1.5)?;
+
+        let mut shard = tally.shard();
+        shard.add(vec![("Alice", 0.5)])?;
+        tally.merge(shard);
+
+        assert_eq!(tally.distribution(&"Alice"), vec![(0.5, 2.0), (1.0, 1.5)]);
+        assert_eq!(tally.distribution(&"Carol"), vec![]);
+
+        // The tally is still ranked by total score, and medians can be taken from the distributions.
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.medians(), vec![("Bob", 0.75), ("Alice", 0.5)]);
+
+        tally.withdraw_candidate(&"Alice");
+        assert_eq!(tally.distribution(&"Alice"), vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn score_average() -> Result<(), TallyError> {
+        let mut tally = ScoreTally::<&str, f64>::new(1).with_variant(Variant::Average);
+        tally.add(vec![("Alice", 4.0), ("Bob", 5.0)])?;
+        tally.add_weighted(vec![("Alice", 5.0), ("Carol", 1.0)], 3.0)?;
+        tally.add(vec![("Bob", 4.5)])?;
+
+        let mut averages = tally.averages();
+        averages[0..2].sort_by_key(|(candidate, _)| *candidate);
+        assert_eq!(averages, vec![("Alice", 4.75), ("Bob", 4.75), ("Carol", 1.0)]);
+        assert_eq!(tally.ratings()[0], ("Alice", 4.0));
+        let mut winners = tally.winners().into_unranked();
+        winners.sort();
+        assert_eq!(winners, vec!["Alice", "Bob"]);
+        assert_eq!(tally.margin_of_victory(5.0), None);
+
+        // Bob is rated on too few ballots to qualify, while Carol's weighted ballot counts three times.
+        let tally = tally.with_minimum_ratings(3.0);
+        assert_eq!(tally.ranked(), vec![("Alice", 0), ("Carol", 1)]);
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        // Integer averages are rounded down, but ranked exactly.
+        let mut tally = DefaultScoreTally::new(1).with_variant(Variant::Average);
+        tally.add(vec![("Alice", 3), ("Bob", 3)])?;
+        tally.add(vec![("Alice", 4), ("Bob", 3)])?;
+        assert_eq!(tally.averages(), vec![("Alice", 3), ("Bob", 3)]);
+        assert_eq!(tally.ranked(), vec![("Alice", 0), ("Bob", 1)]);
+
+        // The minimum applies to totals too.
+        let tally = tally.with_variant(Variant::Total).with_minimum_ratings(3);
+        assert!(tally.winners().is_empty());
+        assert_eq!(tally.totals().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn score_median() -> Result<(), TallyError> {
+        let mut tally = ScoreTally::<&str, f64>::new(1).with_variant(Variant::Median);
+        tally.add_weighted(vec![("Alice", 4.0), ("Bob", 2.0)], 2.0)?;
+        tally.add(vec![("Alice", 1.0), ("Bob", 5.0)])?;
+        tally.add_weighted(vec![("Bob", 5.0), ("Carol", 3.5)], 2.0)?;
+
+        // Alice: 4, 4, 1; Bob: 2, 2, 5, 5, 5; Carol: 3.5, 3.5
+        assert_eq!(tally.medians(), vec![("Bob", 5.0), ("Alice", 4.0), ("Carol", 3.5)]);
+        assert_eq!(tally.margin_of_victory(5.0), None);
+
+        // Shards record scores, and withdrawn candidates lose theirs.
+        let mut shard = tally.shard();
+        shard.add_weighted(vec![("Bob", 1.0), ("Alice", 5.0)], 4.0)?;
+        tally.merge(shard);
+        tally.withdraw_candidate(&"Carol");
+        assert_eq!(tally.medians(), vec![("Alice", 5.0), ("Bob", 2.0)]);
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        // Without recording scores there are no medians.
+        let mut tally = DefaultScoreTally::new(1);
+        tally.add(vec![("Alice", 1)])?;
+        assert!(tally.medians().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn score_normalization() -> Result<(), TallyError> {
+        let mut tally = DefaultScoreTally::new(1)
+            .with_range(0, 10)
+            .with_normalization(Normalization::Sum(10));
+        tally.checked_add(vec![("Alice", 10), ("Bob", 10)])?;
+        tally.add_weighted_ref(&[("Alice", 1), ("Bob", 2), ("Carol", 2)], 2)?;
+        tally.add(vec![("Carol", 0)])?;
+        assert_eq!(
+            tally.add(vec![("Alice", 11)]),
+            Err(TallyError::ScoreOutOfRange {
+                position: 0,
+                score: "11".to_string()
+            })
+        );
+
+        // Scaled scores are rounded down: Alice 5 + 2 * 2, Bob 5 + 2 * 4, Carol 2 * 4
+        assert_eq!(tally.totals(), vec![("Bob", 13), ("Alice", 9), ("Carol", 8)]);
+
+        // Medians are taken of the scaled scores.
+        let mut tally = ScoreTally::<&str, f64>::new(1)
+            .with_variant(Variant::Median)
+            .with_normalization(Normalization::Max(5.0));
+        tally.add(vec![("Alice", 1.0), ("Bob", 0.5)])?;
+        tally.add(vec![("Alice", 2.0), ("Bob", 4.0)])?;
+        tally.add(vec![("Alice", 1.0), ("Bob", 0.0)])?;
+        assert_eq!(tally.medians(), vec![("Alice", 5.0), ("Bob", 2.5)]);
+        Ok(())
     }
 
     #[test]
-    fn score_wikipedia() {
+    fn score_wikipedia() -> Result<(), TallyError> {
         // From: https://en.wikipedia.org/wiki/Score_voting
 
         let mut tally = ScoreTally::with_capacity(1, 4);
-        tally.add_weighted(vec![("Memphis", 10), ("Nashville", 4), ("Chattanooga", 2), ("Knoxville", 0)], 42);
-        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 10), ("Chattanooga", 4), ("Knoxville", 2)], 26);
-        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 6), ("Chattanooga", 10), ("Knoxville", 6)], 15);
-        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 5), ("Chattanooga", 7), ("Knoxville", 10)], 17);
+        tally.add_weighted(vec![("Memphis", 10), ("Nashville", 4), ("Chattanooga", 2), ("Knoxville", 0)], 42)?;
+        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 10), ("Chattanooga", 4), ("Knoxville", 2)], 26)?;
+        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 6), ("Chattanooga", 10), ("Knoxville", 6)], 15)?;
+        tally.add_weighted(vec![("Memphis", 0), ("Nashville", 5), ("Chattanooga", 7), ("Knoxville", 10)], 17)?;
 
         let candidates = tally.candidates();
         assert_eq!(candidates.len(), 4);
@@ -217,9 +1080,10 @@ mod tests {
         assert_eq!(ranked, vec![("Nashville", 0), ("Chattanooga", 1), ("Memphis", 2), ("Knoxville", 3)]);
 
         let winners = tally.winners();
-        assert_eq!(winners.is_empty(), false);
-        assert_eq!(winners.check_overflow(), false);
+        assert!(!winners.is_empty());
+        assert!(!winners.check_overflow());
         assert_eq!(winners.overflow(), Option::None);
         assert_eq!(winners.all(), vec!["Nashville"]);
+        Ok(())
     }
 }
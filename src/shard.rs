@@ -0,0 +1,184 @@
+use std::panic;
+use std::thread;
+
+use super::errors::TallyError;
+
+/// A tally that can be counted in shards and merged back together.
+///
+/// Each shard starts empty, with the same settings as the tally it was made from, and counts a disjoint part of the ballots.
+/// Merging every shard into the original tally gives the same result as adding all the ballots to it directly.
+pub trait Shard: Sized {
+    /// Create an empty tally with the same settings and candidates as this one, to count a shard of the ballots.
+    fn shard(&self) -> Self;
+
+    /// Merge the votes counted by a shard into this tally.
+    fn merge(&mut self, shard: Self);
+}
+
+/// Add ballots to a tally, counting them in one shard per available core.
+///
+/// See [`ingest_shards()`](fn.ingest_shards.html).
+pub fn ingest<S, B, F>(tally: &mut S, ballots: &[B], add: F) -> Result<(), TallyError>
+where
+    S: Shard + Send,
+    B: Sync,
+    F: Fn(&mut S, &B) -> Result<(), TallyError> + Sync,
+{
+    let shards = thread::available_parallelism().map_or(1, |n| n.get());
+    ingest_shards(tally, ballots, shards, add)
+}
+
+/// Add ballots to a tally, splitting them into the given number of shards and counting each shard on its own thread.
+///
+/// `add` adds a single ballot to a shard. If it fails for any ballot, the error for the first failing ballot is returned
+/// and the tally is left unchanged.
+///
+/// # Example
+/// ```
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::shard;
+///
+///    let ballots = vec![vec!["Alice", "Bob"], vec!["Bob", "Alice"], vec!["Alice", "Bob"]];
+///    let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob"]);
+///    shard::ingest_shards(&mut tally, &ballots, 2, |shard, ballot| shard.add(ballot))?;
+///
+///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+/// # Ok::<(), tallystick::TallyError>(())
+/// ```
+pub fn ingest_shards<S, B, F>(tally: &mut S, ballots: &[B], shards: usize, add: F) -> Result<(), TallyError>
+where
+    S: Shard + Send,
+    B: Sync,
+    F: Fn(&mut S, &B) -> Result<(), TallyError> + Sync,
+{
+    if ballots.is_empty() {
+        return Ok(());
+    }
+    let chunk_size = ballots.len().div_ceil(std::cmp::max(shards, 1));
+
+    let counted: Vec<Result<S, TallyError>> = thread::scope(|scope| {
+        let add = &add;
+        let handles: Vec<_> = ballots
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let mut shard = tally.shard();
+                scope.spawn(move || {
+                    for ballot in chunk {
+                        add(&mut shard, ballot)?;
+                    }
+                    Ok(shard)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+
+    // Shards are in ballot order, so the first error is for the first failing ballot.
+    let counted = counted.into_iter().collect::<Result<Vec<S>, TallyError>>()?;
+    for shard in counted {
+        tally.merge(shard);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::DefaultApprovalTally;
+    use crate::condorcet::DefaultCondorcetTally;
+
+    #[test]
+    fn shard_condorcet() -> Result<(), TallyError> {
+        let mut ballots = Vec::new();
+        for _ in 0..5 {
+            ballots.push(vec!["Alice", "Bob", "Carol"]);
+        }
+        for _ in 0..4 {
+            ballots.push(vec!["Bob", "Carol", "Alice"]);
+        }
+        for _ in 0..3 {
+            ballots.push(vec!["Carol", "Alice", "Bob"]);
+        }
+
+        let mut expected = DefaultCondorcetTally::new(1).unchecked();
+        for ballot in ballots.iter() {
+            expected.add(ballot)?;
+        }
+
+        // Unchecked shards see candidates in a different order, so merging maps them by candidate.
+        let mut tally = DefaultCondorcetTally::new(1).unchecked();
+        ingest_shards(&mut tally, &ballots, 3, |shard, ballot| shard.add(ballot))?;
+        assert_eq!(tally.matrix(), expected.matrix());
+        assert_eq!(tally.winners(), expected.winners());
+
+        // Errors leave the tally unchanged.
+        let mut tally = DefaultCondorcetTally::with_candidates(1, vec!["Alice", "Bob", "Carol"]);
+        ballots.push(vec!["Alice", "Dave"]);
+        assert!(ingest_shards(&mut tally, &ballots, 3, |shard, ballot| shard.add(ballot)).is_err());
+        assert!(tally.totals().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn shard_approval() -> Result<(), TallyError> {
+        let ballots: Vec<Vec<u32>> = (0..100).map(|i| vec![i % 3, i % 5]).collect();
+
+        let mut expected = DefaultApprovalTally::new(2);
+        for ballot in ballots.iter() {
+            expected.add_ref(ballot)?;
+        }
+
+        let mut tally = DefaultApprovalTally::new(2);
+        ingest(&mut tally, &ballots, |shard, ballot| shard.add_ref(ballot))?;
+        // Tied totals are in no particular order, so compare them sorted.
+        let (mut totals, mut expected) = (tally.totals(), expected.totals());
+        totals.sort();
+        expected.sort();
+        assert_eq!(totals, expected);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn shard_transferable() -> Result<(), TallyError> {
+        use crate::{irv, stv, Quota, Transfer};
+
+        let mut ballots = Vec::new();
+        for _ in 0..42 {
+            ballots.push(vec!["Memphis", "Nashville", "Chattanooga", "Knoxville"]);
+        }
+        for _ in 0..26 {
+            ballots.push(vec!["Nashville", "Chattanooga", "Knoxville", "Memphis"]);
+        }
+        for _ in 0..15 {
+            ballots.push(vec!["Chattanooga", "Knoxville", "Nashville", "Memphis"]);
+        }
+        for _ in 0..17 {
+            ballots.push(vec!["Knoxville", "Chattanooga", "Nashville", "Memphis"]);
+        }
+
+        let mut expected = stv::DefaultTally::new(2, Quota::Droop);
+        let mut tally = stv::DefaultTally::new(2, Quota::Droop);
+        for ballot in ballots.iter() {
+            expected.add_ref(ballot)?;
+        }
+        ingest_shards(&mut tally, &ballots, 4, |shard, ballot| shard.add_ref(ballot))?;
+        assert_eq!(tally.rounds(), expected.rounds());
+
+        let mut expected = irv::DefaultTally::new(Transfer::Meek);
+        let mut tally = irv::DefaultTally::new(Transfer::Meek);
+        for ballot in ballots.iter() {
+            expected.add_ref(ballot)?;
+        }
+        ingest_shards(&mut tally, &ballots, 4, |shard, ballot| shard.add_ref(ballot))?;
+        assert_eq!(tally.tally_ranked(), expected.tally_ranked());
+        assert_eq!(tally.flows(), expected.flows());
+
+        Ok(())
+    }
+}
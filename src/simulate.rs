@@ -0,0 +1,193 @@
+use rand::Rng;
+use std::hash::Hash;
+
+use super::gen::BallotGenerator;
+use super::RankedWinners;
+use super::TallyError;
+
+#[cfg(feature = "nightly")]
+use super::election::Method;
+#[cfg(feature = "nightly")]
+use num_traits::{FromPrimitive, Num};
+#[cfg(feature = "nightly")]
+use std::ops::AddAssign;
+
+/// A Monte Carlo simulation, that repeatedly samples ballots from a model and tallies them.
+///
+/// # Example
+/// ```
+///    use rand::SeedableRng;
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::gen::Mallows;
+///    use tallystick::simulate::Simulation;
+///    use tallystick::TallyError;
+///
+///    let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+///    let candidates = vec!["Alice", "Bob", "Carlos"];
+///    let simulation = Simulation::new(Mallows::new(candidates.clone(), 0.5), 101, 50);
+///
+///    let result = simulation.run(&mut rng, |ballots| {
+///        let mut tally = DefaultCondorcetTally::with_candidates(1, candidates.clone());
+///        for ballot in ballots.iter() {
+///            tally.add(ballot)?;
+///        }
+///        Ok(tally.winners())
+///    })?;
+///    assert_eq!(result.trials, 50);
+///    assert!(result.win_frequency(&"Alice") > result.win_frequency(&"Carlos"));
+///    # Ok::<(), TallyError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Simulation<G> {
+    model: G,
+    num_ballots: usize,
+    trials: usize,
+}
+
+impl<G> Simulation<G> {
+    /// Create a simulation that samples `num_ballots` ballots from a model, `trials` times.
+    pub fn new(model: G, num_ballots: usize, trials: usize) -> Self {
+        Simulation {
+            model: model,
+            num_ballots: num_ballots,
+            trials: trials,
+        }
+    }
+
+    /// Run the simulation, tallying each sample of ballots with the given function.
+    ///
+    /// The simulation stops at the first error returned by the tally.
+    pub fn run<T, R, F>(&self, rng: &mut R, mut tally: F) -> Result<SimulationResult<T>, TallyError>
+    where
+        G: BallotGenerator<T>,
+        T: Eq + Clone + Hash,
+        R: Rng + ?Sized,
+        F: FnMut(&[Vec<T>]) -> Result<RankedWinners<T>, TallyError>,
+    {
+        let mut result = SimulationResult::new();
+        for _ in 0..self.trials {
+            let ballots = self.model.ballots(rng, self.num_ballots);
+            result.add(&tally(&ballots)?);
+        }
+        Ok(result)
+    }
+
+    /// Run the simulation, tallying each sample of ballots with a tally method. `nightly`
+    #[cfg(feature = "nightly")]
+    pub fn run_method<T, C, R>(
+        &self,
+        rng: &mut R,
+        method: &Method<C>,
+        num_winners: usize,
+        candidates: &[T],
+    ) -> Result<SimulationResult<T>, TallyError>
+    where
+        G: BallotGenerator<T>,
+        T: Eq + Clone + Hash,
+        C: Clone + PartialOrd + Ord + AddAssign + Num + FromPrimitive,
+        R: Rng + ?Sized,
+    {
+        self.run(rng, |ballots| {
+            let weighted: Vec<(Vec<T>, C)> = ballots.iter().map(|ballot| (ballot.clone(), C::one())).collect();
+            method.tally(num_winners, candidates, &weighted)
+        })
+    }
+}
+
+/// The aggregated results of a simulation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationResult<T> {
+    /// The number of trials run.
+    pub trials: usize,
+
+    /// The number of trials each candidate won, from most wins to fewest. Candidates that never won are left out.
+    /// Candidates tied for the last seat are counted as winning.
+    pub wins: Vec<(T, usize)>,
+
+    /// The number of trials with more winners than seats, due to a tie for the last seat.
+    pub ties: usize,
+
+    /// The number of trials with fewer winners than seats.
+    pub unfilled: usize,
+}
+
+impl<T: Eq + Clone + Hash> SimulationResult<T> {
+    fn new() -> Self {
+        SimulationResult {
+            trials: 0,
+            wins: Vec::new(),
+            ties: 0,
+            unfilled: 0,
+        }
+    }
+
+    fn add(&mut self, winners: &RankedWinners<T>) {
+        for ranked in winners.iter() {
+            match self.wins.iter_mut().find(|(c, _)| c == &ranked.candidate) {
+                Some((_, wins)) => *wins += 1,
+                None => self.wins.push((ranked.candidate.clone(), 1)),
+            }
+        }
+        // A stable sort keeps candidates with equal wins in the order they first won.
+        self.wins.sort_by_key(|(_, wins)| std::cmp::Reverse(*wins));
+
+        self.trials += 1;
+        if winners.check_overflow() {
+            self.ties += 1;
+        }
+        if winners.unfilled() > 0 {
+            self.unfilled += 1;
+        }
+    }
+
+    /// Get the fraction of trials a candidate won.
+    pub fn win_frequency(&self, candidate: &T) -> f64 {
+        let wins = self.wins.iter().find(|(c, _)| c == candidate).map_or(0, |(_, wins)| *wins);
+        self.fraction(wins)
+    }
+
+    /// Get the fraction of trials with a tie for the last seat.
+    pub fn tie_rate(&self) -> f64 {
+        self.fraction(self.ties)
+    }
+
+    fn fraction(&self, count: usize) -> f64 {
+        if self.trials == 0 {
+            return 0.0;
+        }
+        count as f64 / self.trials as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::ImpartialCulture;
+    use crate::plurality::DefaultPluralityTally;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn simulation_test() -> Result<(), TallyError> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let simulation = Simulation::new(ImpartialCulture::new(vec!["Alice", "Bob"]), 4, 200);
+
+        let result = simulation.run(&mut rng, |ballots| {
+            let mut tally = DefaultPluralityTally::new(1);
+            for ballot in ballots {
+                tally.add_ref(&ballot[0]);
+            }
+            Ok(tally.winners())
+        })?;
+
+        // With four voters and two candidates, a tie is likely.
+        assert_eq!(result.trials, 200);
+        assert!(result.tie_rate() > 0.2 && result.tie_rate() < 0.5);
+        assert_eq!(result.unfilled, 0);
+        assert_eq!(result.wins.len(), 2);
+        assert!(result.win_frequency(&"Alice") > 0.5);
+        assert_eq!(result.win_frequency(&"Carol"), 0.0);
+
+        Ok(())
+    }
+}
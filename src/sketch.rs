@@ -0,0 +1,311 @@
+use hashbrown::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use super::result::CountedCandidates;
+use super::result::RankedCandidate;
+use super::result::RankedWinners;
+use super::result::Standings;
+use super::result::TalliedWinners;
+use super::TallyError;
+
+/// An approximate plurality or approval tally, using a count-min sketch to count votes in a fixed amount of memory. `sketch`
+///
+/// The sketch never underestimates a candidate's total. With probability `1 - delta` it overestimates it by
+/// at most `epsilon` times the total weight of all votes, however many candidates there are.
+/// Only the candidates with the highest estimated totals are remembered, so [`winners()`](#method.winners)
+/// and [`totals()`](#method.totals) report on those candidates alone.
+///
+/// This suits telemetry-style streams of votes, with more distinct candidates than can be counted exactly.
+///
+/// # Example
+/// ```
+///    use tallystick::sketch::SketchTally;
+///
+///    // Estimate totals to within 0.1% of all votes, with 99% confidence.
+///    let mut tally = SketchTally::new(1, 0.001, 0.01)?;
+///    for i in 1..=10_000u32 {
+///        tally.add(&i); // A long tail of candidates with one vote each
+///        if i % 2 == 0 {
+///            tally.add(&0); // And one popular candidate
+///        }
+///    }
+///
+///    assert_eq!(tally.winners().into_unranked(), vec![0]);
+///    assert!(tally.estimate(&0) >= 5000);
+///    assert!(tally.estimate(&0) <= 5000 + tally.error_bound());
+/// # Ok::<(), tallystick::TallyError>(())
+/// ```
+#[derive(Clone)]
+pub struct SketchTally<T: Eq + Clone + Hash> {
+    width: usize,
+    depth: usize,
+    counts: Vec<u64>, // Row-major, one row of `width` counters for each of `depth` hashes
+    total: u64,
+    num_winners: usize,
+    tracked: usize,
+    top: HashMap<T, u64>, // The tracked candidates with the highest estimated totals
+    floor: u64,           // At most the lowest estimate in `top` once it is full, so most votes need no scan of `top`
+}
+
+impl<T: Eq + Clone + Hash> SketchTally<T> {
+    /// Create a new `SketchTally` with the given number of winners, sized so that estimates are within `epsilon` times
+    /// the total weight of all votes with probability `1 - delta`.
+    ///
+    /// The sketch has `e / epsilon` counters for each of `ln(1 / delta)` hashes, so halving `epsilon` doubles its memory.
+    ///
+    /// Returns `TallyError::InvalidConfig` if `epsilon` or `delta` is not between `0` and `1`,
+    /// or if they are so small that the sketch would not fit in memory.
+    pub fn new(num_winners: usize, epsilon: f64, delta: f64) -> Result<Self, TallyError> {
+        if !(epsilon > 0.0 && epsilon < 1.0) {
+            return Err(TallyError::InvalidConfig("epsilon must be between 0 and 1"));
+        }
+        if !(delta > 0.0 && delta < 1.0) {
+            return Err(TallyError::InvalidConfig("delta must be between 0 and 1"));
+        }
+        let width = (std::f64::consts::E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil() as usize;
+        Self::with_dimensions(num_winners, width, depth)
+    }
+
+    /// Create a new `SketchTally` with the given number of winners, and a sketch of `depth` rows of `width` counters.
+    ///
+    /// Returns `TallyError::InvalidConfig` if `width` or `depth` is zero, or if the sketch would not fit in memory.
+    pub fn with_dimensions(num_winners: usize, width: usize, depth: usize) -> Result<Self, TallyError> {
+        if width == 0 || depth == 0 {
+            return Err(TallyError::InvalidConfig("sketch dimensions must be non-zero"));
+        }
+        let size = width.checked_mul(depth).ok_or(TallyError::InvalidConfig("sketch is too large"))?;
+        Ok(SketchTally {
+            width: width,
+            depth: depth,
+            counts: vec![0; size],
+            total: 0,
+            num_winners: num_winners,
+            tracked: std::cmp::max(num_winners, 1) * 10,
+            top: HashMap::new(),
+            floor: 0,
+        })
+    }
+
+    /// Set the number of candidates to remember, out of those with the highest estimated totals.
+    ///
+    /// Defaults to ten times the number of winners. Remembering more candidates makes it less likely that a candidate
+    /// with few early votes is forgotten before it can catch up.
+    pub fn with_tracked(mut self, tracked: usize) -> Self {
+        self.tracked = std::cmp::max(tracked, self.num_winners);
+        self
+    }
+
+    /// Add a vote.
+    pub fn add(&mut self, vote: &T) {
+        self.add_weighted(vote, 1);
+    }
+
+    /// Add a weighted vote.
+    ///
+    /// Counters saturate at `u64::MAX` rather than overflowing.
+    pub fn add_weighted(&mut self, vote: &T, weight: u64) {
+        self.total = self.total.saturating_add(weight);
+
+        let (hash1, hash2) = self.hashes(vote);
+        let mut estimate = u64::MAX;
+        for row in 0..self.depth {
+            let i = row * self.width + self.column(hash1, hash2, row);
+            self.counts[i] = self.counts[i].saturating_add(weight);
+            estimate = std::cmp::min(estimate, self.counts[i]);
+        }
+
+        self.track(vote, estimate);
+    }
+
+    /// Add an approval vote, approving each of the selected candidates.
+    pub fn add_approval(&mut self, selection: &[T]) {
+        self.add_approval_weighted(selection, 1);
+    }
+
+    /// Add a weighted approval vote.
+    pub fn add_approval_weighted(&mut self, selection: &[T], weight: u64) {
+        for vote in selection {
+            self.add_weighted(vote, weight);
+        }
+    }
+
+    /// Get the estimated total for a candidate, whether or not they are remembered.
+    ///
+    /// The estimate is never less than the candidate's true total.
+    pub fn estimate(&self, candidate: &T) -> u64 {
+        let (hash1, hash2) = self.hashes(candidate);
+        (0..self.depth)
+            .map(|row| self.counts[row * self.width + self.column(hash1, hash2, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Get the total weight of all votes. Each candidate approved on an approval vote counts separately.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Get the most an estimate is likely to exceed a candidate's true total.
+    ///
+    /// This is `e / width` times the total weight of all votes, and holds for each estimate with probability `1 - delta`.
+    pub fn error_bound(&self) -> u64 {
+        (std::f64::consts::E / self.width as f64 * self.total as f64).ceil() as u64
+    }
+
+    /// Get a ranked list of winners among the remembered candidates. Winners with the same rank are tied.
+    /// The number of winners might be greater than the requested `num_winners` if there is a tie.
+    pub fn winners(&self) -> RankedWinners<T> {
+        self.winners_n(self.num_winners)
+    }
+
+    /// Get a ranked list of winners, as [`winners()`](#method.winners) would if the tally had `num_winners` winners.
+    ///
+    /// The tally's own number of winners is unchanged, so the same votes can be viewed with different numbers of winners.
+    pub fn winners_n(&self, num_winners: usize) -> RankedWinners<T> {
+        self.get_counted().into_ranked(num_winners)
+    }
+
+    /// Get the top `n` candidates of [`ranked()`](#method.ranked).
+    /// More than `n` candidates are returned if candidates are tied for the last place. Quorum is not taken into account.
+    pub fn ranked_top(&self, n: usize) -> Vec<RankedCandidate<T>> {
+        RankedWinners::from_ranked(self.ranked(), n).into_vec()
+    }
+
+    /// Get the winners together with the standings of every candidate, including the runner-up.
+    ///
+    /// This is [`winners()`](#method.winners) together with [`ranked()`](#method.ranked).
+    pub fn standings(&self) -> Standings<T> {
+        Standings::new(self.winners(), self.ranked())
+    }
+
+    /// Get a ranked list of the remembered candidates. Candidates with the same rank are tied.
+    pub fn ranked(&self) -> Vec<RankedCandidate<T>> {
+        self.get_counted().into_ranked(0).into_vec()
+    }
+
+    /// Get each candidate with their estimated total, for the remembered candidates, from which both totals and a ranking can be taken.
+    pub fn counted(&self) -> CountedCandidates<T, u64> {
+        self.get_counted()
+    }
+
+    /// Get the estimated totals of the remembered candidates, from highest to lowest.
+    pub fn totals(&self) -> Vec<(T, u64)> {
+        self.get_counted().into_vec()
+    }
+
+    /// Get a ranked list of winners with their estimated totals. Winners with the same rank are tied.
+    ///
+    /// This is [`winners()`](#method.winners) joined with [`totals()`](#method.totals).
+    pub fn winners_with_totals(&self) -> TalliedWinners<T, u64> {
+        self.winners().with_totals(&self.totals())
+    }
+
+    // Remember a candidate if their estimate is among the highest.
+    fn track(&mut self, vote: &T, estimate: u64) {
+        if let Some(tracked) = self.top.get_mut(vote) {
+            *tracked = estimate;
+            return;
+        }
+        if self.top.len() < self.tracked {
+            self.top.insert(vote.clone(), estimate);
+            return;
+        }
+        if estimate <= self.floor {
+            return;
+        }
+
+        // Estimates only grow, so the floor may be stale and low. Find the true lowest estimate, and replace it if this one is higher.
+        let lowest = self
+            .top
+            .iter()
+            .min_by_key(|(_, total)| **total)
+            .map(|(c, total)| (c.clone(), *total));
+        if let Some((lowest, lowest_total)) = lowest {
+            if estimate > lowest_total {
+                self.top.remove(&lowest);
+                self.top.insert(vote.clone(), estimate);
+                self.floor = self.top.values().min().cloned().unwrap_or(0);
+            } else {
+                self.floor = lowest_total;
+            }
+        }
+    }
+
+    // Two independent hashes of a candidate, combined to pick a counter in each row.
+    fn hashes(&self, candidate: &T) -> (u64, u64) {
+        let mut hasher = DefaultHasher::new();
+        candidate.hash(&mut hasher);
+        let hash = hasher.finish();
+        // Mix the hash again for the second hash, and make it odd so every row gets a different column.
+        let hash2 = (hash ^ (hash >> 33)).wrapping_mul(0xff51_afd7_ed55_8ccd) | 1;
+        (hash, hash2)
+    }
+
+    fn column(&self, hash1: u64, hash2: u64, row: usize) -> usize {
+        (hash1.wrapping_add((row as u64).wrapping_mul(hash2)) % self.width as u64) as usize
+    }
+
+    // Get the remembered candidates as CountedCandidates.
+    fn get_counted(&self) -> CountedCandidates<T, u64> {
+        let mut counted = CountedCandidates::new();
+        // Stored estimates are from each candidate's last vote, and may since have grown from other candidates' votes.
+        for candidate in self.top.keys() {
+            counted.push(candidate.clone(), self.estimate(candidate));
+        }
+        counted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sketch_heavy_hitters() -> Result<(), TallyError> {
+        let mut tally = SketchTally::new(3, 0.001, 0.001)?;
+        for i in 0..100_000u32 {
+            tally.add(&(i % 50_000 + 100));
+            if i % 10 == 0 {
+                tally.add_approval(&[1, 2]);
+            }
+            if i % 20 == 0 {
+                tally.add(&3);
+            }
+        }
+
+        assert_eq!(tally.total(), 100_000 + 20_000 + 5_000);
+        let mut winners = tally.winners().into_unranked();
+        winners.sort();
+        assert_eq!(winners, vec![1, 2, 3]);
+        for (candidate, exact) in [(1, 10_000), (2, 10_000), (3, 5_000), (100, 2)] {
+            let estimate = tally.estimate(&candidate);
+            assert!(estimate >= exact);
+            assert!(estimate <= exact + tally.error_bound());
+        }
+        assert!(tally.totals().len() <= 30);
+        Ok(())
+    }
+
+    #[test]
+    fn sketch_invalid_error() {
+        let invalid = |result: Result<SketchTally<u32>, TallyError>| result.err();
+        assert_eq!(
+            invalid(SketchTally::new(1, 0.0, 0.01)),
+            Some(TallyError::InvalidConfig("epsilon must be between 0 and 1"))
+        );
+        assert_eq!(
+            invalid(SketchTally::new(1, 0.01, f64::NAN)),
+            Some(TallyError::InvalidConfig("delta must be between 0 and 1"))
+        );
+        assert_eq!(
+            invalid(SketchTally::new(1, 1e-300, 0.01)),
+            Some(TallyError::InvalidConfig("sketch is too large"))
+        );
+        assert_eq!(
+            invalid(SketchTally::with_dimensions(1, 0, 4)),
+            Some(TallyError::InvalidConfig("sketch dimensions must be non-zero"))
+        );
+    }
+}
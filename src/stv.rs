@@ -2,77 +2,365 @@
 #![allow(missing_docs)]
 
 use hashbrown::HashMap;
-use num_traits::cast::NumCast;
+use num_traits::CheckedAdd;
+use num_traits::FromPrimitive;
 use num_traits::Num;
 use std::hash::Hash;
 use std::ops::AddAssign;
 
+use super::cache::Cache;
+use super::quorum::{turnout_methods, Turnout};
+use super::shard::Shard;
+use super::Exhausted;
+use super::Flow;
 use super::Quota;
 use super::RankedWinners;
+use super::Round;
+use super::Selection;
+use super::TallyCheckpoint;
+use super::TallyError;
+use super::Truncation;
 
-#[derive(Debug)]
+mod spool;
+pub use spool::SpooledTally;
+
+#[derive(Debug, Clone)]
 struct WeightedVote<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // vote count type
 {
     weight: C,
-    remaining: Vec<T>,
+    remaining: Selection<T>,
 }
 
 pub type DefaultTally<T> = Tally<T, f64>;
 
+/// An STV tally using `num_rational::Rational64` to count votes, so surplus transfers are exact. `rational`
+#[cfg(feature = "rational")]
+pub type RationalTally<T> = Tally<T, num_rational::Rational64>;
+
+// The outcome of a count.
+#[derive(Clone)]
+struct Count<T: Clone + Eq, C> {
+    winners: RankedWinners<T>,
+    rounds: Vec<Round<T, C>>,
+    exhausted: Vec<Exhausted<C>>,
+    flows: Vec<Flow<T, C>>,
+    quota: C,
+    surpluses: Vec<Surplus<T, C>>,
+}
+
+/// The count sheet of an STV tally: the quota, every round of the count, and every surplus transferred.
+///
+/// See [`Tally::count_sheet()`](struct.Tally.html#method.count_sheet).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rounds<T, C> {
+    /// The number of votes a candidate needs to be elected. The quota is set by the votes cast, and is the same in every round.
+    pub quota: C,
+
+    /// Every round of the count, with the votes held by each continuing candidate and the candidates elected or eliminated in it.
+    pub rounds: Vec<Round<T, C>>,
+
+    /// Every surplus transferred, in the order of the rounds they were transferred in.
+    pub surpluses: Vec<Surplus<T, C>>,
+}
+
+/// A surplus transferred from a candidate elected with more votes than the quota.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Surplus<T, C> {
+    /// The round in which the surplus was transferred, counting from `0` as an index into [`Rounds::rounds`](struct.Rounds.html#structfield.rounds).
+    pub round: usize,
+
+    /// The elected candidate.
+    pub candidate: T,
+
+    /// The votes the candidate held when they were elected.
+    pub votes: C,
+
+    /// The votes the candidate held over the quota.
+    pub surplus: C,
+
+    /// The share of its value each of the candidate's ballots carried on to its next preference: the surplus divided by the votes.
+    pub transfer_value: C,
+}
+
+#[derive(Clone)]
 pub struct Tally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // vote count type
 {
     running_total: HashMap<T, Vec<WeightedVote<T, C>>>,
+    candidates: Vec<T>, // Candidates in the order they were first seen, so rounds are reported in a stable order.
     num_winners: usize,
     quota: Quota<C>,
     expected_votes: Option<usize>, // Expected votes *per candidate*.
+    turnout: Turnout<C>,
+    truncation: Truncation,
+    check_votes: bool,
+    fixed_candidates: bool, // Whether checked votes may only rank candidates added up front
+    generation: u64,        // Bumped whenever votes or candidates change, invalidating the cached count
+    counted: Cache<Count<T, C>>,
 }
 
 impl<T, C> Tally<T, C>
 where
-    T: Eq + Clone + Hash,                             // Candidate
-    C: Copy + PartialOrd + AddAssign + Num + NumCast, // vote count type
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // vote count type
 {
+    /// Create a new `Tally` with the given number of winners and quota.
     pub fn new(num_winners: usize, quota: Quota<C>) -> Self {
         Tally {
             running_total: HashMap::new(),
+            candidates: Vec::new(),
             num_winners: num_winners,
             quota: quota,
             expected_votes: None,
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            check_votes: true,
+            fixed_candidates: false,
+            generation: 0,
+            counted: Cache::new(),
         }
     }
 
+    /// Create a new `Tally` with the given number of winners and quota, and the number of expected candidates and votes.
+    ///
+    /// Space is reserved up front for the expected candidates, and for each candidate's share of the expected votes.
     pub fn with_capacity(num_winners: usize, quota: Quota<C>, expected_candidates: usize, expected_votes: usize) -> Self {
         Tally {
             running_total: HashMap::with_capacity(expected_candidates),
+            candidates: Vec::with_capacity(expected_candidates),
             num_winners: num_winners,
             quota: quota,
-            expected_votes: Some((expected_votes / expected_candidates) * 2),
+            expected_votes: Some((expected_votes / expected_candidates.max(1)) * 2),
+            turnout: Turnout::new(),
+            truncation: Truncation::Allow,
+            check_votes: true,
+            fixed_candidates: false,
+            generation: 0,
+            counted: Cache::new(),
         }
     }
 
-    pub fn add(&mut self, mut selection: Vec<T>) {
-        if selection.is_empty() {
-            return;
+    /// Create a new `Tally` with the given number of winners, quota and candidates.
+    ///
+    /// Candidates added up front take part in the count even if no ballot ranks them,
+    /// so a candidate without votes is still reported in [`rounds()`](#method.rounds).
+    /// Votes for other candidates are rejected with `TallyError::UnknownCandidate`, unless the tally is [`unchecked()`](#method.unchecked).
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::stv::DefaultTally;
+    ///    use tallystick::Quota;
+    ///
+    ///    let mut tally = DefaultTally::with_candidates(1, Quota::Droop, vec!["Alice", "Bob", "Carlos"]);
+    ///    tally.add(vec!["Alice"])?;
+    ///    tally.add(vec!["Bob", "Alice"])?;
+    ///    tally.add(vec!["Alice", "Bob"])?;
+    ///
+    ///    let rounds = tally.rounds();
+    ///    assert_eq!(rounds[0].totals, vec![("Alice", 2.0), ("Bob", 1.0), ("Carlos", 0.0)]);
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn with_candidates(num_winners: usize, quota: Quota<C>, candidates: Vec<T>) -> Self {
+        let mut tally = Self::new(num_winners, quota);
+        tally.running_total.reserve(candidates.len());
+        tally.fixed_candidates = true;
+        tally.add_candidates(candidates);
+        tally
+    }
+
+    /// Add a candidate to the tally, so that they take part in the count even if no ballot ranks them.
+    ///
+    /// Once candidates have been added, votes may only rank known candidates.
+    pub fn add_candidate(&mut self, candidate: T) {
+        self.fixed_candidates = true;
+        self.register_candidate(candidate);
+    }
+
+    // Add a candidate to the count, if they are new.
+    fn register_candidate(&mut self, candidate: T) {
+        if !self.running_total.contains_key(&candidate) {
+            self.generation += 1;
+            self.running_total.insert(candidate.clone(), vec![]);
+            self.candidates.push(candidate);
         }
+    }
 
-        let choice = selection.remove(0);
+    /// Add some candidates to the tally.
+    pub fn add_candidates(&mut self, candidates: Vec<T>) {
+        for candidate in candidates {
+            self.add_candidate(candidate);
+        }
+    }
+
+    /// Make this tally an unchecked tally, forgoing vote validity checking.
+    ///
+    /// Votes are not checked for duplicate or unknown candidates, or against the truncation policy.
+    /// Ranked votes with duplicate candidates are still rejected, since their weight cannot be split between their rankings.
+    pub fn unchecked(mut self) -> Self {
+        self.check_votes = false;
+        self
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    ///
+    /// Ballots that do not meet the policy are rejected with `TallyError::TruncatedBallot`.
+    /// A full ranking is checked against every candidate the tally has seen so far, including those on the ballot being added.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Add a vote, ranking candidates in order of preference.
+    pub fn add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.add_weighted(selection, C::one())
+    }
+
+    /// Add a vote by reference.
+    pub fn add_ref(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        // Regretably, we need to store the entire selection, so just clone it
+        self.add(selection.to_vec())
+    }
+
+    /// Add a weighted vote.
+    ///
+    /// A vote with a weight of `n` counts the same as `n` identical ballots, so ballots aggregated by precinct can be added at once.
+    /// Weights need not be whole numbers when using a float or rational count type, for share-weighted elections.
+    /// The vote is transferred as a whole, and its surplus value is a share of its weight.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::stv::DefaultTally;
+    ///    use tallystick::Quota;
+    ///
+    ///    let mut tally = DefaultTally::new(2, Quota::Droop);
+    ///    tally.add_weighted(vec!["Alice", "Bob"], 120.0)?;
+    ///    tally.add_weighted(vec!["Carlos"], 60.0)?;
+    ///    tally.add_weighted(vec!["Bob"], 20.0)?;
+    ///
+    ///    // Alice's surplus of 53 over the quota of 67 elects Bob.
+    ///    assert_eq!(tally.winners().into_unranked(), vec!["Alice", "Bob"]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn add_weighted(&mut self, selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        if self.check_votes {
+            self.check_vote(&selection)?;
+        }
+        // An empty vote is a valid ballot for no one, as in every other tally
+        self.turnout.add_valid(&weight);
+        if !selection.is_empty() {
+            self.push_vote(selection, weight);
+        }
+
+        Ok(())
+    }
+
+    /// Add a weighted vote by reference.
+    pub fn add_weighted_ref(&mut self, selection: &[T], weight: C) -> Result<(), TallyError> {
+        self.add_weighted(selection.to_vec(), weight)
+    }
+
+    /// Add a ranked vote.
+    ///
+    /// A ranked vote is a list of tuples of (candidate, rank), where rank is ascending.
+    /// Two candidates with the same rank are equal in preference, and the vote is split equally between them.
+    pub fn ranked_add(&mut self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.ranked_add_weighted(vote, C::one())
+    }
+
+    /// Add a ranked vote with a weight.
+    ///
+    /// Equally ranked candidates split the vote, so that when one of them is elected or eliminated their share
+    /// transfers to the others before moving on to lower preferences.
+    /// A vote with so many ties that it would be split into more than 5040 orderings returns `TallyError::TooManyTies`.
+    pub fn ranked_add_weighted(&mut self, vote: &[(T, u32)], weight: C) -> Result<(), TallyError> {
+        if self.check_votes {
+            self.check_ranked_vote(vote)?;
+        }
+        let split = crate::util::split_ranked_vote(vote, weight.clone())?;
+        self.turnout.add_valid(&weight);
+        if vote.is_empty() {
+            return Ok(());
+        }
+        for (selection, part) in split {
+            self.push_vote(selection, part);
+        }
+
+        Ok(())
+    }
+
+    /// Check the validity of a vote, without adding it.
+    ///
+    /// This will ensure there are no duplicate candidates, and that the vote meets the truncation policy.
+    /// If candidates have been added with [`with_candidates()`](#method.with_candidates) or [`add_candidate()`](#method.add_candidate),
+    /// it will also ensure all candidates are known.
+    pub fn check_vote(&self, selection: &[T]) -> Result<(), TallyError> {
+        self.check_candidates(selection.iter())?;
+        crate::util::check_duplicates_transitive_vote(selection)?;
+        self.check_truncation(selection.iter())
+    }
+
+    /// Check the validity of a ranked vote, without adding it.
+    ///
+    /// The same checks are made as for [`check_vote()`](#method.check_vote).
+    pub fn check_ranked_vote(&self, vote: &[(T, u32)]) -> Result<(), TallyError> {
+        self.check_candidates(vote.iter().map(|(c, _)| c))?;
+        crate::util::check_duplicates_ranked_vote(vote)?;
+        self.check_truncation(vote.iter().map(|(c, _)| c))
+    }
+
+    // Check that every candidate on a vote is known, if candidates were added up front.
+    fn check_candidates<'a>(&self, candidates: impl Iterator<Item = &'a T>) -> Result<(), TallyError>
+    where
+        T: 'a,
+    {
+        if self.fixed_candidates {
+            for (position, candidate) in candidates.enumerate() {
+                if !self.running_total.contains_key(candidate) {
+                    return Err(TallyError::UnknownCandidate { position });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Check a vote against the truncation policy, counting any new candidates on the vote.
+    fn check_truncation<'a>(&self, candidates: impl Iterator<Item = &'a T>) -> Result<(), TallyError>
+    where
+        T: 'a,
+    {
+        let (mut marked, mut new_candidates) = (0, 0);
+        for candidate in candidates {
+            marked += 1;
+            if !self.running_total.contains_key(candidate) {
+                new_candidates += 1;
+            }
+        }
+        self.truncation.check(marked, self.running_total.len() + new_candidates)
+    }
+
+    fn push_vote(&mut self, mut selection: Vec<T>, weight: C) {
+        self.generation += 1;
 
         // Ensure that the running total contains all candidates
         for candidate in selection.iter() {
             if !self.running_total.contains_key(candidate) {
-                self.running_total.insert(candidate.clone(), vec![]);
+                self.register_candidate(candidate.clone());
             }
         }
 
+        let choice = selection.remove(0);
+
         let weighted_vote = WeightedVote {
-            weight: C::one(),
-            remaining: selection,
+            weight: weight,
+            remaining: Selection::from_vec(selection),
         };
 
         match self.expected_votes {
@@ -88,68 +376,242 @@ where
         }
     }
 
-    pub fn add_ref(&mut self, selection: &[T]) {
-        // Regretably, we need to store the entire selection, so just clone it
-        self.add(selection.to_vec().clone());
+    turnout_methods!(winners);
+
+    /// Take a snapshot of the tally, which it can later be restored to with [`restore()`](#method.restore).
+    pub fn snapshot(&self) -> TallyCheckpoint<Self> {
+        TallyCheckpoint::new(self.clone())
+    }
+
+    /// Restore the tally to a snapshot, undoing any changes made since the snapshot was taken.
+    pub fn restore(&mut self, checkpoint: TallyCheckpoint<Self>) {
+        *self = checkpoint.into_tally();
+    }
+
+    /// Merge the votes counted by another tally into this one.
+    ///
+    /// Tallies of disjoint sets of ballots can be counted separately, on different threads or machines, and merged for the result.
+    /// Candidates only known to the other tally are added after this tally's candidates, in the order the other tally first saw them.
+    /// The other tally's number of winners, quota and quorum are ignored.
+    pub fn merge(&mut self, other: Self) {
+        for candidate in other.candidates {
+            if !self.running_total.contains_key(&candidate) {
+                self.running_total.insert(candidate.clone(), vec![]);
+                self.candidates.push(candidate);
+            }
+        }
+        for (candidate, votes) in other.running_total {
+            self.running_total.entry(candidate).or_default().extend(votes);
+        }
+        self.turnout.merge(other.turnout);
+        self.generation += 1;
     }
 
-    pub fn winners(&mut self) -> RankedWinners<T> {
+    /// Withdraw a candidate from the tally.
+    ///
+    /// Results are recounted as if the candidate had never been on the ballot.
+    /// Ballots that ranked the candidate first count for their next preference instead.
+    pub fn withdraw_candidate(&mut self, candidate: &T) {
+        let withdrawn = match self.running_total.remove(candidate) {
+            Some(votes) => votes,
+            None => return,
+        };
+        self.generation += 1;
+        self.candidates.retain(|c| c != candidate);
+
+        for votes in self.running_total.values_mut() {
+            for vote in votes.iter_mut() {
+                vote.remaining.retain(|c| c != candidate);
+            }
+        }
+
+        // Re-add ballots that were for the withdrawn candidate, skipping to their next preference.
+        for vote in withdrawn {
+            let mut selection = vote.remaining;
+            selection.retain(|c| c != candidate);
+            if !selection.is_empty() {
+                let choice = selection.remove(0);
+                let weighted_vote = WeightedVote {
+                    weight: vote.weight,
+                    remaining: selection,
+                };
+                self.running_total.entry(choice).or_default().push(weighted_vote);
+            }
+        }
+    }
+
+    /// If a quorum is set and has not been met, there is no decision and no winners are returned.
+    ///
+    /// The count is cached, and only rerun after votes are added or candidates withdrawn,
+    /// so it is cheap to call `winners()`, [`rounds()`](#method.rounds) or [`flows()`](#method.flows) after every vote.
+    pub fn winners(&self) -> RankedWinners<T> {
+        self.turnout.decide(self.count().winners)
+    }
+
+    /// Get the ballots that exhausted in each round of the count.
+    ///
+    /// There is one entry for each round in which ballots were transferred, in the order the rounds were counted.
+    pub fn exhausted(&self) -> Vec<Exhausted<C>> {
+        self.count().exhausted
+    }
+
+    /// Get the votes transferred in each round of the count, from each elected or eliminated candidate to each continuing candidate,
+    /// or to exhausted ballots.
+    ///
+    /// Flows are ordered by round, with the round counting from `0` as an index into [`rounds()`](#method.rounds).
+    /// They are the data behind a Sankey diagram of the count.
+    pub fn flows(&self) -> Vec<Flow<T, C>> {
+        self.count().flows
+    }
+
+    /// Get every round of the count, with the votes held by each continuing candidate at the start of the round,
+    /// and the candidates elected or eliminated in it.
+    ///
+    /// Candidates are listed in the order they were first seen on a ballot. The last round is the one in which the final seats were filled.
+    /// See [`count_sheet()`](#method.count_sheet) for the rounds together with the quota and the surpluses transferred.
+    pub fn rounds(&self) -> Vec<Round<T, C>> {
+        self.count().rounds
+    }
+
+    /// Get the count sheet: the quota, every round of the count, and every surplus transferred with its transfer value.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::stv::DefaultTally;
+    ///    use tallystick::Quota;
+    ///
+    ///    let mut tally = DefaultTally::new(2, Quota::Droop);
+    ///    for _ in 0..6 { tally.add(vec!["Alice", "Bob"])?; }
+    ///    for _ in 0..2 { tally.add(vec!["Carlos"])?; }
+    ///    tally.add(vec!["Bob"])?;
+    ///
+    ///    let sheet = tally.count_sheet();
+    ///    assert_eq!(sheet.quota, 4.0);
+    ///
+    ///    // Alice is elected with 2 votes over the quota, so each of her ballots carries on a third of its value to Bob.
+    ///    let surplus = &sheet.surpluses[0];
+    ///    assert_eq!((surplus.round, surplus.candidate, surplus.votes, surplus.surplus), (0, "Alice", 6.0, 2.0));
+    ///    assert_eq!(surplus.transfer_value, 2.0 / 6.0);
+    ///    assert_eq!(sheet.rounds[1].totals, vec![("Bob", 3.0), ("Carlos", 2.0)]);
+    /// # Ok::<(), tallystick::TallyError>(())
+    /// ```
+    pub fn count_sheet(&self) -> Rounds<T, C> {
+        let count = self.count();
+        Rounds {
+            quota: count.quota,
+            rounds: count.rounds,
+            surpluses: count.surpluses,
+        }
+    }
+
+    /// Get the quota: the number of votes a candidate needs to be elected.
+    pub fn quota(&self) -> C {
+        self.threshold()
+    }
+
+    /// Get the total ballots that exhausted over all rounds of the count.
+    pub fn total_exhausted(&self) -> Exhausted<C> {
+        Exhausted::total(&self.exhausted())
+    }
+
+    // The count is cached, and only rerun after votes or candidates change.
+    fn count(&self) -> Count<T, C> {
+        self.counted.get_or_compute(self.generation, || self.compute_count())
+    }
+
+    // Run the count, returning the winners, the rounds, and the ballots exhausted in each round.
+    fn compute_count(&self) -> Count<T, C> {
         let threshold = self.threshold();
 
+        let mut running_total = self.running_total.clone();
         let mut winners = RankedWinners::new(self.num_winners);
+        let mut rounds = Vec::new();
+        let mut exhausted = Vec::new();
+        let mut flows = Vec::new();
+        let mut surpluses = Vec::new();
 
         let mut rank: usize = 0;
         loop {
+            let mut round = Round {
+                totals: self
+                    .candidates
+                    .iter()
+                    .filter_map(|c| running_total.get(c).map(|votes| (c.clone(), Self::sum(votes))))
+                    .collect(),
+                elected: Vec::new(),
+                eliminated: Vec::new(),
+                exhausted: Exhausted::new(),
+            };
+
             // Step 1. If we have less candidates left than there are spots to fill, they are all winners
-            if self.running_total.len() <= self.num_winners as usize - winners.len() {
-                for (candidate, _) in self.running_total.drain() {
+            if running_total.len() <= self.num_winners - winners.len() {
+                for (candidate, _) in running_total.drain() {
                     winners.push(candidate, rank);
                 }
-                return winners;
+                round.elected = round.totals.iter().map(|(c, _)| c.clone()).collect();
+                rounds.push(round);
+                return self.finish(winners, rounds, exhausted, flows, threshold, surpluses);
             }
 
-            // Step 2. Check if any candidates are over the threshold
-            let mut new_winners: Vec<T> = Vec::new();
-            for (candidate, votes) in self.running_total.iter() {
-                let mut votecount = C::zero();
-                for vote in votes.iter() {
-                    votecount += vote.weight;
-                }
-                if votecount >= threshold {
-                    new_winners.push(candidate.clone());
-                }
-            }
+            // Step 2. Check if any candidates are over the threshold, in the order candidates were seen
+            let new_winners: Vec<T> = round
+                .totals
+                .iter()
+                .filter(|(_, votecount)| *votecount >= threshold)
+                .map(|(c, _)| c.clone())
+                .collect();
 
             // Step 3. If we have enough winners, end the tally and return results.
             if (winners.len() + new_winners.len()) >= self.num_winners {
-                for winner in new_winners.drain(0..) {
-                    winners.push(winner, rank);
+                for winner in new_winners.iter() {
+                    winners.push(winner.clone(), rank);
                 }
-                return winners;
+                round.elected = new_winners;
+                rounds.push(round);
+                return self.finish(winners, rounds, exhausted, flows, threshold, surpluses);
             }
 
             // Step 4. If there's new winners, redistribute their excess vote.
             if !new_winners.is_empty() {
-                let mut winner_votes: HashMap<T, Vec<WeightedVote<T, C>>> = HashMap::new();
-                for winner in new_winners.drain(0..) {
-                    let votes = self.running_total.remove(&winner).unwrap();
-                    winner_votes.insert(winner, votes);
+                // Surpluses are transferred in the order candidates were seen.
+                let mut winner_votes: Vec<(T, Vec<WeightedVote<T, C>>)> = Vec::new();
+                for winner in new_winners.iter() {
+                    let votes = running_total.remove(winner).unwrap();
+                    winner_votes.push((winner.clone(), votes));
                 }
-                for (winner, mut votes) in winner_votes.drain() {
-                    let overvote = C::from(votes.len()).unwrap() - threshold;
-                    let weight = overvote / C::from(votes.len()).unwrap();
+                let mut transferred = Exhausted::new();
+                for (winner, mut votes) in winner_votes.drain(0..) {
+                    // Votes may carry less than a whole ballot, so the surplus is a share of their total weight.
+                    let mut votecount = C::zero();
+                    for vote in votes.iter() {
+                        votecount += vote.weight.clone();
+                    }
+                    let overvote = votecount.clone() - threshold.clone();
+                    let weight = overvote.clone() / votecount.clone();
+                    surpluses.push(Surplus {
+                        round: rounds.len(),
+                        candidate: winner.clone(),
+                        votes: votecount,
+                        surplus: overvote,
+                        transfer_value: weight.clone(),
+                    });
 
                     // Redistibute to next choice
                     for vote in votes.drain(0..) {
-                        self.redistribute(vote, weight);
+                        let (to, value) = Self::redistribute(&mut running_total, vote, weight.clone(), &mut transferred);
+                        Flow::add(&mut flows, rounds.len(), &winner, to.as_ref(), value);
                     }
 
                     winners.push(winner, rank);
                 }
+                exhausted.push(transferred.clone());
+                round.elected = new_winners;
+                round.exhausted = transferred;
+                rounds.push(round);
 
                 // If we have enough winners, return it.
                 if winners.len() >= self.num_winners {
-                    return winners;
+                    return self.finish(winners, rounds, exhausted, flows, threshold, surpluses);
                 }
 
                 // We've added winners, so increase the rank and continue to the next round.
@@ -162,20 +624,20 @@ where
                     let mut votecounts: HashMap<&T, C> = HashMap::new();
                     let mut first = true;
                     let mut least = C::zero();
-                    for (candidate, votes) in self.running_total.iter() {
+                    for (candidate, votes) in running_total.iter() {
                         let mut votecount = C::zero();
 
                         for vote in votes.iter() {
-                            votecount += vote.weight;
+                            votecount += vote.weight.clone();
                         }
                         if first {
-                            least = votecount;
+                            least = votecount.clone();
                             first = false;
                         } else if votecount < least {
-                            least = votecount;
+                            least = votecount.clone();
                         }
 
-                        votecounts.insert(&candidate, votecount);
+                        votecounts.insert(candidate, votecount);
                     }
                     for (candidate_ref, count) in votecounts.iter() {
                         if *count <= least {
@@ -185,29 +647,46 @@ where
                     }
                 };
 
+                // Losers are found in hash order, so put them in the order candidates were seen.
+                // Rounds then report them the same way every time, and their votes are transferred in a fixed order.
+                new_loosers = round
+                    .totals
+                    .iter()
+                    .map(|(c, _)| c.clone())
+                    .filter(|c| new_loosers.contains(c))
+                    .collect();
+
                 // If the number of loosers to be removed would result in an underelection, then the loosers become winners.
-                let needed_winners = self.num_winners as usize - winners.len();
-                let available_winners = self.running_total.len() - new_loosers.len();
+                let needed_winners = self.num_winners - winners.len();
+                let available_winners = running_total.len() - new_loosers.len();
                 if available_winners < needed_winners {
-                    for winning_loosers in new_loosers.drain(0..) {
-                        winners.push(winning_loosers, rank);
+                    for winning_looser in new_loosers.iter() {
+                        winners.push(winning_looser.clone(), rank);
                     }
-                    return winners;
+                    round.elected = new_loosers;
+                    rounds.push(round);
+                    return self.finish(winners, rounds, exhausted, flows, threshold, surpluses);
                 }
 
                 // If there's new loosers, redistribute their excess vote.
                 if !new_loosers.is_empty() {
-                    let mut looser_votes: Vec<Vec<WeightedVote<T, C>>> = Vec::new();
-                    for looser in new_loosers.drain(0..) {
-                        let votes = self.running_total.remove(&looser).unwrap();
-                        looser_votes.push(votes);
+                    let mut looser_votes: Vec<(T, Vec<WeightedVote<T, C>>)> = Vec::new();
+                    for looser in new_loosers.iter() {
+                        let votes = running_total.remove(looser).unwrap();
+                        looser_votes.push((looser.clone(), votes));
                     }
-                    for mut votes in looser_votes.drain(0..) {
+                    let mut transferred = Exhausted::new();
+                    for (looser, mut votes) in looser_votes.drain(0..) {
                         // Redistibute to next choice
                         for vote in votes.drain(0..) {
-                            self.redistribute(vote, C::one());
+                            let (to, value) = Self::redistribute(&mut running_total, vote, C::one(), &mut transferred);
+                            Flow::add(&mut flows, rounds.len(), &looser, to.as_ref(), value);
                         }
                     }
+                    exhausted.push(transferred.clone());
+                    round.eliminated = new_loosers;
+                    round.exhausted = transferred;
+                    rounds.push(round);
                 } else {
                     unreachable!();
                 }
@@ -215,9 +694,53 @@ where
         }
     }
 
-    fn redistribute(&mut self, vote: WeightedVote<T, C>, weight: C) {
+    fn finish(
+        &self,
+        winners: RankedWinners<T>,
+        rounds: Vec<Round<T, C>>,
+        exhausted: Vec<Exhausted<C>>,
+        mut flows: Vec<Flow<T, C>>,
+        quota: C,
+        mut surpluses: Vec<Surplus<T, C>>,
+    ) -> Count<T, C> {
+        // Order flows by round, then by the order candidates were seen, with exhausted ballots last.
+        let position = |candidate: Option<&T>| match candidate {
+            Some(candidate) => self.candidates.iter().position(|c| c == candidate).unwrap_or(0),
+            None => self.candidates.len(),
+        };
+        flows.sort_by_key(|flow| (flow.round, position(Some(&flow.from)), position(flow.to.as_ref())));
+        surpluses.sort_by_key(|surplus| (surplus.round, position(Some(&surplus.candidate))));
+
+        Count {
+            winners: winners,
+            rounds: rounds,
+            exhausted: exhausted,
+            flows: flows,
+            quota: quota,
+            surpluses: surpluses,
+        }
+    }
+
+    fn sum(votes: &[WeightedVote<T, C>]) -> C {
+        let mut votecount = C::zero();
+        for vote in votes.iter() {
+            votecount += vote.weight.clone();
+        }
+        votecount
+    }
+
+    // Transfer a vote to its next continuing choice, returning that choice (or `None` if the vote exhausted) and the value transferred.
+    fn redistribute(
+        running_total: &mut HashMap<T, Vec<WeightedVote<T, C>>>,
+        vote: WeightedVote<T, C>,
+        weight: C,
+        exhausted: &mut Exhausted<C>,
+    ) -> (Option<T>, C) {
         if vote.remaining.is_empty() {
-            return;
+            // No continuing candidates left, so the ballot exhausts at its current value.
+            let value = weight * vote.weight;
+            exhausted.add(1, value.clone());
+            return (None, value);
         }
 
         let mut remaining = vote.remaining;
@@ -226,68 +749,122 @@ where
             weight: weight * vote.weight,
             remaining: remaining,
         };
-        if self.running_total.contains_key(&next_choice) {
-            if let Some(x) = self.running_total.get_mut(&next_choice) {
-                x.push(weighted_vote);
-            }
+        if let Some(x) = running_total.get_mut(&next_choice) {
+            let value = weighted_vote.weight.clone();
+            x.push(weighted_vote);
+            (Some(next_choice), value)
         } else {
             // Skip to the next choice in line if the preferred next-choice has already won or lost.
-            self.redistribute(weighted_vote, C::one());
+            Self::redistribute(running_total, weighted_vote, C::one(), exhausted)
         }
     }
 
-    fn total_votes(&self) -> usize {
-        let mut total: usize = 0;
+    fn total_votes(&self) -> C {
+        let mut total = C::zero();
 
         for (_, candidate_votes) in self.running_total.iter() {
-            total += candidate_votes.len();
+            for vote in candidate_votes.iter() {
+                total += vote.weight.clone();
+            }
         }
 
         total
     }
 
     fn threshold(&self) -> C {
-        let total_votes = C::from(self.total_votes()).unwrap();
-        let num_winners = C::from(self.num_winners).unwrap();
+        let total_votes = self.total_votes();
+        let num_winners = C::from_usize(self.num_winners).unwrap();
 
         self.quota.threshold(total_votes, num_winners)
     }
 }
 
+impl<T, C> Tally<T, C>
+where
+    T: Eq + Clone + Hash,                                                 // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + CheckedAdd, // vote count type
+{
+    /// Add a new vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the total weight of valid ballots would overflow the count type.
+    pub fn checked_add(&mut self, selection: Vec<T>) -> Result<(), TallyError> {
+        self.checked_add_weighted(selection, C::one())
+    }
+
+    /// Add a weighted vote, checking for overflow.
+    ///
+    /// Returns `TallyError::CountOverflow` if the total weight of valid ballots would overflow the count type.
+    /// Transfers only pass on part of a candidate's votes, so no candidate ever holds more than every valid ballot and their counts cannot overflow either.
+    /// The tally is left unchanged when an error is returned.
+    pub fn checked_add_weighted(&mut self, selection: Vec<T>, weight: C) -> Result<(), TallyError> {
+        self.turnout.check_add_valid(&weight)?;
+        self.add_weighted(selection, weight)
+    }
+}
+
+impl<T, C> Shard for Tally<T, C>
+where
+    T: Eq + Clone + Hash,                                    // Candidate
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive, // vote count type
+{
+    fn shard(&self) -> Self {
+        Tally {
+            running_total: self.candidates.iter().map(|candidate| (candidate.clone(), vec![])).collect(),
+            candidates: self.candidates.clone(),
+            num_winners: self.num_winners,
+            quota: self.quota.clone(),
+            expected_votes: self.expected_votes,
+            turnout: self.turnout.shard(),
+            truncation: self.truncation,
+            check_votes: self.check_votes,
+            fixed_candidates: self.fixed_candidates,
+            generation: 0,
+            counted: Cache::new(),
+        }
+    }
+
+    fn merge(&mut self, shard: Self) {
+        Tally::merge(self, shard);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Quorum;
 
     #[test]
-    fn stv_test() {
+    fn stv_test() -> Result<(), TallyError> {
         // Election between Alice, Bob, and Cir
         let mut tally = DefaultTally::new(2, Quota::Droop);
-        tally.add(vec!["Alice", "Bob", "Cir"]);
-        tally.add(vec!["Alice", "Bob", "Cir"]);
-        tally.add(vec!["Alice", "Bob", "Cir"]);
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
 
         let winners = tally.winners();
         assert_eq!(winners.into_vec(), vec! {("Alice", 0), ("Bob", 1)});
+
+        Ok(())
     }
 
     #[test]
-    fn stv_wikipedia_test() -> Result<(), ()> {
+    fn stv_wikipedia_test() -> Result<(), TallyError> {
         // From https://en.wikipedia.org/wiki/Single_transferable_vote#Counting_the_votes
         let mut tally = DefaultTally::new(3, Quota::Droop);
         for _ in 0..4 {
-            tally.add(vec!["Orange"]);
+            tally.add(vec!["Orange"])?;
         }
         for _ in 0..2 {
-            tally.add(vec!["Pear", "Orange"]);
+            tally.add(vec!["Pear", "Orange"])?;
         }
         for _ in 0..8 {
-            tally.add(vec!["Chocolate", "Strawberry"]);
+            tally.add(vec!["Chocolate", "Strawberry"])?;
         }
         for _ in 0..4 {
-            tally.add(vec!["Chocolate", "Sweets"]);
+            tally.add(vec!["Chocolate", "Sweets"])?;
         }
-        tally.add(vec!["Strawberry"]);
-        tally.add(vec!["Sweets"]);
+        tally.add(vec!["Strawberry"])?;
+        tally.add(vec!["Sweets"])?;
 
         let winners = tally.winners();
         assert_eq!(winners.into_vec(), vec! {("Chocolate", 0), ("Orange", 1), ("Strawberry", 2)});
@@ -296,28 +873,28 @@ mod tests {
         let mut hare_tally = DefaultTally::new(5, Quota::Hare);
         let mut droop_tally = DefaultTally::new(5, Quota::Droop);
         for _ in 0..31 {
-            hare_tally.add(vec!["Andrea", "Carter", "Brad"]);
-            droop_tally.add(vec!["Andrea", "Carter", "Brad"]);
+            hare_tally.add(vec!["Andrea", "Carter", "Brad"])?;
+            droop_tally.add(vec!["Andrea", "Carter", "Brad"])?;
         }
         for _ in 0..30 {
-            hare_tally.add(vec!["Carter", "Andrea", "Brad"]);
-            droop_tally.add(vec!["Carter", "Andrea", "Brad"]);
+            hare_tally.add(vec!["Carter", "Andrea", "Brad"])?;
+            droop_tally.add(vec!["Carter", "Andrea", "Brad"])?;
         }
         for _ in 0..2 {
-            hare_tally.add(vec!["Brad", "Andrea", "Carter"]);
-            droop_tally.add(vec!["Brad", "Andrea", "Carter"]);
+            hare_tally.add(vec!["Brad", "Andrea", "Carter"])?;
+            droop_tally.add(vec!["Brad", "Andrea", "Carter"])?;
         }
         for _ in 0..20 {
-            hare_tally.add(vec!["Delilah", "Scott", "Jennifer"]);
-            droop_tally.add(vec!["Delilah", "Scott", "Jennifer"]);
+            hare_tally.add(vec!["Delilah", "Scott", "Jennifer"])?;
+            droop_tally.add(vec!["Delilah", "Scott", "Jennifer"])?;
         }
         for _ in 0..20 {
-            hare_tally.add(vec!["Scott", "Delilah", "Jennifer"]);
-            droop_tally.add(vec!["Scott", "Delilah", "Jennifer"]);
+            hare_tally.add(vec!["Scott", "Delilah", "Jennifer"])?;
+            droop_tally.add(vec!["Scott", "Delilah", "Jennifer"])?;
         }
         for _ in 0..17 {
-            hare_tally.add(vec!["Jennifer", "Delilah", "Scott"]);
-            droop_tally.add(vec!["Jennifer", "Delilah", "Scott"]);
+            hare_tally.add(vec!["Jennifer", "Delilah", "Scott"])?;
+            droop_tally.add(vec!["Jennifer", "Delilah", "Scott"])?;
         }
 
         let hare_winners = hare_tally.winners();
@@ -339,13 +916,13 @@ mod tests {
         // From https://en.wikipedia.org/wiki/Droop_quota
         let mut tally = DefaultTally::new(2, Quota::Droop);
         for _ in 0..45 {
-            tally.add(vec!["Andrea", "Carter"]);
+            tally.add(vec!["Andrea", "Carter"])?;
         }
         for _ in 0..25 {
-            tally.add(vec!["Carter"]);
+            tally.add(vec!["Carter"])?;
         }
         for _ in 0..30 {
-            tally.add(vec!["Brad"]);
+            tally.add(vec!["Brad"])?;
         }
 
         let winners = tally.winners();
@@ -354,13 +931,13 @@ mod tests {
         // From https://en.wikipedia.org/wiki/Hare_quota
         let mut tally = DefaultTally::new(2, Quota::Hare);
         for _ in 0..60 {
-            tally.add(vec!["Andrea", "Carter"]);
+            tally.add(vec!["Andrea", "Carter"])?;
         }
         for _ in 0..14 {
-            tally.add(vec!["Carter"]);
+            tally.add(vec!["Carter"])?;
         }
         for _ in 0..30 {
-            tally.add(vec!["Brad", "Andrea"]);
+            tally.add(vec!["Brad", "Andrea"])?;
         }
 
         let winners = tally.winners();
@@ -369,13 +946,13 @@ mod tests {
         // From https://en.wikipedia.org/wiki/Hagenbach-Bischoff_quota
         let mut tally = DefaultTally::new(2, Quota::Hagenbach);
         for _ in 0..45 {
-            tally.add(vec!["Andrea", "Carter"]);
+            tally.add(vec!["Andrea", "Carter"])?;
         }
         for _ in 0..25 {
-            tally.add(vec!["Carter"]);
+            tally.add(vec!["Carter"])?;
         }
         for _ in 0..30 {
-            tally.add(vec!["Brad"]);
+            tally.add(vec!["Brad"])?;
         }
 
         let winners = tally.winners();
@@ -385,36 +962,36 @@ mod tests {
         let mut hagen_tally = DefaultTally::new(7, Quota::Hagenbach);
         let mut droop_tally = DefaultTally::new(7, Quota::Droop);
         for _ in 0..14 {
-            hagen_tally.add(vec!["Andrea", "Carter", "Brad", "Delilah"]);
-            droop_tally.add(vec!["Andrea", "Carter", "Brad", "Delilah"]);
+            hagen_tally.add(vec!["Andrea", "Carter", "Brad", "Delilah"])?;
+            droop_tally.add(vec!["Andrea", "Carter", "Brad", "Delilah"])?;
         }
         for _ in 0..14 {
-            hagen_tally.add(vec!["Carter", "Andrea", "Brad", "Delilah"]);
-            droop_tally.add(vec!["Carter", "Andrea", "Brad", "Delilah"]);
+            hagen_tally.add(vec!["Carter", "Andrea", "Brad", "Delilah"])?;
+            droop_tally.add(vec!["Carter", "Andrea", "Brad", "Delilah"])?;
         }
         for _ in 0..14 {
-            hagen_tally.add(vec!["Brad", "Andrea", "Carter", "Delilah"]);
-            droop_tally.add(vec!["Brad", "Andrea", "Carter", "Delilah"]);
+            hagen_tally.add(vec!["Brad", "Andrea", "Carter", "Delilah"])?;
+            droop_tally.add(vec!["Brad", "Andrea", "Carter", "Delilah"])?;
         }
         for _ in 0..11 {
-            hagen_tally.add(vec!["Delilah", "Andrea", "Carter", "Brad"]);
-            droop_tally.add(vec!["Delilah", "Andrea", "Carter", "Brad"]);
+            hagen_tally.add(vec!["Delilah", "Andrea", "Carter", "Brad"])?;
+            droop_tally.add(vec!["Delilah", "Andrea", "Carter", "Brad"])?;
         }
         for _ in 0..13 {
-            hagen_tally.add(vec!["Scott", "Jennifer", "Matt", "Susan"]);
-            droop_tally.add(vec!["Scott", "Jennifer", "Matt", "Susan"]);
+            hagen_tally.add(vec!["Scott", "Jennifer", "Matt", "Susan"])?;
+            droop_tally.add(vec!["Scott", "Jennifer", "Matt", "Susan"])?;
         }
         for _ in 0..13 {
-            hagen_tally.add(vec!["Jennifer", "Scott", "Matt", "Susan"]);
-            droop_tally.add(vec!["Jennifer", "Scott", "Matt", "Susan"]);
+            hagen_tally.add(vec!["Jennifer", "Scott", "Matt", "Susan"])?;
+            droop_tally.add(vec!["Jennifer", "Scott", "Matt", "Susan"])?;
         }
         for _ in 0..13 {
-            hagen_tally.add(vec!["Matt", "Scott", "Jennifer", "Susan"]);
-            droop_tally.add(vec!["Matt", "Scott", "Jennifer", "Susan"]);
+            hagen_tally.add(vec!["Matt", "Scott", "Jennifer", "Susan"])?;
+            droop_tally.add(vec!["Matt", "Scott", "Jennifer", "Susan"])?;
         }
         for _ in 0..12 {
-            hagen_tally.add(vec!["Susan", "Scott", "Jennifer", "Matt"]);
-            droop_tally.add(vec!["Susan", "Scott", "Jennifer", "Matt"]);
+            hagen_tally.add(vec!["Susan", "Scott", "Jennifer", "Matt"])?;
+            droop_tally.add(vec!["Susan", "Scott", "Jennifer", "Matt"])?;
         }
 
         let hagen_winners = hagen_tally.winners();
@@ -440,16 +1017,16 @@ mod tests {
         // From https://en.wikipedia.org/wiki/Hagenbach-Bischoff_quota
         let mut tally = DefaultTally::new(2, Quota::Hagenbach);
         for _ in 0..50 {
-            tally.add(vec!["Andrea", "Brad"]);
+            tally.add(vec!["Andrea", "Brad"])?;
         }
         for _ in 0..150 {
-            tally.add(vec!["Andrea", "Carter"]);
+            tally.add(vec!["Andrea", "Carter"])?;
         }
         for _ in 0..75 {
-            tally.add(vec!["Brad", "Carter"]);
+            tally.add(vec!["Brad", "Carter"])?;
         }
         for _ in 0..25 {
-            tally.add(vec!["Carter", "Brad"]);
+            tally.add(vec!["Carter", "Brad"])?;
         }
 
         let winners = tally.winners();
@@ -457,7 +1034,462 @@ mod tests {
         assert_eq!(winners.rank(&"Andrea").unwrap(), 0);
         assert_eq!(winners.rank(&"Brad").unwrap(), 1);
         assert_eq!(winners.rank(&"Carter").unwrap(), 1);
-        assert_eq!(winners.check_overflow(), true);
+        assert!(winners.check_overflow());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_bigrational_test() -> Result<(), TallyError> {
+        use num_rational::BigRational;
+
+        let mut tally = Tally::<&str, BigRational>::new(2, Quota::Droop);
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Alice", "Bob", "Cir"])?;
+        tally.add(vec!["Cir"])?;
+
+        let winners = tally.winners();
+        assert_eq!(winners.into_vec(), vec! {("Alice", 0), ("Bob", 1)});
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rational")]
+    fn stv_rational_test() -> Result<(), TallyError> {
+        // From https://en.wikipedia.org/wiki/Single_transferable_vote#Counting_the_votes
+        let mut tally = RationalTally::new(3, Quota::Droop);
+        for _ in 0..4 {
+            tally.add(vec!["Orange"])?;
+        }
+        for _ in 0..2 {
+            tally.add(vec!["Pear", "Orange"])?;
+        }
+        for _ in 0..8 {
+            tally.add(vec!["Chocolate", "Strawberry"])?;
+        }
+        for _ in 0..4 {
+            tally.add(vec!["Chocolate", "Sweets"])?;
+        }
+        tally.add(vec!["Strawberry"])?;
+        tally.add(vec!["Sweets"])?;
+
+        let winners = tally.winners();
+        assert_eq!(winners.into_vec(), vec! {("Chocolate", 0), ("Orange", 1), ("Strawberry", 2)});
+
+        // The Droop quota is floored for rational counts too.
+        assert_eq!(
+            Quota::Droop.threshold(num_rational::Rational64::from(20), num_rational::Rational64::from(3)),
+            num_rational::Rational64::from(6)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "fixed_point")]
+    fn stv_fixed_point_test() -> Result<(), TallyError> {
+        use crate::fixed::Fixed;
+
+        let mut tally = Tally::<&str, Fixed<5>>::new(3, Quota::Droop);
+        for _ in 0..4 {
+            tally.add(vec!["Orange"])?;
+        }
+        for _ in 0..2 {
+            tally.add(vec!["Pear", "Orange"])?;
+        }
+        for _ in 0..8 {
+            tally.add(vec!["Chocolate", "Strawberry"])?;
+        }
+        for _ in 0..4 {
+            tally.add(vec!["Chocolate", "Sweets"])?;
+        }
+        tally.add(vec!["Strawberry"])?;
+        tally.add(vec!["Sweets"])?;
+
+        let winners = tally.winners();
+        assert_eq!(winners.into_vec(), vec! {("Chocolate", 0), ("Orange", 1), ("Strawberry", 2)});
+
+        // Fractional quotas are truncated to the configured number of places.
+        let threshold = Quota::Hagenbach.threshold(Fixed::<5>::from_integer(10), Fixed::from_integer(2));
+        assert_eq!(threshold.to_string(), "3.33333");
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_exhausted_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop);
+        for _ in 0..4 {
+            tally.add(vec!["Alice"])?;
+        }
+        for _ in 0..2 {
+            tally.add(vec!["Bob"])?;
+        }
+        for _ in 0..3 {
+            tally.add(vec!["Cir", "Bob"])?;
+        }
+
+        // Bob is eliminated first and those ballots exhaust. Cir is eliminated next, and those ballots also exhaust since Bob is gone.
+        let exhausted = tally.exhausted();
+        assert_eq!(
+            exhausted,
+            vec![Exhausted { ballots: 2, weight: 2.0 }, Exhausted { ballots: 3, weight: 3.0 }]
+        );
+        assert_eq!(tally.total_exhausted(), Exhausted { ballots: 5, weight: 5.0 });
+
+        // Counting is repeatable
+        assert_eq!(tally.winners().into_vec(), vec! {("Alice", 0)});
+        assert_eq!(tally.winners().into_vec(), vec! {("Alice", 0)});
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_checked() -> Result<(), TallyError> {
+        let mut tally = Tally::<&str, u8>::new(1, Quota::Droop);
+        tally.checked_add_weighted(vec!["Alice", "Bob"], 200)?;
+        tally.checked_add(vec!["Bob"])?;
+        assert_eq!(tally.checked_add_weighted(vec!["Bob"], 60), Err(TallyError::CountOverflow));
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        Ok(())
+    }
+
+    #[test]
+    fn stv_quorum_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop).with_quorum(Quorum::ValidVotes(3.0));
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add_blank();
+        assert_eq!(tally.blank(), 1.0);
+        assert!(!tally.quorum_met());
+        assert!(tally.winners().is_empty());
+
+        // An empty vote is valid, as in the other tallies
+        tally.add(vec![])?;
+        assert!(tally.quorum_met());
+        assert_eq!(tally.winners().into_vec(), vec! {("Alice", 0)});
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_withdraw_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop);
+        let mut expected = DefaultTally::new(1, Quota::Droop);
+        for _ in 0..4 {
+            tally.add(vec!["Alice", "Bob"])?;
+            expected.add(vec!["Alice", "Bob"])?;
+        }
+        for _ in 0..3 {
+            tally.add(vec!["Cir", "Bob", "Alice"])?;
+            expected.add(vec!["Bob", "Alice"])?;
+        }
+        for _ in 0..2 {
+            tally.add(vec!["Bob", "Cir"])?;
+            expected.add(vec!["Bob"])?;
+        }
+        tally.add(vec!["Cir"])?;
+        tally.withdraw_candidate(&"Cir");
+
+        assert_eq!(tally.winners(), expected.winners());
+        assert_eq!(tally.winners().into_vec(), vec! {("Bob", 0)});
+        assert_eq!(tally.exhausted(), expected.exhausted());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_equal_ranks_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(2, Quota::Droop);
+        for _ in 0..6 {
+            tally.ranked_add(&[("Alice", 0), ("Bob", 0)])?;
+        }
+        for _ in 0..3 {
+            tally.add(vec!["Cir"])?;
+        }
+        tally.ranked_add_weighted(&[("Dave", 0), ("Cir", 1), ("Bob", 1)], 2.0)?;
+
+        // Alice and Bob split 6 votes. Dave is eliminated first, and their 2 votes split between Cir and Bob.
+        let winners = tally.winners();
+        assert_eq!(winners.len(), 2);
+        assert_eq!(winners.rank(&"Bob"), Some(0));
+        assert_eq!(winners.rank(&"Cir"), Some(0));
+        assert_eq!(tally.total_exhausted(), Exhausted { ballots: 0, weight: 0.0 });
+
+        assert_eq!(
+            tally.ranked_add(&[("Alice", 0), ("Alice", 1)]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_truncation_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop).with_truncation(Truncation::Minimum(2));
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.ranked_add(&[("Bob", 0), ("Cir", 0)])?;
+        assert_eq!(
+            tally.add(vec!["Alice"]),
+            Err(TallyError::TruncatedBallot { rankings: 1, required: 2 })
+        );
+        assert_eq!(tally.add(vec![]), Err(TallyError::TruncatedBallot { rankings: 0, required: 2 }));
+        assert_eq!(
+            tally.ranked_add(&[("Cir", 0)]),
+            Err(TallyError::TruncatedBallot { rankings: 1, required: 2 })
+        );
+        assert_eq!(tally.total_votes(), 2.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_snapshot_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop);
+        tally.add(vec!["Alice", "Bob"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add(vec!["Bob"])?;
+
+        let checkpoint = tally.snapshot();
+        for _ in 0..3 {
+            tally.add(vec!["Bob", "Alice"])?;
+        }
+        assert_eq!(tally.total_votes(), 6.0);
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+
+        tally.restore(checkpoint.clone());
+        assert_eq!(tally.total_votes(), 3.0);
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        // A checkpoint can be restored more than once.
+        tally.add(vec!["Bob"])?;
+        tally.restore(checkpoint);
+        assert_eq!(tally.total_votes(), 3.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_rounds_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(2, Quota::Droop);
+        for _ in 0..4 {
+            tally.add(vec!["Alice", "Bob"])?;
+        }
+        tally.add(vec!["Carol"])?;
+        tally.add(vec!["Carol"])?;
+        tally.add(vec!["Dave"])?;
+
+        // Alice's surplus transfers to Bob, then Bob and Dave are eliminated together and their ballots exhaust.
+        let rounds = tally.rounds();
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[0].totals, vec![("Alice", 4.0), ("Bob", 0.0), ("Carol", 2.0), ("Dave", 1.0)]);
+        assert_eq!(rounds[0].elected, vec!["Alice"]);
+        assert_eq!(rounds[1].totals, vec![("Bob", 1.0), ("Carol", 2.0), ("Dave", 1.0)]);
+        assert_eq!(rounds[1].eliminated, vec!["Bob", "Dave"]);
+        assert_eq!(rounds[1].exhausted, Exhausted { ballots: 5, weight: 2.0 });
+        assert_eq!(rounds[2].elected, vec!["Carol"]);
+        assert_eq!(tally.winners().into_vec(), vec![("Alice", 0), ("Carol", 1)]);
+
+        let flow = |round, from, to, weight| Flow {
+            round: round,
+            from: from,
+            to: to,
+            weight: weight,
+        };
+        assert_eq!(
+            tally.flows(),
+            vec![
+                flow(0, "Alice", Some("Bob"), 1.0),
+                flow(1, "Bob", None, 1.0),
+                flow(1, "Dave", None, 1.0)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_tied_losers_test() -> Result<(), TallyError> {
+        let tally = |num_winners| -> Result<DefaultTally<&str>, TallyError> {
+            let mut tally = DefaultTally::new(num_winners, Quota::Droop);
+            tally.add_weighted(vec!["Alice"], 3.0)?;
+            for candidate in ["Erin", "Dave", "Carol", "Bob"].iter() {
+                tally.add(vec![candidate])?;
+            }
+            Ok(tally)
+        };
+
+        // Candidates tied for last are eliminated together, in the order they were first seen.
+        let rounds = tally(1)?.rounds();
+        assert_eq!(rounds[0].eliminated, vec!["Erin", "Dave", "Carol", "Bob"]);
+        assert_eq!(rounds[1].elected, vec!["Alice"]);
+
+        // Tied candidates that would leave a seat unfilled are elected together instead, in the same order.
+        let rounds = tally(2)?.rounds();
+        assert_eq!(rounds[0].elected, vec!["Alice"]);
+        assert_eq!(rounds[1].elected, vec!["Erin", "Dave", "Carol", "Bob"]);
+        assert!(rounds[1].eliminated.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_cached_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(1, Quota::Droop);
+        tally.add(vec!["Alice", "Bob"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+        assert_eq!(tally.rounds(), tally.rounds());
+
+        // Each change to the tally invalidates the cached count.
+        tally.add_weighted(vec!["Bob", "Alice"], 2.0)?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+        tally.withdraw_candidate(&"Bob");
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_weighted_test() -> Result<(), TallyError> {
+        let ballots = [
+            (vec!["Alice", "Bob", "Cir"], 5),
+            (vec!["Bob", "Alice"], 3),
+            (vec!["Cir", "Dave"], 4),
+            (vec!["Dave", "Cir", "Bob"], 2),
+        ];
+
+        // A weighted vote counts the same as that many identical ballots.
+        let mut weighted = DefaultTally::new(2, Quota::Droop);
+        let mut expanded = DefaultTally::new(2, Quota::Droop);
+        for (ballot, weight) in ballots.iter() {
+            weighted.add_weighted_ref(ballot, *weight as f64)?;
+            for _ in 0..*weight {
+                expanded.add_ref(ballot)?;
+            }
+        }
+        assert_eq!(weighted.winners(), expanded.winners());
+        assert_eq!(weighted.flows(), expanded.flows());
+        let weighted_totals: Vec<_> = weighted.rounds().into_iter().map(|r| r.totals).collect();
+        let expanded_totals: Vec<_> = expanded.rounds().into_iter().map(|r| r.totals).collect();
+        assert_eq!(weighted_totals, expanded_totals);
+
+        // Share weights need not be whole. Cir is eliminated, and their share elects Alice over Bob.
+        let mut shares = DefaultTally::new(1, Quota::Hagenbach);
+        shares.add_weighted(vec!["Alice"], 0.375)?;
+        shares.add_weighted(vec!["Bob"], 0.5)?;
+        shares.add_weighted(vec!["Cir", "Alice"], 0.25)?;
+        assert_eq!(shares.winners().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_with_candidates_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::with_candidates(2, Quota::Droop, vec!["Alice", "Bob", "Cir", "Dave"]);
+        tally.add_candidate("Bob");
+        tally.add_candidates(vec!["Eve"]);
+        tally.add(vec!["Bob", "Alice"])?;
+        tally.add(vec!["Alice"])?;
+        tally.add(vec!["Alice", "Cir"])?;
+
+        // Candidates without votes are counted, in the order they were added, and eliminated first.
+        let rounds = tally.rounds();
+        assert_eq!(
+            rounds[0].totals,
+            vec![("Alice", 2.0), ("Bob", 1.0), ("Cir", 0.0), ("Dave", 0.0), ("Eve", 0.0)]
+        );
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice", "Bob"]);
+
+        // Registered candidates count towards a full ranking.
+        let mut tally = DefaultTally::with_candidates(1, Quota::Droop, vec!["Alice", "Bob"]).with_truncation(Truncation::RequireFull);
+        assert_eq!(
+            tally.add(vec!["Alice"]),
+            Err(TallyError::TruncatedBallot { rankings: 1, required: 2 })
+        );
+
+        // Capacity for no candidates is fine.
+        let mut tally = DefaultTally::with_capacity(1, Quota::Droop, 0, 10);
+        tally.add(vec!["Alice"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_validation_test() -> Result<(), TallyError> {
+        // Candidates are discovered from votes, but a vote may not rank a candidate twice.
+        let mut tally = DefaultTally::new(1, Quota::Droop);
+        tally.add(vec!["Alice", "Bob"])?;
+        assert_eq!(
+            tally.add(vec!["Bob", "Cir", "Bob"]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 2 })
+        );
+        assert_eq!(
+            tally.ranked_add(&[("Cir", 0), ("Cir", 1)]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        assert_eq!(tally.rounds()[0].totals, vec![("Alice", 1.0), ("Bob", 0.0)]);
+
+        // Once candidates are given, votes may only rank them.
+        let mut tally = DefaultTally::with_candidates(1, Quota::Droop, vec!["Alice", "Bob"]);
+        assert_eq!(tally.add(vec!["Alice", "Cir"]), Err(TallyError::UnknownCandidate { position: 1 }));
+        assert_eq!(tally.ranked_add(&[("Dave", 0)]), Err(TallyError::UnknownCandidate { position: 0 }));
+        assert_eq!(tally.check_vote(&["Bob", "Alice"]), Ok(()));
+        tally.add_candidate("Cir");
+        tally.add(vec!["Alice", "Cir"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+
+        // Unchecked tallies take any vote.
+        let mut tally = DefaultTally::with_candidates(1, Quota::Droop, vec!["Alice"])
+            .with_truncation(Truncation::RequireFull)
+            .unchecked();
+        tally.add(vec!["Bob", "Bob"])?;
+        tally.add(vec!["Bob"])?;
+        assert_eq!(tally.winners().into_unranked(), vec!["Bob"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stv_count_sheet_test() -> Result<(), TallyError> {
+        let mut tally = DefaultTally::new(3, Quota::Droop);
+        for _ in 0..8 {
+            tally.add(vec!["Alice", "Cir"])?;
+        }
+        for _ in 0..6 {
+            tally.add(vec!["Bob", "Dave"])?;
+        }
+        tally.add(vec!["Cir"])?;
+        tally.add(vec!["Dave"])?;
+        tally.add(vec!["Eve"])?;
+
+        // 17 votes for 3 seats.
+        let sheet = tally.count_sheet();
+        assert_eq!(sheet.quota, 5.0);
+        assert_eq!(tally.quota(), 5.0);
+        assert_eq!(sheet.rounds, tally.rounds());
+
+        // Alice and Bob are elected in the first round, and their surpluses are transferred in the order candidates were seen.
+        let surpluses: Vec<_> = sheet
+            .surpluses
+            .iter()
+            .map(|s| (s.round, s.candidate, s.votes, s.surplus, s.transfer_value))
+            .collect();
+        assert_eq!(surpluses, vec![(0, "Alice", 8.0, 3.0, 3.0 / 8.0), (0, "Bob", 6.0, 1.0, 1.0 / 6.0)]);
+        for surplus in sheet.surpluses.iter() {
+            let transferred: f64 = tally
+                .flows()
+                .iter()
+                .filter(|f| f.round == surplus.round && f.from == surplus.candidate)
+                .map(|f| f.weight)
+                .sum();
+            assert!((transferred - surplus.surplus).abs() < 1e-9);
+        }
+        assert_eq!(sheet.rounds[0].elected, vec!["Alice", "Bob"]);
+        assert_eq!(tally.winners().into_unranked()[..2], ["Alice", "Bob"]);
 
         Ok(())
     }
@@ -0,0 +1,511 @@
+use std::fs::{self, File};
+use std::hash::Hash;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::intern::CandidateSet;
+use crate::Exhausted;
+use crate::Quota;
+use crate::RankedWinners;
+use crate::Round;
+use crate::TallyError;
+use crate::Truncation;
+
+// Distinguishes the spool files of tallies in the same process.
+static NEXT_SPOOL: AtomicUsize = AtomicUsize::new(0);
+
+/// An STV tally that stores its ballots on disk, for counts with more ballots than fit in memory. `nightly`
+///
+/// Ballots are written to a spool file as they are added, with candidates interned to integer ids.
+/// Only the candidates and their totals are held in memory. Each election or elimination is a single
+/// sequential pass over the spool, transferring ballots to their next continuing preference and writing them
+/// to a new spool. Exhausted ballots are dropped, so the spool shrinks as the count goes on.
+///
+/// The count follows the same rules as [`Tally`](struct.Tally.html), counting votes with `f64`,
+/// and votes are checked the same way before they are spooled.
+/// Errors reading or writing the spool are returned as `TallyError::Io`.
+/// Spool files are created in the system's temporary directory, or in a directory given to [`in_dir()`](#method.in_dir),
+/// and removed when they are no longer needed.
+///
+/// # Example
+/// ```
+///    use tallystick::stv::SpooledTally;
+///    use tallystick::Quota;
+///
+///    let mut tally = SpooledTally::new(2, Quota::Droop)?;
+///    for _ in 0..4 { tally.add(&["Alice", "Bob"])?; }
+///    tally.add(&["Carol"])?;
+///    tally.add(&["Carol"])?;
+///    tally.add(&["Dave"])?;
+///
+///    assert_eq!(tally.winners()?.into_unranked(), vec!["Alice", "Carol"]);
+/// # Ok::<(), tallystick::TallyError>(())
+/// ```
+pub struct SpooledTally<T: Eq + Clone + Hash> {
+    candidates: CandidateSet<T>,
+    fixed_candidates: bool, // Whether votes may only rank candidates added up front
+    truncation: Truncation,
+    num_winners: usize,
+    quota: Quota<f64>,
+    dir: PathBuf,
+    spool: SpoolFile,
+    writer: BufWriter<File>,
+    total: f64,
+}
+
+// The outcome of a count.
+struct Count<T: Clone + Eq> {
+    winners: RankedWinners<T>,
+    rounds: Vec<Round<T, f64>>,
+    exhausted: Vec<Exhausted<f64>>,
+}
+
+impl<T: Eq + Clone + Hash> SpooledTally<T> {
+    /// Create a new spooled tally with the given number of winners, spooling ballots to the system's temporary directory.
+    pub fn new(num_winners: usize, quota: Quota<f64>) -> Result<Self, TallyError> {
+        Self::in_dir(num_winners, quota, std::env::temp_dir())
+    }
+
+    /// Create a new spooled tally with the given number of winners and candidates, spooling ballots to the system's temporary directory.
+    ///
+    /// Votes may only rank the given candidates, as with [`Tally::with_candidates()`](struct.Tally.html#method.with_candidates).
+    pub fn with_candidates(num_winners: usize, quota: Quota<f64>, candidates: Vec<T>) -> Result<Self, TallyError> {
+        let mut tally = Self::new(num_winners, quota)?;
+        tally.candidates = CandidateSet::with_candidates(candidates);
+        tally.fixed_candidates = true;
+        Ok(tally)
+    }
+
+    /// Create a new spooled tally with the given number of winners, spooling ballots to the given directory.
+    pub fn in_dir<P: AsRef<Path>>(num_winners: usize, quota: Quota<f64>, dir: P) -> Result<Self, TallyError> {
+        let dir = dir.as_ref().to_path_buf();
+        let spool = SpoolFile::create(&dir)?;
+        let writer = BufWriter::new(File::create(&spool.path)?);
+        Ok(SpooledTally {
+            candidates: CandidateSet::new(),
+            fixed_candidates: false,
+            truncation: Truncation::Allow,
+            num_winners: num_winners,
+            quota: quota,
+            dir: dir,
+            spool: spool,
+            writer: writer,
+            total: 0.0,
+        })
+    }
+
+    /// Set the policy for ballots that do not rank every candidate, as with [`Tally::with_truncation()`](struct.Tally.html#method.with_truncation).
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Add a vote, ranking candidates in order of preference.
+    pub fn add(&mut self, selection: &[T]) -> Result<(), TallyError> {
+        self.add_weighted(selection, 1.0)
+    }
+
+    /// Add a weighted vote.
+    ///
+    /// The vote is checked with [`check_vote()`](#method.check_vote) before it is spooled.
+    pub fn add_weighted(&mut self, selection: &[T], weight: f64) -> Result<(), TallyError> {
+        self.check_vote(selection)?;
+        if selection.is_empty() {
+            return Ok(());
+        }
+        let ids: Vec<u32> = selection
+            .iter()
+            .map(|candidate| self.candidates.intern(candidate.clone()).0)
+            .collect();
+        write_ballot(&mut self.writer, weight, &ids)?;
+        self.total += weight;
+        Ok(())
+    }
+
+    /// Check the validity of a vote, without adding it.
+    ///
+    /// Votes are checked as by [`Tally::check_vote()`](struct.Tally.html#method.check_vote): for unknown candidates if candidates were given up front,
+    /// for duplicate candidates, and against the truncation policy.
+    pub fn check_vote(&self, selection: &[T]) -> Result<(), TallyError> {
+        let mut new_candidates = 0;
+        for (position, candidate) in selection.iter().enumerate() {
+            if self.candidates.id(candidate).is_none() {
+                if self.fixed_candidates {
+                    return Err(TallyError::UnknownCandidate { position });
+                }
+                new_candidates += 1;
+            }
+        }
+        crate::util::check_duplicates_transitive_vote(selection)?;
+        self.truncation.check(selection.len(), self.candidates.len() + new_candidates)
+    }
+
+    /// Get a ranked list of winners. Winners with the same rank are tied.
+    ///
+    /// Each call recounts the ballots, reading the spool once for each round.
+    pub fn winners(&mut self) -> Result<RankedWinners<T>, TallyError> {
+        Ok(self.count()?.winners)
+    }
+
+    /// Get every round of the count, as returned by [`Tally::rounds()`](struct.Tally.html#method.rounds).
+    pub fn rounds(&mut self) -> Result<Vec<Round<T, f64>>, TallyError> {
+        Ok(self.count()?.rounds)
+    }
+
+    /// Get the ballots that exhausted in each round of the count, as returned by [`Tally::exhausted()`](struct.Tally.html#method.exhausted).
+    pub fn exhausted(&mut self) -> Result<Vec<Exhausted<f64>>, TallyError> {
+        Ok(self.count()?.exhausted)
+    }
+
+    fn count(&mut self) -> io::Result<Count<T>> {
+        self.writer.flush()?;
+        let threshold = self.quota.threshold(self.total, self.num_winners as f64);
+        let num_candidates = self.candidates.len();
+
+        // Candidates are counted in the order they were first seen, which is the order they were interned.
+        let mut continuing = vec![true; num_candidates];
+        let mut totals = vec![0.0; num_candidates];
+        let mut ballot = Ballot::new();
+        let mut reader = BufReader::new(File::open(&self.spool.path)?);
+        while read_ballot(&mut reader, &mut ballot)? {
+            totals[ballot.remaining[0] as usize] += ballot.weight;
+        }
+
+        // The spool of the previous round, or `None` while the ballots are still in the tally's own spool.
+        let mut current: Option<SpoolFile> = None;
+        let mut winners = RankedWinners::new(self.num_winners);
+        let mut rounds = Vec::new();
+        let mut exhausted = Vec::new();
+        let mut rank = 0;
+        loop {
+            let ids: Vec<usize> = (0..num_candidates).filter(|id| continuing[*id]).collect();
+            let mut round = Round {
+                totals: ids.iter().map(|id| (self.candidate(*id), totals[*id])).collect(),
+                elected: Vec::new(),
+                eliminated: Vec::new(),
+                exhausted: Exhausted::new(),
+            };
+
+            // If there are no more candidates left than seats to fill, they are all winners.
+            if ids.len() <= self.num_winners - winners.len() {
+                for id in ids.iter() {
+                    winners.push(self.candidate(*id), rank);
+                }
+                round.elected = round.totals.iter().map(|(c, _)| c.clone()).collect();
+                rounds.push(round);
+                break;
+            }
+
+            let new_winners: Vec<usize> = ids.iter().cloned().filter(|id| totals[*id] >= threshold).collect();
+            if winners.len() + new_winners.len() >= self.num_winners {
+                for id in new_winners.iter() {
+                    winners.push(self.candidate(*id), rank);
+                }
+                round.elected = self.resolve(&new_winners);
+                rounds.push(round);
+                break;
+            }
+
+            // Transfer the surplus of new winners, or else the ballots of the candidates with the fewest votes.
+            let mut factors = vec![None; num_candidates];
+            if !new_winners.is_empty() {
+                for id in new_winners.iter() {
+                    factors[*id] = Some((totals[*id] - threshold) / totals[*id]);
+                    winners.push(self.candidate(*id), rank);
+                }
+                round.elected = self.resolve(&new_winners);
+                rank += 1;
+            } else {
+                let least = ids.iter().map(|id| totals[*id]).fold(f64::INFINITY, f64::min);
+                let losers: Vec<usize> = ids.iter().cloned().filter(|id| totals[*id] <= least).collect();
+
+                // If eliminating the losers would leave seats unfilled, the losers win instead.
+                if ids.len() - losers.len() < self.num_winners - winners.len() {
+                    for id in losers.iter() {
+                        winners.push(self.candidate(*id), rank);
+                    }
+                    round.elected = self.resolve(&losers);
+                    rounds.push(round);
+                    break;
+                }
+                for id in losers.iter() {
+                    factors[*id] = Some(1.0);
+                }
+                round.eliminated = self.resolve(&losers);
+            }
+            for (id, factor) in factors.iter().enumerate() {
+                if factor.is_some() {
+                    continuing[id] = false;
+                }
+            }
+
+            let source = current.as_ref().unwrap_or(&self.spool);
+            let next = SpoolFile::create(&self.dir)?;
+            round.exhausted = transfer(&source.path, &next.path, &factors, &continuing, &mut totals)?;
+            current = Some(next);
+            exhausted.push(round.exhausted.clone());
+            rounds.push(round);
+
+            if winners.len() >= self.num_winners {
+                break;
+            }
+        }
+
+        Ok(Count {
+            winners: winners,
+            rounds: rounds,
+            exhausted: exhausted,
+        })
+    }
+
+    fn candidate(&self, id: usize) -> T {
+        self.candidates.candidates()[id].clone()
+    }
+
+    fn resolve(&self, ids: &[usize]) -> Vec<T> {
+        ids.iter().map(|id| self.candidate(*id)).collect()
+    }
+}
+
+// Copy the ballots in one spool to another, transferring ballots held by candidates with a transfer factor
+// to their next continuing preference, and recounting the totals of continuing candidates.
+// Returns the ballots that exhausted, which are left out of the new spool.
+fn transfer(
+    source: &Path,
+    destination: &Path,
+    factors: &[Option<f64>],
+    continuing: &[bool],
+    totals: &mut [f64],
+) -> io::Result<Exhausted<f64>> {
+    let mut reader = BufReader::new(File::open(source)?);
+    let mut writer = BufWriter::new(File::create(destination)?);
+    let mut exhausted = Exhausted::new();
+    for total in totals.iter_mut() {
+        *total = 0.0;
+    }
+
+    let mut ballot = Ballot::new();
+    while read_ballot(&mut reader, &mut ballot)? {
+        let mut weight = ballot.weight;
+        let mut skip = 0;
+        if let Some(factor) = factors[ballot.remaining[0] as usize] {
+            weight *= factor;
+            skip = ballot
+                .remaining
+                .iter()
+                .position(|id| continuing[*id as usize])
+                .unwrap_or(ballot.remaining.len());
+        }
+
+        let remaining = &ballot.remaining[skip..];
+        match remaining.first() {
+            Some(id) => {
+                totals[*id as usize] += weight;
+                write_ballot(&mut writer, weight, remaining)?;
+            }
+            None => exhausted.add(1, weight),
+        }
+    }
+
+    writer.flush()?;
+    Ok(exhausted)
+}
+
+// A ballot read from a spool: its current weight, and its preferences from the candidate currently holding it.
+struct Ballot {
+    weight: f64,
+    remaining: Vec<u32>,
+}
+
+impl Ballot {
+    fn new() -> Self {
+        Ballot {
+            weight: 0.0,
+            remaining: Vec::new(),
+        }
+    }
+}
+
+// Ballots are stored as a little-endian `f64` weight, a `u32` count of preferences, and a `u32` id for each preference.
+fn write_ballot<W: Write>(writer: &mut W, weight: f64, ids: &[u32]) -> io::Result<()> {
+    writer.write_all(&weight.to_le_bytes())?;
+    writer.write_all(&(ids.len() as u32).to_le_bytes())?;
+    for id in ids {
+        writer.write_all(&id.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+// Read the next ballot into `ballot`, reusing its storage. Returns `false` at the end of the spool.
+fn read_ballot<R: Read>(reader: &mut R, ballot: &mut Ballot) -> io::Result<bool> {
+    let mut weight = [0; 8];
+    match reader.read_exact(&mut weight) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+        Err(e) => return Err(e),
+    }
+    ballot.weight = f64::from_le_bytes(weight);
+
+    let mut word = [0; 4];
+    reader.read_exact(&mut word)?;
+    let len = u32::from_le_bytes(word);
+    ballot.remaining.clear();
+    for _ in 0..len {
+        reader.read_exact(&mut word)?;
+        ballot.remaining.push(u32::from_le_bytes(word));
+    }
+    Ok(true)
+}
+
+// A spool file, removed when dropped.
+struct SpoolFile {
+    path: PathBuf,
+}
+
+impl SpoolFile {
+    fn create(dir: &Path) -> io::Result<Self> {
+        let name = format!(
+            "tallystick-stv-{}-{}.spool",
+            process::id(),
+            NEXT_SPOOL.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = dir.join(name);
+        File::create(&path)?;
+        Ok(SpoolFile { path: path })
+    }
+}
+
+impl Drop for SpoolFile {
+    fn drop(&mut self) {
+        // The file may already be gone, and there is nothing useful to do about a failure here.
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stv::DefaultTally;
+
+    // Add the same ballots to a spooled tally and to a `Tally`, check that they count them the same way,
+    // and return the number of rounds in the count.
+    fn assert_matches_tally(num_winners: usize, ballots: Vec<(Vec<&'static str>, f64)>) -> Result<usize, TallyError> {
+        let mut tally = DefaultTally::new(num_winners, Quota::Droop);
+        let mut spooled = SpooledTally::new(num_winners, Quota::Droop)?;
+        for (ballot, weight) in ballots {
+            spooled.add_weighted(&ballot, weight)?;
+            tally.add_weighted(ballot, weight)?;
+        }
+
+        assert_eq!(spooled.rounds()?, tally.rounds());
+        assert_eq!(spooled.exhausted()?, tally.exhausted());
+        // Tally elects the last candidates standing in no particular order, so compare winners within each rank as sets.
+        let sorted = |winners: RankedWinners<&'static str>| {
+            let mut winners: Vec<(usize, &str)> = winners.iter().map(|r| (r.rank, r.candidate)).collect();
+            winners.sort();
+            winners
+        };
+        assert_eq!(sorted(spooled.winners()?), sorted(tally.winners()));
+        Ok(tally.rounds().len())
+    }
+
+    #[test]
+    fn spooled_matches_tally() -> Result<(), TallyError> {
+        assert_matches_tally(
+            3,
+            vec![
+                (vec!["Alice", "Bob", "Carol"], 5.0),
+                (vec!["Alice", "Dave"], 3.0),
+                (vec!["Bob", "Erin"], 2.0),
+                (vec!["Carol", "Erin", "Bob"], 2.5),
+                (vec!["Dave"], 1.5),
+                (vec!["Erin", "Dave", "Carol"], 2.0),
+            ],
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn spooled_multi_round_transfers() -> Result<(), TallyError> {
+        // Surpluses and eliminations are transferred over several rounds, and ballots pass through
+        // candidates who are already elected or eliminated before they exhaust.
+        let rounds = assert_matches_tally(
+            3,
+            vec![
+                (vec!["Alice", "Bob", "Carol", "Dave"], 12.0),
+                (vec!["Alice", "Erin", "Frank"], 4.0),
+                (vec!["Bob", "Alice", "Frank"], 3.0),
+                (vec!["Carol", "Dave"], 3.5),
+                (vec!["Dave", "Carol", "Erin"], 3.0),
+                (vec!["Erin", "Frank", "Bob"], 2.5),
+                (vec!["Frank", "Erin"], 2.0),
+                (vec!["Frank", "Alice"], 1.0),
+                (vec!["Gina"], 0.5),
+            ],
+        )?;
+        assert!(rounds > 3);
+
+        // A single seat, filled by eliminating candidates one at a time.
+        let rounds = assert_matches_tally(
+            1,
+            vec![
+                (vec!["Alice", "Carol"], 4.0),
+                (vec!["Bob", "Carol", "Alice"], 3.5),
+                (vec!["Carol", "Bob"], 2.0),
+                (vec!["Dave", "Bob"], 1.5),
+                (vec!["Erin"], 1.0),
+            ],
+        )?;
+        assert!(rounds > 3);
+        Ok(())
+    }
+
+    #[test]
+    fn spooled_validation() -> Result<(), TallyError> {
+        let mut tally =
+            SpooledTally::with_candidates(1, Quota::Droop, vec!["Alice", "Bob", "Carol"])?.with_truncation(Truncation::Minimum(2));
+        tally.add(&["Alice", "Bob"])?;
+        assert_eq!(tally.add(&["Alice", "Dave"]), Err(TallyError::UnknownCandidate { position: 1 }));
+        assert_eq!(
+            tally.add(&["Bob", "Alice", "Bob"]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 2 })
+        );
+        assert_eq!(tally.add(&["Carol"]), Err(TallyError::TruncatedBallot { rankings: 1, required: 2 }));
+        tally.add(&["Carol", "Bob"])?;
+
+        // Rejected votes are not counted, and candidates given up front take part in the count.
+        let mut checked = DefaultTally::with_candidates(1, Quota::Droop, vec!["Alice", "Bob", "Carol"]);
+        checked.add(vec!["Alice", "Bob"])?;
+        checked.add(vec!["Carol", "Bob"])?;
+        assert_eq!(tally.rounds()?, checked.rounds());
+
+        // Without candidates, any candidate may be ranked, but duplicates are still rejected.
+        let mut tally = SpooledTally::new(1, Quota::Droop)?;
+        tally.add(&["Dave"])?;
+        assert_eq!(
+            tally.add(&["Erin", "Erin"]),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn spooled_io_error() -> Result<(), TallyError> {
+        let dir = std::env::temp_dir().join(format!("tallystick-missing-{}", process::id()));
+        match SpooledTally::<&str>::in_dir(1, Quota::Droop, &dir) {
+            Err(TallyError::Io { kind, .. }) => assert_eq!(kind, io::ErrorKind::NotFound),
+            _ => panic!("expected an I/O error"),
+        }
+
+        // Spool files are removed with the tally.
+        let mut tally = SpooledTally::new(1, Quota::Droop)?;
+        tally.add(&["Alice"])?;
+        let path = tally.spool.path.clone();
+        assert!(path.exists());
+        drop(tally);
+        assert!(!path.exists());
+        Ok(())
+    }
+}
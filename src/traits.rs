@@ -1,5 +1,11 @@
+#[cfg(all(feature = "nightly", feature = "rational"))]
+use num_integer::Integer;
+#[cfg(all(feature = "nightly", feature = "rational"))]
+use num_rational::Ratio;
 use num_traits::real::Real;
 use num_traits::Num;
+#[cfg(all(feature = "nightly", feature = "rational"))]
+use num_traits::{Bounded, Zero};
 
 /// A trait for numeric types used to count votes.
 ///
@@ -9,6 +15,8 @@ use num_traits::Num;
 /// You should pobably not implement this trait. If you have a numeric type that does not implement `Numeric`,
 /// you should instead implement [`num_traits::Num`](/num-traits/latest/num_traits/trait.Num.html)
 /// (and optionally [`num_traits::real::Real`](/num-traits/latest/num_traits/real/trait.Real.html) for types that support fractions.)
+///
+/// With the `rational` feature enabled, `Numeric` also supports fractions for `num_rational::Ratio`.
 pub trait Numeric {
     /// Get the floor for this numeric type.
     /// For non-fractional types, this just returns self.
@@ -24,23 +32,24 @@ pub trait Numeric {
     fn max_value() -> Self;
 }
 
-// Default implemention of numeric, assumes everything is an integer (non-fraction).
+// Default implemention of numeric, assumes everything is an integer (non-fraction),
+// unless it is specialized by `NotReal` below.
 #[cfg(feature = "nightly")]
 impl<T: Num> Numeric for T {
     default fn floor(self) -> Self {
-        self
+        NotReal::floor(self)
     }
     default fn fraction() -> bool {
-        false
+        <T as NotReal>::fraction()
     }
 
     default fn max_value() -> Self {
-        T::zero()
+        <T as NotReal>::max_value()
     }
 }
 
 // Specialize Numeric using Real.
-// Real covers all floats, as well as things like num_rational::Ratio and num_rational::BigRational.
+// Real covers all floats.
 impl<T: Num + Real> Numeric for T {
     fn floor(self) -> Self {
         self.floor()
@@ -53,5 +62,51 @@ impl<T: Num + Real> Numeric for T {
     }
 }
 
+// Numeric for count types that do not implement Real.
+// Types that support fractions without being Real, such as num_rational::Ratio, specialize this trait rather than Numeric,
+// since an impl of Numeric for Ratio would conflict with the Real impl above if Ratio were ever to implement Real.
+#[cfg(feature = "nightly")]
+trait NotReal {
+    fn floor(self) -> Self;
+    fn fraction() -> bool;
+    fn max_value() -> Self;
+}
+
+// Everything that is not Real is an integer, unless specialized below.
+#[cfg(feature = "nightly")]
+impl<T: Num> NotReal for T {
+    default fn floor(self) -> Self {
+        self
+    }
+    default fn fraction() -> bool {
+        false
+    }
+    default fn max_value() -> Self {
+        T::zero()
+    }
+}
+
+// Specialize NotReal for num_rational::Ratio, which supports fractions but does not implement Real.
+// Ratios over unbounded integers (eg `BigRational`) have no upper bound, so max_value returns zero.
+#[cfg(all(feature = "nightly", feature = "rational"))]
+impl<T: Clone + Integer> NotReal for Ratio<T> {
+    fn floor(self) -> Self {
+        Ratio::floor(&self)
+    }
+    fn fraction() -> bool {
+        true
+    }
+    default fn max_value() -> Self {
+        Self::zero()
+    }
+}
+
+// Ratios over bounded integers (eg `Rational64`) are bounded by the integer's max value.
+#[cfg(all(feature = "nightly", feature = "rational"))]
+impl<T: Clone + Integer + Bounded> NotReal for Ratio<T> {
+    fn max_value() -> Self {
+        Ratio::from_integer(T::max_value())
+    }
+}
+
 // TODO: no_std: should swap Real for num_traits::float::FloatCore
-// TODO: rational: Check that Ratio implements Real
@@ -0,0 +1,79 @@
+use super::TallyError;
+
+/// A truncation policy decides how a ranked tally treats ballots that do not rank every candidate.
+///
+/// By default truncated ballots are allowed. Many election rules instead require voters to rank every candidate,
+/// or to express a minimum number of preferences, and ballots that do not are rejected with `TallyError::TruncatedBallot`.
+///
+/// # Example
+/// ```
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::{TallyError, Truncation};
+///
+///    // Voters must rank at least two candidates.
+///    let candidates = vec!["Alice", "Bob", "Carlos"];
+///    let mut tally = DefaultCondorcetTally::with_candidates(1, candidates).with_truncation(Truncation::Minimum(2));
+///    tally.add(&["Alice", "Bob", "Carlos"])?;
+///    tally.add(&["Bob", "Alice"])?;
+///
+///    let error = tally.add(&["Carlos"]).unwrap_err();
+///    assert_eq!(error, TallyError::TruncatedBallot { rankings: 1, required: 2 });
+///    # Ok::<(), TallyError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Truncation {
+    /// Allow ballots that rank any number of candidates.
+    #[default]
+    Allow,
+
+    /// Require every ballot to rank every candidate known to the tally.
+    RequireFull,
+
+    /// Require every ballot to rank at least this many candidates.
+    ///
+    /// If there are fewer candidates than this, ballots must rank every candidate.
+    Minimum(usize),
+}
+
+impl Truncation {
+    /// Get the number of rankings a ballot must have in an election with `num_candidates` candidates.
+    pub fn required(&self, num_candidates: usize) -> usize {
+        match self {
+            Truncation::Allow => 0,
+            Truncation::RequireFull => num_candidates,
+            Truncation::Minimum(minimum) => std::cmp::min(*minimum, num_candidates),
+        }
+    }
+
+    /// Check that a ballot with `rankings` ranked candidates satisfies this policy.
+    pub fn check(&self, rankings: usize, num_candidates: usize) -> Result<(), TallyError> {
+        let required = self.required(num_candidates);
+        if rankings < required {
+            return Err(TallyError::TruncatedBallot { rankings, required });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_test() {
+        assert_eq!(Truncation::default(), Truncation::Allow);
+        assert!(Truncation::Allow.check(0, 5).is_ok());
+
+        assert!(Truncation::RequireFull.check(5, 5).is_ok());
+        assert_eq!(
+            Truncation::RequireFull.check(4, 5),
+            Err(TallyError::TruncatedBallot { rankings: 4, required: 5 })
+        );
+
+        assert!(Truncation::Minimum(3).check(3, 5).is_ok());
+        assert!(Truncation::Minimum(3).check(2, 2).is_ok());
+        assert_eq!(Truncation::Minimum(3).required(2), 2);
+        assert!(Truncation::Minimum(3).check(1, 5).is_err());
+    }
+}
@@ -1,24 +1,57 @@
 pub use crate::errors::ParseError;
 use crate::errors::TallyError;
+use hashbrown::HashSet;
+use num_traits::FromPrimitive;
 use num_traits::Num;
+use num_traits::One;
 use std::convert::TryInto;
+use std::fmt;
+use std::marker::PhantomData;
 
 use std::io::BufRead;
 use std::io::BufReader;
+use std::io::Write;
 
-/// A parsed vote, either ranked or unranked.
-#[derive(Debug)]
-pub enum ParsedVote {
+use crate::ballotbox::Ballot;
+
+mod blt;
+pub use self::blt::{read_blt, write_blt, Blt};
+
+mod preflib;
+pub use self::preflib::{read_preflib, PrefLib, PrefLibType};
+
+#[cfg(feature = "csv")]
+mod csv_ballots;
+#[cfg(feature = "csv")]
+pub use self::csv_ballots::{read_votes_csv, CsvBallot, CsvFormat, CsvLayout};
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "json")]
+pub use self::json::{read_votes_json, JsonBallot, JsonBallots, JsonRank, JsonSelection};
+
+#[cfg(feature = "json")]
+mod nist_cvr;
+#[cfg(feature = "json")]
+pub use self::nist_cvr::{read_nist_cvr, CvrContest};
+
+/// A parsed vote, either ranked, unranked, or scored.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedVote<C> {
     /// An unranked vote. Candidates are returned in preferential order, with the most significant selection first.
     Unranked(Vec<String>),
 
     /// A ranked vote as (candidate, rank) pairs. Ranks are ordered ascending, so that the most significant rank is rank 0.
     Ranked(Vec<(String, u32)>),
+
+    /// A scored vote as (candidate, score) pairs, in the order they were listed.
+    Scored(Vec<(String, C)>),
 }
 
-impl ParsedVote {
+impl<C: PartialOrd> ParsedVote<C> {
     /// Convert unranked ParsedVote into a ranked parsed vote.
-    /// This is a no-op if the vote is already ranked
+    /// This is a no-op if the vote is already ranked.
+    /// Scored votes are ranked from the highest score to the lowest, with equal scores ranked equally.
     pub fn into_ranked(self) -> Vec<(String, u32)> {
         match self {
             ParsedVote::Ranked(ranked) => ranked,
@@ -30,61 +63,393 @@ impl ParsedVote {
                 }
                 ranked
             }
+            ParsedVote::Scored(mut scored) => {
+                scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                let mut ranked = Vec::<(String, u32)>::with_capacity(scored.len());
+                let mut rank = 0;
+                let mut last_score: Option<C> = None;
+                for (candidate, score) in scored.drain(..) {
+                    if last_score.as_ref().is_some_and(|last| *last != score) {
+                        rank += 1;
+                    }
+                    ranked.push((candidate, rank));
+                    last_score = Some(score);
+                }
+                ranked
+            }
+        }
+    }
+}
+
+impl<C: fmt::Display> fmt::Display for ParsedVote<C> {
+    /// Format the vote as it is read by `read_votes`, such as `Alice > Bob = Carol`, or `Alice:5 Bob:3`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsedVote::Unranked(unranked) => write!(f, "{}", unranked.join(" > ")),
+            ParsedVote::Scored(scored) => {
+                for (i, (candidate, score)) in scored.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}:{}", candidate, score)?;
+                }
+                Ok(())
+            }
+            ParsedVote::Ranked(ranked) => {
+                let mut sorted: Vec<&(String, u32)> = ranked.iter().collect();
+                sorted.sort_by_key(|(_, rank)| *rank);
+                for (i, (candidate, rank)) in sorted.iter().enumerate() {
+                    if i > 0 {
+                        let separator = if *rank == sorted[i - 1].1 { " = " } else { " > " };
+                        write!(f, "{}", separator)?;
+                    }
+                    write!(f, "{}", candidate)?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
-/// Read votes from a reader, parsing them and returning a vector of parsed votes and their weights.
+/// The delimiters used in a vote file.
+///
+/// By default votes are written as `Alice > Bob = Carol * 2`, with `>` separating preferences, `=` joining equally ranked
+/// candidates, and `*` marking the weight. Each delimiter can be changed to read files in other formats.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_votes_with_config, ParsedVote, ParserConfig};
 ///
-/// TODO: Add Example
-pub fn read_votes<T: std::io::Read, C: Num>(votes: T) -> Result<Vec<(ParsedVote, C)>, ParseError> {
-    let reader = BufReader::new(votes);
+///    let config = ParserConfig::new().with_preference_separator(';').with_equality_separator('~');
+///    let parsed = read_votes_with_config::<_, u32>("Alice ; Bob ~ Carol * 3\n".as_bytes(), &config)?;
+///    assert_eq!(
+///        parsed.votes[0],
+///        (ParsedVote::Ranked(vec![("Alice".to_string(), 0), ("Bob".to_string(), 1), ("Carol".to_string(), 1)]), 3)
+///    );
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserConfig {
+    weight_marker: char,
+    preference_separator: char,
+    equality_separator: char,
+    candidates: Option<HashSet<String>>,
+}
 
-    let mut res = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-        if !line.trim().is_empty() {
-            res.push(parse_line_into_vote(&line)?)
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParserConfig {
+    /// Create the default configuration, using `*`, `>` and `=`.
+    pub fn new() -> Self {
+        ParserConfig {
+            weight_marker: '*',
+            preference_separator: '>',
+            equality_separator: '=',
+            candidates: None,
         }
     }
 
-    Ok(res)
+    /// Mark the weight of a vote with a character other than `*`.
+    pub fn with_weight_marker(mut self, marker: char) -> Self {
+        self.weight_marker = marker;
+        self
+    }
+
+    /// Separate preferences with a character other than `>`.
+    pub fn with_preference_separator(mut self, separator: char) -> Self {
+        self.preference_separator = separator;
+        self
+    }
+
+    /// Join equally ranked candidates with a character other than `=`.
+    pub fn with_equality_separator(mut self, separator: char) -> Self {
+        self.equality_separator = separator;
+        self
+    }
+
+    /// Only accept votes for the given candidates.
+    ///
+    /// A vote for any other candidate is reported as a `ParseError::UnknownCandidate`, with the line and column of the candidate.
+    ///
+    /// # Example
+    /// ```
+    ///    use tallystick::util::{read_votes_with_config, ParseError, ParserConfig};
+    ///
+    ///    let config = ParserConfig::new().with_candidates(vec!["Alice".to_string(), "Bob".to_string()]);
+    ///    let error = read_votes_with_config::<_, u32>("Alice > Bob\nBob > Carol\n".as_bytes(), &config).unwrap_err();
+    ///    assert_eq!((error.line(), error.column()), (Some(2), Some(7)));
+    ///    match error {
+    ///        ParseError::InvalidLine { error, .. } => assert!(matches!(*error, ParseError::UnknownCandidate(c) if c == "Carol")),
+    ///        _ => unreachable!(),
+    ///    }
+    /// ```
+    pub fn with_candidates(mut self, candidates: Vec<String>) -> Self {
+        self.candidates = Some(candidates.into_iter().collect());
+        self
+    }
+
+    // Check a candidate against the candidates accepted, if any.
+    fn check_candidate<'a>(&self, candidate: &'a str) -> Result<(), (&'a str, ParseError)> {
+        match &self.candidates {
+            Some(candidates) if !candidates.contains(candidate) => Err((candidate, ParseError::UnknownCandidate(candidate.to_string()))),
+            _ => Ok(()),
+        }
+    }
 }
 
-fn parse_line_into_vote<C: Num>(line: &str) -> Result<(ParsedVote, C), ParseError> {
-    let parts: Vec<&str> = line.trim().split('*').collect();
+/// Votes read by `read_votes`, with the candidates declared in the file's header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedVotes<C> {
+    /// The candidates declared in the header, in the order they were declared. Empty if the file declares no candidates.
+    pub candidates: Vec<String>,
 
-    let weight;
-    if parts.len() == 1 {
-        weight = C::one();
-    } else {
-        weight = match C::from_str_radix(parts[1].trim(), 10) {
-            Ok(num) => num,
-            Err(_) => {
-                return Err(ParseError::ParseError(parts[1].trim().to_string()));
+    /// The parsed votes and their weights.
+    pub votes: Vec<(ParsedVote<C>, C)>,
+}
+
+/// Read votes from a reader, parsing them and returning the declared candidates, and a vector of parsed votes and their weights.
+///
+/// Each line holds one vote, optionally followed by `*` and its weight:
+///  - An unranked vote lists candidates in order of preference, separated by `>`, such as `Alice > Bob > Carol * 2`.
+///  - A ranked vote also joins equally ranked candidates with `=`, such as `Alice = Bob > Carol`.
+///  - A scored vote gives each candidate a score after a `:`, such as `Alice:5 Bob:3 * 2`.
+///
+/// Lines starting with `#` are comments. Before the first vote, a header may declare the candidates standing, with one or more
+/// `@candidates` lines listing them separated by commas, such as `@candidates Alice, Bob, Carol`.
+///
+/// To parse votes one at a time, without holding them all in memory, use a [`VoteReader`](struct.VoteReader.html).
+///
+/// # Example
+/// ```
+///    use tallystick::score::DefaultScoreTally;
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::util::{read_votes, ParsedVote};
+///
+///    let file = [
+///        "# Club election",
+///        "@candidates Alice, Bob, Carol",
+///        "Alice > Bob * 2",
+///        "",
+///        "Bob = Carol",
+///    ]
+///    .join("\n");
+///
+///    let parsed = read_votes::<_, u64>(file.as_bytes())?;
+///    assert_eq!(parsed.votes.len(), 2);
+///    assert_eq!(parsed.votes[0], (ParsedVote::Unranked(vec!["Alice".to_string(), "Bob".to_string()]), 2));
+///
+///    let mut tally = DefaultCondorcetTally::with_candidates(1, parsed.candidates);
+///    for (vote, weight) in parsed.votes {
+///        tally.ranked_add_weighted(&vote.into_ranked(), weight)?;
+///    }
+///    assert_eq!(tally.winners().into_unranked(), vec!["Alice".to_string()]);
+///
+///    let parsed = read_votes::<_, u64>("Alice:5 Bob:3 * 2\nBob:3\n".as_bytes())?;
+///    let mut tally = DefaultScoreTally::new(1);
+///    for (vote, weight) in parsed.votes {
+///        if let ParsedVote::Scored(scores) = vote {
+///            tally.add_weighted(scores, weight)?;
+///        }
+///    }
+///    assert_eq!(tally.winners().into_unranked(), vec!["Alice".to_string()]);
+///    # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn read_votes<T: std::io::Read, C: Num>(votes: T) -> Result<ParsedVotes<C>, ParseError> {
+    read_votes_with_config(votes, &ParserConfig::new())
+}
+
+/// Read votes from a reader, like `read_votes`, using the given delimiters.
+///
+/// See [`ParserConfig`](struct.ParserConfig.html) for an example.
+pub fn read_votes_with_config<T: std::io::Read, C: Num>(votes: T, config: &ParserConfig) -> Result<ParsedVotes<C>, ParseError> {
+    let mut reader = VoteReader::with_config(votes, config.clone());
+    let votes = reader.by_ref().collect::<Result<_, _>>()?;
+    Ok(ParsedVotes {
+        candidates: reader.candidates,
+        votes: votes,
+    })
+}
+
+/// Read votes from a reader, like `read_votes_with_config`, collecting invalid lines rather than stopping at the first one.
+///
+/// Returns the votes from every valid line, and an error for every invalid line, with its line number and column.
+/// Errors reading from the reader still stop reading and are returned.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_votes_lenient, ParserConfig};
+///
+///    let file = "Alice > Bob * 2\nBob * two\nCarol:5 Alice:x\n";
+///    let (parsed, errors) = read_votes_lenient::<_, u32>(file.as_bytes(), &ParserConfig::new())?;
+///    assert_eq!(parsed.votes.len(), 1);
+///    assert_eq!(errors.len(), 2);
+///    assert_eq!((errors[0].line(), errors[0].column()), (Some(2), Some(7)));
+///    assert_eq!((errors[1].line(), errors[1].column()), (Some(3), Some(15)));
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub fn read_votes_lenient<T: std::io::Read, C: Num>(
+    votes: T,
+    config: &ParserConfig,
+) -> Result<(ParsedVotes<C>, Vec<ParseError>), ParseError> {
+    let mut reader = VoteReader::with_config(votes, config.clone());
+    let mut parsed = Vec::new();
+    let mut errors = Vec::new();
+    for vote in reader.by_ref() {
+        match vote {
+            Ok(vote) => parsed.push(vote),
+            Err(e @ ParseError::InvalidLine { .. }) => errors.push(e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let parsed = ParsedVotes {
+        candidates: reader.candidates,
+        votes: parsed,
+    };
+    Ok((parsed, errors))
+}
+
+/// An iterator that reads and parses votes one line at a time, in the format read by `read_votes`.
+///
+/// Blank lines and comments are skipped. Each item is a parsed vote and its weight, or the error from reading or parsing a line.
+/// Parse errors are `ParseError::InvalidLine`, giving the line number and column of the problem.
+/// Reading continues after a parse error, so an invalid line can be reported and skipped.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::util::VoteReader;
+///
+///    let file = "@candidates Alice, Bob\nAlice > Bob\nBob > Alice * 3\nAlice\n";
+///
+///    let mut reader = VoteReader::<_, u64>::new(file.as_bytes());
+///    let mut tally = DefaultPluralityTally::new(1);
+///    for vote in reader.by_ref() {
+///        let (vote, weight) = vote?;
+///        tally.add_weighted(vote.into_ranked().remove(0).0, weight);
+///    }
+///    assert_eq!(reader.candidates(), ["Alice", "Bob"]);
+///    assert_eq!(tally.totals(), vec![("Bob".to_string(), 3), ("Alice".to_string(), 2)]);
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub struct VoteReader<R, C> {
+    lines: std::io::Lines<BufReader<R>>,
+    config: ParserConfig,
+    candidates: Vec<String>,
+    in_header: bool,
+    line_number: usize,
+    count: PhantomData<C>,
+}
+
+impl<R: std::io::Read, C: Num> VoteReader<R, C> {
+    /// Create a new `VoteReader`, buffering the given reader.
+    pub fn new(votes: R) -> Self {
+        Self::with_config(votes, ParserConfig::new())
+    }
+
+    /// Create a new `VoteReader` that reads votes using the given delimiters.
+    pub fn with_config(votes: R, config: ParserConfig) -> Self {
+        VoteReader {
+            lines: BufReader::new(votes).lines(),
+            config: config,
+            candidates: Vec::new(),
+            in_header: true,
+            line_number: 0,
+            count: PhantomData,
+        }
+    }
+
+    /// Get the candidates declared in the header.
+    /// The header is read along with the first vote, so this is empty until `next` has been called.
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+}
+
+impl<R: std::io::Read, C: Num> Iterator for VoteReader<R, C> {
+    type Item = Result<(ParsedVote<C>, C), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for raw in self.lines.by_ref() {
+            let raw = match raw {
+                Ok(raw) => raw,
+                Err(e) => return Some(Err(e.into())),
+            };
+            self.line_number += 1;
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
             }
+
+            let parsed = match line.strip_prefix("@candidates") {
+                Some(_) if !self.in_header => Err((line, ParseError::ParseError(line.to_string()))),
+                Some(declared) => {
+                    let declared = declared.split(',').map(str::trim).filter(|candidate| !candidate.is_empty());
+                    self.candidates.extend(declared.map(String::from));
+                    continue;
+                }
+                None => {
+                    self.in_header = false;
+                    parse_line_into_vote(line, &self.config)
+                }
+            };
+
+            return Some(parsed.map_err(|(text, error)| locate(error, self.line_number, &raw, text)));
         }
+        None
     }
+}
+
+// Attach the location of the offending text to an error. The text must be a slice of the raw line, or it is located at its start.
+pub(crate) fn locate(error: ParseError, line_number: usize, raw: &str, text: &str) -> ParseError {
+    let offset = (text.as_ptr() as usize)
+        .checked_sub(raw.as_ptr() as usize)
+        .filter(|offset| *offset <= raw.len() && raw.is_char_boundary(*offset))
+        .unwrap_or(0);
+    error.on_line(line_number, raw[..offset].chars().count() + 1, raw)
+}
+
+// Parse a line into a vote. On error, also returns the offending slice of the line.
+fn parse_line_into_vote<'a, C: Num>(line: &'a str, config: &ParserConfig) -> Result<(ParsedVote<C>, C), (&'a str, ParseError)> {
+    let parts: Vec<&str> = line.trim().split(config.weight_marker).collect();
 
+    let weight = if parts.len() == 1 {
+        C::one()
+    } else {
+        parse_weight(parts[1]).map_err(|e| (parts[1].trim(), e))?
+    };
+
+    if parts[0].contains(':') {
+        return Ok((ParsedVote::Scored(parse_scores(parts[0], config)?), weight));
+    }
+
+    let body = parts[0].trim();
     let mut vote = Vec::<(String, u32)>::new();
-    let mut candidate_buf = String::new();
+    let mut start = 0;
     let mut rank = 0;
     let mut is_ranked = false;
-    for c in parts[0].trim().chars() {
-        if c == '>' {
-            vote.push((candidate_buf.trim().to_string(), rank));
-            candidate_buf.clear();
+    for (i, c) in body.char_indices() {
+        if c != config.preference_separator && c != config.equality_separator {
+            continue;
+        }
+        let candidate = body[start..i].trim();
+        config.check_candidate(candidate)?;
+        vote.push((candidate.to_string(), rank));
+        if c == config.preference_separator {
             rank += 1;
-        } else if c == '=' {
-            vote.push((candidate_buf.trim().to_string(), rank));
-            candidate_buf.clear();
-            is_ranked = true;
         } else {
-            candidate_buf.push(c);
+            is_ranked = true;
         }
+        start = i + c.len_utf8();
     }
-    if !candidate_buf.trim().is_empty() {
-        vote.push((candidate_buf.trim().to_string(), rank));
+    let candidate = body[start..].trim();
+    if !candidate.is_empty() {
+        config.check_candidate(candidate)?;
+        vote.push((candidate.to_string(), rank));
     }
 
     if is_ranked {
@@ -98,28 +463,412 @@ fn parse_line_into_vote<C: Num>(line: &str) -> Result<(ParsedVote, C), ParseErro
     }
 }
 
+// Parse a scored vote such as `Alice:5 Bob Smith:3`. Candidate names may contain spaces, but not colons.
+fn parse_scores<'a, C: Num>(vote: &'a str, config: &ParserConfig) -> Result<Vec<(String, C)>, (&'a str, ParseError)> {
+    let segments: Vec<&str> = vote.split(':').collect();
+    let mut scored = Vec::with_capacity(segments.len() - 1);
+    let mut candidate = segments[0].trim();
+    for (i, segment) in segments[1..].iter().enumerate() {
+        let segment = segment.trim();
+        // Every segment but the last holds a score followed by the next candidate.
+        let (score, next) = if i + 2 == segments.len() {
+            (segment, "")
+        } else {
+            segment.split_once(char::is_whitespace).unwrap_or((segment, ""))
+        };
+        if candidate.is_empty() {
+            return Err((segment, ParseError::ParseError(vote.trim().to_string())));
+        }
+        config.check_candidate(candidate)?;
+        scored.push((candidate.to_string(), parse_weight(score).map_err(|e| (score, e))?));
+        candidate = next.trim();
+    }
+    Ok(scored)
+}
+
+/// Write votes and their weights, one per line, in the format read by `read_votes`.
+///
+/// Weights of one are left out. Returns an error of kind `InvalidInput` if a candidate name is empty, or contains a `>`, `=`, `*`,
+/// `:` or line break, since it could not be read back.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_votes, write_votes, ParsedVote};
+///
+///    let votes = vec![
+///        (ParsedVote::Unranked(vec!["Alice".to_string(), "Bob".to_string()]), 5),
+///        (ParsedVote::Ranked(vec![("Bob".to_string(), 0), ("Carol".to_string(), 0), ("Alice".to_string(), 1)]), 1),
+///    ];
+///
+///    let mut file = Vec::new();
+///    write_votes(&mut file, &votes)?;
+///    assert_eq!(String::from_utf8(file.clone()).unwrap(), "Alice > Bob * 5\nBob = Carol > Alice\n");
+///    assert_eq!(read_votes::<_, u32>(file.as_slice()).unwrap().votes, votes);
+///    # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_votes<W: Write, C: fmt::Display + One + PartialEq>(mut writer: W, votes: &[(ParsedVote<C>, C)]) -> std::io::Result<()> {
+    for (vote, weight) in votes.iter() {
+        match vote {
+            ParsedVote::Unranked(unranked) => unranked.iter().try_for_each(|candidate| check_writable(candidate))?,
+            ParsedVote::Ranked(ranked) => ranked.iter().try_for_each(|(candidate, _)| check_writable(candidate))?,
+            ParsedVote::Scored(scored) => scored.iter().try_for_each(|(candidate, _)| check_writable(candidate))?,
+        }
+        if weight.is_one() {
+            writeln!(writer, "{}", vote)?;
+        } else {
+            writeln!(writer, "{} * {}", vote, weight)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write ballots, such as those stored in a [`BallotBox`](../ballotbox/struct.BallotBox.html), in the format read by `read_votes`.
+///
+/// Returns the same errors as `write_votes`.
+///
+/// # Example
+/// ```
+///    use tallystick::ballotbox::BallotBox;
+///    use tallystick::util::write_ballots;
+///
+///    let mut ballot_box = BallotBox::<&str, u64>::new();
+///    ballot_box.add_weighted(vec!["Alice", "Bob"], 2).unwrap();
+///    ballot_box.scored_add(vec![("Alice", 3), ("Bob", 5), ("Carol", 3)]).unwrap();
+///
+///    let mut file = Vec::new();
+///    write_ballots(&mut file, ballot_box.ballots())?;
+///    assert_eq!(String::from_utf8(file).unwrap(), "Alice > Bob * 2\nAlice:3 Bob:5 Carol:3\n");
+///    # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_ballots<W, T, C>(writer: W, ballots: &[(Ballot<T, C>, C)]) -> std::io::Result<()>
+where
+    W: Write,
+    T: fmt::Display + Clone,
+    C: fmt::Display + One + PartialEq + PartialOrd + Clone,
+{
+    let votes: Vec<(ParsedVote<C>, C)> = ballots
+        .iter()
+        .map(|(ballot, weight)| {
+            let vote = match ballot {
+                Ballot::Ranked(ranked) => ParsedVote::Ranked(ranked.iter().map(|(c, rank)| (c.to_string(), *rank)).collect()),
+                Ballot::Scored(scored) => ParsedVote::Scored(scored.iter().map(|(c, score)| (c.to_string(), score.clone())).collect()),
+            };
+            (vote, weight.clone())
+        })
+        .collect();
+    write_votes(writer, &votes)
+}
+
+// Check that a candidate name can be read back by `read_votes`.
+fn check_writable(candidate: &str) -> std::io::Result<()> {
+    if candidate.trim().is_empty() || candidate.contains(['>', '=', '*', ':', '\n', '\r']) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("candidate {:?} cannot be written as a vote", candidate),
+        ));
+    }
+    Ok(())
+}
+
+// Parse a vote weight.
+//
+// Weights are parsed by the count type first. Failing that, fractions such as `1/3` and decimals such as `0.25` are
+// parsed as a quotient of integers, so floats read fractions and rationals read decimals. A quotient that an integer
+// count type cannot represent exactly is an error, rather than being truncated.
+fn parse_weight<C: Num>(weight: &str) -> Result<C, ParseError> {
+    let weight = weight.trim();
+    let error = || ParseError::ParseError(weight.to_string());
+    if let Ok(parsed) = C::from_str_radix(weight, 10) {
+        return Ok(parsed);
+    }
+
+    let (numer, denom) = if let Some((numer, denom)) = weight.split_once('/') {
+        (numer.trim().to_string(), denom.trim().to_string())
+    } else if let Some((whole, fraction)) = weight.split_once('.') {
+        if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+        (format!("{}{}", whole, fraction), format!("1{}", "0".repeat(fraction.len())))
+    } else {
+        // Rational types only parse "numer/denom", so retry integer weights as a fraction over one.
+        return C::from_str_radix(&format!("{}/1", weight), 10).map_err(|_| error());
+    };
+
+    let parse = || -> Result<(C, C), ParseError> { Ok((parse_integer(&numer)?, parse_integer(&denom)?)) };
+    let (n, d) = parse()?;
+    if d.is_zero() {
+        return Err(error());
+    }
+
+    // Integer count types truncate on division, so the quotient must be exact.
+    let truncates = (C::one() / (C::one() + C::one())).is_zero();
+    if truncates && !(n % d).is_zero() {
+        return Err(error());
+    }
+    let (n, d) = parse()?;
+    Ok(n / d)
+}
+
+// Parse an integer in any count type, including rational types that only parse fractions.
+fn parse_integer<C: Num>(integer: &str) -> Result<C, ParseError> {
+    C::from_str_radix(integer, 10)
+        .or_else(|_| C::from_str_radix(&format!("{}/1", integer), 10))
+        .map_err(|_| ParseError::ParseError(integer.to_string()))
+}
+
 /// Check for duplicates in a transitive vote.
 pub fn check_duplicates_transitive_vote<T: Eq>(vote: &[T]) -> Result<(), TallyError> {
     for (i, candidate) in vote.iter().enumerate() {
-        for j in i + 1..vote.len() {
-            if &vote[j] == candidate {
-                return Err(TallyError::VoteHasDuplicateCandidates);
-            }
+        if let Some(offset) = vote[i + 1..].iter().position(|c| c == candidate) {
+            return Err(TallyError::VoteHasDuplicateCandidates { position: i + 1 + offset });
         }
     }
 
     Ok(())
 }
 
-/// Check for duplicates in a ranked vote.
-pub fn check_duplicates_ranked_vote<T: Eq>(vote: &[(T, u32)]) -> Result<(), TallyError> {
+/// Check for duplicates in a ranked vote, or any vote that pairs each candidate with a rank or score.
+pub fn check_duplicates_ranked_vote<T: Eq, R>(vote: &[(T, R)]) -> Result<(), TallyError> {
     for (i, (candidate, _rank)) in vote.iter().enumerate() {
-        for j in i + 1..vote.len() {
-            if &vote[j].0 == candidate {
-                return Err(TallyError::VoteHasDuplicateCandidates);
-            }
+        if let Some(offset) = vote[i + 1..].iter().position(|(c, _)| c == candidate) {
+            return Err(TallyError::VoteHasDuplicateCandidates { position: i + 1 + offset });
         }
     }
 
     Ok(())
 }
+
+/// The most strict orderings a ranked vote with equally ranked candidates may be split into.
+pub(crate) const MAX_TIED_ORDERINGS: usize = 5040;
+
+/// Split a ranked vote into every strict ordering of its candidates, dividing the weight equally between them.
+///
+/// Equally ranked candidates share the vote, so that a transfer from one of them goes to the others first.
+/// The number of orderings grows factorially with the number of equally ranked candidates, so a vote that would be
+/// split into more than `MAX_TIED_ORDERINGS` orderings returns `TallyError::TooManyTies` before any are built.
+///
+/// Returns `TallyError::IndivisibleWeight` if the count type is an integer that cannot divide the weight evenly,
+/// which is the case for any tie on a ballot with a weight of one.
+pub(crate) fn split_ranked_vote<T, C>(vote: &[(T, u32)], weight: C) -> Result<Vec<(Vec<T>, C)>, TallyError>
+where
+    T: Eq + Clone,
+    C: Clone + Num + FromPrimitive,
+{
+    check_duplicates_ranked_vote(vote)?;
+
+    let mut sorted: Vec<&(T, u32)> = vote.iter().collect();
+    sorted.sort_by_key(|(_, rank)| *rank);
+
+    let mut groups: Vec<Vec<T>> = Vec::new();
+    let mut start = 0;
+    while start < sorted.len() {
+        let rank = sorted[start].1;
+        let end = start + sorted[start..].iter().take_while(|(_, r)| *r == rank).count();
+        groups.push(sorted[start..end].iter().map(|(c, _)| c.clone()).collect());
+        start = end;
+    }
+
+    // Count the orderings before building any, so a large tie is rejected without running out of memory.
+    let mut num_orderings: usize = 1;
+    for group in groups.iter() {
+        for size in 2..=group.len() {
+            num_orderings = num_orderings
+                .checked_mul(size)
+                .filter(|n| *n <= MAX_TIED_ORDERINGS)
+                .ok_or(TallyError::TooManyTies { max: MAX_TIED_ORDERINGS })?;
+        }
+    }
+
+    let parts = C::from_usize(num_orderings).ok_or(TallyError::IndivisibleWeight)?;
+    let part = weight.clone() / parts.clone();
+
+    // Integer count types truncate on division, so the parts must add back up to the whole.
+    let truncates = (C::one() / (C::one() + C::one())).is_zero();
+    if truncates && part.clone() * parts != weight {
+        return Err(TallyError::IndivisibleWeight);
+    }
+
+    let mut orderings: Vec<Vec<T>> = vec![Vec::with_capacity(vote.len())];
+    for group in groups.iter() {
+        let permuted = permutations(group);
+        let mut extended = Vec::with_capacity(orderings.len() * permuted.len());
+        for ordering in orderings.iter() {
+            for permutation in permuted.iter() {
+                let mut next = ordering.clone();
+                next.extend(permutation.iter().cloned());
+                extended.push(next);
+            }
+        }
+        orderings = extended;
+    }
+
+    Ok(orderings.into_iter().map(|ordering| (ordering, part.clone())).collect())
+}
+
+// Every ordering of a group of candidates.
+fn permutations<T: Clone>(group: &[T]) -> Vec<Vec<T>> {
+    if group.len() <= 1 {
+        return vec![group.to_vec()];
+    }
+    let mut result = Vec::new();
+    for i in 0..group.len() {
+        let mut rest = group.to_vec();
+        let first = rest.remove(i);
+        for mut permutation in permutations(&rest) {
+            permutation.insert(0, first.clone());
+            result.push(permutation);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_ranked_vote_test() -> Result<(), TallyError> {
+        let split = split_ranked_vote(&[("Alice", 1), ("Bob", 0), ("Cir", 0)], 1.0)?;
+        assert_eq!(split, vec![(vec!["Bob", "Cir", "Alice"], 0.5), (vec!["Cir", "Bob", "Alice"], 0.5)]);
+
+        let split = split_ranked_vote(&[("Alice", 0), ("Bob", 1), ("Cir", 1), ("Dave", 1)], 6)?;
+        assert_eq!(split.len(), 6);
+        assert!(split.iter().all(|(ordering, weight)| ordering[0] == "Alice" && *weight == 1));
+
+        assert_eq!(
+            split_ranked_vote(&[("Alice", 0), ("Bob", 0)], 1),
+            Err(TallyError::IndivisibleWeight)
+        );
+        assert_eq!(
+            split_ranked_vote(&[("Alice", 0), ("Alice", 1)], 1.0),
+            Err(TallyError::VoteHasDuplicateCandidates { position: 1 })
+        );
+        assert_eq!(split_ranked_vote::<&str, u32>(&[], 1)?, vec![(vec![], 1)]);
+
+        // A twelve-way tie has 479001600 orderings, and is rejected before any are built.
+        let tied: Vec<(u32, u32)> = (0..12).map(|candidate| (candidate, 0)).collect();
+        assert_eq!(
+            split_ranked_vote(&tied, 1.0),
+            Err(TallyError::TooManyTies { max: MAX_TIED_ORDERINGS })
+        );
+        assert_eq!(split_ranked_vote(&tied[..7], 1.0)?.len(), MAX_TIED_ORDERINGS);
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_votes_round_trip() -> Result<(), ParseError> {
+        let file = "Alice > Bob > Carol * 3\nBob = Carol > Alice * 1.5\nCarol\n";
+        let votes = read_votes::<_, f64>(file.as_bytes())?.votes;
+
+        let mut written = Vec::new();
+        write_votes(&mut written, &votes)?;
+        assert_eq!(String::from_utf8(written.clone()).unwrap(), file);
+        assert_eq!(read_votes::<_, f64>(written.as_slice())?.votes, votes);
+
+        let invalid = vec![(ParsedVote::Unranked(vec!["A > B".to_string()]), 1.0)];
+        assert!(write_votes(&mut Vec::new(), &invalid).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn scored_votes() -> Result<(), ParseError> {
+        let votes = read_votes::<_, f64>("Alice:5 Bob Smith : 3.5 Carol:0 * 2\nBob Smith:1\n".as_bytes())?.votes;
+        assert_eq!(
+            votes[0],
+            (
+                ParsedVote::Scored(vec![
+                    ("Alice".to_string(), 5.0),
+                    ("Bob Smith".to_string(), 3.5),
+                    ("Carol".to_string(), 0.0)
+                ]),
+                2.0
+            )
+        );
+        assert_eq!(
+            votes[0].0.clone().into_ranked(),
+            vec![("Alice".to_string(), 0), ("Bob Smith".to_string(), 1), ("Carol".to_string(), 2)]
+        );
+
+        let mut written = Vec::new();
+        write_votes(&mut written, &votes)?;
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "Alice:5 Bob Smith:3.5 Carol:0 * 2\nBob Smith:1\n"
+        );
+
+        assert!(read_votes::<_, u32>("Alice:5 Bob\n".as_bytes()).is_err());
+        assert!(read_votes::<_, u32>(":5 Bob:3\n".as_bytes()).is_err());
+        assert!(read_votes::<_, u32>("Alice:5 :3\n".as_bytes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn candidate_declarations() -> Result<(), ParseError> {
+        let file = "# Declared candidates\n@candidates Alice, Bob,\n  @candidates Carol\n# A comment\nAlice > Carol\n";
+        let parsed = read_votes::<_, u32>(file.as_bytes())?;
+        assert_eq!(parsed.candidates, vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(parsed.votes.len(), 1);
+
+        assert!(read_votes::<_, u32>("Alice\n@candidates Alice, Bob\n".as_bytes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fractional_weights() -> Result<(), ParseError> {
+        assert_eq!(parse_weight::<f64>(" 0.5 ")?, 0.5);
+        assert_eq!(parse_weight::<f64>("1/4")?, 0.25);
+        assert_eq!(parse_weight::<u32>("4/2")?, 2);
+        assert_eq!(parse_weight::<u32>("3.0")?, 3);
+        assert!(parse_weight::<u32>("1/3").is_err());
+        assert!(parse_weight::<u32>("0.5").is_err());
+        assert!(parse_weight::<f64>("1/0").is_err());
+        assert!(parse_weight::<f64>("1.x").is_err());
+
+        let votes = read_votes::<_, f64>("Alice > Bob * 1/3\nBob * .25\n".as_bytes())?.votes;
+        assert_eq!(votes[0].1, 1.0 / 3.0);
+        assert_eq!(votes[1].1, 0.25);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "rational")]
+    fn rational_decimal_weights() -> Result<(), ParseError> {
+        use num_rational::Rational64;
+
+        assert_eq!(parse_weight::<Rational64>("0.25")?, Rational64::new(1, 4));
+        assert_eq!(parse_weight::<Rational64>("-1.5")?, Rational64::new(-3, 2));
+        assert_eq!(parse_weight::<Rational64>("2/6")?, Rational64::new(1, 3));
+        assert_eq!(parse_weight::<Rational64>("7")?, Rational64::from_integer(7));
+
+        Ok(())
+    }
+
+    #[test]
+    fn candidate_whitelist() {
+        let config = ParserConfig::new().with_candidates(vec!["Alice".to_string(), "Bob".to_string()]);
+        let file = "Alice = Bob\nAlice:3 Dave:2\nBob >  > Alice\n";
+        let (parsed, errors) = read_votes_lenient::<_, u32>(file.as_bytes(), &config).unwrap();
+        assert_eq!(parsed.votes.len(), 1);
+        assert_eq!(
+            errors.iter().map(|e| (e.line(), e.column())).collect::<Vec<_>>(),
+            vec![(Some(2), Some(9)), (Some(3), Some(6))]
+        );
+    }
+
+    #[test]
+    fn vote_reader_continues_after_errors() {
+        let mut reader = VoteReader::<_, u32>::new("Alice * 2\n\nBob * many\nCarol > Alice\n".as_bytes());
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            (ParsedVote::Unranked(vec!["Alice".to_string()]), 2)
+        );
+        assert!(reader.next().unwrap().is_err());
+        assert_eq!(reader.next().unwrap().unwrap().0.into_ranked().len(), 2);
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,329 @@
+use num_traits::Num;
+use std::convert::TryInto;
+use std::fmt::Display;
+use std::io::{BufRead, BufReader, Read, Write};
+
+use super::{locate, parse_weight, ParseError, ParsedVote};
+
+/// An election read from, or to be written to, a `.blt` ballot file.
+///
+/// The BLT format is used by OpenSTV, Droop, and many published STV elections. A file lists the number of candidates and seats,
+/// any withdrawn candidates, one weighted ballot per line with candidates numbered from `1`, the candidate names, and a title:
+///
+/// ```text
+/// 3 2
+/// -3
+/// 4 1 2 0
+/// 2 2=3 1 0
+/// 0
+/// "Alice"
+/// "Bob"
+/// "Carol"
+/// "Club committee"
+/// ```
+///
+/// Each ballot line starts with its weight and ends with `0`. Candidates joined with `=` are ranked equally.
+#[derive(Debug)]
+pub struct Blt<C> {
+    /// The names of the candidates, in the order they are numbered.
+    pub candidates: Vec<String>,
+
+    /// The number of seats to fill.
+    pub seats: usize,
+
+    /// Candidates that withdrew from the election.
+    pub withdrawn: Vec<String>,
+
+    /// The ballots and their weights. Ballots with equally ranked candidates are parsed as ranked votes.
+    pub votes: Vec<(ParsedVote<C>, C)>,
+
+    /// The title of the election.
+    pub title: String,
+}
+
+/// Read an election from a `.blt` ballot file.
+///
+/// Lines starting with `#` are treated as comments. Ballot lines may start with a ballot id in parentheses, which is ignored.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_blt, ParsedVote};
+///
+///    let file = "2 1\n3 1 2 0\n1 2 0\n0\n\"Alice\"\n\"Bob\"\n\"Example\"\n";
+///    let blt = read_blt::<_, u64>(file.as_bytes())?;
+///    assert_eq!(blt.candidates, vec!["Alice", "Bob"]);
+///    assert_eq!(blt.seats, 1);
+///    assert_eq!(blt.votes.len(), 2);
+///    assert_eq!(blt.votes[0].1, 3);
+///    assert_eq!(blt.title, "Example");
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub fn read_blt<R: Read, C: Num>(reader: R) -> Result<Blt<C>, ParseError> {
+    // Lines are kept as they were read, with their line numbers, to locate errors.
+    let mut lines = Vec::new();
+    let mut line_count = 0;
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        line_count += 1;
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && !trimmed.starts_with('#') {
+            lines.push((line_count, line));
+        }
+    }
+    // Missing lines are reported at the end of the file.
+    let missing = |expected: &'static str| ParseError::Expected(expected).on_line(line_count + 1, 1, "");
+    let mut lines = lines.iter();
+
+    let (number, raw) = lines
+        .next()
+        .ok_or_else(|| missing("a header with the number of candidates and seats"))?;
+    let header: Vec<&str> = raw.split_whitespace().collect();
+    if header.len() != 2 {
+        return Err(locate(
+            ParseError::Expected("a header with the number of candidates and seats"),
+            *number,
+            raw,
+            raw.trim(),
+        ));
+    }
+    let num_candidates = parse_number(header[0], "the number of candidates").map_err(|e| locate(e, *number, raw, header[0]))?;
+    let seats = parse_number(header[1], "the number of seats").map_err(|e| locate(e, *number, raw, header[1]))?;
+
+    let mut withdrawn_ids = Vec::new();
+    let mut ballots = Vec::new();
+    for (number, raw) in lines.by_ref() {
+        let line = raw.trim();
+        if line == "0" {
+            break;
+        }
+        if line.starts_with('-') {
+            for token in line.split_whitespace() {
+                let id = parse_candidate(token.trim_start_matches('-'), num_candidates).map_err(|e| locate(e, *number, raw, token))?;
+                withdrawn_ids.push(id);
+            }
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace().skip_while(|token| token.starts_with('('));
+        let weight: C = match tokens.next() {
+            Some(token) => parse_weight(token).map_err(|_| locate(ParseError::Expected("a ballot weight"), *number, raw, token))?,
+            None => return Err(locate(ParseError::Expected("a ballot weight"), *number, raw, line)),
+        };
+        let mut groups = Vec::new();
+        for token in tokens.take_while(|token| *token != "0") {
+            let group = token
+                .split('=')
+                .map(|id| parse_candidate(id, num_candidates).map_err(|e| locate(e, *number, raw, id)))
+                .collect::<Result<Vec<usize>, ParseError>>()?;
+            groups.push(group);
+        }
+        ballots.push((groups, weight));
+    }
+
+    let mut candidates = Vec::with_capacity(num_candidates);
+    for _ in 0..num_candidates {
+        let (_, name) = lines.next().ok_or_else(|| missing("a name for each candidate"))?;
+        candidates.push(unquote(name));
+    }
+    let title = lines.next().map(|(_, title)| unquote(title)).unwrap_or_default();
+
+    let name = |id: usize| candidates[id - 1].clone();
+    let votes = ballots
+        .into_iter()
+        .map(|(groups, weight)| {
+            let vote = if groups.iter().any(|group| group.len() > 1) {
+                let mut ranked = Vec::new();
+                for (rank, group) in groups.iter().enumerate() {
+                    // Safe to unwrap here since we can't have more than u32::MAX candidates anyways.
+                    let rank: u32 = rank.try_into().unwrap();
+                    ranked.extend(group.iter().map(|id| (name(*id), rank)));
+                }
+                ParsedVote::Ranked(ranked)
+            } else {
+                ParsedVote::Unranked(groups.iter().flatten().map(|id| name(*id)).collect())
+            };
+            (vote, weight)
+        })
+        .collect();
+
+    Ok(Blt {
+        withdrawn: withdrawn_ids.into_iter().map(name).collect(),
+        candidates: candidates,
+        seats: seats,
+        votes: votes,
+        title: title,
+    })
+}
+
+/// Write an election to a `.blt` ballot file.
+///
+/// Returns an error of kind `InvalidInput` if a vote or withdrawn candidate is not one of the election's candidates,
+/// or if a vote is scored, since BLT files only hold ranked ballots.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{write_blt, Blt, ParsedVote};
+///
+///    let blt = Blt {
+///        candidates: vec!["Alice".to_string(), "Bob".to_string()],
+///        seats: 1,
+///        withdrawn: vec![],
+///        votes: vec![(ParsedVote::Unranked(vec!["Bob".to_string(), "Alice".to_string()]), 2)],
+///        title: "Example".to_string(),
+///    };
+///
+///    let mut file = Vec::new();
+///    write_blt(&mut file, &blt)?;
+///    assert_eq!(String::from_utf8(file).unwrap(), "2 1\n2 2 1 0\n0\n\"Alice\"\n\"Bob\"\n\"Example\"\n");
+///    # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_blt<W: Write, C: Display>(mut writer: W, blt: &Blt<C>) -> std::io::Result<()> {
+    let id = |candidate: &str| {
+        blt.candidates
+            .iter()
+            .position(|c| c == candidate)
+            .map(|i| i + 1)
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("unknown candidate {}", candidate)))
+    };
+
+    writeln!(writer, "{} {}", blt.candidates.len(), blt.seats)?;
+    if !blt.withdrawn.is_empty() {
+        let withdrawn = blt
+            .withdrawn
+            .iter()
+            .map(|candidate| Ok(format!("-{}", id(candidate)?)))
+            .collect::<std::io::Result<Vec<String>>>()?;
+        writeln!(writer, "{}", withdrawn.join(" "))?;
+    }
+
+    for (vote, weight) in blt.votes.iter() {
+        let mut groups: Vec<(u32, Vec<usize>)> = Vec::new();
+        match vote {
+            ParsedVote::Unranked(unranked) => {
+                for candidate in unranked.iter() {
+                    groups.push((0, vec![id(candidate)?]));
+                }
+            }
+            ParsedVote::Ranked(ranked) => {
+                let mut sorted: Vec<&(String, u32)> = ranked.iter().collect();
+                sorted.sort_by_key(|(_, rank)| *rank);
+                for (candidate, rank) in sorted {
+                    match groups.last_mut() {
+                        Some((last, group)) if last == rank => group.push(id(candidate)?),
+                        _ => groups.push((*rank, vec![id(candidate)?])),
+                    }
+                }
+            }
+            ParsedVote::Scored(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "scored votes cannot be written to a BLT file",
+                ))
+            }
+        }
+
+        write!(writer, "{}", weight)?;
+        for (_, group) in groups.iter() {
+            let group: Vec<String> = group.iter().map(|id| id.to_string()).collect();
+            write!(writer, " {}", group.join("="))?;
+        }
+        writeln!(writer, " 0")?;
+    }
+    writeln!(writer, "0")?;
+
+    for candidate in blt.candidates.iter() {
+        writeln!(writer, "\"{}\"", candidate)?;
+    }
+    writeln!(writer, "\"{}\"", blt.title)
+}
+
+// Parse a whole number, naming what was expected if it is not one.
+fn parse_number(token: &str, expected: &'static str) -> Result<usize, ParseError> {
+    token.parse().map_err(|_| ParseError::Expected(expected))
+}
+
+// Parse a candidate number, which must be between 1 and the number of candidates.
+fn parse_candidate(token: &str, num_candidates: usize) -> Result<usize, ParseError> {
+    const EXPECTED: &str = "a candidate number between 1 and the number of candidates";
+    match parse_number(token, EXPECTED)? {
+        id if id >= 1 && id <= num_candidates => Ok(id),
+        _ => Err(ParseError::Expected(EXPECTED)),
+    }
+}
+
+fn unquote(name: &str) -> String {
+    let name = name.trim();
+    name.strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+        .unwrap_or(name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blt_round_trip() -> Result<(), ParseError> {
+        let file =
+            "# An example election\n4 2\n-4\n(a1) 3 1 2 0\n2 2=3 1 0\n1.5 4 0\n0\n\"Alice\"\n\"Bob\"\n\"Carol\"\n\"Dave\"\n\"Committee\"\n";
+        let blt = read_blt::<_, f64>(file.as_bytes())?;
+        assert_eq!(blt.seats, 2);
+        assert_eq!(blt.withdrawn, vec!["Dave"]);
+        assert_eq!(blt.title, "Committee");
+        assert_eq!(blt.votes.len(), 3);
+        assert_eq!(blt.votes[2].1, 1.5);
+        match &blt.votes[1].0 {
+            ParsedVote::Ranked(ranked) => assert_eq!(
+                ranked,
+                &vec![("Bob".to_string(), 0), ("Carol".to_string(), 0), ("Alice".to_string(), 1)]
+            ),
+            _ => panic!("Expected a ranked vote"),
+        }
+
+        let mut written = Vec::new();
+        write_blt(&mut written, &blt)?;
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "4 2\n-4\n3 1 2 0\n2 2=3 1 0\n1.5 4 0\n0\n\"Alice\"\n\"Bob\"\n\"Carol\"\n\"Dave\"\n\"Committee\"\n"
+        );
+
+        assert!(read_blt::<_, u64>("2 1\n1 3 0\n0\n\"Alice\"\n\"Bob\"\n".as_bytes()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn blt_errors() {
+        let error = |file: &str| read_blt::<_, u64>(file.as_bytes()).unwrap_err();
+
+        let unknown = error("# Header\n2 1\n1 3 0\n0\n\"Alice\"\n\"Bob\"\n");
+        assert_eq!((unknown.line(), unknown.column()), (Some(3), Some(3)));
+        assert_eq!(
+            unknown.to_string(),
+            "tallystick: expected a candidate number between 1 and the number of candidates (line 3, column 3: 1 3 0)"
+        );
+
+        let header = error("2\n0\n");
+        assert_eq!(header.line(), Some(1));
+        assert_eq!(
+            header.to_string(),
+            "tallystick: expected a header with the number of candidates and seats (line 1, column 1: 2)"
+        );
+
+        let seats = error("2 one\n0\n");
+        assert_eq!((seats.line(), seats.column()), (Some(1), Some(3)));
+        assert!(seats.to_string().starts_with("tallystick: expected the number of seats"));
+
+        let weight = error("2 1\nx 1 2 0\n0\n\"Alice\"\n\"Bob\"\n");
+        assert_eq!((weight.line(), weight.column()), (Some(2), Some(1)));
+        assert!(weight.to_string().starts_with("tallystick: expected a ballot weight"));
+
+        // Missing candidate names are reported after the last line.
+        let names = error("2 1\n1 1 2 0\n0\n\"Alice\"\n");
+        assert_eq!(names.line(), Some(5));
+        assert!(names.to_string().starts_with("tallystick: expected a name for each candidate"));
+
+        assert_eq!(error("").line(), Some(1));
+    }
+}
@@ -0,0 +1,194 @@
+use num_traits::Num;
+use std::convert::TryInto;
+use std::io::Read;
+
+use super::{parse_weight, ParseError};
+use crate::ballotbox::Ballot;
+
+/// How ballots are laid out in the columns of a CSV file. `csv`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CsvLayout {
+    /// One column per rank, holding the name of the candidate ranked there. Columns are listed from the first choice to the last.
+    /// Empty cells are skipped.
+    Ranks(Vec<usize>),
+
+    /// One column per candidate, holding the score given to that candidate. Candidates are named by the header row.
+    /// Empty cells are skipped.
+    Scores(Vec<usize>),
+}
+
+/// The format of a CSV ballot file: which columns hold the voter id, the ballot weight, and the ballot itself. `csv`
+///
+/// Columns are numbered from `0`.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_votes_csv, CsvFormat};
+///    use tallystick::ballotbox::Ballot;
+///
+///    let file = "voter,first,second,third\n\
+///                v1,Alice,Bob,Carol\n\
+///                v2,Bob,,\n";
+///
+///    let format = CsvFormat::ranks(vec![1, 2, 3]).with_voter_column(0);
+///    let ballots = read_votes_csv::<_, u64>(file.as_bytes(), &format)?;
+///    assert_eq!(ballots[1].voter, Some("v2".to_string()));
+///    assert_eq!(ballots[1].ballot, Ballot::Ranked(vec![("Bob".to_string(), 0)]));
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvFormat {
+    layout: CsvLayout,
+    voter_column: Option<usize>,
+    weight_column: Option<usize>,
+    delimiter: u8,
+}
+
+impl CsvFormat {
+    /// A format with one column per rank. The first row is a header row and is skipped.
+    pub fn ranks(columns: Vec<usize>) -> Self {
+        Self::new(CsvLayout::Ranks(columns))
+    }
+
+    /// A format with one column per candidate, holding scores. The first row is a header row naming the candidates.
+    pub fn scores(columns: Vec<usize>) -> Self {
+        Self::new(CsvLayout::Scores(columns))
+    }
+
+    fn new(layout: CsvLayout) -> Self {
+        CsvFormat {
+            layout: layout,
+            voter_column: None,
+            weight_column: None,
+            delimiter: b',',
+        }
+    }
+
+    /// Read a voter id for each ballot from a column.
+    pub fn with_voter_column(mut self, column: usize) -> Self {
+        self.voter_column = Some(column);
+        self
+    }
+
+    /// Read a weight for each ballot from a column. Without a weight column, every ballot has a weight of one.
+    pub fn with_weight_column(mut self, column: usize) -> Self {
+        self.weight_column = Some(column);
+        self
+    }
+
+    /// Use a delimiter other than a comma, such as `b'\t'` for tab-separated files.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Get the ballot layout of this format.
+    pub fn layout(&self) -> &CsvLayout {
+        &self.layout
+    }
+}
+
+/// A ballot read from a CSV file. `csv`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvBallot<C> {
+    /// The voter id, if the format has a voter column.
+    pub voter: Option<String>,
+
+    /// The weight of the ballot.
+    pub weight: C,
+
+    /// The ballot. Ranked for a rank layout, and scored for a score layout.
+    pub ballot: Ballot<String, C>,
+}
+
+/// Read ballots from a CSV file. `csv`
+///
+/// Requires the `csv` feature to be enabled.
+/// See [`CsvFormat`](struct.CsvFormat.html) for how columns are configured.
+pub fn read_votes_csv<R: Read, C: Num>(reader: R, format: &CsvFormat) -> Result<Vec<CsvBallot<C>>, ParseError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(format.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    let cell = |record: &::csv::StringRecord, column: usize| record.get(column).map(str::trim).unwrap_or_default().to_string();
+
+    let mut ballots = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+
+        let voter = format.voter_column.map(|column| cell(&record, column));
+        let weight = match format.weight_column {
+            Some(column) => parse_weight(&cell(&record, column))?,
+            None => C::one(),
+        };
+
+        let ballot = match &format.layout {
+            CsvLayout::Ranks(columns) => {
+                let ranked = columns
+                    .iter()
+                    .map(|column| cell(&record, *column))
+                    .filter(|candidate| !candidate.is_empty())
+                    .enumerate()
+                    // Safe to unwrap here since we can't have more than u32::MAX candidates anyways.
+                    .map(|(rank, candidate)| (candidate, rank.try_into().unwrap()))
+                    .collect();
+                Ballot::Ranked(ranked)
+            }
+            CsvLayout::Scores(columns) => {
+                let mut scored = Vec::with_capacity(columns.len());
+                for column in columns.iter() {
+                    let score = cell(&record, *column);
+                    if !score.is_empty() {
+                        scored.push((cell(&headers, *column), parse_weight(&score)?));
+                    }
+                }
+                Ballot::Scored(scored)
+            }
+        };
+
+        ballots.push(CsvBallot {
+            voter: voter,
+            weight: weight,
+            ballot: ballot,
+        });
+    }
+
+    Ok(ballots)
+}
+
+fn csv_error(error: ::csv::Error) -> ParseError {
+    let message = error.to_string();
+    match error.into_kind() {
+        ::csv::ErrorKind::Io(error) => ParseError::ReadError(error),
+        _ => ParseError::ParseError(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_scores() -> Result<(), ParseError> {
+        let file = "id\tweight\tAlice\tBob\tCarol\n1\t2\t5\t3\t\n2\t1\t0\t\"4\"\t1\n";
+        let format = CsvFormat::scores(vec![2, 3, 4])
+            .with_voter_column(0)
+            .with_weight_column(1)
+            .with_delimiter(b'\t');
+        let ballots = read_votes_csv::<_, u32>(file.as_bytes(), &format)?;
+        assert_eq!(ballots.len(), 2);
+        assert_eq!(ballots[0].weight, 2);
+        assert_eq!(
+            ballots[0].ballot,
+            Ballot::Scored(vec![("Alice".to_string(), 5), ("Bob".to_string(), 3)])
+        );
+        assert_eq!(ballots[1].ballot.candidates(), vec!["Alice", "Bob", "Carol"]);
+
+        let file = "id,weight,Alice\n1,heavy,5\n";
+        assert!(read_votes_csv::<_, u32>(file.as_bytes(), &format.with_delimiter(b',')).is_err());
+
+        Ok(())
+    }
+}
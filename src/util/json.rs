@@ -0,0 +1,205 @@
+use num_traits::FromPrimitive;
+use num_traits::Num;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::io::Read;
+use std::ops::AddAssign;
+
+use super::ParseError;
+use crate::ballotbox::BallotBox;
+use crate::errors::TallyError;
+
+/// A set of ballots in the JSON ballot schema. `json`
+///
+/// ```json
+/// {
+///   "candidates": ["Alice", "Bob", "Carol"],
+///   "ballots": [
+///     { "ranked": ["Alice", "Bob", "Carol"] },
+///     { "ranked": [["Bob", "Carol"], "Alice"], "weight": 2 },
+///     { "scores": { "Alice": 5, "Carol": 3 }, "voter": "v3" }
+///   ]
+/// }
+/// ```
+///
+/// Ranked selections list candidates from the most preferred to the least, with equally ranked candidates grouped in an array.
+/// The candidate list is optional. When it is given, every candidate on a ballot must be in it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonBallots<C> {
+    /// The candidates standing in the election, if declared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<String>,
+
+    /// The ballots.
+    pub ballots: Vec<JsonBallot<C>>,
+}
+
+/// A single ballot in the JSON ballot schema. `json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonBallot<C> {
+    /// The voter id, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voter: Option<String>,
+
+    /// The weight of the ballot. A ballot without a weight has a weight of one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<C>,
+
+    /// The candidates selected on the ballot.
+    #[serde(flatten)]
+    pub selection: JsonSelection<C>,
+}
+
+/// The selections on a JSON ballot, either ranked or scored. `json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonSelection<C> {
+    /// Candidates in order of preference.
+    Ranked(Vec<JsonRank>),
+
+    /// A score for each candidate.
+    Scores(BTreeMap<String, C>),
+}
+
+/// A position in a ranked JSON ballot, holding one candidate or several equally ranked candidates. `json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonRank {
+    /// A single candidate.
+    Single(String),
+
+    /// Equally ranked candidates.
+    Tied(Vec<String>),
+}
+
+impl<C> JsonBallots<C>
+where
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive,
+{
+    /// Put the ballots in a [`BallotBox`](../ballotbox/struct.BallotBox.html), so they can be added to any tally.
+    ///
+    /// Returns an error, with the index of the offending ballot, if a ballot repeats a candidate, or names a candidate
+    /// missing from a declared candidate list.
+    pub fn into_ballot_box(self) -> Result<BallotBox<String, C>, TallyError> {
+        let mut ballot_box = BallotBox::with_capacity(self.ballots.len());
+        for (index, ballot) in self.ballots.into_iter().enumerate() {
+            let weight = ballot.weight.unwrap_or_else(C::one);
+            let added = match ballot.selection {
+                JsonSelection::Ranked(ranks) => {
+                    let mut ranked = Vec::new();
+                    for (rank, position) in ranks.into_iter().enumerate() {
+                        // Safe to unwrap here since we can't have more than u32::MAX candidates anyways.
+                        let rank: u32 = rank.try_into().unwrap();
+                        match position {
+                            JsonRank::Single(candidate) => ranked.push((candidate, rank)),
+                            JsonRank::Tied(candidates) => ranked.extend(candidates.into_iter().map(|c| (c, rank))),
+                        }
+                    }
+                    check_candidates(&self.candidates, ranked.iter().map(|(c, _)| c))
+                        .and_then(|_| ballot_box.ranked_add_weighted(ranked, weight))
+                }
+                JsonSelection::Scores(scores) => {
+                    let scored: Vec<(String, C)> = scores.into_iter().collect();
+                    check_candidates(&self.candidates, scored.iter().map(|(c, _)| c))
+                        .and_then(|_| ballot_box.scored_add_weighted(scored, weight))
+                }
+            };
+            added.map_err(|e| e.on_ballot(index))?;
+        }
+        Ok(ballot_box)
+    }
+}
+
+// Check that every candidate is declared, unless no candidates are declared.
+fn check_candidates<'a, I: Iterator<Item = &'a String>>(declared: &[String], candidates: I) -> Result<(), TallyError> {
+    if declared.is_empty() {
+        return Ok(());
+    }
+    for (position, candidate) in candidates.enumerate() {
+        if !declared.contains(candidate) {
+            return Err(TallyError::UnknownCandidate { position });
+        }
+    }
+    Ok(())
+}
+
+/// Read ballots in the JSON ballot schema. `json`
+///
+/// Requires the `json` feature to be enabled.
+/// See [`JsonBallots`](struct.JsonBallots.html) for the schema.
+///
+/// # Example
+/// ```
+///    use tallystick::condorcet::DefaultCondorcetTally;
+///    use tallystick::util::read_votes_json;
+///
+///    let json = r#"{
+///        "candidates": ["Alice", "Bob", "Carol"],
+///        "ballots": [
+///            { "ranked": ["Alice", "Bob", "Carol"], "weight": 3 },
+///            { "ranked": [["Bob", "Carol"], "Alice"], "weight": 2 }
+///        ]
+///    }"#;
+///
+///    let ballots = read_votes_json::<_, u64>(json.as_bytes())?;
+///    let mut tally = DefaultCondorcetTally::with_candidates(1, ballots.candidates.clone());
+///    ballots.into_ballot_box().unwrap().add_to_condorcet(&mut tally).unwrap();
+///    assert_eq!(tally.winners().into_unranked(), vec!["Alice"]);
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub fn read_votes_json<R: Read, C: DeserializeOwned>(reader: R) -> Result<JsonBallots<C>, ParseError> {
+    serde_json::from_reader(reader).map_err(|e| {
+        if e.is_io() {
+            ParseError::ReadError(e.into())
+        } else {
+            ParseError::ParseError(e.to_string())
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ballotbox::Ballot;
+
+    #[test]
+    fn json_ballots() -> Result<(), ParseError> {
+        let json = r#"{
+            "ballots": [
+                { "scores": { "Bob": 3, "Alice": 5 }, "voter": "v1" },
+                { "ranked": ["Carol", ["Alice", "Bob"]] }
+            ]
+        }"#;
+        let ballots = read_votes_json::<_, f64>(json.as_bytes())?;
+        assert_eq!(ballots.ballots[0].voter, Some("v1".to_string()));
+        assert_eq!(ballots.ballots[1].weight, None);
+
+        let ballot_box = ballots.clone().into_ballot_box().unwrap();
+        assert_eq!(
+            ballot_box.ballots()[0],
+            (Ballot::Scored(vec![("Alice".to_string(), 5.0), ("Bob".to_string(), 3.0)]), 1.0)
+        );
+        assert_eq!(
+            ballot_box.ballots()[1].0.ranking(),
+            vec![("Carol".to_string(), 0), ("Alice".to_string(), 1), ("Bob".to_string(), 1)]
+        );
+
+        // Round trip through JSON.
+        let written = serde_json::to_string(&ballots).unwrap();
+        assert_eq!(read_votes_json::<_, f64>(written.as_bytes())?, ballots);
+
+        // Candidates must be declared, if there is a candidate list.
+        let mut declared = ballots;
+        declared.candidates = vec!["Alice".to_string(), "Bob".to_string()];
+        assert_eq!(
+            declared.into_ballot_box(),
+            Err(TallyError::UnknownCandidate { position: 0 }.on_ballot(1))
+        );
+
+        assert!(read_votes_json::<_, u64>("{ \"ballots\": [{ \"ranked\": 5 }] }".as_bytes()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use hashbrown::HashMap;
+use num_traits::Num;
+use serde::Deserialize;
+use std::io::Read;
+
+use super::{ParseError, ParsedVote};
+
+/// The ballots cast in one contest of a NIST cast vote record report. `json`
+#[derive(Debug)]
+pub struct CvrContest<C> {
+    /// The contest id, such as `contest-1`.
+    pub id: String,
+
+    /// The name of the contest, falling back to its id.
+    pub name: String,
+
+    /// The names of the candidates, in the order the contest lists its selections.
+    /// Selections without a candidate, such as write-ins, are named by their selection id.
+    pub candidates: Vec<String>,
+
+    /// One vote for every cast vote record that has marks in this contest. Ballots with equally ranked candidates, such as
+    /// overvotes, are parsed as ranked votes.
+    pub votes: Vec<(ParsedVote<C>, C)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Report {
+    #[serde(default)]
+    election: Vec<Election>,
+    #[serde(rename = "CVR", default)]
+    cvr: Vec<Cvr>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Election {
+    #[serde(default)]
+    candidate: Vec<Candidate>,
+    #[serde(default)]
+    contest: Vec<Contest>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Candidate {
+    #[serde(rename = "@id")]
+    id: String,
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Contest {
+    #[serde(rename = "@id")]
+    id: String,
+    name: Option<String>,
+    #[serde(default)]
+    contest_selection: Vec<ContestSelection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContestSelection {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(default)]
+    candidate_ids: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Cvr {
+    current_snapshot_id: Option<String>,
+    #[serde(rename = "CVRSnapshot", default)]
+    cvr_snapshot: Vec<Snapshot>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Snapshot {
+    #[serde(rename = "@id")]
+    id: Option<String>,
+    #[serde(rename = "CVRContest", default)]
+    cvr_contest: Vec<CvrContestMarks>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CvrContestMarks {
+    contest_id: String,
+    #[serde(rename = "CVRContestSelection", default)]
+    cvr_contest_selection: Vec<CvrContestSelection>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CvrContestSelection {
+    contest_selection_id: Option<String>,
+    rank: Option<u32>,
+    #[serde(default)]
+    selection_position: Vec<SelectionPosition>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct SelectionPosition {
+    has_indication: Option<String>,
+    is_allocable: Option<String>,
+    rank: Option<u32>,
+}
+
+/// Read the contests and ballots from a NIST cast vote record report, in the JSON format of NIST SP 1500-103. `json`
+///
+/// Requires the `json` feature to be enabled.
+///
+/// Each cast vote record is counted once, using its current snapshot (or its last snapshot if no current snapshot is named).
+/// Only marks with an indication that are allocable are counted. Ranks come from the selection position, or from the
+/// contest selection, and marks without a rank are all ranked first, as in a plurality or approval contest.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_nist_cvr, ParsedVote};
+///
+///    let report = r#"{
+///        "@type": "CVR.CastVoteRecordReport",
+///        "Election": [{
+///            "Candidate": [{ "@id": "c1", "Name": "Alice" }, { "@id": "c2", "Name": "Bob" }],
+///            "Contest": [{
+///                "@id": "mayor",
+///                "Name": "Mayor",
+///                "ContestSelection": [{ "@id": "s1", "CandidateIds": ["c1"] }, { "@id": "s2", "CandidateIds": ["c2"] }]
+///            }]
+///        }],
+///        "CVR": [{
+///            "CVRSnapshot": [{
+///                "@id": "snapshot",
+///                "CVRContest": [{
+///                    "ContestId": "mayor",
+///                    "CVRContestSelection": [
+///                        { "ContestSelectionId": "s2", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 1 }] },
+///                        { "ContestSelectionId": "s1", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 2 }] }
+///                    ]
+///                }]
+///            }]
+///        }]
+///    }"#;
+///
+///    let contests = read_nist_cvr::<_, u64>(report.as_bytes())?;
+///    assert_eq!(contests[0].name, "Mayor");
+///    assert_eq!(contests[0].candidates, vec!["Alice", "Bob"]);
+///    assert_eq!(contests[0].votes, vec![(ParsedVote::Unranked(vec!["Bob".to_string(), "Alice".to_string()]), 1)]);
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub fn read_nist_cvr<R: Read, C: Num>(reader: R) -> Result<Vec<CvrContest<C>>, ParseError> {
+    let report: Report = serde_json::from_reader(reader).map_err(|e| {
+        if e.is_io() {
+            ParseError::ReadError(e.into())
+        } else {
+            ParseError::ParseError(e.to_string())
+        }
+    })?;
+
+    let mut candidate_names = HashMap::new();
+    for election in report.election.iter() {
+        for candidate in election.candidate.iter() {
+            candidate_names.insert(candidate.id.as_str(), candidate.name.as_deref().unwrap_or(&candidate.id));
+        }
+    }
+
+    // Map each contest and selection id to the contest's index and the selection's name.
+    let mut contests = Vec::new();
+    let mut contest_index = HashMap::new();
+    let mut selection_names = HashMap::new();
+    for contest in report.election.iter().flat_map(|election| election.contest.iter()) {
+        let mut candidates = Vec::with_capacity(contest.contest_selection.len());
+        for selection in contest.contest_selection.iter() {
+            let name = match selection.candidate_ids.first() {
+                Some(id) => candidate_names.get(id.as_str()).copied().unwrap_or(id).to_string(),
+                None => selection.id.clone(),
+            };
+            selection_names.insert((contest.id.as_str(), selection.id.as_str()), name.clone());
+            candidates.push(name);
+        }
+        contest_index.insert(contest.id.as_str(), contests.len());
+        contests.push(CvrContest {
+            id: contest.id.clone(),
+            name: contest.name.clone().unwrap_or_else(|| contest.id.clone()),
+            candidates: candidates,
+            votes: Vec::new(),
+        });
+    }
+
+    for cvr in report.cvr.iter() {
+        let snapshot = match &cvr.current_snapshot_id {
+            Some(id) => cvr.cvr_snapshot.iter().find(|snapshot| snapshot.id.as_ref() == Some(id)),
+            None => cvr.cvr_snapshot.last(),
+        };
+        let snapshot = match snapshot {
+            Some(snapshot) => snapshot,
+            None => continue,
+        };
+
+        for marks in snapshot.cvr_contest.iter() {
+            let index = *contest_index
+                .get(marks.contest_id.as_str())
+                .ok_or_else(|| ParseError::ParseError(marks.contest_id.clone()))?;
+
+            let mut marked: Vec<(&str, u32)> = Vec::new();
+            for selection in marks.cvr_contest_selection.iter() {
+                let selection_id = match &selection.contest_selection_id {
+                    Some(id) => id.as_str(),
+                    None => continue,
+                };
+                for position in selection.selection_position.iter() {
+                    let indicated = position.has_indication.as_deref().is_none_or(|indication| indication == "yes");
+                    let allocable = position.is_allocable.as_deref().is_none_or(|allocable| allocable != "no");
+                    if indicated && allocable {
+                        marked.push((selection_id, position.rank.or(selection.rank).unwrap_or(1)));
+                    }
+                }
+            }
+            if marked.is_empty() {
+                continue;
+            }
+
+            // Keep only the highest rank given to each selection, then renumber the ranks from 0 without gaps.
+            marked.sort_by_key(|(_, rank)| *rank);
+            let mut ranked: Vec<(String, u32)> = Vec::with_capacity(marked.len());
+            let mut last_rank = None;
+            let mut rank = 0;
+            for (selection_id, cvr_rank) in marked {
+                let name = selection_names
+                    .get(&(marks.contest_id.as_str(), selection_id))
+                    .ok_or_else(|| ParseError::ParseError(selection_id.to_string()))?;
+                if ranked.iter().any(|(candidate, _)| candidate == name) {
+                    continue;
+                }
+                if last_rank.is_some_and(|last| last != cvr_rank) {
+                    rank += 1;
+                }
+                last_rank = Some(cvr_rank);
+                ranked.push((name.clone(), rank));
+            }
+
+            let vote = if rank as usize + 1 == ranked.len() {
+                ParsedVote::Unranked(ranked.into_iter().map(|(candidate, _)| candidate).collect())
+            } else {
+                ParsedVote::Ranked(ranked)
+            };
+            contests[index].votes.push((vote, C::one()));
+        }
+    }
+
+    Ok(contests)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nist_cvr_snapshots_and_overvotes() -> Result<(), ParseError> {
+        let report = r#"{
+            "Election": [{
+                "Candidate": [{ "@id": "c1", "Name": "Alice" }, { "@id": "c2", "Name": "Bob" }, { "@id": "c3" }],
+                "Contest": [
+                    { "@id": "k1", "ContestSelection": [
+                        { "@id": "s1", "CandidateIds": ["c1"] },
+                        { "@id": "s2", "CandidateIds": ["c2"] },
+                        { "@id": "s3", "CandidateIds": ["c3"] },
+                        { "@id": "write-in" }
+                    ] },
+                    { "@id": "k2", "Name": "Measure", "ContestSelection": [{ "@id": "yes" }, { "@id": "no" }] }
+                ]
+            }],
+            "CVR": [
+                {
+                    "CurrentSnapshotId": "adjudicated",
+                    "CVRSnapshot": [
+                        { "@id": "original", "CVRContest": [{ "ContestId": "k1", "CVRContestSelection": [
+                            { "ContestSelectionId": "s1", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 1 }] }
+                        ] }] },
+                        { "@id": "adjudicated", "CVRContest": [
+                            { "ContestId": "k1", "CVRContestSelection": [
+                                { "ContestSelectionId": "s1", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 3 }] },
+                                { "ContestSelectionId": "s2", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 1 }] },
+                                { "ContestSelectionId": "s3", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 1 }] },
+                                { "ContestSelectionId": "write-in", "SelectionPosition": [{ "HasIndication": "no", "Rank": 2 }] },
+                                { "ContestSelectionId": "s2", "SelectionPosition": [{ "HasIndication": "yes", "Rank": 4 }] }
+                            ] },
+                            { "ContestId": "k2", "CVRContestSelection": [
+                                { "ContestSelectionId": "no", "SelectionPosition": [{ "HasIndication": "yes", "IsAllocable": "no" }] }
+                            ] }
+                        ] }
+                    ]
+                },
+                { "CVRSnapshot": [{ "CVRContest": [{ "ContestId": "k2", "CVRContestSelection": [
+                    { "ContestSelectionId": "yes", "SelectionPosition": [{}] }
+                ] }] }] }
+            ]
+        }"#;
+
+        let contests = read_nist_cvr::<_, f64>(report.as_bytes())?;
+        assert_eq!(contests.len(), 2);
+        assert_eq!(contests[0].name, "k1");
+        assert_eq!(contests[0].candidates, vec!["Alice", "Bob", "c3", "write-in"]);
+        assert_eq!(
+            contests[0].votes,
+            vec![(
+                ParsedVote::Ranked(vec![("Bob".to_string(), 0), ("c3".to_string(), 0), ("Alice".to_string(), 1)]),
+                1.0
+            )]
+        );
+        assert_eq!(contests[1].votes, vec![(ParsedVote::Unranked(vec!["yes".to_string()]), 1.0)]);
+
+        let unknown = r#"{ "CVR": [{ "CVRSnapshot": [{ "CVRContest": [{ "ContestId": "k9" }] }] }] }"#;
+        assert!(read_nist_cvr::<_, f64>(unknown.as_bytes()).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,329 @@
+use hashbrown::HashMap;
+use num_traits::Num;
+use std::convert::TryInto;
+use std::io::{BufRead, BufReader, Read};
+
+use super::{locate, parse_weight, ParseError, ParsedVote};
+
+/// The kinds of ordinal preference data in PrefLib.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrefLibType {
+    /// Strict orders, complete list. Every ballot ranks every candidate, without ties.
+    Soc,
+
+    /// Strict orders, incomplete list. Ballots may leave candidates out, but have no ties.
+    Soi,
+
+    /// Orders with ties, complete list.
+    Toc,
+
+    /// Orders with ties, incomplete list.
+    Toi,
+}
+
+/// Ordinal preference data read from a PrefLib file.
+///
+/// See [https://www.preflib.org/format](https://www.preflib.org/format).
+#[derive(Debug)]
+pub struct PrefLib<C> {
+    /// The kind of data in the file.
+    pub data_type: PrefLibType,
+
+    /// The names of the candidates, in the order they are numbered.
+    pub candidates: Vec<String>,
+
+    /// Each distinct ballot and the number of voters that cast it. Ballots with tied candidates are parsed as ranked votes.
+    pub votes: Vec<(ParsedVote<C>, C)>,
+}
+
+/// Read a PrefLib SOC, SOI, TOC or TOI file.
+///
+/// Both the current PrefLib format, with metadata in `#` comment lines, and the older format, with a candidate list and a
+/// summary line before the ballots, are supported. For the older format the data type is inferred from the ballots.
+///
+/// # Example
+/// ```
+///    use tallystick::util::{read_preflib, PrefLibType};
+///
+///    let file = [
+///        "# DATA TYPE: toc",
+///        "# NUMBER ALTERNATIVES: 3",
+///        "# ALTERNATIVE NAME 1: Alice",
+///        "# ALTERNATIVE NAME 2: Bob",
+///        "# ALTERNATIVE NAME 3: Carol",
+///        "12: 1,2,3",
+///        "7: 2,{1,3}",
+///    ]
+///    .join("\n");
+///
+///    let preflib = read_preflib::<_, u64>(file.as_bytes())?;
+///    assert_eq!(preflib.data_type, PrefLibType::Toc);
+///    assert_eq!(preflib.candidates, vec!["Alice", "Bob", "Carol"]);
+///    assert_eq!(preflib.votes[1].0.clone().into_ranked(), vec![("Bob".to_string(), 0), ("Alice".to_string(), 1), ("Carol".to_string(), 1)]);
+///    assert_eq!(preflib.votes[1].1, 7);
+///    # Ok::<(), tallystick::util::ParseError>(())
+/// ```
+pub fn read_preflib<R: Read, C: Num>(reader: R) -> Result<PrefLib<C>, ParseError> {
+    // Lines are kept as they were read, with their line numbers, to locate errors.
+    let mut lines = Vec::new();
+    let mut line_count = 0;
+    for line in BufReader::new(reader).lines() {
+        let line = line?;
+        line_count += 1;
+        if !line.trim().is_empty() {
+            lines.push((line_count, line));
+        }
+    }
+
+    let mut data_type = None;
+    let mut num_candidates = 0;
+    let mut names = HashMap::<usize, String>::new();
+    // Each ballot, with the number and text of its line and the offending slice of the line for any unknown candidate.
+    let mut ballots = Vec::<(Vec<Vec<usize>>, C, usize, &str, &str)>::new();
+
+    if lines.first().is_some_and(|(_, line)| !line.trim().starts_with('#')) {
+        // The older format: the number of candidates, a line per candidate, a summary line, then the ballots.
+        let mut lines = lines.iter();
+        if let Some((number, raw)) = lines.next() {
+            num_candidates = parse_number(raw, "the number of candidates").map_err(|(text, e)| locate(e, *number, raw, text))?;
+        }
+        for _ in 0..num_candidates {
+            let (number, raw) = lines
+                .next()
+                .ok_or_else(|| ParseError::Expected("a line for each candidate").on_line(line_count + 1, 1, ""))?;
+            let (id, name) = split_once(raw, ',', "a candidate number and name separated by ','")
+                .and_then(|(id, name)| Ok((parse_number(id, "a candidate number")?, name)))
+                .map_err(|(text, e)| locate(e, *number, raw, text))?;
+            names.insert(id, name.trim().to_string());
+        }
+        lines.next(); // The summary of voters and unique orders is not needed.
+        for (number, raw) in lines {
+            let (count, order) =
+                split_once(raw, ',', "a count and an order separated by ','").map_err(|(text, e)| locate(e, *number, raw, text))?;
+            let groups = parse_order(order).map_err(|(text, e)| locate(e, *number, raw, text))?;
+            let count = parse_count(count).map_err(|(text, e)| locate(e, *number, raw, text))?;
+            ballots.push((groups, count, *number, raw, order));
+        }
+    } else {
+        for (number, raw) in lines.iter() {
+            let line = raw.trim();
+            let parsed = match line.strip_prefix('#') {
+                Some(metadata) => {
+                    let (key, value) = match metadata.split_once(':') {
+                        Some((key, value)) => (key.trim(), value.trim()),
+                        None => continue,
+                    };
+                    if key == "DATA TYPE" {
+                        parse_data_type(value).map(|parsed| data_type = Some(parsed))
+                    } else if key == "NUMBER ALTERNATIVES" {
+                        parse_number(value, "the number of candidates").map(|parsed| num_candidates = parsed)
+                    } else if let Some(id) = key.strip_prefix("ALTERNATIVE NAME") {
+                        parse_number(id, "a candidate number").map(|id| {
+                            names.insert(id, value.to_string());
+                        })
+                    } else {
+                        Ok(())
+                    }
+                }
+                None => split_once(line, ':', "a count and an order separated by ':'").and_then(|(count, order)| {
+                    ballots.push((parse_order(order)?, parse_count(count)?, *number, raw, order));
+                    Ok(())
+                }),
+            };
+            parsed.map_err(|(text, e)| locate(e, *number, raw, text))?;
+        }
+    }
+
+    let candidates: Vec<String> = (1..=num_candidates)
+        .map(|id| names.get(&id).cloned().unwrap_or_else(|| id.to_string()))
+        .collect();
+    let name = |id: usize| {
+        if id >= 1 && id <= num_candidates {
+            Ok(candidates[id - 1].clone())
+        } else {
+            Err(ParseError::Expected("a candidate number between 1 and the number of candidates"))
+        }
+    };
+
+    let data_type = data_type.unwrap_or_else(|| {
+        let ties = ballots.iter().any(|(order, ..)| order.iter().any(|group| group.len() > 1));
+        let complete = ballots
+            .iter()
+            .all(|(order, ..)| order.iter().map(|group| group.len()).sum::<usize>() == num_candidates);
+        match (ties, complete) {
+            (false, true) => PrefLibType::Soc,
+            (false, false) => PrefLibType::Soi,
+            (true, true) => PrefLibType::Toc,
+            (true, false) => PrefLibType::Toi,
+        }
+    });
+
+    let mut votes = Vec::with_capacity(ballots.len());
+    for (order, count, number, raw, text) in ballots {
+        let located = |e| locate(e, number, raw, text);
+        let vote = if order.iter().any(|group| group.len() > 1) {
+            let mut ranked = Vec::new();
+            for (rank, group) in order.iter().enumerate() {
+                // Safe to unwrap here since we can't have more than u32::MAX candidates anyways.
+                let rank: u32 = rank.try_into().unwrap();
+                for id in group.iter() {
+                    ranked.push((name(*id).map_err(located)?, rank));
+                }
+            }
+            ParsedVote::Ranked(ranked)
+        } else {
+            ParsedVote::Unranked(
+                order
+                    .iter()
+                    .flatten()
+                    .map(|id| name(*id))
+                    .collect::<Result<_, _>>()
+                    .map_err(located)?,
+            )
+        };
+        votes.push((vote, count));
+    }
+
+    Ok(PrefLib {
+        data_type: data_type,
+        candidates: candidates,
+        votes: votes,
+    })
+}
+
+// Parse an order such as `2,{1,3},4` into groups of equally ranked candidates.
+// On error, also returns the offending slice of the order.
+fn parse_order(order: &str) -> Result<Vec<Vec<usize>>, (&str, ParseError)> {
+    let unbalanced = || {
+        (
+            order.trim(),
+            ParseError::Expected("tied candidates to be enclosed in matching braces"),
+        )
+    };
+    let mut groups = Vec::new();
+    let mut group: Option<Vec<usize>> = None;
+    for token in order.split(',') {
+        let token = token.trim();
+        let opens = token.starts_with('{');
+        let closes = token.ends_with('}');
+        let id = token.trim_start_matches('{').trim_end_matches('}').trim();
+
+        if opens {
+            group = Some(Vec::new());
+        }
+        if !id.is_empty() {
+            let id = parse_number(id, "a candidate number")?;
+            match group.as_mut() {
+                Some(group) => group.push(id),
+                None => groups.push(vec![id]),
+            }
+        }
+        if closes {
+            match group.take() {
+                Some(group) if !group.is_empty() => groups.push(group),
+                Some(_) => {}
+                None => return Err(unbalanced()),
+            }
+        }
+    }
+
+    if group.is_some() {
+        return Err(unbalanced());
+    }
+    Ok(groups)
+}
+
+// The helpers below return the offending slice of their input with any error, so that it can be located in its line.
+
+fn parse_data_type(data_type: &str) -> Result<PrefLibType, (&str, ParseError)> {
+    match data_type.to_lowercase().as_str() {
+        "soc" => Ok(PrefLibType::Soc),
+        "soi" => Ok(PrefLibType::Soi),
+        "toc" => Ok(PrefLibType::Toc),
+        "toi" => Ok(PrefLibType::Toi),
+        _ => Err((data_type, ParseError::Expected("a data type of soc, soi, toc or toi"))),
+    }
+}
+
+// Parse a whole number, naming what was expected if it is not one.
+fn parse_number<'a>(token: &'a str, expected: &'static str) -> Result<usize, (&'a str, ParseError)> {
+    let token = token.trim();
+    token.parse().map_err(|_| (token, ParseError::Expected(expected)))
+}
+
+// Parse the number of voters who cast a ballot.
+fn parse_count<C: Num>(count: &str) -> Result<C, (&str, ParseError)> {
+    let count = count.trim();
+    parse_weight(count).map_err(|_| (count, ParseError::Expected("a count of voters")))
+}
+
+fn split_once<'a>(line: &'a str, separator: char, expected: &'static str) -> Result<(&'a str, &'a str), (&'a str, ParseError)> {
+    let line = line.trim();
+    match line.split_once(separator) {
+        Some((before, after)) => Ok((before.trim(), after.trim())),
+        None => Err((line, ParseError::Expected(expected))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preflib_old_format() -> Result<(), ParseError> {
+        let file = "3\n1,Alice\n2,Bob\n3,Carol\n10,10,3\n5,1,2\n3,{2,3},1\n2,3\n";
+        let preflib = read_preflib::<_, u32>(file.as_bytes())?;
+        assert_eq!(preflib.data_type, PrefLibType::Toi);
+        assert_eq!(preflib.candidates, vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(preflib.votes.len(), 3);
+        assert_eq!(preflib.votes[0].1, 5);
+        assert_eq!(
+            preflib.votes[1].0.clone().into_ranked(),
+            vec![("Bob".to_string(), 0), ("Carol".to_string(), 0), ("Alice".to_string(), 1)]
+        );
+
+        assert!(read_preflib::<_, u32>("# DATA TYPE: soc\n# NUMBER ALTERNATIVES: 2\n1: 1,3\n".as_bytes()).is_err());
+        assert!(parse_order("1,{2,3").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn preflib_errors() {
+        let error = |file: &str| read_preflib::<_, u32>(file.as_bytes()).unwrap_err();
+
+        let unknown = error("# DATA TYPE: soc\n# NUMBER ALTERNATIVES: 2\n\n1: 1,3\n");
+        assert_eq!((unknown.line(), unknown.column()), (Some(4), Some(4)));
+        assert_eq!(
+            unknown.to_string(),
+            "tallystick: expected a candidate number between 1 and the number of candidates (line 4, column 4: 1: 1,3)"
+        );
+
+        let data_type = error("# DATA TYPE: xyz\n");
+        assert_eq!((data_type.line(), data_type.column()), (Some(1), Some(14)));
+        assert!(data_type
+            .to_string()
+            .starts_with("tallystick: expected a data type of soc, soi, toc or toi"));
+
+        let braces = error("# NUMBER ALTERNATIVES: 3\n2: 1,{2,3\n");
+        assert_eq!((braces.line(), braces.column()), (Some(2), Some(4)));
+        assert!(braces
+            .to_string()
+            .starts_with("tallystick: expected tied candidates to be enclosed in matching braces"));
+
+        let count = error("# NUMBER ALTERNATIVES: 2\nmany: 1,2\n");
+        assert_eq!((count.line(), count.column()), (Some(2), Some(1)));
+        assert!(count.to_string().starts_with("tallystick: expected a count of voters"));
+
+        // The older format names each missing candidate line after the last line.
+        let names = error("3\n1,Alice\n2,Bob\n");
+        assert_eq!(names.line(), Some(4));
+        assert!(names.to_string().starts_with("tallystick: expected a line for each candidate"));
+
+        let name = error("2\n1 Alice\n");
+        assert_eq!(name.line(), Some(2));
+        assert!(name
+            .to_string()
+            .starts_with("tallystick: expected a candidate number and name separated by ','"));
+    }
+}
@@ -0,0 +1,191 @@
+use hashbrown::HashSet;
+use std::hash::Hash;
+
+use super::TallyError;
+use super::Truncation;
+
+/// A problem found with a single ballot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic<T> {
+    /// Index of the ballot in the validated ballots.
+    pub index: usize,
+
+    /// What is wrong with the ballot.
+    pub error: TallyError,
+
+    /// The candidate that caused the error, if the error was caused by a particular candidate.
+    pub candidate: Option<T>,
+}
+
+impl<T> Diagnostic<T> {
+    fn new(index: usize, error: TallyError, candidate: Option<T>) -> Self {
+        Diagnostic {
+            index: index,
+            error: error,
+            candidate: candidate,
+        }
+    }
+}
+
+/// Validate ballots before counting them, without adding them to a tally.
+///
+/// A `BallotValidator` checks every ballot in a batch and reports every problem it finds,
+/// so that invalid ballots can be shown to voters or election administrators before the count.
+/// Ballots with no candidates are blank ballots and are not reported.
+///
+/// # Example
+/// ```
+///    use tallystick::validate::BallotValidator;
+///    use tallystick::{TallyError, Truncation};
+///
+///    let validator = BallotValidator::with_candidates(vec!["Alice", "Bob", "Carlos"]).with_truncation(Truncation::Minimum(2));
+///    let ballots = vec![
+///        vec!["Alice", "Bob"],
+///        vec!["Bob", "Dave"],
+///        vec!["Carlos"],
+///    ];
+///
+///    let diagnostics = validator.validate_ballots(&ballots);
+///    assert_eq!(diagnostics.len(), 2);
+///    assert_eq!(diagnostics[0].index, 1);
+///    assert_eq!(diagnostics[0].error, TallyError::UnknownCandidate { position: 1 });
+///    assert_eq!(diagnostics[0].candidate, Some("Dave"));
+///    assert_eq!(diagnostics[1].index, 2);
+///    assert_eq!(diagnostics[1].error, TallyError::TruncatedBallot { rankings: 1, required: 2 });
+/// ```
+#[derive(Debug, Clone)]
+pub struct BallotValidator<T: Eq + Clone + Hash> {
+    candidates: Option<HashSet<T>>,
+    truncation: Truncation,
+}
+
+impl<T: Eq + Clone + Hash> Default for BallotValidator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Clone + Hash> BallotValidator<T> {
+    /// Create a new `BallotValidator` that accepts any candidate.
+    ///
+    /// When checking for full rankings, the candidates are all those that appear on any of the validated ballots.
+    pub fn new() -> Self {
+        BallotValidator {
+            candidates: None,
+            truncation: Truncation::Allow,
+        }
+    }
+
+    /// Create a new `BallotValidator` that only accepts the provided candidates.
+    pub fn with_candidates(candidates: Vec<T>) -> Self {
+        BallotValidator {
+            candidates: Some(candidates.into_iter().collect()),
+            truncation: Truncation::Allow,
+        }
+    }
+
+    /// Set the policy for ballots that do not rank every candidate.
+    pub fn with_truncation(mut self, truncation: Truncation) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Validate ballots that list candidates in order of preference.
+    ///
+    /// Returns a diagnostic for every problem found, ordered by ballot. An empty result means all ballots are valid.
+    pub fn validate_ballots(&self, ballots: &[Vec<T>]) -> Vec<Diagnostic<T>> {
+        let num_candidates = self.num_candidates(ballots.iter().flat_map(|ballot| ballot.iter()));
+
+        let mut diagnostics = Vec::new();
+        for (index, ballot) in ballots.iter().enumerate() {
+            self.check_ballot(index, ballot.iter(), num_candidates, &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    /// Validate ranked ballots, as lists of (candidate, rank) pairs.
+    ///
+    /// Returns a diagnostic for every problem found, ordered by ballot. An empty result means all ballots are valid.
+    pub fn validate_ranked_ballots(&self, ballots: &[Vec<(T, u32)>]) -> Vec<Diagnostic<T>> {
+        let num_candidates = self.num_candidates(ballots.iter().flat_map(|ballot| ballot.iter().map(|(c, _)| c)));
+
+        let mut diagnostics = Vec::new();
+        for (index, ballot) in ballots.iter().enumerate() {
+            self.check_ballot(index, ballot.iter().map(|(c, _)| c), num_candidates, &mut diagnostics);
+        }
+        diagnostics
+    }
+
+    // The number of candidates a full ranking must include.
+    fn num_candidates<'a, I: Iterator<Item = &'a T>>(&self, on_ballots: I) -> usize
+    where
+        T: 'a,
+    {
+        match &self.candidates {
+            Some(candidates) => candidates.len(),
+            None => on_ballots.collect::<HashSet<&T>>().len(),
+        }
+    }
+
+    fn check_ballot<'a, I>(&self, index: usize, ballot: I, num_candidates: usize, diagnostics: &mut Vec<Diagnostic<T>>)
+    where
+        I: Iterator<Item = &'a T>,
+        T: 'a,
+    {
+        let mut seen = HashSet::new();
+        let mut rankings = 0;
+        for (position, candidate) in ballot.enumerate() {
+            if let Some(candidates) = &self.candidates {
+                if !candidates.contains(candidate) {
+                    let error = TallyError::UnknownCandidate { position };
+                    diagnostics.push(Diagnostic::new(index, error, Some(candidate.clone())));
+                }
+            }
+            if !seen.insert(candidate) {
+                let error = TallyError::VoteHasDuplicateCandidates { position };
+                diagnostics.push(Diagnostic::new(index, error, Some(candidate.clone())));
+            }
+            rankings += 1;
+        }
+
+        // Blank ballots are not truncated.
+        if rankings > 0 {
+            if let Err(error) = self.truncation.check(rankings, num_candidates) {
+                diagnostics.push(Diagnostic::new(index, error, None));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_ballots_test() {
+        let validator = BallotValidator::with_candidates(vec!["Alice", "Bob", "Carol"]).with_truncation(Truncation::RequireFull);
+        let ballots = vec![
+            vec!["Alice", "Bob", "Carol"],
+            vec!["Alice", "Dave", "Alice"],
+            vec![],
+            vec!["Carol", "Bob", "Alice"],
+        ];
+        let diagnostics = validator.validate_ballots(&ballots);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(1, TallyError::UnknownCandidate { position: 1 }, Some("Dave")),
+                Diagnostic::new(1, TallyError::VoteHasDuplicateCandidates { position: 2 }, Some("Alice")),
+            ]
+        );
+
+        // Without a candidate list, a full ranking includes every candidate on any ballot.
+        let validator = BallotValidator::new().with_truncation(Truncation::RequireFull);
+        let ballots = vec![vec![("Alice", 0), ("Bob", 0)], vec![("Carol", 0)]];
+        let diagnostics = validator.validate_ranked_ballots(&ballots);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].error, TallyError::TruncatedBallot { rankings: 2, required: 3 });
+        assert_eq!(diagnostics[1].index, 1);
+        assert_eq!(diagnostics[1].candidate, None);
+    }
+}
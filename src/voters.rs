@@ -0,0 +1,141 @@
+use hashbrown::HashMap;
+use std::hash::Hash;
+
+use super::TallyError;
+
+/// What to do when a voter casts a second ballot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DuplicateVoter {
+    /// Keep the voter's first ballot, and reject any later ballot with `TallyError::DuplicateVoter`.
+    #[default]
+    Reject,
+
+    /// Replace the voter's earlier ballot with their latest ballot.
+    Replace,
+}
+
+/// A roll of voters and the ballot each of them cast, ensuring each voter is counted only once.
+///
+/// Ballots are kept in the order voters first voted. Once voting is closed, add the ballots to a tally.
+///
+/// # Example
+/// ```
+///    use tallystick::plurality::DefaultPluralityTally;
+///    use tallystick::voters::{DuplicateVoter, VoterRoll};
+///    use tallystick::TallyError;
+///
+///    let mut roll = VoterRoll::new(DuplicateVoter::Reject);
+///    roll.add_with_voter("voter-1", "Alice")?;
+///    roll.add_with_voter("voter-2", "Bob")?;
+///    roll.add_with_voter("voter-3", "Alice")?;
+///    assert_eq!(roll.add_with_voter("voter-1", "Bob"), Err(TallyError::DuplicateVoter));
+///
+///    let mut tally = DefaultPluralityTally::new(1);
+///    for ballot in roll.ballots() {
+///        tally.add_ref(ballot);
+///    }
+///    assert_eq!(tally.totals(), vec![("Alice", 2), ("Bob", 1)]);
+///    # Ok::<(), TallyError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct VoterRoll<V: Eq + Hash, B> {
+    ballots: Vec<B>,
+    voters: HashMap<V, usize>, // Map voters to the index of their ballot
+    duplicates: DuplicateVoter,
+}
+
+impl<V: Eq + Hash, B> VoterRoll<V, B> {
+    /// Create a new, empty `VoterRoll` with the given policy for voters who cast a second ballot.
+    pub fn new(duplicates: DuplicateVoter) -> Self {
+        VoterRoll {
+            ballots: Vec::new(),
+            voters: HashMap::new(),
+            duplicates: duplicates,
+        }
+    }
+
+    /// Create a new `VoterRoll` with capacity for the expected number of voters.
+    pub fn with_capacity(duplicates: DuplicateVoter, expected_voters: usize) -> Self {
+        VoterRoll {
+            ballots: Vec::with_capacity(expected_voters),
+            voters: HashMap::with_capacity(expected_voters),
+            duplicates: duplicates,
+        }
+    }
+
+    /// Add a voter's ballot.
+    ///
+    /// If the voter has already voted, the ballot is rejected with `TallyError::DuplicateVoter`,
+    /// or replaces their earlier ballot if the roll was created with `DuplicateVoter::Replace`.
+    /// A replaced ballot is returned.
+    pub fn add_with_voter(&mut self, voter: V, ballot: B) -> Result<Option<B>, TallyError> {
+        match self.voters.get(&voter) {
+            Some(&index) => match self.duplicates {
+                DuplicateVoter::Reject => Err(TallyError::DuplicateVoter),
+                DuplicateVoter::Replace => Ok(Some(std::mem::replace(&mut self.ballots[index], ballot))),
+            },
+            None => {
+                self.voters.insert(voter, self.ballots.len());
+                self.ballots.push(ballot);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Check if a voter has voted.
+    pub fn has_voted(&self, voter: &V) -> bool {
+        self.voters.contains_key(voter)
+    }
+
+    /// Get a voter's ballot, if they have voted.
+    pub fn ballot(&self, voter: &V) -> Option<&B> {
+        self.voters.get(voter).map(|&index| &self.ballots[index])
+    }
+
+    /// Get the number of voters who have voted.
+    pub fn len(&self) -> usize {
+        self.ballots.len()
+    }
+
+    /// Check if no one has voted.
+    pub fn is_empty(&self) -> bool {
+        self.ballots.is_empty()
+    }
+
+    /// Get the ballots cast, one for each voter, in the order voters first voted.
+    pub fn ballots(&self) -> &[B] {
+        &self.ballots
+    }
+
+    /// Transform the roll into the ballots cast, one for each voter, in the order voters first voted.
+    pub fn into_ballots(self) -> Vec<B> {
+        self.ballots
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voter_roll_test() -> Result<(), TallyError> {
+        let mut roll = VoterRoll::new(DuplicateVoter::Replace);
+        assert!(roll.is_empty());
+        assert_eq!(roll.add_with_voter(1, vec!["Alice", "Bob"])?, None);
+        assert_eq!(roll.add_with_voter(2, vec!["Bob"])?, None);
+        assert_eq!(roll.add_with_voter(1, vec!["Bob", "Alice"])?, Some(vec!["Alice", "Bob"]));
+
+        assert_eq!(roll.len(), 2);
+        assert!(roll.has_voted(&2));
+        assert!(!roll.has_voted(&3));
+        assert_eq!(roll.ballot(&1), Some(&vec!["Bob", "Alice"]));
+        assert_eq!(roll.into_ballots(), vec![vec!["Bob", "Alice"], vec!["Bob"]]);
+
+        let mut roll = VoterRoll::with_capacity(DuplicateVoter::default(), 2);
+        roll.add_with_voter("voter", 1)?;
+        assert_eq!(roll.add_with_voter("voter", 2), Err(TallyError::DuplicateVoter));
+        assert_eq!(roll.ballots(), &[1]);
+
+        Ok(())
+    }
+}
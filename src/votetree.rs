@@ -2,13 +2,13 @@
 //! This data structure is taken from https://gitlab.com/mbq/wybr,
 //! with a special thanks to mbq.
 
+use super::Flow;
 use super::Numeric;
 use hashbrown::HashMap;
 use hashbrown::HashSet;
-use num_traits::cast::NumCast;
+use num_traits::FromPrimitive;
 use num_traits::Num;
 use std::hash::Hash;
-use std::iter::FromIterator;
 use std::ops::AddAssign;
 
 #[derive(Copy, Clone)]
@@ -21,143 +21,376 @@ pub enum Transfer {
     Warren,
 }
 
+// A tree of ballots, flattened into a vector of nodes.
+// Candidates are interned to ids, so nodes store a `u32` rather than a clone of their candidate,
+// and each node's children are a short list sorted by candidate id instead of a hash map.
+#[derive(Clone)]
 pub(crate) struct VoteTree<T, C = u64>
 where
-    T: Eq + Clone + Hash,                                       // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast + Numeric, // Count type
+    T: Eq + Clone + Hash,                                              // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // Count type
 {
-    pub(crate) count: C,
-    children: HashMap<T, VoteTree<T, C>>,
-    candidates: Option<HashSet<T>>, // Only exists on top level VoteTree
+    nodes: Vec<Node<C>>, // The root, standing for every ballot, is the first node
+    candidates: Vec<T>,  // Candidates indexed by id
+    ids: HashMap<T, u32>,
+    withdrawn: Vec<bool>, // Withdrawn candidates keep their id, but are no longer on any ballot
+}
+
+#[derive(Clone)]
+struct Node<C> {
+    count: C,
+    ballots: usize,
+    children: Vec<(u32, usize)>, // Candidate id and node index of each child, sorted by candidate id
+}
+
+const ROOT: usize = 0;
+
+impl<C: Num> Node<C> {
+    fn new() -> Self {
+        Node {
+            count: C::zero(),
+            ballots: 0,
+            children: Vec::new(),
+        }
+    }
+}
+
+// Find the child of a node for a candidate, adding it if there is none.
+fn child_or_insert<C: Num>(nodes: &mut Vec<Node<C>>, node: usize, candidate: u32) -> usize {
+    match nodes[node].children.binary_search_by_key(&candidate, |(c, _)| *c) {
+        Ok(i) => nodes[node].children[i].1,
+        Err(i) => {
+            let child = nodes.len();
+            nodes.push(Node::new());
+            nodes[node].children.insert(i, (candidate, child));
+            child
+        }
+    }
 }
 
 impl<T, C> VoteTree<T, C>
 where
-    T: Eq + Clone + Hash,                                       // Candidate type
-    C: Copy + PartialOrd + AddAssign + Num + NumCast + Numeric, // Count type
+    T: Eq + Clone + Hash,                                              // Candidate type
+    C: Clone + PartialOrd + AddAssign + Num + FromPrimitive + Numeric, // Count type
 {
     pub(crate) fn new() -> VoteTree<T, C> {
         VoteTree {
-            count: C::zero(),
-            children: HashMap::new(),
-            candidates: Some(HashSet::new()),
+            nodes: vec![Node::new()],
+            candidates: Vec::new(),
+            ids: HashMap::new(),
+            withdrawn: Vec::new(),
         }
     }
 
     pub(crate) fn with_candidates(candidates: Vec<T>) -> VoteTree<T, C> {
-        VoteTree {
-            count: C::zero(),
-            children: HashMap::new(),
-            candidates: Some(HashSet::from_iter(candidates)),
+        let mut tree = Self::new();
+        for candidate in candidates.iter() {
+            tree.intern(candidate);
         }
+        tree
     }
 
     pub(crate) fn candidates(&self) -> Vec<T> {
-        self.candidates.clone().unwrap().drain().collect()
+        self.candidates
+            .iter()
+            .zip(self.withdrawn.iter())
+            .filter(|(_, withdrawn)| !**withdrawn)
+            .map(|(candidate, _)| candidate.clone())
+            .collect()
+    }
+
+    // Number of candidates known to the tree, including any new candidates on a vote that has not been added yet.
+    pub(crate) fn num_candidates_with(&self, vote: &[T]) -> usize {
+        let known = self.withdrawn.iter().filter(|withdrawn| !**withdrawn).count();
+        let new = vote
+            .iter()
+            .filter(|c| self.ids.get(*c).is_none_or(|id| self.withdrawn[*id as usize]))
+            .count();
+        known + new
+    }
+
+    // The id of a candidate, interning them if they are new.
+    fn intern(&mut self, candidate: &T) -> u32 {
+        if let Some(id) = self.ids.get(candidate) {
+            self.withdrawn[*id as usize] = false;
+            return *id;
+        }
+        let id = self.candidates.len() as u32;
+        self.candidates.push(candidate.clone());
+        self.ids.insert(candidate.clone(), id);
+        self.withdrawn.push(false);
+        id
+    }
+
+    pub(crate) fn count(&self) -> C {
+        self.nodes[ROOT].count.clone()
     }
 
     pub(crate) fn add(&mut self, vote: &[T], weight: C) -> C {
-        self.count += weight;
-        if vote.is_empty() {
-            self.count
-        } else {
-            // Record all candidates
-            match &mut self.candidates {
-                Some(cand) => {
-                    for v in vote {
-                        cand.get_or_insert_with(v, |c| c.clone());
-                    }
+        // Record all candidates
+        let ids: Vec<u32> = vote.iter().map(|candidate| self.intern(candidate)).collect();
+
+        let mut node = ROOT;
+        self.nodes[node].count += weight.clone();
+        self.nodes[node].ballots += 1;
+        for id in ids {
+            node = child_or_insert(&mut self.nodes, node, id);
+            self.nodes[node].count += weight.clone();
+            self.nodes[node].ballots += 1;
+        }
+        self.nodes[node].count.clone()
+    }
+
+    // Remove a candidate from every vote, as if they had never been on the ballot.
+    // Preferences below the candidate are merged into the candidate's parent.
+    pub(crate) fn remove_candidate(&mut self, candidate: &T) {
+        let removed = match self.ids.get(candidate) {
+            Some(id) => *id,
+            None => return,
+        };
+        self.withdrawn[removed as usize] = true;
+
+        // Rebuild the tree without the candidate, so merged nodes do not leave unreachable nodes behind.
+        let mut nodes = vec![Node {
+            count: self.nodes[ROOT].count.clone(),
+            ballots: self.nodes[ROOT].ballots,
+            children: Vec::new(),
+        }];
+        self.merge_into(&mut nodes, ROOT, ROOT, removed);
+        self.nodes = nodes;
+    }
+
+    // Copy the children of node `from` into node `to` of a new tree, merging the children of the removed candidate into their parent.
+    fn merge_into(&self, nodes: &mut Vec<Node<C>>, to: usize, from: usize, removed: u32) {
+        for (candidate, child) in self.nodes[from].children.iter() {
+            if *candidate == removed {
+                self.merge_into(nodes, to, *child, removed);
+            } else {
+                let target = child_or_insert(nodes, to, *candidate);
+                nodes[target].count += self.nodes[*child].count.clone();
+                nodes[target].ballots += self.nodes[*child].ballots;
+                self.merge_into(nodes, target, *child, removed);
+            }
+        }
+    }
+
+    // Add every ballot in another tree to this one.
+    pub(crate) fn merge(&mut self, other: &VoteTree<T, C>) {
+        // Map the other tree's candidate ids to ids in this tree. Withdrawn candidates are on no ballot, so they are left unmapped.
+        let ids: Vec<Option<u32>> = other
+            .candidates
+            .iter()
+            .zip(other.withdrawn.iter())
+            .map(|(candidate, withdrawn)| if *withdrawn { None } else { Some(self.intern(candidate)) })
+            .collect();
+
+        self.nodes[ROOT].count += other.nodes[ROOT].count.clone();
+        self.nodes[ROOT].ballots += other.nodes[ROOT].ballots;
+        self.merge_from(other, &ids, ROOT, ROOT);
+    }
+
+    // Add the children of node `from` in another tree to node `to` of this tree.
+    fn merge_from(&mut self, other: &VoteTree<T, C>, ids: &[Option<u32>], to: usize, from: usize) {
+        for (candidate, child) in other.nodes[from].children.iter() {
+            match ids[*candidate as usize] {
+                Some(id) => {
+                    let target = child_or_insert(&mut self.nodes, to, id);
+                    self.nodes[target].count += other.nodes[*child].count.clone();
+                    self.nodes[target].ballots += other.nodes[*child].ballots;
+                    self.merge_from(other, ids, target, *child);
                 }
-                None => {}
-            };
+                None => self.merge_from(other, ids, to, *child),
+            }
+        }
+    }
 
-            // TODO: Map candidates to usize to remove clones
-            // TODO: For ranked votes, check for equal ranks, then divide weight by number of equal ranks
-            self.children
-                .entry(vote[0].clone())
-                .or_insert(VoteTree {
-                    count: C::zero(),
-                    children: HashMap::new(),
-                    candidates: None,
-                })
-                .add(&vote[1..], weight)
+    // A set of candidates as a mask indexed by candidate id.
+    fn mask(&self, candidates: &HashSet<T>) -> Vec<bool> {
+        let mut mask = vec![false; self.candidates.len()];
+        for candidate in candidates.iter() {
+            if let Some(id) = self.ids.get(candidate) {
+                mask[*id as usize] = true;
+            }
         }
+        mask
+    }
+
+    // Scores indexed by candidate id as a map of candidates to scores, leaving out candidates without a score.
+    fn named_scores(&self, scores: Vec<Option<C>>) -> HashMap<T, C> {
+        scores
+            .into_iter()
+            .enumerate()
+            .filter_map(|(id, score)| score.map(|score| (self.candidates[id].clone(), score)))
+            .collect()
     }
 
-    pub(crate) fn distribute_votes(&self, scores: &mut HashMap<T, C>, eliminated: &HashSet<T>) -> C {
+    fn distribute_votes(&self, node: usize, scores: &mut [Option<C>], eliminated: &[bool]) -> C {
         let mut assigned = C::zero();
-        for (cand, deeper) in &self.children {
-            if !eliminated.contains(&cand) {
-                // TODO: Remove this clone
-                *scores.entry(cand.clone()).or_insert(C::zero()) += deeper.count;
-                assigned += deeper.count;
+        for (cand, deeper) in self.nodes[node].children.iter() {
+            let count = &self.nodes[*deeper].count;
+            if !eliminated[*cand as usize] {
+                add_score(&mut scores[*cand as usize], count.clone());
+                assigned += count.clone();
             } else {
-                assigned += deeper.distribute_votes(scores, eliminated);
+                assigned += self.distribute_votes(*deeper, scores, eliminated);
             }
         }
         assigned
     }
 
-    pub(crate) fn transfer_votes(&self, scores: &mut HashMap<T, C>, weights: &HashMap<T, C>, vote: &C, base: &C, transfer: Transfer) -> C {
+    fn transfer_votes(&self, node: usize, scores: &mut [Option<C>], weights: &[C], vote: &C, base: &C, transfer: Transfer) -> C {
         use partial_min_max::min;
-        let zero = C::zero();
         let mut assigned = C::zero();
-        for (c, deeper) in &self.children {
+        for (c, deeper) in self.nodes[node].children.iter() {
+            let weight = weights[*c as usize].clone();
             let given = match transfer {
                 //c gets its weight * remaining part of vote
-                Transfer::Meek => (*vote * *weights.get(c).unwrap_or(&zero)) / *base,
+                Transfer::Meek => (vote.clone() * weight) / base.clone(),
                 //c gets its weight or the remaining vote, whatever is smaller
-                Transfer::Warren => min(*vote, *weights.get(&c).unwrap_or(&C::zero())),
+                Transfer::Warren => min(vote.clone(), weight),
             };
+            let count = &self.nodes[*deeper].count;
             if given > C::zero() {
-                // TODO: remove this clone
-                *scores.entry(c.clone()).or_insert(C::zero()) += deeper.count * given;
-                assigned += deeper.count * given;
+                add_score(&mut scores[*c as usize], count.clone() * given.clone());
+                assigned += count.clone() * given.clone();
             }
             if given < *vote {
-                let remaining = *vote - given;
-                assigned += deeper.transfer_votes(scores, weights, &remaining, base, transfer);
+                let remaining = vote.clone() - given;
+                assigned += self.transfer_votes(*deeper, scores, weights, &remaining, base, transfer);
             }
         }
         assigned
     }
 
+    // Count ballots that have no continuing candidate once the eliminated candidates are skipped.
+    // Returns the number of exhausted ballots and their total weight.
+    pub(crate) fn exhausted(&self, eliminated: &HashSet<T>) -> (usize, C) {
+        let eliminated = self.mask(eliminated);
+        let mut ballots = 0;
+        let mut weight = C::zero();
+        for (cand, deeper) in self.nodes[ROOT].children.iter() {
+            if eliminated[*cand as usize] {
+                let (b, w) = self.exhausted_below(*deeper, &eliminated);
+                ballots += b;
+                weight += w;
+            }
+        }
+        (ballots, weight)
+    }
+
+    // Exhausted ballots passing through an eliminated candidate.
+    fn exhausted_below(&self, node: usize, eliminated: &[bool]) -> (usize, C) {
+        // Ballots that end at this node have run out of preferences
+        let mut ballots = self.nodes[node].ballots;
+        let mut weight = self.nodes[node].count.clone();
+        for (cand, deeper) in self.nodes[node].children.iter() {
+            ballots -= self.nodes[*deeper].ballots;
+            weight = weight - self.nodes[*deeper].count.clone();
+            if eliminated[*cand as usize] {
+                let (b, w) = self.exhausted_below(*deeper, eliminated);
+                ballots += b;
+                weight += w;
+            }
+        }
+        (ballots, weight)
+    }
+
+    // Votes transferred away from newly eliminated candidates, once every eliminated candidate (including the newly eliminated) is skipped.
+    // Ballots with no continuing candidate left are transferred to `None`, as exhausted.
+    pub(crate) fn transfers(&self, eliminated: &HashSet<T>, newly_eliminated: &HashSet<T>, round: usize, flows: &mut Vec<Flow<T, C>>) {
+        let eliminated = self.mask(eliminated);
+        let newly_eliminated = self.mask(newly_eliminated);
+        self.transfers_below(ROOT, &eliminated, &newly_eliminated, round, flows);
+    }
+
+    fn transfers_below(&self, node: usize, eliminated: &[bool], newly_eliminated: &[bool], round: usize, flows: &mut Vec<Flow<T, C>>) {
+        for (cand, deeper) in self.nodes[node].children.iter() {
+            if newly_eliminated[*cand as usize] {
+                self.transfers_from(*deeper, *cand, eliminated, round, flows);
+            } else if eliminated[*cand as usize] {
+                self.transfers_below(*deeper, eliminated, newly_eliminated, round, flows);
+            }
+        }
+    }
+
+    // Transfer the ballots below a candidate's node to their next continuing candidate.
+    fn transfers_from(&self, node: usize, from: u32, eliminated: &[bool], round: usize, flows: &mut Vec<Flow<T, C>>) {
+        let from_candidate = &self.candidates[from as usize];
+        // Ballots that end at this node have run out of preferences
+        let mut ending = self.nodes[node].count.clone();
+        for (cand, deeper) in self.nodes[node].children.iter() {
+            let count = self.nodes[*deeper].count.clone();
+            ending = ending - count.clone();
+            if eliminated[*cand as usize] {
+                self.transfers_from(*deeper, from, eliminated, round, flows);
+            } else {
+                Flow::add(flows, round, from_candidate, Some(&self.candidates[*cand as usize]), count);
+            }
+        }
+        if ending > C::zero() {
+            Flow::add(flows, round, from_candidate, None, ending);
+        }
+    }
+
     pub(crate) fn count_ranks(&self, points: &mut HashMap<(T, usize), C>, skipped: &HashSet<T>, depth: usize) {
-        for (c, deeper) in &self.children {
-            if !skipped.contains(&c) {
-                // TODO: remove this clone using lifetimes
-                *points.entry((c.clone(), depth)).or_insert(C::zero()) += deeper.count;
-                deeper.count_ranks(points, skipped, depth + 1);
+        let mut by_id = HashMap::<(u32, usize), C>::new();
+        self.count_ranks_below(ROOT, &mut by_id, &self.mask(skipped), depth);
+        for ((id, depth), count) in by_id {
+            *points.entry((self.candidates[id as usize].clone(), depth)).or_insert(C::zero()) += count;
+        }
+    }
+
+    fn count_ranks_below(&self, node: usize, points: &mut HashMap<(u32, usize), C>, skipped: &[bool], depth: usize) {
+        for (c, deeper) in self.nodes[node].children.iter() {
+            if !skipped[*c as usize] {
+                *points.entry((*c, depth)).or_insert(C::zero()) += self.nodes[*deeper].count.clone();
+                self.count_ranks_below(*deeper, points, skipped, depth + 1);
             } else {
                 //Skip, hence go deeper without increasing depth
-                deeper.count_ranks(points, skipped, depth);
+                self.count_ranks_below(*deeper, points, skipped, depth);
             }
         }
     }
 
     pub(crate) fn transfer_votes_fp(&self, weights: &HashMap<T, C>, base: &C, transfer: Transfer) -> (C, HashMap<T, C>) {
-        let mut scores = HashMap::new();
-        let total = self.count * *base;
-        let excess = total - self.transfer_votes(&mut scores, weights, base, base, transfer);
-        (excess, scores)
+        let weights: Vec<C> = self
+            .candidates
+            .iter()
+            .map(|candidate| weights.get(candidate).cloned().unwrap_or_else(C::zero))
+            .collect();
+        let mut scores = vec![None; self.candidates.len()];
+        let total = self.count() * base.clone();
+        let excess = total - self.transfer_votes(ROOT, &mut scores, &weights, base, base, transfer);
+        (excess, self.named_scores(scores))
     }
 
     pub(crate) fn assign_votes(&self, eliminated: &HashSet<T>) -> (C, HashMap<T, C>) {
-        let mut scores = HashMap::new();
-        let excess = self.count - self.distribute_votes(&mut scores, &eliminated);
-        (excess, scores)
+        let mut scores = vec![None; self.candidates.len()];
+        let excess = self.count() - self.distribute_votes(ROOT, &mut scores, &self.mask(eliminated));
+        (excess, self.named_scores(scores))
     }
 
     #[cfg(test)]
     fn first_vote_count(&self, candidate: &T) -> C {
-        if let Some(b) = self.children.get(candidate) {
-            b.count
-        } else {
-            C::zero()
+        let child = self
+            .ids
+            .get(candidate)
+            .and_then(|id| self.nodes[ROOT].children.iter().find(|(c, _)| c == id));
+        match child {
+            Some((_, node)) => self.nodes[*node].count.clone(),
+            None => C::zero(),
         }
     }
 }
 
+// Add to a candidate's score, giving them a score if they have none.
+fn add_score<C: AddAssign>(score: &mut Option<C>, count: C) {
+    match score {
+        Some(score) => *score += count,
+        None => *score = Some(count),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,7 +439,7 @@ mod tests {
 
         let weights_half: HashMap<u32, u64> = [(0, base / 2), (1, base / 2), (2, base / 2)]
             .iter()
-            .map(|(a, b)| (*a as u32, *b as u64))
+            .map(|(a, b)| (*a as u32, *b))
             .collect();
         //Meek
         let (excess, score) = x.transfer_votes_fp(&weights_half, &base, Transfer::Meek);
@@ -215,7 +448,7 @@ mod tests {
         assert_eq!(score[&1], 7 * base / 2 + (1 + 6) * base / 4);
         assert_eq!(score[&2], 11 * base / 2 + (4 + 2) * base / 4);
         assert_eq!(excess, (base * (1 + 2 + 3 + 4 + 5 + 6)) / 4);
-        assert_eq!(score.iter().map(|(_, v)| *v).sum::<u64>() + excess, x.count * base);
+        assert_eq!(score.iter().map(|(_, v)| *v).sum::<u64>() + excess, x.count() * base);
 
         //Warren
         let (excess, score) = x.transfer_votes_fp(&weights_half, &base, Transfer::Warren);
@@ -224,7 +457,7 @@ mod tests {
         assert_eq!(score[&1], 7 * base / 2 + (1 + 6) * base / 2);
         assert_eq!(score[&2], 11 * base / 2 + (4 + 2) * base / 2);
         assert_eq!(excess, 0);
-        assert_eq!(score.iter().map(|(_, v)| *v).sum::<u64>() + excess, x.count * base);
+        assert_eq!(score.iter().map(|(_, v)| *v).sum::<u64>() + excess, x.count() * base);
     }
 
     #[test]
@@ -239,7 +472,7 @@ mod tests {
         ]);
         let base = 1_000_000;
 
-        let vsum = x.count;
+        let vsum = x.count();
 
         let mut w: HashMap<u32, u64> = (0..4).map(|i| (i as u32, base)).collect();
         w.remove(&1);
@@ -306,12 +539,12 @@ mod tests {
         let empty_hs = HashSet::new();
         let (excess, score) = x.assign_votes(&empty_hs);
         assert_eq!(excess, 0);
-        assert_eq!(score, (0..3).map(|e| (e as u32, x.first_vote_count(&e))).collect());
+        assert_eq!(score, (0..3).map(|e| (e, x.first_vote_count(&e))).collect());
 
         let (excess2, score2) = x.assign_votes(&([0u32, 2u32].iter().cloned().collect()));
         assert_eq!(*score2.get(&0).unwrap_or(&0), 0);
         assert_eq!(*score2.get(&2).unwrap_or(&0), 0);
-        assert_eq!(score2[&1], x.count - excess2);
+        assert_eq!(score2[&1], x.count() - excess2);
     }
     #[test]
     fn assign_zero() {
@@ -324,8 +557,35 @@ mod tests {
             (1, vec![2, 3, 1]),
         ]);
 
-        let vsum = x.count;
+        let vsum = x.count();
         let (excess, score) = x.assign_votes(&([1u32].iter().cloned().collect()));
         assert_eq!(excess + score.values().sum::<u64>(), vsum);
     }
+
+    #[test]
+    fn remove_candidate() {
+        let mut x = VoteTree::from(vec![
+            (3, vec![0, 2, 3]),
+            (4, vec![0, 2, 1]),
+            (2, vec![3, 0, 2]),
+            (1, vec![2]),
+            (2, vec![1, 3, 2, 0]),
+            (1, vec![2, 3, 1]),
+        ]);
+        x.remove_candidate(&2);
+
+        let expected = VoteTree::from(vec![
+            (3, vec![0, 3]),
+            (4, vec![0, 1]),
+            (2, vec![3, 0]),
+            (1, vec![]),
+            (2, vec![1, 3, 0]),
+            (1, vec![3, 1]),
+        ]);
+        let (excess, score) = x.assign_votes(&HashSet::new());
+        assert_eq!((excess, score), expected.assign_votes(&HashSet::new()));
+        assert_eq!(x.exhausted(&([3u32].iter().cloned().collect())), (0, 0));
+        assert_eq!(x.exhausted(&([0u32].iter().cloned().collect())), (0, 0));
+        assert_eq!(x.exhausted(&([0u32, 1u32].iter().cloned().collect())), (1, 4));
+    }
 }
@@ -0,0 +1,222 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use wasm_bindgen::prelude::*;
+
+use crate::approval::ApprovalTally;
+use crate::condorcet::CondorcetTally;
+use crate::errors::TallyError;
+use crate::plurality::PluralityTally;
+use crate::score::ScoreTally;
+use crate::RankedWinners;
+
+#[cfg(feature = "nightly")]
+use crate::irv;
+#[cfg(feature = "nightly")]
+use crate::Transfer;
+
+// Convert a result into a JavaScript value, with candidates and totals as plain objects and arrays.
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn tally_error(error: TallyError) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+// Totals as a `{ candidate: total }` object.
+fn totals_to_js(totals: Vec<(String, f64)>) -> Result<JsValue, JsError> {
+    to_js(&totals.into_iter().collect::<BTreeMap<_, _>>())
+}
+
+// Winners as an array of `{ candidate, rank }` objects.
+fn winners_to_js(winners: RankedWinners<String>) -> Result<JsValue, JsError> {
+    to_js(&winners.winners)
+}
+
+/// A plurality tally for JavaScript, with string candidates and number weights. `wasm`
+#[wasm_bindgen(js_name = PluralityTally)]
+pub struct WasmPluralityTally {
+    tally: PluralityTally<String, f64>,
+}
+
+#[wasm_bindgen(js_class = PluralityTally)]
+impl WasmPluralityTally {
+    /// Create a new plurality tally with the given number of winners.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_winners: usize) -> Self {
+        WasmPluralityTally {
+            tally: PluralityTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote for a candidate.
+    pub fn add(&mut self, candidate: String) {
+        self.tally.add(candidate);
+    }
+
+    /// Add a weighted vote for a candidate.
+    #[wasm_bindgen(js_name = addWeighted)]
+    pub fn add_weighted(&mut self, candidate: String, weight: f64) {
+        self.tally.add_weighted(candidate, weight);
+    }
+
+    /// Get the winners, as an array of `{ candidate, rank }` objects.
+    pub fn winners(&self) -> Result<JsValue, JsError> {
+        winners_to_js(self.tally.winners())
+    }
+
+    /// Get the vote totals, as a `{ candidate: total }` object.
+    pub fn totals(&self) -> Result<JsValue, JsError> {
+        totals_to_js(self.tally.totals())
+    }
+}
+
+/// An approval tally for JavaScript, with string candidates and number weights. `wasm`
+#[wasm_bindgen(js_name = ApprovalTally)]
+pub struct WasmApprovalTally {
+    tally: ApprovalTally<String, f64>,
+}
+
+#[wasm_bindgen(js_class = ApprovalTally)]
+impl WasmApprovalTally {
+    /// Create a new approval tally with the given number of winners.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_winners: usize) -> Self {
+        WasmApprovalTally {
+            tally: ApprovalTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote approving an array of candidates.
+    pub fn add(&mut self, selection: Vec<String>) -> Result<(), JsError> {
+        self.tally.add(selection).map_err(tally_error)
+    }
+
+    /// Add a weighted vote approving an array of candidates.
+    #[wasm_bindgen(js_name = addWeighted)]
+    pub fn add_weighted(&mut self, selection: Vec<String>, weight: f64) -> Result<(), JsError> {
+        self.tally.add_weighted(selection, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as an array of `{ candidate, rank }` objects.
+    pub fn winners(&self) -> Result<JsValue, JsError> {
+        winners_to_js(self.tally.winners())
+    }
+
+    /// Get the approval totals, as a `{ candidate: total }` object.
+    pub fn totals(&self) -> Result<JsValue, JsError> {
+        totals_to_js(self.tally.totals())
+    }
+}
+
+/// A score tally for JavaScript, with string candidates and number scores. `wasm`
+#[wasm_bindgen(js_name = ScoreTally)]
+pub struct WasmScoreTally {
+    tally: ScoreTally<String, f64>,
+}
+
+#[wasm_bindgen(js_class = ScoreTally)]
+impl WasmScoreTally {
+    /// Create a new score tally with the given number of winners.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_winners: usize) -> Self {
+        WasmScoreTally {
+            tally: ScoreTally::new(num_winners),
+        }
+    }
+
+    /// Add a vote, given as a `{ candidate: score }` object.
+    pub fn add(&mut self, scores: JsValue) -> Result<(), JsError> {
+        self.add_weighted(scores, 1.0)
+    }
+
+    /// Add a weighted vote, given as a `{ candidate: score }` object.
+    #[wasm_bindgen(js_name = addWeighted)]
+    pub fn add_weighted(&mut self, scores: JsValue, weight: f64) -> Result<(), JsError> {
+        let scores: BTreeMap<String, f64> = serde_wasm_bindgen::from_value(scores).map_err(|e| JsError::new(&e.to_string()))?;
+        self.tally.add_weighted(scores.into_iter().collect(), weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as an array of `{ candidate, rank }` objects.
+    pub fn winners(&self) -> Result<JsValue, JsError> {
+        winners_to_js(self.tally.winners())
+    }
+
+    /// Get the score totals, as a `{ candidate: total }` object.
+    pub fn totals(&self) -> Result<JsValue, JsError> {
+        totals_to_js(self.tally.totals())
+    }
+}
+
+/// A condorcet tally for JavaScript, with string candidates and number weights. `wasm`
+#[wasm_bindgen(js_name = CondorcetTally)]
+pub struct WasmCondorcetTally {
+    tally: CondorcetTally<String, f64>,
+}
+
+#[wasm_bindgen(js_class = CondorcetTally)]
+impl WasmCondorcetTally {
+    /// Create a new condorcet tally with the given number of winners and array of candidates.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_winners: usize, candidates: Vec<String>) -> Self {
+        WasmCondorcetTally {
+            tally: CondorcetTally::with_candidates(num_winners, candidates),
+        }
+    }
+
+    /// Add a vote, given as an array of candidates in order of preference.
+    pub fn add(&mut self, vote: Vec<String>) -> Result<(), JsError> {
+        self.tally.add(&vote).map_err(tally_error)
+    }
+
+    /// Add a weighted vote, given as an array of candidates in order of preference.
+    #[wasm_bindgen(js_name = addWeighted)]
+    pub fn add_weighted(&mut self, vote: Vec<String>, weight: f64) -> Result<(), JsError> {
+        self.tally.add_weighted(&vote, weight).map_err(tally_error)
+    }
+
+    /// Get the winners, as an array of `{ candidate, rank }` objects.
+    pub fn winners(&self) -> Result<JsValue, JsError> {
+        winners_to_js(self.tally.winners())
+    }
+}
+
+/// An instant-runoff tally for JavaScript, with string candidates and whole number weights. `wasm` `nightly`
+#[cfg(feature = "nightly")]
+#[wasm_bindgen(js_name = IrvTally)]
+pub struct WasmIrvTally {
+    tally: irv::Tally<String, u64>,
+}
+
+#[cfg(feature = "nightly")]
+#[wasm_bindgen(js_class = IrvTally)]
+impl WasmIrvTally {
+    /// Create a new instant-runoff tally with the given array of candidates.
+    #[wasm_bindgen(constructor)]
+    pub fn new(candidates: Vec<String>) -> Self {
+        WasmIrvTally {
+            tally: irv::Tally::with_candidates(Transfer::Meek, candidates),
+        }
+    }
+
+    /// Add a vote, given as an array of candidates in order of preference.
+    pub fn add(&mut self, vote: Vec<String>) -> Result<(), JsError> {
+        self.tally.add(vote).map_err(tally_error)
+    }
+
+    /// Add a weighted vote, given as an array of candidates in order of preference.
+    #[wasm_bindgen(js_name = addWeighted)]
+    pub fn add_weighted(&mut self, vote: Vec<String>, weight: u32) -> Result<(), JsError> {
+        self.tally.add_weighted(vote, weight.into()).map_err(tally_error)
+    }
+
+    /// Get the winner, as an array of `{ candidate, rank }` objects.
+    pub fn winners(&self) -> Result<JsValue, JsError> {
+        winners_to_js(self.tally.tally_winners())
+    }
+
+    /// Get every candidate in the order they finished, as an array of `{ candidate, rank }` objects.
+    pub fn ranked(&self) -> Result<JsValue, JsError> {
+        to_js(&self.tally.tally_ranked())
+    }
+}